@@ -0,0 +1,47 @@
+//! Injects build metadata shown by the !version command
+
+use std::path::Path;
+use std::process::Command;
+
+/// Runs a command and returns its trimmed output, or None if it can't be run or fails
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let output = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    if output.is_empty() {
+        None
+    } else {
+        Some(output)
+    }
+}
+
+fn main() {
+    let commit = output("git", &["rev-parse", "--short", "HEAD"]);
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let rustc_version = output(&rustc, &["--version"]);
+    println!(
+        "cargo:rustc-env=MATRIX_BOT_GIT_COMMIT={}",
+        commit.as_deref().unwrap_or("unknown")
+    );
+    println!(
+        "cargo:rustc-env=MATRIX_BOT_RUSTC_VERSION={}",
+        rustc_version.as_deref().unwrap_or("unknown")
+    );
+    // Watching a missing file would rerun this on every build
+    let head = Path::new(".git/HEAD");
+    if head.exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(reference) = std::fs::read_to_string(head)
+            .ok()
+            .and_then(|v| v.strip_prefix("ref: ").map(|r| r.trim().to_string()))
+        {
+            let reference = Path::new(".git").join(reference);
+            if reference.exists() {
+                println!("cargo:rerun-if-changed={}", reference.display());
+            }
+        }
+    }
+    println!("cargo:rerun-if-changed=build.rs");
+}
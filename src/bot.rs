@@ -1,13 +1,17 @@
 use crate::config::{Config, SessionStorage};
+use crate::logging::{set_feature_levels, LogHandle};
 use crate::matrix::listener::MatrixListener;
+use crate::matrix::profile::sync_profile;
 use crate::matrix::responder::MatrixResponder;
 use crate::webhook::listener::WebhookListener;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tracing::{error, info, trace};
 
-pub async fn init() -> anyhow::Result<()> {
+pub async fn init(log_handle: LogHandle) -> anyhow::Result<()> {
     // Load config data
     let config = Config::load_config()?;
+    set_feature_levels(&log_handle, &config.log_levels);
 
     // Matrix initalization and login
     let mut session_storage = SessionStorage::load_storage()?;
@@ -29,6 +33,9 @@ pub async fn init() -> anyhow::Result<()> {
     };
     info!("Successfully logged in as {}", config.mx_uname);
 
+    // Bring bot profile in line with config
+    sync_profile(&config, &matrix_listener_client).await;
+
     // Clone required clients/servers and channels
     let matrix_responder_client = matrix_listener_client.clone();
     let (matrix_tx, matrix_rx) = mpsc::channel(8);
@@ -36,12 +43,15 @@ pub async fn init() -> anyhow::Result<()> {
 
     // Create thread structures
     let mut matrix_listener = MatrixListener::new(&config, matrix_tx)?;
-    let mut matrix_responder = MatrixResponder::new(matrix_rx)?;
+    let mut matrix_responder = MatrixResponder::new(&config, matrix_rx)?;
+    // Bans only count once the responder performs them
+    matrix_responder.storage.banned_users = Arc::clone(&matrix_listener.storage.banned_users);
     let webhook_listener = WebhookListener::new(&config, webhook_tx);
 
     // Spawn threads from thread structures, save their cached data when they exit
     let matrix_listener_task = tokio::spawn(async move {
         matrix_listener.start(matrix_listener_client).await;
+        matrix_listener.storage.count_bans();
         if let Err(e) = matrix_listener.storage.save_storage() {
             error!("Unable to save matrix_listener.ron on shutdown. {}", e)
         };
@@ -62,10 +72,14 @@ pub async fn init() -> anyhow::Result<()> {
     matrix_listener_task
         .await
         .expect("The matrix listener task has panicked!");
-    #[allow(unused_must_use)]
-    webhook_listener_task
-        .await
-        .expect("The webhook listener task has panicked!");
+    // The matrix listener only returns on shutdown. Stopping the webhook listener closes the
+    // responder's channel, letting it send what is queued and save before exiting
+    webhook_listener_task.abort();
+    if let Err(e) = webhook_listener_task.await {
+        if e.is_panic() {
+            panic!("The webhook listener task has panicked!");
+        }
+    }
     matrix_responder_task
         .await
         .expect("The matrix responder task has panicked!");
@@ -1,4 +1,6 @@
 //! Structs and functions for loading and saving configuration and storage data.
+//!
+//! Relevant tests are in a test submodule
 
 // TODO: Implement Option type enum that will encapsulate the logic and potential states of config options that
 // TODO: are disable-able. This would be to prevent improper use down the line, whereas right now I pass around
@@ -6,24 +8,105 @@
 // TODO: This problem has gotten worse recently, as now not all empty items mean disabled
 // TODO: and as such, the type system needs to come to the rescue
 
+#[cfg(test)]
+pub(crate) mod tests;
+
+use crate::helpers::{
+    find_timezone, TemplateData, DEFAULT_PRECISION, DEFAULT_SPEED_OF_SOUND, DIMENSIONS,
+    MAX_PRECISION,
+};
+use crate::logging::{feature_targets, FEATURES, LOG_LEVELS};
+use crate::matrix_handlers::responders::Throttle;
+use crate::messages::MatrixBanMessage;
+use crate::queries::{IssueOrPullLookup, IssueOrPullSearch, SingleFlight};
 use anyhow::{anyhow, Context};
 use axum::http::Uri;
+use chrono_tz::Tz;
+use once_cell::sync::OnceCell;
 use reqwest::header::HeaderValue;
-use ruma::{OwnedRoomId, OwnedTransactionId, OwnedUserId, RoomId, UserId};
+use ruma::{
+    EventId, OwnedEventId, OwnedMxcUri, OwnedRoomAliasId, OwnedRoomId, OwnedServerName,
+    OwnedTransactionId, OwnedUserId, RoomAliasId, RoomId, UserId,
+};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::fmt::{Display, Formatter};
-use std::fs::{File, OpenOptions};
+use std::fs::File;
 use std::io::{ErrorKind, Read, Write};
-use std::path::PathBuf;
-use std::time::{Duration, SystemTime};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime};
 use tracing::{info, trace};
 
 /// Constant representing the crate name.
 pub const NAME: &str = env!("CARGO_PKG_NAME");
 /// Constant representing the crate version.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Constant representing the git commit the crate was built from, or "unknown".
+pub const GIT_COMMIT: &str = env!("MATRIX_BOT_GIT_COMMIT");
+/// Constant representing the version of the compiler the crate was built with, or "unknown".
+pub const RUSTC_VERSION: &str = env!("MATRIX_BOT_RUSTC_VERSION");
+/// Maximum number of triggering events the responder remembers its responses for.
+const MAX_TRACKED_RESPONSES: usize = 500;
+/// Usage shown when invalid command line arguments are given.
+const USAGE: &str = "Usage: matrix-bot [--config <path>] [--data-dir <path>]";
+
+/// Command line arguments, set once at startup.
+static ARGS: OnceCell<Args> = OnceCell::new();
+
+#[derive(Debug, Default, PartialEq)]
+/// Command line arguments that take precedence over the environment.
+pub struct Args {
+    /// Path to the config file, from `--config`.
+    pub config: Option<PathBuf>,
+    /// Directory storage files are kept in, from `--data-dir`.
+    pub data_dir: Option<PathBuf>,
+}
+
+/// Number of moderation actions kept in the audit log.
+const MAX_AUDIT_ENTRIES: usize = 1000;
+/// Number of distinct spellings whose corrections are counted per room.
+const MAX_TRACKED_SPELLINGS: usize = 50;
+/// Minimum time between karma changes from one user to another in a room.
+const KARMA_COOLDOWN: Duration = Duration::from_secs(300);
+/// Minimum time between telling a user they can't use an admin command.
+const UNAUTHORIZED_REPLY_COOLDOWN: Duration = Duration::from_secs(300);
+/// Minimum time between corrections in a room without a cooldown override.
+const CORRECTION_COOLDOWN: Duration = Duration::from_secs(300);
+/// Time the bot edits its correction of a user in a room instead of sending another one.
+const CORRECTION_EDIT_WINDOW: Duration = Duration::from_secs(3600);
+/// Minimum time between about replies in a direct message.
+const ABOUT_REPLY_COOLDOWN: Duration = Duration::from_secs(3600);
+/// Reaction the bot acknowledges successful commands with when reactions are enabled.
+pub const ACKNOWLEDGEMENT_REACTION: &str = "✅";
+/// Time an admin has to confirm a ban held back by the room confirmation threshold.
+pub const BAN_CONFIRMATION_WINDOW: Duration = Duration::from_secs(60);
+/// Time the members of a room are reused for %here and room group pings before being fetched again.
+const ROOM_MEMBERS_TTL: Duration = Duration::from_secs(60);
+/// Minimum time between inviting a user to a room through a reaction role.
+const ROLE_INVITE_COOLDOWN: Duration = Duration::from_secs(3600);
+/// Text used to tell users they can't use an admin command if none is configured.
+const DEFAULT_UNAUTHORIZED_REPLY: &str = "You're not authorized to use that.";
+/// Text sent when a conversion command has nothing that can be converted if none is configured.
+const DEFAULT_NO_CONVERSION_REPLY: &str = "No valid units were found to convert.";
+/// Length of the window a user's reply budget applies to.
+const REPLY_BUDGET_WINDOW: Duration = Duration::from_secs(3600);
+/// Time without sync activity before the bot pings the homeserver if none is configured.
+const DEFAULT_IDLE_PING_INTERVAL: Duration = Duration::from_secs(300);
+/// Power level users need to be admins if none is configured.
+const DEFAULT_POWER_LEVEL_ADMINS_MIN: i64 = 100;
+/// Time between refreshes of power level admins if none is configured.
+const DEFAULT_POWER_LEVEL_ADMINS_REFRESH: Duration = Duration::from_secs(300);
+/// Time between stats reports if none is configured.
+const DEFAULT_STATS_REPORT_INTERVAL: Duration = Duration::from_secs(24 * 3600);
+/// Number of messages a user can send within the flood window if none is configured.
+const DEFAULT_FLOOD_MESSAGE_LIMIT: usize = 10;
+/// Length of the flood window if none is configured.
+const DEFAULT_FLOOD_WINDOW: Duration = Duration::from_secs(10);
+/// Number of warnings that get a user banned if none is configured.
+const DEFAULT_WARNING_BAN_THRESHOLD: usize = 3;
 
 #[derive(Debug)]
 /// Configuration struct used at runtime. Loaded from RawConfig and its constituent parts.
@@ -40,36 +123,179 @@ pub struct MatrixListenerConfig {
     pub gh_access_token: String,
     /// Bool used to determine if unit conversions will be supported from plain text messages.
     pub enable_unit_conversions: bool,
+    /// Bool used to determine if edited messages are checked for unit conversions.
+    pub convert_on_edit: bool,
+    /// List of rooms in which commandless unit conversions are always performed, limited by a cooldown.
+    pub passive_conversions: HashSet<OwnedRoomId>,
+    /// Commands that can also be used with a "/" prefix instead of "!".
+    pub slash_commands: HashSet<String>,
+    /// Prefix commands start with in rooms without their own prefix.
+    pub command_prefix: String,
+    /// Minimum time between commandless unit conversions in passive conversion rooms.
+    pub passive_conversion_cooldown: Duration,
+    /// Speed of sound in m/s used to convert sound frequencies to wavelengths.
+    pub speed_of_sound: f64,
+    /// Whether unit conversions show the formula used to convert them.
+    pub verbose_conversions: bool,
+    /// Text sent when a conversion command has nothing that can be converted.
+    pub no_conversion_reply: String,
+    /// Whether adjacent imperial volumes like "1gal 2qt" are summed and converted as one volume.
+    pub enable_compound_volumes: bool,
+    /// Bool used to determine if an index of commands is posted and pinned in rooms the bot joins.
+    pub enable_command_index: bool,
     /// Bool used to determine if the corrections feature is enabled or not.
     pub enable_corrections: bool,
+    /// Bool used to determine if bot responses are redacted when their triggering message is.
+    pub enable_response_redaction: bool,
+    /// Bool used to determine if syncs are filtered to the events enabled features need.
+    pub enable_sync_filter: bool,
+    /// Bool used to determine if actions are only logged instead of sent to the homeserver.
+    pub dry_run: bool,
+    /// Bool used to determine if successful commands are acknowledged with a reaction.
+    pub use_reactions: bool,
+    /// Time the listener was started, used to show uptime.
+    pub start_time: SystemTime,
+    /// Minimum time between saves of the sync token. Every sync is saved if zero.
+    pub sync_save_interval: Duration,
+    /// Time without sync activity before the bot pings the homeserver. Disabled if None.
+    pub idle_ping_interval: Option<Duration>,
+    /// Room stats reports are posted to. Reports are disabled if None.
+    pub stats_room: Option<OwnedRoomId>,
+    /// Time between stats reports.
+    pub stats_report_interval: Duration,
     /// List of units to exclude from conversions if there is a space between the quantity and unit.
     pub unit_conversion_exclusion: HashSet<String>,
-    /// List of all incorrect spellings to match against
-    pub incorrect_spellings: Vec<SpellCheckKind>,
+    /// Text that must directly follow a unit for commandless conversions. Not required if None.
+    pub conversion_marker: Option<String>,
+    /// Number of decimal places used for conversions of dimensions without their own precision.
+    pub default_unit_conversion_precision: usize,
+    /// Hashmap containing a dimension as key and the number of decimal places its conversions use as the value.
+    pub unit_conversion_precision: HashMap<String, usize>,
+    /// How converted values are rounded.
+    pub rounding_mode: RoundingMode,
+    /// How units that are both a built-in and a custom unit are converted.
+    pub ambiguous_units: AmbiguousUnits,
+    /// List of all incorrect spellings to match against. Locked so admins can change them at runtime.
+    pub incorrect_spellings: RwLock<Vec<SpellCheckKind>>,
     /// Text used in spellcheck correction feature.
     pub correction_text: String,
     /// List of all rooms to be excluded from spellcheck correction feature.
     pub correction_exclusion: HashSet<OwnedRoomId>,
-    /// List of all words that can be used to link URLs.
-    pub linkers: HashSet<String>,
+    /// Hashmap containing a room id as key and the seconds between corrections in that room as the value.
+    pub correction_cooldown_overrides: HashMap<OwnedRoomId, u64>,
+    /// Time a user's misspellings are collected into one correction. Corrections are sent right away if None.
+    pub correction_batch_window: Option<Duration>,
     /// List of matrix users that can invite the bot to rooms.
     pub admins: HashSet<OwnedUserId>,
+    /// List of admins that can temporarily grant admin to other users.
+    pub super_admins: HashSet<OwnedUserId>,
+    /// List of users that can ban. Admins can ban if None.
+    pub ban_authorized_users: Option<HashSet<OwnedUserId>>,
+    /// Room whose power levels make users admins. Disabled if None.
+    pub power_level_admins: Option<PowerLevelAdmins>,
+    /// List of homeservers whose rooms and users can never invite the bot.
+    pub blocked_servers: HashSet<OwnedServerName>,
     /// List of rooms in which help function can be used.
     pub help_rooms: HashSet<OwnedRoomId>,
     /// List of rooms in which ban function will apply.
     pub ban_rooms: HashSet<OwnedRoomId>,
-    /// Hashmap containing short name for a repo as a key and the org/repo as a value.
-    pub repos: HashMap<String, String>,
-    /// Hashmap containing searched key and matching URL for linking.
-    pub links: HashMap<String, Uri>,
+    /// Number of users a single ban can target before it has to be confirmed.
+    pub ban_confirmation_threshold: usize,
+    /// Bans targeting more rooms than this must be confirmed with !ban --confirm. Disabled if None.
+    pub ban_room_confirmation_threshold: Option<usize>,
+    /// Reaction admins confirm actions with.
+    pub confirmation_reaction: Emoji,
+    /// Limits and action used to stop users flooding ban rooms. Disabled if None.
+    pub flood_protection: Option<FloodProtection>,
+    /// Number of warnings that get a user banned and where they're counted. Disabled if None.
+    pub warnings: Option<Warnings>,
+    /// Bool used to determine if moderation commands are only accepted in encrypted rooms.
+    pub moderation_requires_encryption: bool,
+    /// Where the maintenance message goes on command replies while maintenance mode is on.
+    pub maintenance_banner: MaintenanceBanner,
+    /// Bool used to determine if users are told when they can't use an admin command.
+    pub enable_unauthorized_reply: bool,
+    /// Text used to tell users they can't use an admin command.
+    pub unauthorized_reply: String,
+    /// Maximum number of replies a non-admin user can get per hour. Unlimited if None.
+    pub max_replies_per_user: Option<usize>,
+    /// Maximum age of events that will be processed. Unlimited if None.
+    pub max_event_age: Option<Duration>,
+    /// Minimum time between identical help messages in a room. Unlimited if None.
+    pub help_cooldown: Option<Duration>,
+    /// Time identical conversions in a room are only answered once in. Always answered if None.
+    pub duplicate_conversion_window: Option<Duration>,
+    /// Bool used to determine if the karma feature is enabled or not.
+    pub enable_karma: bool,
+    /// Which features are performed in direct messages with the bot.
+    pub direct_messages: DirectMessagePolicy,
+    /// Rooms users are invited to by reacting in the reaction roles room. Disabled if None.
+    pub reaction_roles: Option<ReactionRoles>,
+    /// Searchable repos and linkable URLs. Locked so they can be reloaded at runtime.
+    pub link_settings: RwLock<LinkSettings>,
     /// List of all text expansions.
     pub text_expansions: HashMap<String, String>,
     /// UserAgent used by reqwest
     pub user_agent: HeaderValue,
+    /// Group pings and the users that can initiate them. Locked so they can be reloaded at runtime.
+    pub group_pings: RwLock<GroupPings>,
+    /// How the users of a group ping are shown.
+    pub group_ping_format: GroupPingFormat,
+    /// Time a user has to wait between group pings. Unlimited if None.
+    pub group_ping_cooldown: Option<Duration>,
+    /// Hashmap containing a room id as key and the custom conversions usable in that room as the value.
+    pub custom_conversions: HashMap<OwnedRoomId, HashMap<String, CustomConversion>>,
+    /// Hashmap containing a room id as key and the only commands allowed in that room as the value.
+    pub allowed_commands: HashMap<OwnedRoomId, HashSet<String>>,
+    /// Hashmap containing a room id as key and the prefix commands start with in that room as the value.
+    pub command_prefixes: HashMap<OwnedRoomId, String>,
+}
+
+#[derive(Debug, Default)]
+/// Searchable repos and linkable URLs that can be reloaded without restarting the bot.
+pub struct LinkSettings {
+    /// Hashmap containing short name for a repo as a key and the org/repo as a value.
+    pub repos: HashMap<String, String>,
+    /// Hashmap containing searched key and matching URL for linking.
+    pub links: HashMap<String, Uri>,
+    /// List of all words that can be used to link URLs.
+    pub linkers: HashSet<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+/// Group pings that can be reloaded without restarting the bot.
+pub struct GroupPings {
     /// Hashmap containing group ping name as key and list of user IDs as the value.
-    pub group_pings: HashMap<String, HashSet<OwnedUserId>>,
+    pub groups: HashMap<String, HashSet<OwnedUserId>>,
+    /// Hashmap containing group ping name as key and the rooms whose members it also pings as the value.
+    ///
+    /// Kept as aliases since their members change, so they're resolved when the group is pinged.
+    pub rooms: HashMap<String, HashSet<OwnedRoomAliasId>>,
     /// Hashset containing list of users that can initiate group pings
-    pub group_ping_users: HashSet<OwnedUserId>,
+    pub users: HashSet<OwnedUserId>,
+}
+
+impl GroupPings {
+    /// Returns the users a group would ping. The reserved group "all" pings every configured user.
+    ///
+    /// None if the group does not exist. The reserved group "here" depends on the room, so it's
+    /// resolved by the group ping handler instead and is None here.
+    pub fn members(&self, group: &str) -> Option<HashSet<OwnedUserId>> {
+        if group == "all" {
+            Some(self.groups.values().flatten().cloned().collect())
+        } else {
+            self.groups.get(group).cloned()
+        }
+    }
+    /// Returns the rooms whose members a group also pings. The reserved group "all" includes the
+    /// rooms of every group.
+    pub fn rooms_of(&self, group: &str) -> HashSet<OwnedRoomAliasId> {
+        if group == "all" {
+            self.rooms.values().flatten().cloned().collect()
+        } else {
+            self.rooms.get(group).cloned().unwrap_or_default()
+        }
+    }
 }
 
 pub struct WebhookListenerConfig {
@@ -87,28 +313,128 @@ pub struct Config {
     pub mx_uname: OwnedUserId,
     /// Matrix bot account password.
     pub mx_pass: String,
+    /// Display name the bot account should have, if managed by config.
+    pub display_name: Option<String>,
+    /// Avatar the bot account should have, if managed by config.
+    pub avatar_url: Option<OwnedMxcUri>,
     /// Github access token as string.
     gh_access_token: String,
     /// Bool used to determine if unit conversions will be supported from plain text messages.
     enable_unit_conversions: bool,
+    /// Bool used to determine if edited messages are checked for unit conversions.
+    convert_on_edit: bool,
+    /// List of rooms in which commandless unit conversions are always performed, limited by a cooldown.
+    passive_conversions: HashSet<OwnedRoomId>,
+    /// Commands that can also be used with a "/" prefix instead of "!".
+    slash_commands: HashSet<String>,
+    /// Prefix commands start with in rooms without their own prefix.
+    command_prefix: String,
+    /// Minimum time between commandless unit conversions in passive conversion rooms.
+    passive_conversion_cooldown: Duration,
+    /// Speed of sound in m/s used to convert sound frequencies to wavelengths.
+    speed_of_sound: f64,
+    /// Whether unit conversions show the formula used to convert them.
+    verbose_conversions: bool,
+    /// Text sent when a conversion command has nothing that can be converted.
+    no_conversion_reply: String,
+    /// Whether adjacent imperial volumes like "1gal 2qt" are summed and converted as one volume.
+    enable_compound_volumes: bool,
+    /// Bool used to determine if an index of commands is posted and pinned in rooms the bot joins.
+    enable_command_index: bool,
     /// Bool used to determine if the corrections feature is enabled or not.
     enable_corrections: bool,
+    /// Bool used to determine if bot responses are redacted when their triggering message is.
+    enable_response_redaction: bool,
+    /// Bool used to determine if syncs are filtered to the events enabled features need.
+    enable_sync_filter: bool,
+    /// Bool used to determine if successful commands are acknowledged with a reaction.
+    use_reactions: bool,
+    /// Minimum time between saves of the sync token. Every sync is saved if zero.
+    sync_save_interval: Duration,
+    /// Time without sync activity before the bot pings the homeserver. Disabled if None.
+    idle_ping_interval: Option<Duration>,
+    /// Room stats reports are posted to. Reports are disabled if None.
+    stats_room: Option<OwnedRoomId>,
+    /// Time between stats reports.
+    stats_report_interval: Duration,
     /// List of units to exclude from conversions if there is a space between the quantity and unit.
     unit_conversion_exclusion: HashSet<String>,
+    /// Text that must directly follow a unit for commandless conversions. Not required if None.
+    conversion_marker: Option<String>,
+    /// Number of decimal places used for conversions of dimensions without their own precision.
+    default_unit_conversion_precision: usize,
+    /// Hashmap containing a dimension as key and the number of decimal places its conversions use as the value.
+    unit_conversion_precision: HashMap<String, usize>,
+    /// How converted values are rounded.
+    rounding_mode: RoundingMode,
+    /// How units that are both a built-in and a custom unit are converted.
+    ambiguous_units: AmbiguousUnits,
+    /// How the users of a group ping are shown.
+    group_ping_format: GroupPingFormat,
+    /// Time a user has to wait between group pings. Unlimited if None.
+    group_ping_cooldown: Option<Duration>,
     /// List of all incorrect spellings to match against
     incorrect_spellings: Vec<SpellCheckKind>,
     /// Text used in spellcheck correction feature.
     correction_text: String,
     /// List of all rooms to be excluded from spellcheck correction feature.
     correction_exclusion: HashSet<OwnedRoomId>,
+    /// Hashmap containing a room id as key and the seconds between corrections in that room as the value.
+    correction_cooldown_overrides: HashMap<OwnedRoomId, u64>,
+    /// Time a user's misspellings are collected into one correction. Corrections are sent right away if None.
+    correction_batch_window: Option<Duration>,
     /// List of all words that can be used to link URLs.
     linkers: HashSet<String>,
     /// List of matrix users that can invite the bot to rooms.
     admins: HashSet<OwnedUserId>,
+    /// List of admins that can temporarily grant admin to other users.
+    super_admins: HashSet<OwnedUserId>,
+    /// List of users that can ban. Admins can ban if None.
+    ban_authorized_users: Option<HashSet<OwnedUserId>>,
+    /// Room whose power levels make users admins. Disabled if None.
+    pub power_level_admins: Option<PowerLevelAdmins>,
+    /// List of homeservers whose rooms and users can never invite the bot.
+    blocked_servers: HashSet<OwnedServerName>,
     /// List of matrix rooms that the help function can be used in
     help_rooms: HashSet<OwnedRoomId>,
     /// List of matrix rooms in which bans will be applied
     ban_rooms: HashSet<OwnedRoomId>,
+    /// Number of users a single ban can target before it has to be confirmed.
+    ban_confirmation_threshold: usize,
+    /// Bans targeting more rooms than this must be confirmed with !ban --confirm. Disabled if None.
+    ban_room_confirmation_threshold: Option<usize>,
+    /// Reaction admins confirm actions with.
+    pub confirmation_reaction: Emoji,
+    /// Limits and action used to stop users flooding ban rooms. Disabled if None.
+    flood_protection: Option<FloodProtection>,
+    /// Number of warnings that get a user banned and where they're counted. Disabled if None.
+    warnings: Option<Warnings>,
+    /// Bool used to determine if moderation commands are only accepted in encrypted rooms.
+    moderation_requires_encryption: bool,
+    /// Where the maintenance message goes on command replies while maintenance mode is on.
+    maintenance_banner: MaintenanceBanner,
+    /// Bool used to determine if users are told when they can't use an admin command.
+    enable_unauthorized_reply: bool,
+    /// Text used to tell users they can't use an admin command.
+    unauthorized_reply: String,
+    /// Maximum number of replies a non-admin user can get per hour. Unlimited if None.
+    max_replies_per_user: Option<usize>,
+    /// Maximum number of requests the responder sends to the homeserver per second. Unlimited if None.
+    pub messages_per_second: Option<f64>,
+    /// Bool used to determine if actions are only logged instead of sent to the homeserver.
+    pub dry_run: bool,
+    /// Maximum age of events that will be processed. Unlimited if None.
+    max_event_age: Option<Duration>,
+    /// Minimum time between identical help messages in a room. Unlimited if None.
+    help_cooldown: Option<Duration>,
+    /// Time identical conversions in a room are only answered once in. Always answered if None.
+    duplicate_conversion_window: Option<Duration>,
+    /// Bool used to determine if the karma feature is enabled or not.
+    enable_karma: bool,
+    /// Which features are performed in direct messages with the bot.
+    direct_messages: DirectMessagePolicy,
+    /// Rooms users are invited to by reacting in the reaction roles room. Disabled if None.
+    reaction_roles: Option<ReactionRoles>,
     /// Hashmap containing short name for a repo as a key and the org/repo as a value.
     repos: HashMap<String, String>,
     /// Hashmap containing searched key and matching URL for linking.
@@ -117,10 +443,16 @@ pub struct Config {
     text_expansions: HashMap<String, String>,
     /// UserAgent used by reqwest
     user_agent: HeaderValue,
-    /// Hashmap containing group ping name as key and list of user IDs as the value.
-    group_pings: HashMap<String, HashSet<OwnedUserId>>,
-    /// Hashset containing list of users that can initiate group pings
-    group_ping_users: HashSet<OwnedUserId>,
+    /// Group pings, their rooms, and the users that can initiate them.
+    group_pings: GroupPings,
+    /// Hashmap containing a room id as key and the custom conversions usable in that room as the value.
+    custom_conversions: HashMap<OwnedRoomId, HashMap<String, CustomConversion>>,
+    /// Hashmap containing a room id as key and the only commands allowed in that room as the value.
+    allowed_commands: HashMap<OwnedRoomId, HashSet<String>>,
+    /// Hashmap containing a room id as key and the prefix commands start with in that room as the value.
+    command_prefixes: HashMap<OwnedRoomId, String>,
+    /// Hashmap containing a feature or module path as key and its log level as the value.
+    pub log_levels: HashMap<String, String>,
     pub webhook_token: String,
 }
 
@@ -143,6 +475,24 @@ pub struct RawConfig {
     text_expansion: Option<HashMap<String, String>>,
     /// Hashmap containing group ping name as key and list of user IDs as the value.
     group_pings: Option<HashMap<String, Vec<String>>>,
+    /// Hashmap containing a room id as key and a map of custom unit names to their conversion as the value.
+    custom_conversions: Option<HashMap<OwnedRoomId, HashMap<String, CustomConversion>>>,
+    /// Hashmap containing a room id as key and the only commands allowed in that room as the value.
+    allowed_commands: Option<HashMap<OwnedRoomId, HashSet<String>>>,
+    /// Hashmap containing a room id as key and the prefix commands start with in that room as the value.
+    command_prefixes: Option<HashMap<OwnedRoomId, String>>,
+    /// Hashmap containing a room id as key and the seconds between corrections in that room as the value.
+    correction_cooldown_overrides: Option<HashMap<String, u64>>,
+    /// Hashmap containing a dimension as key and the number of decimal places its conversions use as the value.
+    unit_conversion_precision: Option<HashMap<String, usize>>,
+    /// Hashmap containing a feature or module path as key and its log level as the value.
+    log_levels: Option<HashMap<String, String>>,
+    /// Which features are performed in direct messages with the bot.
+    direct_messages: Option<DirectMessagePolicy>,
+    /// Room reactions invite users to other rooms in, and the room each reaction invites to.
+    reaction_roles: Option<RawReactionRoles>,
+    /// Hashmap containing a custom emoji shortcode as key and the mxc:// URI of its image as the value.
+    custom_emoji: Option<HashMap<String, OwnedMxcUri>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -150,16 +500,102 @@ pub struct RawConfig {
 struct RawGeneral {
     /// List of matrix users that can invite the bot to rooms.
     authorized_users: Option<HashSet<OwnedUserId>>,
+    /// List of authorized users that can temporarily grant admin to other users.
+    super_admins: Option<HashSet<OwnedUserId>>,
+    /// List of users that can ban, replacing authorized_users for bans.
+    ban_authorized_users: Option<HashSet<OwnedUserId>>,
+    /// Room whose power levels make users admins.
+    power_level_admins_room: Option<OwnedRoomId>,
+    /// Power level users need in power_level_admins_room to be admins.
+    power_level_admins_min: Option<i64>,
+    /// Time in seconds between refreshes of power level admins.
+    power_level_admins_refresh_seconds: Option<u64>,
+    /// List of homeservers whose rooms and users can never invite the bot.
+    blocked_servers: Option<HashSet<OwnedServerName>>,
     /// List of rooms the help function can be used in.
     help_rooms: Option<HashSet<OwnedRoomId>>,
     /// List of rooms the ban function will apply to
     ban_rooms: Option<HashSet<OwnedRoomId>>,
+    /// Number of users a single ban can target before it has to be confirmed.
+    ban_confirmation_threshold: Option<usize>,
+    /// Bans targeting more rooms than this must be confirmed with !ban --confirm.
+    ban_room_confirmation_threshold: Option<usize>,
+    /// Unicode emoji or :shortcode: of a custom emoji admins confirm actions with.
+    confirmation_reaction: Option<String>,
+    /// Bool used to determine if users flooding ban rooms are automatically banned or muted.
+    enable_flood_protection: Option<bool>,
+    /// Number of messages a user can send within flood_seconds before flood protection acts.
+    flood_message_limit: Option<usize>,
+    /// Time in seconds flood_message_limit applies to.
+    flood_seconds: Option<u64>,
+    /// Action flood protection takes against flooding users.
+    flood_action: Option<FloodAction>,
+    /// Bool used to determine if admins can warn users, banning them after warning_ban_threshold warnings.
+    enable_warnings: Option<bool>,
+    /// Number of warnings that get a user banned from every ban room.
+    warning_ban_threshold: Option<usize>,
+    /// Whether warnings count in every room or only the room they were given in.
+    warning_scope: Option<WarningScope>,
+    /// Bool used to determine if moderation commands are only accepted in encrypted rooms.
+    moderation_requires_encryption: Option<bool>,
+    /// Where the maintenance message goes on command replies while maintenance mode is on.
+    maintenance_banner: Option<MaintenanceBanner>,
+    /// Bool used to determine if users are told when they can't use an admin command.
+    enable_unauthorized_reply: Option<bool>,
+    /// Text used to tell users they can't use an admin command.
+    unauthorized_reply: Option<String>,
+    /// Maximum number of replies a non-admin user can get per hour.
+    max_replies_per_user: Option<usize>,
+    /// Maximum number of requests the responder sends to the homeserver per second.
+    messages_per_second: Option<f64>,
+    /// Bool used to determine if actions are only logged instead of sent to the homeserver.
+    dry_run: Option<bool>,
+    /// Maximum age in seconds of events that will be processed.
+    max_event_age_seconds: Option<u64>,
+    /// Minimum time in seconds between identical help messages in a room.
+    help_cooldown_seconds: Option<u64>,
+    /// Time in seconds identical conversions in a room are only answered once in.
+    duplicate_conversion_seconds: Option<u64>,
+    /// Bool used to determine if the karma feature is enabled or not.
+    enable_karma: Option<bool>,
     /// Bool used to determine if unit conversions will be supported from plain text messages.
     enable_unit_conversions: bool,
+    /// Bool used to determine if edited messages are checked for unit conversions.
+    convert_on_edit: Option<bool>,
+    /// List of rooms in which commandless unit conversions are always performed, limited by a cooldown.
+    passive_conversions: Option<HashSet<OwnedRoomId>>,
+    /// Commands that can also be used with a "/" prefix instead of "!".
+    slash_commands: Option<HashSet<String>>,
+    /// Prefix commands start with in rooms without their own prefix.
+    command_prefix: Option<String>,
+    /// Minimum number of seconds between commandless unit conversions in passive conversion rooms.
+    passive_conversion_cooldown: Option<u64>,
+    /// Speed of sound in m/s used to convert sound frequencies to wavelengths.
+    speed_of_sound: Option<f64>,
+    /// Number of decimal places used for conversions of dimensions without their own precision.
+    unit_conversion_precision: Option<usize>,
+    /// Whether unit conversions show the formula used to convert them.
+    verbose_conversions: Option<bool>,
+    /// Text sent when a conversion command has nothing that can be converted.
+    no_conversion_reply: Option<String>,
+    /// Whether adjacent imperial volumes like "1gal 2qt" are summed and converted as one volume.
+    enable_compound_volumes: Option<bool>,
+    /// Bool used to determine if an index of commands is posted and pinned in rooms the bot joins.
+    enable_command_index: Option<bool>,
     /// Bool used to determine if the corrections feature is enabled or not.
     enable_corrections: bool,
     /// List of units to exclude from conversions if there is a space between the quantity and unit.
     unit_conversion_exclusion: Option<HashSet<String>>,
+    /// Text that must directly follow a unit for commandless conversions.
+    conversion_marker: Option<String>,
+    /// How converted values are rounded.
+    rounding_mode: Option<RoundingMode>,
+    /// How units that are both a built-in and a custom unit are converted.
+    ambiguous_units: Option<AmbiguousUnits>,
+    /// How the users of a group ping are shown.
+    group_ping_format: Option<GroupPingFormat>,
+    /// Time in seconds a user has to wait between group pings.
+    group_ping_cooldown: Option<u64>,
     /// List of text that will be matched case insensitively for corrections feature.
     insensitive_corrections: Option<Vec<String>>,
     /// List of text that will be matched case sensitively for corrections feature.
@@ -168,8 +604,26 @@ struct RawGeneral {
     correction_text: Option<String>,
     /// List of all rooms to be excluded from spellcheck correction feature.
     correction_exclusion: Option<HashSet<OwnedRoomId>>,
+    /// Time in seconds a user's misspellings are collected into one correction.
+    correction_batch_seconds: Option<u64>,
     /// List of all words that can be used to link URLs.
     link_matchers: Option<HashSet<String>>,
+    /// Bool used to determine if bot responses are redacted when their triggering message is.
+    enable_response_redaction: Option<bool>,
+    /// Bool used to determine if syncs are filtered to the events enabled features need.
+    enable_sync_filter: Option<bool>,
+    /// Bool used to determine if successful commands are acknowledged with a reaction.
+    use_reactions: Option<bool>,
+    /// Minimum time in seconds between saves of the sync token.
+    sync_save_seconds: Option<u64>,
+    /// Bool used to determine if the bot pings the homeserver after a period without sync activity.
+    enable_idle_ping: Option<bool>,
+    /// Time in seconds without sync activity before the bot pings the homeserver.
+    idle_ping_seconds: Option<u64>,
+    /// Room stats reports are posted to.
+    stats_room: Option<OwnedRoomId>,
+    /// Time in hours between stats reports.
+    stats_report_hours: Option<u64>,
 
     webhook_token: String,
 }
@@ -183,6 +637,19 @@ struct RawMatrixAuthentication {
     username: OwnedUserId,
     /// Matrix password for bot account.
     password: String,
+    /// Display name for bot account.
+    display_name: Option<String>,
+    /// Avatar for bot account as an mxc:// URI.
+    avatar_url: Option<OwnedMxcUri>,
+}
+
+#[derive(Debug, Deserialize)]
+/// Struct that contains raw reaction roles config data.
+struct RawReactionRoles {
+    /// Room reactions are counted in.
+    room: OwnedRoomId,
+    /// Hashmap containing an emoji or :shortcode: as key and the room reacting with it invites to as the value.
+    roles: HashMap<String, OwnedRoomId>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -198,6 +665,18 @@ pub struct SessionStorage {
     pub access_token: Option<String>,
 }
 
+/// Room and requested topic of a help message.
+type HelpRequest = (OwnedRoomId, String);
+
+/// Room, giver, and receiver of a karma change.
+type KarmaChange = (OwnedRoomId, OwnedUserId, OwnedUserId);
+/// Room and user of a burst of misspellings.
+type CorrectionBurst = (OwnedRoomId, OwnedUserId);
+/// Room and conversions of a conversion request, like "100km".
+type ConversionRequest = (OwnedRoomId, String);
+/// Room and user of an invite through a reaction role.
+type RoleInvite = (OwnedRoomId, OwnedUserId);
+
 #[derive(Debug, Default, Deserialize, Serialize)]
 /// Struct that contains persistent matrix listener data the bot modifies during runtime
 pub struct ListenerStorage {
@@ -205,6 +684,113 @@ pub struct ListenerStorage {
     pub last_sync: Option<String>,
     /// Hashmap that contains a room id key and a system time of the last correction.
     pub last_correction_time: HashMap<OwnedRoomId, SystemTime>,
+    /// Hashmap that contains a room id key and a system time of the last passive unit conversion.
+    #[serde(default)]
+    pub last_passive_conversion_time: HashMap<OwnedRoomId, SystemTime>,
+    /// Hashmap that contains a user id key and a system time of the last group ping they started.
+    #[serde(default)]
+    pub last_group_ping_time: HashMap<OwnedUserId, SystemTime>,
+    /// Hashmap that contains a room id key and the number of joined members in that room as the value.
+    #[serde(default)]
+    pub joined_member_counts: HashMap<OwnedRoomId, u64>,
+    /// List of rooms known to have encryption enabled.
+    #[serde(default)]
+    pub encrypted_rooms: HashSet<OwnedRoomId>,
+    /// Hashmap that contains a room id key and the name of that room as the value.
+    #[serde(default)]
+    pub room_names: HashMap<OwnedRoomId, String>,
+    /// Hashmap that contains a user id key and when their temporarily granted admin expires as the value.
+    #[serde(default)]
+    pub temporary_admins: HashMap<OwnedUserId, SystemTime>,
+    /// Users with enough power in the power level admins room. Refreshed periodically, so never saved.
+    #[serde(skip)]
+    pub power_level_admins: HashSet<OwnedUserId>,
+    /// Hashmap that contains a room id key and the corrections made in that room as the value.
+    #[serde(default)]
+    pub correction_stats: HashMap<OwnedRoomId, CorrectionStats>,
+    /// Hashmap that contains a room id key and the karma of users in that room as the value.
+    #[serde(default)]
+    pub karma: HashMap<OwnedRoomId, HashMap<OwnedUserId, i64>>,
+    /// Activity counted since the last stats report.
+    #[serde(default)]
+    pub activity: ActivityStats,
+    /// Number of users the responder banned that the activity doesn't include yet. Shared with the responder.
+    #[serde(skip)]
+    pub banned_users: Arc<AtomicU64>,
+    /// Time GitHub searches resume after GitHub rate limited the bot.
+    #[serde(default)]
+    pub github_paused_until: Option<SystemTime>,
+    /// Whether a user has been told GitHub searches are paused during the current pause.
+    #[serde(skip)]
+    pub github_pause_notified: bool,
+    /// GitHub searches in flight, so identical searches made at the same time share one request.
+    #[serde(skip)]
+    pub github_lookups: Arc<SingleFlight<IssueOrPullSearch, IssueOrPullLookup>>,
+    /// Hashmap that contains the room, giver, and receiver of a karma change as key and when it happened as the value.
+    #[serde(skip)]
+    pub last_karma_time: HashMap<KarmaChange, Instant>,
+    /// Hashmap that contains the room and topic of a help message as key and when it was last sent as the value.
+    #[serde(skip)]
+    pub last_help_time: HashMap<HelpRequest, Instant>,
+    /// Hashmap that contains the room and conversions of a conversion request as key and when it was last answered as the value.
+    #[serde(skip)]
+    pub last_conversion_time: HashMap<ConversionRequest, Instant>,
+    /// Hashmap that contains a user id key and when they were last told they can't use an admin command.
+    #[serde(skip)]
+    pub last_unauthorized_reply_time: HashMap<OwnedUserId, Instant>,
+    /// Hashmap that contains a room id key and when the about text was last sent there as the value.
+    #[serde(skip)]
+    pub last_about_reply_time: HashMap<OwnedRoomId, Instant>,
+    /// Hashmap that contains the room and user of a reaction role invite as key and when it was last sent as the value.
+    #[serde(skip)]
+    pub last_role_invite_time: HashMap<RoleInvite, Instant>,
+    /// Hashmap that contains an admin's user id key and the ban they were asked to confirm plus when as the value.
+    #[serde(skip)]
+    pub pending_bans: HashMap<OwnedUserId, (Instant, MatrixBanMessage)>,
+    /// Hashmap that contains a room id key and when its joined members were fetched plus the members as the value.
+    #[serde(skip)]
+    pub room_members: HashMap<OwnedRoomId, (Instant, HashSet<OwnedUserId>)>,
+    /// Hashmap that contains a room alias key used in group pings and when its members were fetched plus the members as the value.
+    #[serde(skip)]
+    pub alias_members: HashMap<OwnedRoomAliasId, (Instant, HashSet<OwnedUserId>)>,
+    /// Hashmap that contains a user id key and the start of their reply budget window plus replies sent in it.
+    #[serde(skip)]
+    pub reply_counts: HashMap<OwnedUserId, (Instant, usize)>,
+    /// Hashmap that contains the room and user of a burst of misspellings as key and its correction as the value.
+    #[serde(skip)]
+    pub pending_corrections: HashMap<CorrectionBurst, PendingCorrection>,
+    /// Hashmap that contains the room and user of a correction as key and when it was sent plus the event it corrected as the value.
+    #[serde(skip)]
+    pub sent_corrections: HashMap<CorrectionBurst, (Instant, OwnedEventId)>,
+    /// Hashmap that contains a user id key and when they sent their recent messages in ban rooms, oldest first.
+    #[serde(skip)]
+    pub recent_messages: HashMap<OwnedUserId, VecDeque<Instant>>,
+    /// Message shown on command replies while maintenance mode is on.
+    #[serde(default)]
+    pub maintenance: Option<String>,
+    /// Users that asked not to be corrected, in any room.
+    #[serde(default)]
+    pub correction_opt_outs: HashSet<OwnedUserId>,
+    /// Users the bot doesn't respond to, set by admins.
+    #[serde(default)]
+    pub ignored_users: HashSet<OwnedUserId>,
+    /// Hashmap that contains a user id key and the warnings they were given, oldest first.
+    #[serde(default)]
+    pub warnings: HashMap<OwnedUserId, Vec<Warning>>,
+    /// Hashmap that contains a user id key and the name of their timezone as the value.
+    #[serde(default)]
+    pub user_timezones: HashMap<OwnedUserId, String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// A correction waiting for a user's burst of misspellings to end.
+pub struct PendingCorrection {
+    /// Configured spellings the user misspelled, in the order they were first misspelled.
+    pub spellings: Vec<String>,
+    /// Latest misspelled message, which the correction is sent in response to.
+    pub event_id: OwnedEventId,
+    /// Time the burst ends if the user doesn't misspell anything else.
+    pub due: Instant,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize)]
@@ -212,6 +798,304 @@ pub struct ListenerStorage {
 pub struct ResponderStorage {
     /// Transaction id for last sent message.
     pub last_txn_id: u64,
+    /// Pairs of triggering event id and the id of the response the bot sent for it, oldest first.
+    #[serde(default)]
+    pub responses: VecDeque<(OwnedEventId, OwnedEventId)>,
+    /// Hashmap containing a room id as key and the announcements the bot pinned there as the value, oldest first.
+    #[serde(default)]
+    pub announcements: HashMap<OwnedRoomId, Vec<OwnedEventId>>,
+    /// Moderation actions the bot took, oldest first.
+    #[serde(default)]
+    pub audit_log: VecDeque<AuditEntry>,
+    /// Schedule that spaces out requests to the homeserver.
+    #[serde(skip)]
+    pub throttle: Throttle,
+    /// Number of users banned since the listener last counted them. Shared with the listener.
+    #[serde(skip)]
+    pub banned_users: Arc<AtomicU64>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+/// A kind of moderation action recorded in the audit log.
+pub enum AuditAction {
+    /// Users were banned.
+    Ban,
+    /// Users were muted.
+    Mute,
+    /// Users were unbanned.
+    Unban,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// A warning an admin gave a user.
+pub struct Warning {
+    /// Time the warning was given.
+    pub time: SystemTime,
+    /// Room the warning was given in.
+    pub room: OwnedRoomId,
+    /// Admin that gave the warning.
+    pub admin: OwnedUserId,
+    /// Reason given for the warning, if any.
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+/// A moderation action the bot took on behalf of an admin.
+pub struct AuditEntry {
+    /// Time the action was taken.
+    pub time: SystemTime,
+    /// Admin that requested the action.
+    pub admin: OwnedUserId,
+    /// Kind of action that was taken.
+    pub action: AuditAction,
+    /// Users the action was taken against.
+    pub users: Vec<OwnedUserId>,
+    /// Reason given for the action, if any.
+    pub reason: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+/// Activity counted since the last stats report.
+pub struct ActivityStats {
+    /// Start of the current report period. Set once the first report is scheduled.
+    pub since: Option<SystemTime>,
+    /// Number of messages the bot processed.
+    pub messages: u64,
+    /// Number of corrections made.
+    pub corrections: u64,
+    /// Number of unit conversions the bot replied to.
+    pub conversions: u64,
+    /// Number of users the bot banned.
+    pub bans: u64,
+    /// Number of failed syncs with the homeserver.
+    pub errors: u64,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+/// Corrections the bot made in a room.
+pub struct CorrectionStats {
+    /// Number of corrections made.
+    pub total: u64,
+    /// Number of corrections made for each configured spelling.
+    pub spellings: HashMap<String, u64>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// A user defined linear conversion from a custom unit to a target unit.
+pub struct CustomConversion {
+    /// Value the quantity is multiplied by to produce the converted quantity.
+    pub factor: f64,
+    /// Label of the unit the quantity is converted to.
+    pub unit: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+/// Which features are performed in direct messages with the bot.
+pub struct DirectMessagePolicy {
+    /// Bool used to determine if commands are performed.
+    pub commands: bool,
+    /// Bool used to determine if commandless unit conversions are performed.
+    pub unit_conversions: bool,
+    /// Bool used to determine if github searches are performed.
+    pub github_search: bool,
+    /// Bool used to determine if urls are linked.
+    pub links: bool,
+    /// Bool used to determine if group pings are performed.
+    pub group_pings: bool,
+    /// Bool used to determine if text expansions are performed.
+    pub text_expansions: bool,
+    /// Bool used to determine if corrections are performed.
+    pub corrections: bool,
+    /// Bool used to determine if karma is tracked.
+    pub karma: bool,
+    /// Text the bot replies with when greeted. Disabled if None.
+    pub about: Option<String>,
+}
+
+impl Default for DirectMessagePolicy {
+    fn default() -> Self {
+        Self {
+            commands: true,
+            unit_conversions: true,
+            github_search: true,
+            links: true,
+            group_pings: true,
+            text_expansions: true,
+            corrections: false,
+            karma: false,
+            about: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// An emoji used in reactions.
+///
+/// Displays as the emoji itself, or as ":shortcode:" for custom emoji.
+pub enum Emoji {
+    /// A unicode emoji like "✅".
+    Unicode(String),
+    /// A custom emoji from an image pack.
+    Custom {
+        /// Name of the emoji, without colons.
+        shortcode: String,
+        /// Image of the emoji.
+        url: OwnedMxcUri,
+    },
+}
+
+impl Emoji {
+    /// Key of a reaction with this emoji. Custom emoji are keyed by their mxc:// URI.
+    pub fn key(&self) -> &str {
+        match self {
+            Emoji::Unicode(v) => v,
+            Emoji::Custom { url, .. } => url.as_str(),
+        }
+    }
+
+    /// Checks if a reaction key is this emoji, ignoring emoji variation selectors.
+    pub fn matches(&self, key: &str) -> bool {
+        key.trim_end_matches('\u{fe0f}') == self.key().trim_end_matches('\u{fe0f}')
+    }
+}
+
+impl Display for Emoji {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Emoji::Unicode(v) => write!(f, "{}", v),
+            Emoji::Custom { shortcode, .. } => write!(f, ":{}:", shortcode),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// Action taken against a user flooding ban rooms.
+pub enum FloodAction {
+    /// Bans the user from every ban room.
+    #[default]
+    Ban,
+    /// Lowers the user's power level so they can't send messages in any ban room.
+    Mute,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Limits used to detect users flooding ban rooms and the action taken against them.
+pub struct FloodProtection {
+    /// Number of messages a user can send within `window`. Sending more is a flood.
+    pub message_limit: usize,
+    /// Length of the window messages are counted in.
+    pub window: Duration,
+    pub action: FloodAction,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// Where a user's warnings are counted.
+pub enum WarningScope {
+    /// Warnings from every room count together.
+    #[default]
+    Global,
+    /// Only warnings given in a room count in that room.
+    Room,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Number of warnings that get a user banned from every ban room and where they're counted.
+pub struct Warnings {
+    pub ban_threshold: usize,
+    pub scope: WarningScope,
+}
+
+impl Warnings {
+    /// Returns the room warnings given in `room_id` are counted in, or None if they count in every room.
+    pub fn counted_in<'a>(&self, room_id: &'a RoomId) -> Option<&'a RoomId> {
+        match self.scope {
+            WarningScope::Global => None,
+            WarningScope::Room => Some(room_id),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Room whose power levels make users admins, in addition to the configured admins.
+pub struct PowerLevelAdmins {
+    pub room: OwnedRoomId,
+    /// Power level users need in `room` to be admins.
+    pub min_power_level: i64,
+    /// Time between refreshes of the room's power levels.
+    pub refresh_interval: Duration,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Room where reacting to a message invites users to other rooms, like roles in other chat apps.
+pub struct ReactionRoles {
+    pub room: OwnedRoomId,
+    /// Emoji and the room reacting with it invites to, in the order they're listed in the role message.
+    pub roles: Vec<(Emoji, OwnedRoomId)>,
+}
+
+impl ReactionRoles {
+    /// Returns the room a reaction key invites to.
+    pub fn room_for(&self, key: &str) -> Option<&OwnedRoomId> {
+        self.roles
+            .iter()
+            .find(|(emoji, _)| emoji.matches(key))
+            .map(|(_, room)| room)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// How a unit that is both a built-in unit and a custom unit in a room is converted.
+pub enum AmbiguousUnits {
+    /// Converts it as the built-in unit.
+    #[default]
+    Builtin,
+    /// Converts it as the room's custom unit.
+    Custom,
+    /// Asks which was meant when converting with a command. Commandless conversions use the built-in unit.
+    Ask,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// Where the maintenance message goes on command replies while maintenance mode is on.
+pub enum MaintenanceBanner {
+    /// Shows the message above the reply.
+    #[default]
+    Prepend,
+    /// Shows the message below the reply.
+    Append,
+    /// Replies with only the message.
+    Replace,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// How converted values are rounded to their configured number of decimal places.
+pub enum RoundingMode {
+    /// Drops any digits past the last decimal place.
+    Truncate,
+    /// Rounds halves away from zero.
+    HalfUp,
+    /// Rounds halves to the nearest even digit.
+    #[default]
+    HalfEven,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+/// How the users of a group ping are shown.
+pub enum GroupPingFormat {
+    /// Lists the full user IDs as plain text, which clients highlight as mentions.
+    Plain,
+    /// Links each user with a matrix.to pill.
+    #[default]
+    Html,
+    /// Lists the full user IDs separated by commas.
+    List,
 }
 
 #[derive(Clone, Debug)]
@@ -237,6 +1121,89 @@ pub struct SensitiveSpelling {
     spelling: String,
 }
 
+impl Args {
+    /// Parses command line arguments, not including the program name.
+    pub fn parse(args: impl IntoIterator<Item = String>) -> anyhow::Result<Self> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let (flag, value) = match arg.split_once('=') {
+                Some((f, v)) => (f.to_string(), Some(v.to_string())),
+                None => (arg, None),
+            };
+            let target = match flag.as_str() {
+                "--config" => &mut parsed.config,
+                "--data-dir" => &mut parsed.data_dir,
+                _ => return Err(anyhow!("Unknown argument {}. {}", flag, USAGE)),
+            };
+            match value.or_else(|| args.next()) {
+                Some(v) if !v.is_empty() => *target = Some(PathBuf::from(v)),
+                _ => return Err(anyhow!("Missing path for {}. {}", flag, USAGE)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Uses these arguments for loading config and storage files. Only the first call has an effect.
+    pub fn set(self) {
+        if ARGS.set(self).is_err() {
+            trace!("Command line arguments were already set");
+        }
+    }
+}
+
+/// Resolves the path of the config file.
+///
+/// A `--config` path is used as is, otherwise config.toml is read from `MATRIX_BOT_CONFIG_DIR`
+/// or the working directory.
+pub fn resolve_config_path(cli_path: Option<&Path>, env_dir: Option<&str>) -> PathBuf {
+    match (cli_path, env_dir) {
+        (Some(p), _) => p.to_path_buf(),
+        (None, Some(d)) => Path::new(d).join("config.toml"),
+        (None, None) => PathBuf::from("config.toml"),
+    }
+}
+
+/// Resolves the path of a storage file in the `--data-dir`, `MATRIX_BOT_DATA_DIR`, or working directory.
+pub fn resolve_data_path(cli_dir: Option<&Path>, env_dir: Option<&str>, file: &str) -> PathBuf {
+    match (cli_dir, env_dir) {
+        (Some(d), _) => d.join(file),
+        (None, Some(d)) => Path::new(d).join(file),
+        (None, None) => PathBuf::from(file),
+    }
+}
+
+fn config_path() -> PathBuf {
+    resolve_config_path(
+        ARGS.get().and_then(|a| a.config.as_deref()),
+        env::var("MATRIX_BOT_CONFIG_DIR").ok().as_deref(),
+    )
+}
+
+/// Replaces the contents of a storage file.
+///
+/// Writes to a temporary file next to it first and renames it over the old file, so a save that
+/// is interrupted or shorter than the old file never leaves a file that can't be loaded.
+fn write_storage_file(path: &Path, contents: &str) -> anyhow::Result<()> {
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+    let mut file = File::create(&temp)
+        .with_context(|| format!("Unable to open {} during save", temp.display()))?;
+    file.write_all(contents.as_bytes())
+        .and_then(|_| file.sync_all())
+        .with_context(|| format!("Unable to write {}", temp.display()))?;
+    std::fs::rename(&temp, path).with_context(|| format!("Unable to replace {}", path.display()))
+}
+
+fn data_path(file: &str) -> PathBuf {
+    resolve_data_path(
+        ARGS.get().and_then(|a| a.data_dir.as_deref()),
+        env::var("MATRIX_BOT_DATA_DIR").ok().as_deref(),
+        file,
+    )
+}
+
 impl MatrixListenerConfig {
     pub fn new(config: &Config) -> Self {
         Self {
@@ -245,23 +1212,140 @@ impl MatrixListenerConfig {
             mx_pass: config.mx_pass.clone(),
             gh_access_token: config.gh_access_token.clone(),
             enable_unit_conversions: config.enable_unit_conversions,
+            convert_on_edit: config.convert_on_edit,
+            passive_conversions: config.passive_conversions.clone(),
+            slash_commands: config.slash_commands.clone(),
+            command_prefix: config.command_prefix.clone(),
+            passive_conversion_cooldown: config.passive_conversion_cooldown,
+            speed_of_sound: config.speed_of_sound,
+            verbose_conversions: config.verbose_conversions,
+            no_conversion_reply: config.no_conversion_reply.clone(),
+            enable_compound_volumes: config.enable_compound_volumes,
+            enable_command_index: config.enable_command_index,
             enable_corrections: config.enable_corrections,
+            enable_response_redaction: config.enable_response_redaction,
+            enable_sync_filter: config.enable_sync_filter,
+            use_reactions: config.use_reactions,
+            start_time: SystemTime::now(),
+            dry_run: config.dry_run,
+            sync_save_interval: config.sync_save_interval,
+            idle_ping_interval: config.idle_ping_interval,
+            stats_room: config.stats_room.clone(),
+            stats_report_interval: config.stats_report_interval,
             unit_conversion_exclusion: config.unit_conversion_exclusion.clone(),
-            incorrect_spellings: config.incorrect_spellings.clone(),
+            conversion_marker: config.conversion_marker.clone(),
+            default_unit_conversion_precision: config.default_unit_conversion_precision,
+            unit_conversion_precision: config.unit_conversion_precision.clone(),
+            rounding_mode: config.rounding_mode,
+            ambiguous_units: config.ambiguous_units,
+            incorrect_spellings: RwLock::new(config.incorrect_spellings.clone()),
             correction_text: config.correction_text.clone(),
             correction_exclusion: config.correction_exclusion.clone(),
-            linkers: config.linkers.clone(),
+            correction_cooldown_overrides: config.correction_cooldown_overrides.clone(),
+            correction_batch_window: config.correction_batch_window,
             admins: config.admins.clone(),
+            super_admins: config.super_admins.clone(),
+            ban_authorized_users: config.ban_authorized_users.clone(),
+            power_level_admins: config.power_level_admins.clone(),
+            blocked_servers: config.blocked_servers.clone(),
             help_rooms: config.help_rooms.clone(),
             ban_rooms: config.ban_rooms.clone(),
-            repos: config.repos.clone(),
-            links: config.links.clone(),
+            ban_confirmation_threshold: config.ban_confirmation_threshold,
+            ban_room_confirmation_threshold: config.ban_room_confirmation_threshold,
+            confirmation_reaction: config.confirmation_reaction.clone(),
+            flood_protection: config.flood_protection,
+            warnings: config.warnings,
+            moderation_requires_encryption: config.moderation_requires_encryption,
+            maintenance_banner: config.maintenance_banner,
+            enable_unauthorized_reply: config.enable_unauthorized_reply,
+            unauthorized_reply: config.unauthorized_reply.clone(),
+            max_replies_per_user: config.max_replies_per_user,
+            max_event_age: config.max_event_age,
+            help_cooldown: config.help_cooldown,
+            duplicate_conversion_window: config.duplicate_conversion_window,
+            enable_karma: config.enable_karma,
+            direct_messages: config.direct_messages.clone(),
+            reaction_roles: config.reaction_roles.clone(),
+            link_settings: RwLock::new(LinkSettings {
+                repos: config.repos.clone(),
+                links: config.links.clone(),
+                linkers: config.linkers.clone(),
+            }),
             text_expansions: config.text_expansions.clone(),
             user_agent: config.user_agent.clone(),
-            group_pings: config.group_pings.clone(),
-            group_ping_users: config.group_ping_users.clone(),
+            group_pings: RwLock::new(config.group_pings.clone()),
+            group_ping_format: config.group_ping_format,
+            group_ping_cooldown: config.group_ping_cooldown,
+            custom_conversions: config.custom_conversions.clone(),
+            allowed_commands: config.allowed_commands.clone(),
+            command_prefixes: config.command_prefixes.clone(),
+        }
+    }
+
+    /// Reloads group pings from the contents of a config.toml.
+    ///
+    /// The current group pings are kept if the new ones are invalid.
+    /// Returns the number of groups loaded.
+    pub fn reload_group_pings(&self, contents: &str) -> anyhow::Result<usize> {
+        let toml: RawConfig = toml::from_str(contents).context("Invalid toml")?;
+        let loaded = load_group_ping_settings(&toml)?;
+        let count = loaded.groups.len();
+        let mut group_pings = self
+            .group_pings
+            .write()
+            .map_err(|_| anyhow!("Group ping lock is poisoned"))?;
+        *group_pings = loaded;
+        Ok(count)
+    }
+
+    /// Reloads searchable repos and linkable URLs from the contents of a config.toml.
+    ///
+    /// The current repos and links are kept if the new ones are invalid.
+    /// Returns the number of repos and links loaded.
+    pub fn reload_links(&self, contents: &str) -> anyhow::Result<(usize, usize)> {
+        let toml: RawConfig = toml::from_str(contents).context("Invalid toml")?;
+        let (repos, _) = load_github_settings(&toml)?;
+        let (linkers, links) = load_linker_settings(&toml)?;
+        let counts = (repos.len(), links.len());
+        let mut link_settings = self
+            .link_settings
+            .write()
+            .map_err(|_| anyhow!("Link lock is poisoned"))?;
+        *link_settings = LinkSettings {
+            repos,
+            links,
+            linkers,
+        };
+        Ok(counts)
+    }
+
+    /// Checks if `command` may be used in the supplied room.
+    ///
+    /// Rooms without an allowlist allow every command.
+    pub fn command_allowed(&self, room_id: &RoomId, command: &str) -> bool {
+        match self.allowed_commands.get(room_id) {
+            Some(v) => v.contains(command),
+            None => true,
         }
     }
+
+    /// Returns the prefix commands start with in the supplied room.
+    ///
+    /// Rooms without their own prefix use the global prefix.
+    pub fn command_prefix(&self, room_id: &RoomId) -> &str {
+        self.command_prefixes
+            .get(room_id)
+            .unwrap_or(&self.command_prefix)
+    }
+
+    /// Returns the minimum time between corrections in the supplied room.
+    ///
+    /// Rooms without a cooldown override use the global cooldown.
+    pub fn correction_cooldown(&self, room_id: &RoomId) -> Duration {
+        self.correction_cooldown_overrides
+            .get(room_id)
+            .map_or(CORRECTION_COOLDOWN, |v| Duration::from_secs(*v))
+    }
 }
 
 impl Config {
@@ -275,28 +1359,84 @@ impl Config {
     /// If something is disabled, the value in the final struct is just "new" or "blank" but
     /// does not utilize Option<T> for ease of use and matching later on in the program.
     pub fn load_config() -> anyhow::Result<Self> {
-        let path = match env::var("MATRIX_BOT_CONFIG_DIR") {
-            Ok(v) => [&v, "config.toml"].iter().collect::<PathBuf>(),
-            Err(_) => ["config.toml"].iter().collect::<PathBuf>(),
-        };
+        Self::parse_config(&Self::read_config_file()?)
+    }
+
+    /// Reads the contents of the config file given by `--config`, or the config.toml in
+    /// `MATRIX_BOT_CONFIG_DIR` or the working directory.
+    pub fn read_config_file() -> anyhow::Result<String> {
+        let path = config_path();
         // File Load Section
         let mut file = File::open(&path)
             .with_context(|| format!("Unable to open config file at {:?}", path))?;
         let mut contents = String::new();
         file.read_to_string(&mut contents)
             .with_context(|| format!("Unable to read file contents at {:?}", path))?;
-        let toml: RawConfig = toml::from_str(&contents).context("Invalid toml")?;
+        Ok(contents)
+    }
+
+    /// Parses the contents of a config.toml into the final config struct.
+    ///
+    /// Split from `load_config` so config text can be validated without touching the filesystem.
+    pub fn parse_config(contents: &str) -> anyhow::Result<Self> {
+        let toml: RawConfig = toml::from_str(contents).context("Invalid toml")?;
 
         // Set variables and exit/error if set improperly
         let (repos, gh_access_token) = load_github_settings(&toml)?;
         let (linkers, links) = load_linker_settings(&toml)?;
         let text_expansions = load_text_expansions(&toml);
         let unit_conversion_exclusion = load_unit_conversion_settings(&toml);
-        let (incorrect_spellings, correction_text, correction_exclusion) =
-            load_spell_correct_settings(&toml)?;
+        let conversion_marker = load_conversion_marker_settings(&toml)?;
+        let (
+            incorrect_spellings,
+            correction_text,
+            correction_exclusion,
+            correction_cooldown_overrides,
+        ) = load_spell_correct_settings(&toml)?;
+        let correction_batch_window = toml
+            .general
+            .correction_batch_seconds
+            .map(Duration::from_secs);
         let admins = load_admin_settings(&toml)?;
+        let super_admins = load_super_admin_settings(&toml, &admins)?;
+        let ban_authorized_users = toml.general.ban_authorized_users.clone();
+        let power_level_admins = load_power_level_admin_settings(&toml)?;
+        let blocked_servers = toml.general.blocked_servers.clone().unwrap_or_default();
         let help_rooms = load_help_settings(&toml);
         let ban_rooms = load_ban_room_settings(&toml);
+        let ban_confirmation_threshold = toml.general.ban_confirmation_threshold.unwrap_or(1);
+        let ban_room_confirmation_threshold = toml.general.ban_room_confirmation_threshold;
+        let flood_protection = load_flood_protection_settings(&toml, &ban_rooms)?;
+        let warnings = load_warning_settings(&toml, &ban_rooms)?;
+        let confirmation_reaction = load_confirmation_reaction_settings(&toml)?;
+        let moderation_requires_encryption =
+            toml.general.moderation_requires_encryption.unwrap_or(false);
+        let maintenance_banner = toml.general.maintenance_banner.unwrap_or_default();
+        let enable_unauthorized_reply = toml.general.enable_unauthorized_reply.unwrap_or(false);
+        let unauthorized_reply = toml
+            .general
+            .unauthorized_reply
+            .clone()
+            .unwrap_or_else(|| DEFAULT_UNAUTHORIZED_REPLY.to_string());
+        let max_replies_per_user = toml.general.max_replies_per_user;
+        let messages_per_second = toml.general.messages_per_second;
+        if let Some(v) = messages_per_second {
+            if !(v.is_finite() && v > 0.0) {
+                return Err(anyhow!(
+                    "Messages per second must be a positive number, got {}",
+                    v
+                ));
+            }
+        }
+        let max_event_age = toml.general.max_event_age_seconds.map(Duration::from_secs);
+        let help_cooldown = toml.general.help_cooldown_seconds.map(Duration::from_secs);
+        let duplicate_conversion_window = toml
+            .general
+            .duplicate_conversion_seconds
+            .map(Duration::from_secs);
+        let enable_karma = toml.general.enable_karma.unwrap_or(false);
+        let direct_messages = toml.direct_messages.clone().unwrap_or_default();
+        let reaction_roles = load_reaction_roles_settings(&toml)?;
         let (mx_url, mx_uname, mx_pass, enable_corrections, enable_unit_conversions) = (
             toml.matrix_authentication
                 .url
@@ -316,7 +1456,53 @@ impl Config {
                 )
             })?;
 
-        let (group_pings, group_ping_users) = load_group_ping_settings(&toml)?;
+        let group_pings = load_group_ping_settings(&toml)?;
+        let (display_name, avatar_url) = load_profile_settings(&toml)?;
+        let custom_conversions = load_custom_conversion_settings(&toml)?;
+        let enable_response_redaction = toml.general.enable_response_redaction.unwrap_or(false);
+        let enable_sync_filter = toml.general.enable_sync_filter.unwrap_or(false);
+        let use_reactions = toml.general.use_reactions.unwrap_or(false);
+        let dry_run = toml.general.dry_run.unwrap_or(false);
+        let sync_save_interval = Duration::from_secs(toml.general.sync_save_seconds.unwrap_or(0));
+        let idle_ping_interval = load_idle_ping_settings(&toml)?;
+        let stats_room = toml.general.stats_room.clone();
+        let stats_report_interval = match toml.general.stats_report_hours {
+            Some(0) => return Err(anyhow!("Stats report interval must be at least 1 hour")),
+            Some(v) => Duration::from_secs(v * 3600),
+            None => DEFAULT_STATS_REPORT_INTERVAL,
+        };
+        let allowed_commands = load_allowed_command_settings(&toml);
+        let log_levels = load_log_level_settings(&toml)?;
+        let convert_on_edit = toml.general.convert_on_edit.unwrap_or(true);
+        let passive_conversions = toml.general.passive_conversions.clone().unwrap_or_default();
+        let slash_commands = load_slash_command_settings(&toml);
+        let (command_prefix, command_prefixes) = load_command_prefix_settings(&toml)?;
+        let passive_conversion_cooldown =
+            Duration::from_secs(toml.general.passive_conversion_cooldown.unwrap_or(60));
+        let speed_of_sound = toml
+            .general
+            .speed_of_sound
+            .unwrap_or(DEFAULT_SPEED_OF_SOUND);
+        if !(speed_of_sound.is_finite() && speed_of_sound > 0.0) {
+            return Err(anyhow!(
+                "Speed of sound must be a positive number of m/s, got {}",
+                speed_of_sound
+            ));
+        }
+        let verbose_conversions = toml.general.verbose_conversions.unwrap_or(false);
+        let no_conversion_reply = toml
+            .general
+            .no_conversion_reply
+            .clone()
+            .unwrap_or_else(|| DEFAULT_NO_CONVERSION_REPLY.to_string());
+        let enable_compound_volumes = toml.general.enable_compound_volumes.unwrap_or(false);
+        let enable_command_index = toml.general.enable_command_index.unwrap_or(false);
+        let (default_unit_conversion_precision, unit_conversion_precision) =
+            load_unit_conversion_precision_settings(&toml)?;
+        let rounding_mode = toml.general.rounding_mode.unwrap_or_default();
+        let ambiguous_units = toml.general.ambiguous_units.unwrap_or_default();
+        let group_ping_format = toml.general.group_ping_format.unwrap_or_default();
+        let group_ping_cooldown = toml.general.group_ping_cooldown.map(Duration::from_secs);
         let webhook_token = toml.general.webhook_token;
 
         // Return value
@@ -324,23 +1510,76 @@ impl Config {
             mx_url,
             mx_uname,
             mx_pass,
+            display_name,
+            avatar_url,
             gh_access_token,
             enable_unit_conversions,
+            convert_on_edit,
+            passive_conversions,
+            slash_commands,
+            command_prefix,
+            passive_conversion_cooldown,
+            speed_of_sound,
+            verbose_conversions,
+            no_conversion_reply,
+            enable_compound_volumes,
+            enable_command_index,
             enable_corrections,
+            enable_response_redaction,
+            enable_sync_filter,
+            use_reactions,
+            sync_save_interval,
+            idle_ping_interval,
+            stats_room,
+            stats_report_interval,
             unit_conversion_exclusion,
+            conversion_marker,
+            default_unit_conversion_precision,
+            unit_conversion_precision,
+            rounding_mode,
+            ambiguous_units,
+            group_ping_format,
+            group_ping_cooldown,
             incorrect_spellings,
             correction_text,
             correction_exclusion,
+            correction_cooldown_overrides,
+            correction_batch_window,
             linkers,
             text_expansions,
             admins,
+            super_admins,
+            ban_authorized_users,
+            power_level_admins,
+            blocked_servers,
             help_rooms,
             ban_rooms,
+            ban_confirmation_threshold,
+            ban_room_confirmation_threshold,
+            confirmation_reaction,
+            flood_protection,
+            warnings,
+            moderation_requires_encryption,
+            maintenance_banner,
+            enable_unauthorized_reply,
+            unauthorized_reply,
+            max_replies_per_user,
+            messages_per_second,
+            dry_run,
+            max_event_age,
+            help_cooldown,
+            duplicate_conversion_window,
+            enable_karma,
+            direct_messages,
+            reaction_roles,
             repos,
             links,
             user_agent,
             group_pings,
-            group_ping_users,
+            custom_conversions,
+            allowed_commands,
+            command_prefixes,
+            log_levels,
             webhook_token,
         })
     }
@@ -353,10 +1592,7 @@ impl SessionStorage {
     ///
     /// If file exists, attempts load and will exit program if it fails.
     pub fn load_storage() -> anyhow::Result<Self> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "session.ron".to_string()].iter().collect::<PathBuf>(),
-            Err(_) => ["session.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("session.ron");
         let mut file = match File::open(path) {
             Ok(v) => v,
             Err(e) => match e.kind() {
@@ -384,19 +1620,10 @@ impl SessionStorage {
     ///
     /// One of the few functions that can terminate the program if it doesnt go well.
     pub fn save_storage(&self) -> anyhow::Result<()> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "session.ron".to_string()].iter().collect::<PathBuf>(),
-            Err(_) => ["session.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("session.ron");
         let ron = ron::to_string(self)
             .context("Unable to format session.ron save data as RON. This should never occur!")?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(path)
-            .context("Unable to open session.ron during saving")?;
-        file.write_all(ron.as_bytes())
-            .context("Unable to write session data")?;
+        write_storage_file(&path, &ron).context("Unable to write session data")?;
         trace!("Saved Session!");
         Ok(())
     }
@@ -409,12 +1636,7 @@ impl ListenerStorage {
     ///
     /// If file exists, attempts load and will exit program if it fails.
     pub fn load_storage() -> anyhow::Result<Self> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "matrix_listener.ron".to_string()]
-                .iter()
-                .collect::<PathBuf>(),
-            Err(_) => ["matrix_listener.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("matrix_listener.ron");
         let mut file = match File::open(path) {
             Ok(v) => v,
             Err(e) => match e.kind() {
@@ -447,21 +1669,11 @@ impl ListenerStorage {
     ///
     /// One of the few functions that can terminate the program if it doesnt go well.
     pub fn save_storage(&self) -> anyhow::Result<()> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "matrix_listener.ron".to_string()]
-                .iter()
-                .collect::<PathBuf>(),
-            Err(_) => ["matrix_listener.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("matrix_listener.ron");
         let ron = ron::to_string(self).context(
             "Unable to format matrix_listener.ron save data as RON. This should never occur!",
         )?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(path)
-            .context("Unable to open matrix_listener.ron during save")?;
-        file.write_all(ron.as_bytes())
+        write_storage_file(&path, &ron)
             .context("Unable to write matrix_listener.ron while saving")?;
         trace!("Saved Session!");
         Ok(())
@@ -469,15 +1681,464 @@ impl ListenerStorage {
     /// Checks that the correction time cooldown for a specific room has passed.
     ///
     /// Returns true if there has never been a correction done in the room before.
-    pub fn correction_time_cooldown(&self, room_id: &RoomId) -> bool {
+    pub fn correction_time_cooldown(&self, room_id: &RoomId, cooldown: Duration) -> bool {
         match self.last_correction_time.get(room_id) {
             Some(t) => match t.elapsed() {
-                Ok(d) => d >= Duration::new(300, 0),
+                Ok(d) => d >= cooldown,
                 Err(_) => false,
             },
             None => true, // Will only be None if this client has not yet corrected anyone in specified room, so return true to allow correction
         }
     }
+    /// Checks that the passive conversion cooldown for a specific room has passed.
+    ///
+    /// Returns true if there has never been a passive conversion done in the room before.
+    pub fn passive_conversion_cooldown(&self, room_id: &RoomId, cooldown: Duration) -> bool {
+        match self.last_passive_conversion_time.get(room_id) {
+            Some(t) => match t.elapsed() {
+                Ok(d) => d >= cooldown,
+                Err(_) => false,
+            },
+            None => true,
+        }
+    }
+    /// Checks that a user's group ping cooldown has passed.
+    ///
+    /// Returns true if the user has never started a group ping before.
+    pub fn group_ping_cooldown(&self, user: &UserId, cooldown: Duration, now: SystemTime) -> bool {
+        match self.last_group_ping_time.get(user) {
+            Some(t) => match now.duration_since(*t) {
+                Ok(d) => d >= cooldown,
+                Err(_) => false,
+            },
+            None => true,
+        }
+    }
+    /// Checks if a room is a direct message, meaning the bot and one other user are its only members.
+    ///
+    /// Rooms with an unknown member count are not direct messages.
+    pub fn is_direct_message(&self, room_id: &RoomId) -> bool {
+        self.joined_member_counts.get(room_id) == Some(&2)
+    }
+    /// Returns the data substituted into message templates sent to a room.
+    ///
+    /// Rooms without a known name use their room id as the name.
+    pub fn template_data<'a>(
+        &'a self,
+        room_id: &'a RoomId,
+        sender: &'a UserId,
+    ) -> TemplateData<'a> {
+        TemplateData {
+            time: Some(SystemTime::now()),
+            room: Some(
+                self.room_names
+                    .get(room_id)
+                    .map_or(room_id.as_str(), String::as_str),
+            ),
+            member_count: self.joined_member_counts.get(room_id).copied(),
+            sender: Some(sender),
+        }
+    }
+    /// Checks if a user is a configured admin, has enough power in the power level admins room,
+    /// or has been granted admin that hasn't expired yet.
+    pub fn is_admin(&self, user: &UserId, admins: &HashSet<OwnedUserId>, now: SystemTime) -> bool {
+        admins.contains(user)
+            || self.power_level_admins.contains(user)
+            || matches!(self.temporary_admins.get(user), Some(expiry) if *expiry > now)
+    }
+    /// Grants admin to a user until `duration` after `now`, dropping grants that have already expired.
+    pub fn grant_admin(&mut self, user: &UserId, duration: Duration, now: SystemTime) {
+        self.temporary_admins.retain(|_, expiry| *expiry > now);
+        self.temporary_admins
+            .insert(user.to_owned(), now + duration);
+    }
+    /// Removes a user's temporarily granted admin.
+    ///
+    /// Returns true if the user had been granted admin.
+    pub fn revoke_admin(&mut self, user: &UserId) -> bool {
+        self.temporary_admins.remove(user).is_some()
+    }
+    /// Counts a correction of `spelling` in a room.
+    ///
+    /// Only the first few distinct spellings in a room are counted individually so stats stay bounded,
+    /// but every correction counts towards the total.
+    pub fn record_correction(&mut self, room_id: &RoomId, spelling: &str) {
+        self.activity.corrections += 1;
+        let stats = self.correction_stats.entry(room_id.to_owned()).or_default();
+        stats.total += 1;
+        if stats.spellings.len() < MAX_TRACKED_SPELLINGS || stats.spellings.contains_key(spelling) {
+            *stats.spellings.entry(spelling.to_owned()).or_default() += 1;
+        }
+    }
+    /// Returns the event the bot corrected a user for in a room if it was recent enough to edit that correction.
+    pub fn recent_correction(
+        &self,
+        room_id: &RoomId,
+        user: &UserId,
+        now: Instant,
+    ) -> Option<&OwnedEventId> {
+        match self
+            .sent_corrections
+            .get(&(room_id.to_owned(), user.to_owned()))
+        {
+            Some((t, event_id)) if now.duration_since(*t) < CORRECTION_EDIT_WINDOW => {
+                Some(event_id)
+            }
+            _ => None,
+        }
+    }
+    /// Remembers the event a correction was sent for, unless a recent correction of the user in the room is being edited.
+    pub fn record_sent_correction(
+        &mut self,
+        room_id: &RoomId,
+        user: &UserId,
+        event_id: &EventId,
+        now: Instant,
+    ) {
+        self.sent_corrections
+            .retain(|_, (t, _)| now.duration_since(*t) < CORRECTION_EDIT_WINDOW);
+        self.sent_corrections
+            .entry((room_id.to_owned(), user.to_owned()))
+            .or_insert_with(|| (now, event_id.to_owned()));
+    }
+    /// Adds a misspelling to a user's pending correction in a room, restarting the window the burst ends after.
+    pub fn queue_correction(
+        &mut self,
+        room_id: &RoomId,
+        user: &UserId,
+        event_id: &EventId,
+        spelling: &str,
+        window: Duration,
+        now: Instant,
+    ) {
+        let pending = self
+            .pending_corrections
+            .entry((room_id.to_owned(), user.to_owned()))
+            .or_insert_with(|| PendingCorrection {
+                spellings: Vec::new(),
+                event_id: event_id.to_owned(),
+                due: now,
+            });
+        if !pending.spellings.iter().any(|s| s == spelling) {
+            pending.spellings.push(spelling.to_owned());
+        }
+        pending.event_id = event_id.to_owned();
+        pending.due = now + window;
+    }
+    /// Removes and returns the pending corrections whose bursts have ended, oldest first.
+    pub fn take_due_corrections(
+        &mut self,
+        now: Instant,
+    ) -> Vec<(OwnedRoomId, OwnedUserId, PendingCorrection)> {
+        let due: Vec<CorrectionBurst> = self
+            .pending_corrections
+            .iter()
+            .filter(|(_, pending)| pending.due <= now)
+            .map(|(burst, _)| burst.clone())
+            .collect();
+        let mut corrections: Vec<_> = due
+            .into_iter()
+            .filter_map(|burst| {
+                let pending = self.pending_corrections.remove(&burst)?;
+                Some((burst.0, burst.1, pending))
+            })
+            .collect();
+        corrections.sort_by_key(|(_, _, pending)| pending.due);
+        corrections
+    }
+    /// Returns when the next pending correction is due, if any are pending.
+    pub fn next_correction_due(&self) -> Option<Instant> {
+        self.pending_corrections.values().map(|p| p.due).min()
+    }
+    /// Checks if a stats report period has ended, starting the first period if none has started yet.
+    pub fn stats_report_due(&mut self, interval: Duration, now: SystemTime) -> bool {
+        match self.activity.since {
+            Some(since) => now >= since + interval,
+            None => {
+                self.activity.since = Some(now);
+                false
+            }
+        }
+    }
+    /// Adds the users the responder banned since the last call to the activity.
+    pub fn count_bans(&mut self) {
+        self.activity.bans += self.banned_users.swap(0, Ordering::Relaxed);
+    }
+    /// Returns the activity of the period that ended and starts counting a new period at `now`.
+    pub fn take_activity(&mut self, now: SystemTime) -> ActivityStats {
+        self.count_bans();
+        std::mem::replace(
+            &mut self.activity,
+            ActivityStats {
+                since: Some(now),
+                ..ActivityStats::default()
+            },
+        )
+    }
+    /// Returns the karma of a user in a room.
+    pub fn karma(&self, room_id: &RoomId, user: &UserId) -> i64 {
+        self.karma
+            .get(room_id)
+            .and_then(|r| r.get(user))
+            .copied()
+            .unwrap_or(0)
+    }
+    /// Changes the karma of `receiver` by `change` unless `giver` changed it within the karma cooldown.
+    ///
+    /// Returns true if the karma was changed.
+    pub fn change_karma(
+        &mut self,
+        room_id: &RoomId,
+        giver: &UserId,
+        receiver: &UserId,
+        change: i64,
+        now: Instant,
+    ) -> bool {
+        let key = (room_id.to_owned(), giver.to_owned(), receiver.to_owned());
+        if let Some(t) = self.last_karma_time.get(&key) {
+            if now.duration_since(*t) < KARMA_COOLDOWN {
+                return false;
+            }
+        }
+        self.last_karma_time.insert(key, now);
+        *self
+            .karma
+            .entry(room_id.to_owned())
+            .or_default()
+            .entry(receiver.to_owned())
+            .or_default() += change;
+        true
+    }
+    /// Records a help message for `topic` in a room unless one was sent there within `cooldown`.
+    ///
+    /// Returns true if the help message can be sent.
+    pub fn help_cooldown(
+        &mut self,
+        room_id: &RoomId,
+        topic: &str,
+        cooldown: Duration,
+        now: Instant,
+    ) -> bool {
+        let key = (room_id.to_owned(), topic.to_owned());
+        if let Some(t) = self.last_help_time.get(&key) {
+            if now.duration_since(*t) < cooldown {
+                return false;
+            }
+        }
+        self.last_help_time.insert(key, now);
+        true
+    }
+    /// Records conversions requested in a room unless the same conversions were answered there within `window`.
+    ///
+    /// Returns true if the conversions are a duplicate that shouldn't be answered.
+    pub fn duplicate_conversion(
+        &mut self,
+        room_id: &RoomId,
+        conversions: &str,
+        window: Duration,
+        now: Instant,
+    ) -> bool {
+        self.last_conversion_time
+            .retain(|_, t| now.duration_since(*t) < window);
+        let key = (room_id.to_owned(), conversions.to_owned());
+        if self.last_conversion_time.contains_key(&key) {
+            return true;
+        }
+        self.last_conversion_time.insert(key, now);
+        false
+    }
+    /// Pauses GitHub searches until `until`.
+    pub fn pause_github(&mut self, until: SystemTime) {
+        self.github_paused_until = Some(until);
+        self.github_pause_notified = false;
+    }
+    /// Checks if GitHub searches are paused because of a rate limit.
+    pub fn github_paused(&self, now: SystemTime) -> bool {
+        matches!(self.github_paused_until, Some(t) if now < t)
+    }
+    /// Returns true the first time it is called during a GitHub pause, so users are only told once.
+    pub fn github_pause_notice(&mut self) -> bool {
+        !std::mem::replace(&mut self.github_pause_notified, true)
+    }
+    /// Records telling a user they can't use an admin command unless they were told within the cooldown.
+    ///
+    /// Returns true if the user can be told.
+    pub fn unauthorized_reply_cooldown(&mut self, user: &UserId, now: Instant) -> bool {
+        if let Some(t) = self.last_unauthorized_reply_time.get(user) {
+            if now.duration_since(*t) < UNAUTHORIZED_REPLY_COOLDOWN {
+                return false;
+            }
+        }
+        self.last_unauthorized_reply_time
+            .insert(user.to_owned(), now);
+        true
+    }
+    /// Timezone a user set for themselves. None if they haven't set one.
+    pub fn user_timezone(&self, user: &UserId) -> Option<Tz> {
+        self.user_timezones.get(user).and_then(|v| find_timezone(v))
+    }
+    /// Records sending the about text in a room unless it was sent there within the cooldown.
+    ///
+    /// Returns true if the about text can be sent.
+    pub fn about_reply_cooldown(&mut self, room_id: &RoomId, now: Instant) -> bool {
+        if let Some(t) = self.last_about_reply_time.get(room_id) {
+            if now.duration_since(*t) < ABOUT_REPLY_COOLDOWN {
+                return false;
+            }
+        }
+        self.last_about_reply_time.insert(room_id.to_owned(), now);
+        true
+    }
+    /// Records inviting a user to a room through a reaction role unless they were invited there within the cooldown.
+    ///
+    /// Returns true if the invite can be sent.
+    pub fn role_invite_cooldown(&mut self, room_id: &RoomId, user: &UserId, now: Instant) -> bool {
+        let key = (room_id.to_owned(), user.to_owned());
+        if let Some(t) = self.last_role_invite_time.get(&key) {
+            if now.duration_since(*t) < ROLE_INVITE_COOLDOWN {
+                return false;
+            }
+        }
+        self.last_role_invite_time.insert(key, now);
+        true
+    }
+    /// Returns the joined members of a room if they were fetched recently enough to reuse.
+    pub fn room_members(&self, room_id: &RoomId, now: Instant) -> Option<&HashSet<OwnedUserId>> {
+        match self.room_members.get(room_id) {
+            Some((t, members)) if now.duration_since(*t) < ROOM_MEMBERS_TTL => Some(members),
+            _ => None,
+        }
+    }
+    /// Remembers the joined members of a room, dropping members of other rooms that are too old to reuse.
+    pub fn set_room_members(
+        &mut self,
+        room_id: &RoomId,
+        members: HashSet<OwnedUserId>,
+        now: Instant,
+    ) {
+        self.room_members
+            .retain(|_, (t, _)| now.duration_since(*t) < ROOM_MEMBERS_TTL);
+        self.room_members.insert(room_id.to_owned(), (now, members));
+    }
+    /// Returns the members of a group ping room if they were fetched recently enough to reuse.
+    pub fn alias_members(
+        &self,
+        alias: &RoomAliasId,
+        now: Instant,
+    ) -> Option<&HashSet<OwnedUserId>> {
+        match self.alias_members.get(alias) {
+            Some((t, members)) if now.duration_since(*t) < ROOM_MEMBERS_TTL => Some(members),
+            _ => None,
+        }
+    }
+    /// Remembers the members of a group ping room, dropping members of other rooms that are too old to reuse.
+    pub fn set_alias_members(
+        &mut self,
+        alias: &RoomAliasId,
+        members: HashSet<OwnedUserId>,
+        now: Instant,
+    ) {
+        self.alias_members
+            .retain(|_, (t, _)| now.duration_since(*t) < ROOM_MEMBERS_TTL);
+        self.alias_members.insert(alias.to_owned(), (now, members));
+    }
+    /// Holds a ban until the admin that requested it confirms it, replacing their previous pending ban.
+    pub fn set_pending_ban(&mut self, admin: &UserId, ban: MatrixBanMessage, now: Instant) {
+        self.pending_bans
+            .retain(|_, (t, _)| now.duration_since(*t) < BAN_CONFIRMATION_WINDOW);
+        self.pending_bans.insert(admin.to_owned(), (now, ban));
+    }
+    /// Removes and returns an admin's pending ban if it was requested within the confirmation window.
+    pub fn take_pending_ban(&mut self, admin: &UserId, now: Instant) -> Option<MatrixBanMessage> {
+        match self.pending_bans.remove(admin) {
+            Some((t, ban)) if now.duration_since(t) < BAN_CONFIRMATION_WINDOW => Some(ban),
+            _ => None,
+        }
+    }
+    /// Checks if a user has been sent `limit` or more replies in their current reply budget window.
+    pub fn reply_budget_exhausted(&self, user: &UserId, limit: usize, now: Instant) -> bool {
+        match self.reply_counts.get(user) {
+            Some((start, count)) => {
+                now.duration_since(*start) < REPLY_BUDGET_WINDOW && *count >= limit
+            }
+            None => false,
+        }
+    }
+    /// Adds `replies` to a user's reply count, starting a new window if the previous one has passed.
+    pub fn record_replies(&mut self, user: &UserId, replies: usize, now: Instant) {
+        let (start, count) = self.reply_counts.entry(user.to_owned()).or_insert((now, 0));
+        if now.duration_since(*start) >= REPLY_BUDGET_WINDOW {
+            *start = now;
+            *count = 0;
+        }
+        *count += replies;
+    }
+    /// Records a message from a user and checks if they sent more than `limit` messages within `window`.
+    ///
+    /// A flooding user's messages are forgotten so one flood only triggers one action.
+    pub fn record_flood_message(
+        &mut self,
+        user: &UserId,
+        limit: usize,
+        window: Duration,
+        now: Instant,
+    ) -> bool {
+        self.recent_messages.retain(|_, times| {
+            while matches!(times.front(), Some(t) if now.duration_since(*t) >= window) {
+                times.pop_front();
+            }
+            !times.is_empty()
+        });
+        let times = self.recent_messages.entry(user.to_owned()).or_default();
+        times.push_back(now);
+        if times.len() > limit {
+            self.recent_messages.remove(user);
+            true
+        } else {
+            false
+        }
+    }
+    /// Records a warning given to a user and returns their number of warnings counted in `room_id`.
+    ///
+    /// Warnings in every room are counted if `room_id` is None.
+    pub fn add_warning(
+        &mut self,
+        user: &UserId,
+        warning: Warning,
+        room_id: Option<&RoomId>,
+    ) -> usize {
+        self.warnings
+            .entry(user.to_owned())
+            .or_default()
+            .push(warning);
+        self.warning_count(user, room_id)
+    }
+    /// Number of warnings a user was given in `room_id`, or in every room if None.
+    pub fn warning_count(&self, user: &UserId, room_id: Option<&RoomId>) -> usize {
+        match self.warnings.get(user) {
+            Some(v) => v
+                .iter()
+                .filter(|w| room_id.is_none_or(|r| w.room == r))
+                .count(),
+            None => 0,
+        }
+    }
+    /// Removes the warnings a user was given in `room_id`, or in every room if None.
+    ///
+    /// Returns the number of warnings removed.
+    pub fn clear_warnings(&mut self, user: &UserId, room_id: Option<&RoomId>) -> usize {
+        let warnings = match self.warnings.get_mut(user) {
+            Some(v) => v,
+            None => return 0,
+        };
+        let before = warnings.len();
+        warnings.retain(|w| room_id.is_some_and(|r| w.room != r));
+        let removed = before - warnings.len();
+        if warnings.is_empty() {
+            self.warnings.remove(user);
+        }
+        removed
+    }
 }
 
 impl ResponderStorage {
@@ -487,12 +2148,7 @@ impl ResponderStorage {
     ///
     /// If file exists, attempts load and will exit program if it fails.
     pub fn load_storage() -> anyhow::Result<Self> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "matrix_responder.ron".to_string()]
-                .iter()
-                .collect::<PathBuf>(),
-            Err(_) => ["matrix_responder.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("matrix_responder.ron");
         let mut file = match File::open(path) {
             Ok(v) => v,
             Err(e) => match e.kind() {
@@ -525,21 +2181,10 @@ impl ResponderStorage {
     ///
     /// One of the few functions that can terminate the program if it doesnt go well.
     pub fn save_storage(&self) -> anyhow::Result<()> {
-        let path = match env::var("MATRIX_BOT_DATA_DIR") {
-            Ok(v) => [v, "matrix_responder.ron".to_string()]
-                .iter()
-                .collect::<PathBuf>(),
-            Err(_) => ["matrix_responder.ron"].iter().collect::<PathBuf>(),
-        };
+        let path = data_path("matrix_responder.ron");
         let ron = ron::to_string(self)
             .context("Unable to format matrix_responder data as RON. This should never happen!")?;
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .open(path)
-            .context("Unable to open matrix_responder.ron file")?;
-        file.write_all(ron.as_bytes())
-            .context("Unable to write matrix_listener.ron data")?;
+        write_storage_file(&path, &ron).context("Unable to write matrix_responder.ron data")?;
         trace!("Saved Session!");
         Ok(())
     }
@@ -552,6 +2197,48 @@ impl ResponderStorage {
         self.last_txn_id += 1;
         self.last_txn_id.to_string().into()
     }
+
+    /// Remembers that `response` was sent because of `trigger`.
+    ///
+    /// Only the most recent responses are kept so storage stays bounded.
+    pub fn record_response(&mut self, trigger: OwnedEventId, response: OwnedEventId) {
+        if self.responses.len() >= MAX_TRACKED_RESPONSES {
+            self.responses.pop_front();
+        }
+        self.responses.push_back((trigger, response));
+    }
+
+    /// Records a moderation action in the audit log.
+    ///
+    /// Only the most recent actions are kept so storage stays bounded.
+    pub fn record_audit_entry(&mut self, entry: AuditEntry) {
+        if self.audit_log.len() >= MAX_AUDIT_ENTRIES {
+            self.audit_log.pop_front();
+        }
+        self.audit_log.push_back(entry);
+    }
+
+    /// Returns the first remembered response to `trigger`.
+    pub fn response_to(&self, trigger: &EventId) -> Option<&OwnedEventId> {
+        self.responses
+            .iter()
+            .find(|(t, _)| t == trigger)
+            .map(|(_, r)| r)
+    }
+
+    /// Removes and returns all remembered responses to `trigger`.
+    pub fn take_responses(&mut self, trigger: &EventId) -> Vec<OwnedEventId> {
+        let mut taken = Vec::new();
+        self.responses.retain(|(t, r)| {
+            if t == trigger {
+                taken.push(r.clone());
+                false
+            } else {
+                true
+            }
+        });
+        taken
+    }
 }
 
 impl From<&str> for InsensitiveSpelling {
@@ -615,13 +2302,11 @@ fn load_linker_settings(
                 if !d.is_empty() {
                     let d = d
                         .iter()
-                        .map(|(k, v)| {
-                            (
-                                k.clone(),
-                                v.parse().expect("Invalid URL in linker settings"),
-                            )
+                        .map(|(k, v)| match v.parse() {
+                            Ok(url) => Ok((k.clone(), url)),
+                            Err(e) => Err(anyhow!("Invalid URL {} for link {}: {}", v, k, e)),
                         })
-                        .collect();
+                        .collect::<anyhow::Result<_>>()?;
 
                     Ok((m.clone(), d))
                 } else {
@@ -652,6 +2337,22 @@ fn load_text_expansions(toml: &RawConfig) -> HashMap<String, String> {
     }
 }
 
+fn load_conversion_marker_settings(toml: &RawConfig) -> anyhow::Result<Option<String>> {
+    match &toml.general.conversion_marker {
+        Some(v) => {
+            // Letters, digits, and whitespace would be read as part of the quantity or unit
+            if v.is_empty() || v.chars().any(|c| c.is_alphanumeric() || c.is_whitespace()) {
+                return Err(anyhow!(
+                    "Conversion marker {:?} must not be empty or contain letters, digits, or whitespace",
+                    v
+                ));
+            }
+            Ok(Some(v.clone()))
+        }
+        None => Ok(None),
+    }
+}
+
 fn load_unit_conversion_settings(toml: &RawConfig) -> HashSet<String> {
     match &toml.general.unit_conversion_exclusion {
         Some(v) => {
@@ -668,10 +2369,27 @@ fn load_unit_conversion_settings(toml: &RawConfig) -> HashSet<String> {
     }
 }
 
-fn load_spell_correct_settings(
-    toml: &RawConfig,
-) -> anyhow::Result<(Vec<SpellCheckKind>, String, HashSet<OwnedRoomId>)> {
+/// Spellings to correct, correction text, excluded rooms, and per room cooldowns of the correction feature.
+type SpellCorrectSettings = (
+    Vec<SpellCheckKind>,
+    String,
+    HashSet<OwnedRoomId>,
+    HashMap<OwnedRoomId, u64>,
+);
+
+fn load_spell_correct_settings(toml: &RawConfig) -> anyhow::Result<SpellCorrectSettings> {
     if toml.general.enable_corrections {
+        let mut cooldown_overrides = HashMap::new();
+        for (room_id, seconds) in toml.correction_cooldown_overrides.iter().flatten() {
+            let room_id = RoomId::parse(room_id).map_err(|e| {
+                anyhow!(
+                    "Correction cooldown override room {} is not a valid room ID: {}",
+                    room_id,
+                    e
+                )
+            })?;
+            cooldown_overrides.insert(room_id, *seconds);
+        }
         match &toml.general.insensitive_corrections {
             Some(i) => match &toml.general.sensitive_corrections {
                 Some(s) => match &toml.general.correction_text {
@@ -698,7 +2416,7 @@ fn load_spell_correct_settings(
                                     spelling: spelling.clone(),
                                 }));
                             }
-                            Ok((spk, c.to_string(), e))
+                            Ok((spk, c.to_string(), e, cooldown_overrides))
                         }
                         None => {
                             let mut spk = Vec::new();
@@ -715,7 +2433,7 @@ fn load_spell_correct_settings(
                                 }));
                             }
                             info!("No list found. No rooms will be excluded from corrections");
-                            Ok((spk, c.to_string(), HashSet::new()))
+                            Ok((spk, c.to_string(), HashSet::new(), cooldown_overrides))
                         }
                     },
                     None => {
@@ -732,7 +2450,7 @@ fn load_spell_correct_settings(
         }
     } else {
         info!("Disabling corrections feature");
-        Ok((Vec::new(), String::new(), HashSet::new()))
+        Ok((Vec::new(), String::new(), HashSet::new(), HashMap::new()))
     }
 }
 
@@ -745,6 +2463,20 @@ fn load_admin_settings(toml: &RawConfig) -> anyhow::Result<HashSet<OwnedUserId>>
     }
 }
 
+fn load_super_admin_settings(
+    toml: &RawConfig,
+    admins: &HashSet<OwnedUserId>,
+) -> anyhow::Result<HashSet<OwnedUserId>> {
+    let super_admins = toml.general.super_admins.clone().unwrap_or_default();
+    if let Some(user) = super_admins.iter().find(|u| !admins.contains(*u)) {
+        return Err(anyhow!(
+            "Super admin {} must also be an authorized user",
+            user
+        ));
+    }
+    Ok(super_admins)
+}
+
 fn load_help_settings(toml: &RawConfig) -> HashSet<OwnedRoomId> {
     match &toml.general.help_rooms {
         Some(v) => v.clone(),
@@ -765,18 +2497,29 @@ fn load_ban_room_settings(toml: &RawConfig) -> HashSet<OwnedRoomId> {
     }
 }
 
-fn load_group_ping_settings(
-    toml: &RawConfig,
-) -> anyhow::Result<(HashMap<String, HashSet<OwnedUserId>>, HashSet<OwnedUserId>)> {
+fn load_group_ping_settings(toml: &RawConfig) -> anyhow::Result<GroupPings> {
     match &toml.group_pings {
         Some(v) => {
+            let v = &expand_group_files(v)?;
+            if let Some(cycle) = find_group_cycle(v) {
+                return Err(anyhow!(
+                    "Group aliases form a cycle: %{}",
+                    cycle.join(" -> %")
+                ));
+            }
+            if v.contains_key("here") {
+                return Err(anyhow!(
+                    "%here is a reserved group_ping name, do not configure it manually"
+                ));
+            }
             let mut group_ping_users = HashSet::new();
             let groups = v.clone();
             for group in groups {
                 for user in group.1 {
-                    if user.eq("%all") {
+                    if user.eq("%all") || user.eq("%here") {
                         return Err(anyhow!(format!(
-                            "%all is a reserved group_ping name, do not configure it manually"
+                            "{} is a reserved group_ping name, do not configure it manually",
+                            user
                         )));
                     }
                     if user.starts_with('@') {
@@ -789,53 +2532,471 @@ fn load_group_ping_settings(
             }
 
             let mut expanded_groups: HashMap<String, HashSet<OwnedUserId>> = HashMap::new();
-            for (group, users) in v {
+            let mut group_rooms: HashMap<String, HashSet<OwnedRoomAliasId>> = HashMap::new();
+            for group in v.keys() {
                 let mut expanded_users: HashSet<OwnedUserId> = HashSet::new();
+                let mut rooms: HashSet<OwnedRoomAliasId> = HashSet::new();
+                expand_group(group, v, &mut expanded_users, &mut rooms)?;
+                expanded_groups.insert(group.to_string(), expanded_users);
+                if !rooms.is_empty() {
+                    group_rooms.insert(group.to_string(), rooms);
+                }
+            }
+
+            Ok(GroupPings {
+                groups: expanded_groups,
+                rooms: group_rooms,
+                users: group_ping_users,
+            })
+        }
+        None => {
+            info!("No group pings defined. Disabling feature...");
+            Ok(GroupPings::default())
+        }
+    }
+}
+
+/// Replaces "@file:path" group ping entries with the user IDs listed in that file, one per line.
+///
+/// Relative paths are relative to the config file. Blank lines and lines starting with # are ignored.
+fn expand_group_files(
+    groups: &HashMap<String, Vec<String>>,
+) -> anyhow::Result<HashMap<String, Vec<String>>> {
+    let mut expanded = HashMap::new();
+    for (group, entries) in groups {
+        let mut users = Vec::new();
+        for entry in entries {
+            let file = match entry.strip_prefix("@file:") {
+                Some(v) => v,
+                None => {
+                    users.push(entry.clone());
+                    continue;
+                }
+            };
+            let path = config_path()
+                .parent()
+                .map_or_else(|| PathBuf::from(file), |dir| dir.join(file));
+            let contents = std::fs::read_to_string(&path).with_context(|| {
+                format!("Unable to read group file {:?} for group %{}", path, group)
+            })?;
+            for (number, line) in contents.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                if UserId::parse(line).is_err() {
+                    return Err(anyhow!(
+                        "Invalid user ID {:?} on line {} of group file {:?} for group %{}",
+                        line,
+                        number + 1,
+                        path,
+                        group
+                    ));
+                }
+                users.push(line.to_string());
+            }
+        }
+        expanded.insert(group.clone(), users);
+    }
+    Ok(expanded)
+}
+
+/// Returns the groups of a chain of group aliases that leads back to where it started, if any.
+///
+/// The chain starts and ends with the same group, like ["a", "b", "a"].
+fn find_group_cycle(groups: &HashMap<String, Vec<String>>) -> Option<Vec<&str>> {
+    fn visit<'a>(
+        group: &'a str,
+        groups: &'a HashMap<String, Vec<String>>,
+        path: &mut Vec<&'a str>,
+    ) -> Option<Vec<&'a str>> {
+        if let Some(start) = path.iter().position(|g| *g == group) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(group);
+            return Some(cycle);
+        }
+        path.push(group);
+        let found = groups
+            .get(group)
+            .into_iter()
+            .flatten()
+            .filter_map(|user| user.strip_prefix('%'))
+            .find_map(|alias| visit(alias, groups, path));
+        path.pop();
+        found
+    }
+    // Sorted so the same config always reports the same cycle
+    let mut names: Vec<&str> = groups.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    names
+        .into_iter()
+        .find_map(|group| visit(group, groups, &mut Vec::new()))
+}
+
+/// Adds the users and rooms of a group to `users` and `rooms`, following aliases through every group they lead to.
+///
+/// Groups must be checked for cycles first. Rooms are only checked to be valid aliases, since
+/// their members are looked up when the group is pinged.
+fn expand_group(
+    group: &str,
+    groups: &HashMap<String, Vec<String>>,
+    users: &mut HashSet<OwnedUserId>,
+    rooms: &mut HashSet<OwnedRoomAliasId>,
+) -> anyhow::Result<()> {
+    let entries = groups
+        .get(group)
+        .ok_or_else(|| anyhow!("Group alias %{} has no corresponding group", group))?;
+    for entry in entries {
+        if let Some(alias) = entry.strip_prefix('%') {
+            expand_group(alias, groups, users, rooms)?;
+        } else if entry.starts_with('#') {
+            let room = RoomAliasId::parse(entry.as_str()).with_context(|| {
+                format!("{} in group %{} is not a valid room alias", entry, group)
+            })?;
+            rooms.insert(room);
+        } else {
+            let user_id = UserId::parse(entry.as_str())
+                .with_context(|| format!("{} in group %{} is not a valid user ID", entry, group))?;
+            users.insert(user_id);
+        }
+    }
+    Ok(())
+}
 
-                for user in users {
-                    if user.eq("%all") {
+fn load_custom_conversion_settings(
+    toml: &RawConfig,
+) -> anyhow::Result<HashMap<OwnedRoomId, HashMap<String, CustomConversion>>> {
+    match &toml.custom_conversions {
+        Some(v) => {
+            let mut rooms = HashMap::new();
+            for (room_id, conversions) in v {
+                let mut room_conversions = HashMap::new();
+                for (unit, conversion) in conversions {
+                    if unit.trim().is_empty() || unit.contains(char::is_whitespace) {
                         return Err(anyhow!(format!(
-                            "%all is a reserved group_ping name, do not configure it manually"
+                            "Custom conversion unit {:?} in room {} must be a single word",
+                            unit, room_id
                         )));
                     }
-                    if user.starts_with('%') {
-                        // If user is an alias, expand it to list of users and insert them
-                        let alias = user.replace('%', "");
-                        match v.get(&alias) {
-                            // If list of users found, insert them
-                            Some(g) => {
-                                for u in g {
-                                    if u.starts_with('@') {
-                                        let user_id = UserId::parse(u.clone()).context("Somehow got an alias in a part of code meant to handle UserIds")?;
-                                        expanded_users.insert(user_id);
-                                    }
-                                }
-                            }
-                            // If list of users are not found, print error to console and move on
-                            None => {
-                                return Err(anyhow!(format!(
-                                    "Group alias %{} has no corresponding group. Ignoring...",
-                                    alias
-                                )))
-                            }
-                        }
-                    } else {
-                        // If user is not alias, just insert it
-                        let user_id = UserId::parse(user.clone()).context(
-                            "Somehow got an alias in a part of code meant to handle UserIds",
-                        )?;
-                        expanded_users.insert(user_id);
+                    if !conversion.factor.is_finite() || conversion.factor == 0.0 {
+                        return Err(anyhow!(format!(
+                            "Custom conversion {} in room {} has invalid factor {}",
+                            unit, room_id, conversion.factor
+                        )));
                     }
+                    // Matching is done against lowercased message text
+                    room_conversions.insert(unit.to_lowercase(), conversion.clone());
                 }
+                rooms.insert(room_id.clone(), room_conversions);
+            }
+            Ok(rooms)
+        }
+        None => {
+            info!("No custom conversions found. Disabling feature...");
+            Ok(HashMap::new())
+        }
+    }
+}
 
-                expanded_groups.insert(group.to_string(), expanded_users);
+fn load_unit_conversion_precision_settings(
+    toml: &RawConfig,
+) -> anyhow::Result<(usize, HashMap<String, usize>)> {
+    let default_precision = toml
+        .general
+        .unit_conversion_precision
+        .unwrap_or(DEFAULT_PRECISION);
+    if default_precision > MAX_PRECISION {
+        return Err(anyhow!(format!(
+            "Unit conversion precision must be at most {}",
+            MAX_PRECISION
+        )));
+    }
+    let precisions = match &toml.unit_conversion_precision {
+        Some(v) => {
+            let mut precisions = HashMap::new();
+            for (dimension, precision) in v {
+                let dimension = dimension.to_lowercase();
+                if !DIMENSIONS.contains(&dimension.as_str()) {
+                    return Err(anyhow!(format!(
+                        "Unknown unit conversion dimension {:?}. Must be one of {}",
+                        dimension,
+                        DIMENSIONS.join(", ")
+                    )));
+                }
+                if *precision > MAX_PRECISION {
+                    return Err(anyhow!(format!(
+                        "Unit conversion precision for {} must be at most {}",
+                        dimension, MAX_PRECISION
+                    )));
+                }
+                precisions.insert(dimension, *precision);
             }
+            precisions
+        }
+        None => {
+            info!("No unit conversion precisions found. Using defaults.");
+            HashMap::new()
+        }
+    };
+    Ok((default_precision, precisions))
+}
 
-            Ok((expanded_groups, group_ping_users))
+fn load_log_level_settings(toml: &RawConfig) -> anyhow::Result<HashMap<String, String>> {
+    match &toml.log_levels {
+        Some(v) => {
+            let mut log_levels = HashMap::new();
+            for (feature, level) in v {
+                if feature_targets(feature).is_none() {
+                    return Err(anyhow!(
+                        "Unknown log level feature {}. Expected one of {} or a module path",
+                        feature,
+                        FEATURES.map(|(f, _)| f).join(", ")
+                    ));
+                }
+                let level = level.to_lowercase();
+                if !LOG_LEVELS.contains(&level.as_str()) {
+                    return Err(anyhow!(
+                        "Invalid log level {} for {}. Expected one of {}",
+                        level,
+                        feature,
+                        LOG_LEVELS.join(", ")
+                    ));
+                }
+                log_levels.insert(feature.clone(), level);
+            }
+            Ok(log_levels)
         }
+        None => Ok(HashMap::new()),
+    }
+}
+
+fn load_allowed_command_settings(toml: &RawConfig) -> HashMap<OwnedRoomId, HashSet<String>> {
+    match &toml.allowed_commands {
+        Some(v) => v
+            .iter()
+            .map(|(room_id, commands)| {
+                let commands = commands
+                    .iter()
+                    .map(|c| c.trim_start_matches('!').to_lowercase())
+                    .collect();
+                (room_id.clone(), commands)
+            })
+            .collect(),
         None => {
-            info!("No group pings defined. Disabling feature...");
-            Ok((HashMap::new(), HashSet::new()))
+            info!("No command allowlists found. Allowing all commands in all rooms.");
+            HashMap::new()
+        }
+    }
+}
+
+fn load_command_prefix_settings(
+    toml: &RawConfig,
+) -> anyhow::Result<(String, HashMap<OwnedRoomId, String>)> {
+    let valid = |prefix: &str| !prefix.is_empty() && !prefix.contains(char::is_whitespace);
+    let command_prefix = toml
+        .general
+        .command_prefix
+        .clone()
+        .unwrap_or_else(|| "!".to_string());
+    if !valid(&command_prefix) {
+        return Err(anyhow!(
+            "Invalid command prefix {:?}. Prefixes can't be empty or contain whitespace",
+            command_prefix
+        ));
+    }
+    let command_prefixes = toml.command_prefixes.clone().unwrap_or_default();
+    for (room_id, prefix) in &command_prefixes {
+        if !valid(prefix) {
+            return Err(anyhow!(
+                "Invalid command prefix {:?} for room {}. Prefixes can't be empty or contain whitespace",
+                prefix,
+                room_id
+            ));
+        }
+    }
+    Ok((command_prefix, command_prefixes))
+}
+
+fn load_slash_command_settings(toml: &RawConfig) -> HashSet<String> {
+    match &toml.general.slash_commands {
+        Some(v) => v
+            .iter()
+            .map(|c| c.trim_start_matches(&['/', '!'][..]).to_lowercase())
+            .collect(),
+        None => HashSet::new(),
+    }
+}
+
+fn load_profile_settings(
+    toml: &RawConfig,
+) -> anyhow::Result<(Option<String>, Option<OwnedMxcUri>)> {
+    let auth = &toml.matrix_authentication;
+    if let Some(v) = &auth.avatar_url {
+        if !v.is_valid() {
+            return Err(anyhow!(format!(
+                "Avatar URL {} is not a valid mxc:// URI",
+                v
+            )));
         }
     }
+    if auth.display_name.is_none() && auth.avatar_url.is_none() {
+        info!("No profile configured. Bot profile will not be managed.");
+    }
+    Ok((auth.display_name.clone(), auth.avatar_url.clone()))
+}
+
+fn load_idle_ping_settings(toml: &RawConfig) -> anyhow::Result<Option<Duration>> {
+    if !toml.general.enable_idle_ping.unwrap_or(false) {
+        return Ok(None);
+    }
+    match toml.general.idle_ping_seconds {
+        Some(0) => Err(anyhow!("Idle ping interval must be at least 1 second")),
+        Some(v) => Ok(Some(Duration::from_secs(v))),
+        None => Ok(Some(DEFAULT_IDLE_PING_INTERVAL)),
+    }
+}
+
+fn load_power_level_admin_settings(toml: &RawConfig) -> anyhow::Result<Option<PowerLevelAdmins>> {
+    let room = match &toml.general.power_level_admins_room {
+        Some(v) => v.clone(),
+        None => return Ok(None),
+    };
+    let refresh_interval = match toml.general.power_level_admins_refresh_seconds {
+        Some(0) => {
+            return Err(anyhow!(
+                "Power level admin refresh interval must be at least 1 second"
+            ))
+        }
+        Some(v) => Duration::from_secs(v),
+        None => DEFAULT_POWER_LEVEL_ADMINS_REFRESH,
+    };
+    Ok(Some(PowerLevelAdmins {
+        room,
+        min_power_level: toml
+            .general
+            .power_level_admins_min
+            .unwrap_or(DEFAULT_POWER_LEVEL_ADMINS_MIN),
+        refresh_interval,
+    }))
+}
+
+fn load_flood_protection_settings(
+    toml: &RawConfig,
+    ban_rooms: &HashSet<OwnedRoomId>,
+) -> anyhow::Result<Option<FloodProtection>> {
+    if !toml.general.enable_flood_protection.unwrap_or(false) {
+        return Ok(None);
+    }
+    if ban_rooms.is_empty() {
+        return Err(anyhow!(
+            "Flood protection needs ban_rooms to watch and act in"
+        ));
+    }
+    let message_limit = match toml.general.flood_message_limit {
+        Some(0) => return Err(anyhow!("Flood message limit must be at least 1 message")),
+        Some(v) => v,
+        None => DEFAULT_FLOOD_MESSAGE_LIMIT,
+    };
+    let window = match toml.general.flood_seconds {
+        Some(0) => return Err(anyhow!("Flood window must be at least 1 second")),
+        Some(v) => Duration::from_secs(v),
+        None => DEFAULT_FLOOD_WINDOW,
+    };
+    Ok(Some(FloodProtection {
+        message_limit,
+        window,
+        action: toml.general.flood_action.unwrap_or_default(),
+    }))
+}
+
+fn load_warning_settings(
+    toml: &RawConfig,
+    ban_rooms: &HashSet<OwnedRoomId>,
+) -> anyhow::Result<Option<Warnings>> {
+    if !toml.general.enable_warnings.unwrap_or(false) {
+        return Ok(None);
+    }
+    if ban_rooms.is_empty() {
+        return Err(anyhow!("Warnings need ban_rooms to ban warned users in"));
+    }
+    let ban_threshold = match toml.general.warning_ban_threshold {
+        Some(0) => return Err(anyhow!("Warning ban threshold must be at least 1 warning")),
+        Some(v) => v,
+        None => DEFAULT_WARNING_BAN_THRESHOLD,
+    };
+    Ok(Some(Warnings {
+        ban_threshold,
+        scope: toml.general.warning_scope.unwrap_or_default(),
+    }))
+}
+
+/// Loads the confirmation reaction, resolving a :shortcode: to the custom emoji of the same name.
+fn load_confirmation_reaction_settings(toml: &RawConfig) -> anyhow::Result<Emoji> {
+    match &toml.general.confirmation_reaction {
+        Some(v) if v.trim().is_empty() => Err(anyhow!("Confirmation reaction must not be empty")),
+        Some(v) => load_emoji(v.trim(), toml),
+        None => Ok(Emoji::Unicode("✅".to_string())),
+    }
+}
+
+/// Loads the reaction roles, sorted by room so the role message lists them in a stable order.
+fn load_reaction_roles_settings(toml: &RawConfig) -> anyhow::Result<Option<ReactionRoles>> {
+    let raw = match &toml.reaction_roles {
+        Some(v) => v,
+        None => {
+            info!("No reaction roles found. Disabling feature...");
+            return Ok(None);
+        }
+    };
+    if raw.roles.is_empty() {
+        return Err(anyhow!("Reaction roles need at least 1 role"));
+    }
+    let mut roles = Vec::new();
+    for (reaction, room) in &raw.roles {
+        if reaction.trim().is_empty() {
+            return Err(anyhow!("Reaction role emoji must not be empty"));
+        }
+        if room == &raw.room {
+            return Err(anyhow!(
+                "Reaction role {} invites to the reaction roles room {} itself",
+                reaction,
+                room
+            ));
+        }
+        roles.push((load_emoji(reaction.trim(), toml)?, room.clone()));
+    }
+    roles.sort_by(|(_, a), (_, b)| a.cmp(b));
+    Ok(Some(ReactionRoles {
+        room: raw.room.clone(),
+        roles,
+    }))
+}
+
+/// Loads an emoji, resolving a :shortcode: to the custom emoji of the same name.
+fn load_emoji(reaction: &str, toml: &RawConfig) -> anyhow::Result<Emoji> {
+    let shortcode = match reaction.strip_prefix(':').and_then(|r| r.strip_suffix(':')) {
+        Some(v) => v,
+        None => return Ok(Emoji::Unicode(reaction.to_string())),
+    };
+    let url = toml
+        .custom_emoji
+        .as_ref()
+        .and_then(|e| e.get(shortcode))
+        .ok_or_else(|| {
+            anyhow!(
+                "Unknown custom emoji :{}:. Add it to [custom_emoji]",
+                shortcode
+            )
+        })?;
+    if !url.is_valid() {
+        return Err(anyhow!(
+            "Custom emoji :{}: has invalid image {}. Must be an mxc:// URI",
+            shortcode,
+            url
+        ));
+    }
+    Ok(Emoji::Custom {
+        shortcode: shortcode.to_string(),
+        url: url.clone(),
+    })
 }
@@ -0,0 +1,53 @@
+use super::common::{listener_config, load_config};
+use crate::config::ListenerStorage;
+use ruma::user_id;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn super_admins() {
+    let config = listener_config("super_admins = ['@admin:example.com']", "");
+    assert!(config.super_admins.contains(user_id!("@admin:example.com")))
+}
+#[test]
+fn super_admin_not_authorized() {
+    assert!(load_config("super_admins = ['@other:example.com']", "").is_err())
+}
+#[test]
+fn grants_persist() {
+    let mut storage = ListenerStorage::default();
+    let now = SystemTime::now();
+    storage.grant_admin(
+        user_id!("@oncall:example.com"),
+        Duration::from_secs(3600),
+        now,
+    );
+    let storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    let config = listener_config("", "");
+    assert!(storage.is_admin(user_id!("@oncall:example.com"), &config.admins, now))
+}
+#[test]
+fn power_level_admins() {
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("", "");
+    let moderator = user_id!("@moderator:example.com");
+    assert!(!storage.is_admin(moderator, &config.admins, SystemTime::now()));
+    storage.power_level_admins.insert(moderator.to_owned());
+    assert!(storage.is_admin(moderator, &config.admins, SystemTime::now()))
+}
+#[test]
+fn power_level_admin_settings() {
+    assert_eq!(None, listener_config("", "").power_level_admins);
+    let config = listener_config("power_level_admins_room = '!mods:example.com'", "");
+    let power_level_admins = config.power_level_admins.unwrap();
+    assert_eq!("!mods:example.com", power_level_admins.room.as_str());
+    assert_eq!(100, power_level_admins.min_power_level);
+    assert_eq!(
+        Duration::from_secs(300),
+        power_level_admins.refresh_interval
+    );
+    assert!(load_config(
+        "power_level_admins_room = '!mods:example.com'\npower_level_admins_refresh_seconds = 0",
+        ""
+    )
+    .is_err())
+}
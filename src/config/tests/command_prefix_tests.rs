@@ -0,0 +1,24 @@
+use super::common::{listener_config, load_config};
+use ruma::room_id;
+
+#[test]
+fn room_prefix_overrides_global() {
+    let config = listener_config(
+        "command_prefix = '?'",
+        "[command_prefixes]\n'!room:example.com' = '.'",
+    );
+    assert_eq!(".", config.command_prefix(room_id!("!room:example.com")));
+    assert_eq!("?", config.command_prefix(room_id!("!other:example.com")))
+}
+#[test]
+fn bang_by_default() {
+    assert_eq!(
+        "!",
+        listener_config("", "").command_prefix(room_id!("!room:example.com"))
+    )
+}
+#[test]
+fn invalid_prefix() {
+    assert!(load_config("command_prefix = ''", "").is_err());
+    assert!(load_config("", "[command_prefixes]\n'!room:example.com' = 'bot '").is_err())
+}
@@ -0,0 +1,41 @@
+use crate::config::{Config, MatrixListenerConfig};
+
+/// Builds a minimal valid config.toml, inserting `general` into the `[general]` table
+/// and appending `tables` after all required tables.
+///
+/// Required feature toggles default to unit conversions on and corrections off unless set in `general`.
+pub(crate) fn config_text(general: &str, tables: &str) -> String {
+    let mut defaults = String::new();
+    if !general.contains("enable_unit_conversions") {
+        defaults.push_str("enable_unit_conversions = true\n");
+    }
+    if !general.contains("enable_corrections") {
+        defaults.push_str("enable_corrections = false\n");
+    }
+    format!(
+        "[general]
+authorized_users = ['@admin:example.com']
+webhook_token = 'token'
+{}{}
+
+[matrix_authentication]
+url = 'https://matrix.example.com'
+username = '@bot:example.com'
+password = 'password'
+
+{}",
+        defaults, general, tables
+    )
+}
+
+/// Parses a minimal valid config with the supplied additions.
+pub(crate) fn load_config(general: &str, tables: &str) -> anyhow::Result<Config> {
+    Config::parse_config(&config_text(general, tables))
+}
+
+/// Builds a listener config from a minimal valid config with the supplied additions.
+///
+/// Panics if the config is invalid.
+pub(crate) fn listener_config(general: &str, tables: &str) -> MatrixListenerConfig {
+    MatrixListenerConfig::new(&load_config(general, tables).unwrap())
+}
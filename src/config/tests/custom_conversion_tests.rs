@@ -0,0 +1,34 @@
+use super::common::{listener_config, load_config};
+use ruma::room_id;
+
+#[test]
+fn valid_factor() {
+    let config = listener_config(
+        "",
+        "[custom_conversions.'!room:example.com']
+Pizzas = { factor = 8.0, unit = 'slices' }",
+    );
+    let room = config
+        .custom_conversions
+        .get(room_id!("!room:example.com"))
+        .unwrap();
+    assert_eq!(8.0, room.get("pizzas").unwrap().factor)
+}
+#[test]
+fn zero_factor() {
+    assert!(load_config(
+        "",
+        "[custom_conversions.'!room:example.com']
+pizzas = { factor = 0.0, unit = 'slices' }",
+    )
+    .is_err())
+}
+#[test]
+fn non_finite_factor() {
+    assert!(load_config(
+        "",
+        "[custom_conversions.'!room:example.com']
+pizzas = { factor = inf, unit = 'slices' }",
+    )
+    .is_err())
+}
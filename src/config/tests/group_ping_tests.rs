@@ -0,0 +1,187 @@
+use super::common::{config_text, listener_config, load_config};
+use crate::config::GroupPingFormat;
+use ruma::{room_alias_id, user_id};
+
+const GROUPS: &str = "[group_pings]
+admins = ['@admin:example.com']
+mods = ['@mod:example.com', '%admins']";
+
+#[test]
+fn alias_expanded() {
+    let config = listener_config("", GROUPS);
+    let group_pings = config.group_pings.read().unwrap();
+    let mods = group_pings.groups.get("mods").unwrap();
+    assert!(mods.contains(user_id!("@admin:example.com")));
+    assert!(mods.contains(user_id!("@mod:example.com")))
+}
+#[test]
+fn self_reference_rejected() {
+    assert!(load_config(
+        "",
+        "[group_pings]
+admins = ['@admin:example.com', '%admins']"
+    )
+    .is_err())
+}
+#[test]
+fn alias_cycle_rejected() {
+    let error = load_config(
+        "",
+        "[group_pings]
+admins = ['@admin:example.com', '%mods']
+mods = ['@mod:example.com', '%admins']",
+    )
+    .unwrap_err();
+    assert_eq!(
+        "Group aliases form a cycle: %admins -> %mods -> %admins",
+        error.to_string()
+    )
+}
+#[test]
+fn nested_aliases_expanded() {
+    let config = listener_config(
+        "",
+        "[group_pings]
+company = ['@ceo:example.com', '%engineering']
+engineering = ['@cto:example.com', '%backend']
+backend = ['@dev:example.com']",
+    );
+    let group_pings = config.group_pings.read().unwrap();
+    let company = group_pings.groups.get("company").unwrap();
+    assert_eq!(3, company.len());
+    assert!(company.contains(user_id!("@ceo:example.com")));
+    assert!(company.contains(user_id!("@cto:example.com")));
+    assert!(company.contains(user_id!("@dev:example.com")));
+    assert_eq!(2, group_pings.groups.get("engineering").unwrap().len())
+}
+#[test]
+fn reload_replaces_groups() {
+    let config = listener_config("", GROUPS);
+    let count = config
+        .reload_group_pings(&config_text(
+            "",
+            "[group_pings]
+devs = ['@dev:example.com']",
+        ))
+        .unwrap();
+    assert_eq!(1, count);
+    let group_pings = config.group_pings.read().unwrap();
+    assert!(group_pings.groups.contains_key("devs"));
+    assert!(!group_pings.groups.contains_key("mods"));
+    assert!(group_pings.users.contains(user_id!("@dev:example.com")))
+}
+#[test]
+fn reload_with_cycle_keeps_groups() {
+    let config = listener_config("", GROUPS);
+    assert!(config
+        .reload_group_pings(&config_text(
+            "",
+            "[group_pings]
+admins = ['@admin:example.com', '%mods']
+mods = ['@mod:example.com', '%admins']",
+        ))
+        .is_err());
+    let group_pings = config.group_pings.read().unwrap();
+    assert_eq!(2, group_pings.groups.len());
+    assert!(group_pings.groups["mods"].contains(user_id!("@admin:example.com")))
+}
+/// Writes a group file to the temp directory and returns its path
+fn group_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(name);
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+#[test]
+fn file_expanded() {
+    let path = group_file(
+        "matrix_bot_oncall.txt",
+        "# On call rotation\n@oncall1:example.com\n\n  @oncall2:example.com  \n",
+    );
+    let config = listener_config(
+        "",
+        &format!(
+            "[group_pings]
+oncall = ['@lead:example.com', '@file:{}']
+ops = ['%oncall']",
+            path
+        ),
+    );
+    let group_pings = config.group_pings.read().unwrap();
+    let oncall = group_pings.groups.get("oncall").unwrap();
+    assert_eq!(3, oncall.len());
+    assert!(oncall.contains(user_id!("@oncall1:example.com")));
+    assert!(oncall.contains(user_id!("@oncall2:example.com")));
+    assert!(group_pings.groups["ops"].contains(user_id!("@oncall2:example.com")));
+    assert!(group_pings.users.contains(user_id!("@oncall1:example.com")))
+}
+#[test]
+fn malformed_file_rejected() {
+    let path = group_file(
+        "matrix_bot_malformed.txt",
+        "@oncall1:example.com\noncall2\n",
+    );
+    let error =
+        load_config("", &format!("[group_pings]\noncall = ['@file:{}']", path)).unwrap_err();
+    assert!(format!("{}", error).contains("Invalid user ID \"oncall2\" on line 2"))
+}
+#[test]
+fn missing_file_rejected() {
+    assert!(load_config(
+        "",
+        "[group_pings]\noncall = ['@file:/nonexistent/matrix_bot_oncall.txt']"
+    )
+    .is_err())
+}
+#[test]
+fn group_ping_format() {
+    let config = listener_config("group_ping_format = 'list'", GROUPS);
+    assert_eq!(GroupPingFormat::List, config.group_ping_format)
+}
+#[test]
+fn group_ping_format_default() {
+    let config = listener_config("", GROUPS);
+    assert_eq!(GroupPingFormat::Html, config.group_ping_format)
+}
+#[test]
+fn invalid_group_ping_format() {
+    assert!(load_config("group_ping_format = 'markdown'", GROUPS).is_err())
+}
+#[test]
+fn here_rejected() {
+    assert!(load_config(
+        "",
+        "[group_pings]
+here = ['@admin:example.com']"
+    )
+    .is_err());
+    assert!(load_config(
+        "",
+        "[group_pings]
+admins = ['@admin:example.com', '%here']"
+    )
+    .is_err())
+}
+#[test]
+fn room_kept_as_alias() {
+    let config = listener_config(
+        "",
+        "[group_pings]
+oncall = ['@admin:example.com', '#oncall:example.com']
+support = ['%oncall']",
+    );
+    let group_pings = config.group_pings.read().unwrap();
+    assert_eq!(1, group_pings.groups["oncall"].len());
+    for group in ["oncall", "support"].iter() {
+        assert!(group_pings.rooms[*group].contains(room_alias_id!("#oncall:example.com")))
+    }
+    assert!(group_pings.groups["support"].contains(user_id!("@admin:example.com")))
+}
+#[test]
+fn malformed_room_rejected() {
+    assert!(load_config(
+        "",
+        "[group_pings]
+oncall = ['#oncall']"
+    )
+    .is_err())
+}
@@ -0,0 +1,60 @@
+use super::common::{config_text, listener_config};
+
+const LINKS: &str = "[github_authentication]
+access_token = 'token'
+
+[searchable_repos]
+jf = 'jellyfin/jellyfin'
+
+[linkable_urls]
+hwa = 'https://jellyfin.org/docs/hwa'";
+
+#[test]
+fn reload_replaces_links() {
+    let config = listener_config("link_matchers = ['docs']", LINKS);
+    let counts = config
+        .reload_links(&config_text(
+            "link_matchers = ['docs', 'wiki']",
+            "[github_authentication]
+access_token = 'token'
+
+[searchable_repos]
+jf = 'jellyfin/jellyfin'
+jf-web = 'jellyfin/jellyfin-web'
+
+[linkable_urls]
+network = 'https://jellyfin.org/docs/network'",
+        ))
+        .unwrap();
+    assert_eq!((2, 1), counts);
+    let settings = config.link_settings.read().unwrap();
+    assert!(settings.repos.contains_key("jf-web"));
+    assert!(settings.links.contains_key("network"));
+    assert!(!settings.links.contains_key("hwa"));
+    assert!(settings.linkers.contains("wiki"))
+}
+#[test]
+fn reload_with_malformed_links_keeps_links() {
+    let config = listener_config("link_matchers = ['docs']", LINKS);
+    assert!(config
+        .reload_links(&config_text(
+            "link_matchers = ['docs']",
+            "[linkable_urls]
+hwa = 'not a url'",
+        ))
+        .is_err());
+    assert!(config
+        .reload_links(&config_text(
+            "",
+            "[searchable_repos]
+jf = 'jellyfin/jellyfin'",
+        ))
+        .is_err());
+    let settings = config.link_settings.read().unwrap();
+    assert_eq!("jellyfin/jellyfin", settings.repos["jf"]);
+    assert_eq!(
+        "https://jellyfin.org/docs/hwa",
+        settings.links["hwa"].to_string()
+    );
+    assert!(settings.linkers.contains("docs"))
+}
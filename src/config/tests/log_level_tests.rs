@@ -0,0 +1,53 @@
+use super::common::load_config;
+use crate::logging::feature_filter;
+
+#[test]
+fn filter_from_config() {
+    let config = load_config(
+        "",
+        "[log_levels]
+corrections = 'WARN'
+'matrix_bot::matrix' = 'debug'",
+    )
+    .unwrap();
+    assert_eq!(
+        "matrix_bot=info,\
+matrix_bot::matrix_handlers::listeners::commandless_handler::spellcheck=warn,\
+matrix_bot::matrix_handlers::listeners::correction_handler=warn,\
+matrix_bot::matrix_handlers::listeners::stats_handler=warn,\
+matrix_bot::matrix=debug",
+        feature_filter("matrix_bot=info", &config.log_levels)
+    )
+}
+#[test]
+fn feature_with_multiple_modules() {
+    let config = load_config("", "[log_levels]\nkarma = 'trace'").unwrap();
+    assert_eq!(
+        "matrix_bot=info,\
+matrix_bot::matrix_handlers::listeners::commandless_handler::karma=trace,\
+matrix_bot::matrix_handlers::listeners::karma_handler=trace",
+        feature_filter("matrix_bot=info", &config.log_levels)
+    )
+}
+#[test]
+fn no_log_levels() {
+    let config = load_config("", "").unwrap();
+    assert_eq!(
+        "matrix_bot=info",
+        feature_filter("matrix_bot=info", &config.log_levels)
+    )
+}
+#[test]
+fn unknown_feature() {
+    assert!(load_config("", "[log_levels]\nweather = 'trace'").is_err())
+}
+#[test]
+fn invalid_level() {
+    assert!(load_config("", "[log_levels]\nkarma = 'loud'").is_err())
+}
+#[test]
+fn filter_parses() {
+    let config = load_config("", "[log_levels]\nconversions = 'trace'\nwebhooks = 'off'").unwrap();
+    let filter = feature_filter("matrix_bot=info", &config.log_levels);
+    assert!(tracing_subscriber::EnvFilter::try_new(filter).is_ok())
+}
@@ -0,0 +1,11 @@
+pub(crate) mod common;
+
+mod admin_tests;
+mod command_prefix_tests;
+mod custom_conversion_tests;
+mod group_ping_tests;
+mod link_tests;
+mod log_level_tests;
+mod path_tests;
+mod precision_tests;
+mod storage_tests;
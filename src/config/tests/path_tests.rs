@@ -0,0 +1,68 @@
+use crate::config::{resolve_config_path, resolve_data_path, Args};
+use std::path::{Path, PathBuf};
+
+fn args(args: &[&str]) -> anyhow::Result<Args> {
+    Args::parse(args.iter().map(|a| a.to_string()))
+}
+
+#[test]
+fn config_from_cli() {
+    assert_eq!(
+        PathBuf::from("/etc/bot/other.toml"),
+        resolve_config_path(Some(Path::new("/etc/bot/other.toml")), Some("/env"))
+    )
+}
+#[test]
+fn config_from_env() {
+    assert_eq!(
+        PathBuf::from("/env/config.toml"),
+        resolve_config_path(None, Some("/env"))
+    )
+}
+#[test]
+fn config_default() {
+    assert_eq!(
+        PathBuf::from("config.toml"),
+        resolve_config_path(None, None)
+    )
+}
+#[test]
+fn data_from_cli() {
+    assert_eq!(
+        PathBuf::from("/var/bot/session.ron"),
+        resolve_data_path(Some(Path::new("/var/bot")), Some("/env"), "session.ron")
+    )
+}
+#[test]
+fn data_from_env() {
+    assert_eq!(
+        PathBuf::from("/env/session.ron"),
+        resolve_data_path(None, Some("/env"), "session.ron")
+    )
+}
+#[test]
+fn data_default() {
+    assert_eq!(
+        PathBuf::from("session.ron"),
+        resolve_data_path(None, None, "session.ron")
+    )
+}
+#[test]
+fn parse_arguments() {
+    let parsed = args(&["--config", "other.toml", "--data-dir=/var/bot"]).unwrap();
+    assert_eq!(Some(PathBuf::from("other.toml")), parsed.config);
+    assert_eq!(Some(PathBuf::from("/var/bot")), parsed.data_dir)
+}
+#[test]
+fn parse_no_arguments() {
+    assert_eq!(Args::default(), args(&[]).unwrap())
+}
+#[test]
+fn parse_missing_path() {
+    assert!(args(&["--config"]).is_err());
+    assert!(args(&["--data-dir="]).is_err())
+}
+#[test]
+fn parse_unknown_argument() {
+    assert!(args(&["--verbose"]).is_err())
+}
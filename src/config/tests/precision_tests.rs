@@ -0,0 +1,250 @@
+use super::common::{listener_config, load_config};
+use crate::config::RoundingMode;
+use ruma::user_id;
+use std::collections::HashSet;
+
+#[test]
+fn valid_precisions() {
+    let config = listener_config(
+        "",
+        "[unit_conversion_precision]
+Temperature = 1
+mass = 2",
+    );
+    assert_eq!(
+        Some(&1),
+        config.unit_conversion_precision.get("temperature")
+    );
+    assert_eq!(Some(&2), config.unit_conversion_precision.get("mass"))
+}
+#[test]
+fn default_precision() {
+    assert_eq!(2, listener_config("", "").default_unit_conversion_precision);
+    assert_eq!(
+        4,
+        listener_config("unit_conversion_precision = 4", "").default_unit_conversion_precision
+    );
+    assert!(load_config("unit_conversion_precision = 50", "").is_err())
+}
+#[test]
+fn unknown_dimension() {
+    assert!(load_config(
+        "",
+        "[unit_conversion_precision]
+energy = 1",
+    )
+    .is_err())
+}
+#[test]
+fn precision_too_large() {
+    assert!(load_config(
+        "",
+        "[unit_conversion_precision]
+mass = 50",
+    )
+    .is_err())
+}
+#[test]
+fn rounding_mode() {
+    let config = listener_config("rounding_mode = 'truncate'", "");
+    assert_eq!(RoundingMode::Truncate, config.rounding_mode)
+}
+#[test]
+fn rounding_mode_default() {
+    let config = listener_config("", "");
+    assert_eq!(RoundingMode::HalfEven, config.rounding_mode)
+}
+#[test]
+fn invalid_rounding_mode() {
+    assert!(load_config("rounding_mode = 'ceiling'", "").is_err())
+}
+#[test]
+fn speed_of_sound_default() {
+    let config = listener_config("", "");
+    assert_eq!(343.0, config.speed_of_sound)
+}
+#[test]
+fn invalid_speed_of_sound() {
+    assert!(load_config("speed_of_sound = 0.0", "").is_err());
+    assert!(load_config("speed_of_sound = -343.0", "").is_err())
+}
+#[test]
+fn invalid_messages_per_second() {
+    assert!(load_config("messages_per_second = 0.0", "").is_err());
+    assert!(load_config("messages_per_second = 2.5", "").is_ok())
+}
+#[test]
+fn idle_ping_interval() {
+    use std::time::Duration;
+    assert_eq!(None, listener_config("", "").idle_ping_interval);
+    assert_eq!(
+        Some(Duration::from_secs(300)),
+        listener_config("enable_idle_ping = true", "").idle_ping_interval
+    );
+    assert_eq!(
+        Some(Duration::from_secs(60)),
+        listener_config("enable_idle_ping = true\nidle_ping_seconds = 60", "").idle_ping_interval
+    );
+    assert!(load_config("enable_idle_ping = true\nidle_ping_seconds = 0", "").is_err())
+}
+#[test]
+fn flood_protection() {
+    use crate::config::FloodAction;
+    use std::time::Duration;
+    let rooms = "ban_rooms = ['!room:example.com']\nenable_flood_protection = true";
+    assert_eq!(None, listener_config("", "").flood_protection);
+    let flood_protection = listener_config(rooms, "").flood_protection.unwrap();
+    assert_eq!(10, flood_protection.message_limit);
+    assert_eq!(Duration::from_secs(10), flood_protection.window);
+    assert_eq!(FloodAction::Ban, flood_protection.action);
+    let flood_protection = listener_config(
+        &format!("{}\nflood_message_limit = 5\nflood_action = 'mute'", rooms),
+        "",
+    )
+    .flood_protection
+    .unwrap();
+    assert_eq!(5, flood_protection.message_limit);
+    assert_eq!(FloodAction::Mute, flood_protection.action);
+    assert!(load_config(&format!("{}\nflood_seconds = 0", rooms), "").is_err());
+    assert!(load_config("enable_flood_protection = true", "").is_err())
+}
+#[test]
+fn warnings() {
+    use crate::config::WarningScope;
+    let rooms = "ban_rooms = ['!room:example.com']\nenable_warnings = true";
+    assert_eq!(None, listener_config("", "").warnings);
+    let warnings = listener_config(rooms, "").warnings.unwrap();
+    assert_eq!(3, warnings.ban_threshold);
+    assert_eq!(WarningScope::Global, warnings.scope);
+    let warnings = listener_config(
+        &format!(
+            "{}\nwarning_ban_threshold = 2\nwarning_scope = 'room'",
+            rooms
+        ),
+        "",
+    )
+    .warnings
+    .unwrap();
+    assert_eq!(2, warnings.ban_threshold);
+    assert_eq!(WarningScope::Room, warnings.scope);
+    assert!(load_config(&format!("{}\nwarning_ban_threshold = 0", rooms), "").is_err());
+    assert!(load_config("enable_warnings = true", "").is_err())
+}
+#[test]
+fn sync_save_interval() {
+    use std::time::Duration;
+    assert_eq!(Duration::ZERO, listener_config("", "").sync_save_interval);
+    assert_eq!(
+        Duration::from_secs(30),
+        listener_config("sync_save_seconds = 30", "").sync_save_interval
+    )
+}
+#[test]
+fn confirmation_reaction() {
+    use crate::config::Emoji;
+    assert_eq!(
+        Emoji::Unicode("✅".to_string()),
+        listener_config("", "").confirmation_reaction
+    );
+    assert_eq!(
+        Emoji::Unicode("👍".to_string()),
+        listener_config("confirmation_reaction = '👍'", "").confirmation_reaction
+    );
+    let emoji = "[custom_emoji]\napprove = 'mxc://example.com/approve'";
+    let reaction =
+        listener_config("confirmation_reaction = ':approve:'", emoji).confirmation_reaction;
+    assert_eq!("mxc://example.com/approve", reaction.key());
+    assert_eq!(":approve:", reaction.to_string());
+    assert!(load_config("confirmation_reaction = ':deny:'", emoji).is_err());
+    assert!(load_config(
+        "confirmation_reaction = ':approve:'",
+        "[custom_emoji]\napprove = 'https://example.com/approve.png'"
+    )
+    .is_err())
+}
+#[test]
+fn reaction_roles() {
+    use ruma::room_id;
+    assert!(listener_config("", "").reaction_roles.is_none());
+    let roles = "[custom_emoji]
+approve = 'mxc://example.com/approve'
+[reaction_roles]
+room = '!roles:example.com'
+[reaction_roles.roles]
+'🎮' = '!gaming:example.com'
+':approve:' = '!announcements:example.com'";
+    let config = listener_config("", roles).reaction_roles.unwrap();
+    assert_eq!(room_id!("!roles:example.com"), config.room);
+    assert_eq!(
+        Some(&room_id!("!gaming:example.com").to_owned()),
+        config.room_for("🎮")
+    );
+    assert_eq!(
+        Some(&room_id!("!announcements:example.com").to_owned()),
+        config.room_for("mxc://example.com/approve")
+    );
+    assert_eq!(None, config.room_for("👍"));
+    // Roles are listed by room
+    assert_eq!(":approve:", config.roles[0].0.to_string());
+    assert!(load_config(
+        "",
+        "[reaction_roles]\nroom = '!roles:example.com'\n[reaction_roles.roles]\n':deny:' = '!a:example.com'"
+    )
+    .is_err());
+    assert!(load_config(
+        "",
+        "[reaction_roles]\nroom = '!roles:example.com'\n[reaction_roles.roles]\n'🎮' = '!roles:example.com'"
+    )
+    .is_err());
+    assert!(load_config(
+        "",
+        "[reaction_roles]\nroom = '!roles:example.com'\n[reaction_roles.roles]"
+    )
+    .is_err())
+}
+#[test]
+fn correction_cooldown_overrides() {
+    use ruma::room_id;
+    use std::time::Duration;
+    let corrections = "enable_corrections = true
+insensitive_corrections = ['Jellyfish']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'";
+    let config = listener_config(
+        corrections,
+        "[correction_cooldown_overrides]\n'!busy:example.com' = 60",
+    );
+    assert_eq!(
+        Duration::from_secs(60),
+        config.correction_cooldown(room_id!("!busy:example.com"))
+    );
+    assert_eq!(
+        Duration::from_secs(300),
+        config.correction_cooldown(room_id!("!room:example.com"))
+    );
+    assert!(load_config(
+        corrections,
+        "[correction_cooldown_overrides]\n'#busy:example.com' = 60"
+    )
+    .is_err())
+}
+#[test]
+fn dry_run_default() {
+    assert!(!listener_config("", "").dry_run)
+}
+#[test]
+fn dry_run_enabled() {
+    assert!(listener_config("dry_run = true", "").dry_run)
+}
+#[test]
+fn ban_authorized_users_default() {
+    assert_eq!(None, listener_config("", "").ban_authorized_users)
+}
+#[test]
+fn ban_authorized_users() {
+    let config = listener_config("ban_authorized_users = ['@mod:example.com']", "");
+    assert_eq!(
+        Some(HashSet::from([user_id!("@mod:example.com").to_owned()])),
+        config.ban_authorized_users
+    )
+}
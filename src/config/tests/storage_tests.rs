@@ -0,0 +1,370 @@
+use crate::config::{ListenerStorage, ResponderStorage};
+use ruma::{event_id, room_id, user_id, EventId};
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
+
+#[test]
+fn take_recorded_responses() {
+    let mut storage = ResponderStorage::default();
+    storage.record_response(
+        event_id!("$trigger:example.com").to_owned(),
+        event_id!("$response1:example.com").to_owned(),
+    );
+    storage.record_response(
+        event_id!("$other:example.com").to_owned(),
+        event_id!("$response2:example.com").to_owned(),
+    );
+    storage.record_response(
+        event_id!("$trigger:example.com").to_owned(),
+        event_id!("$response3:example.com").to_owned(),
+    );
+    let taken = storage.take_responses(event_id!("$trigger:example.com"));
+    assert_eq!(2, taken.len());
+    assert_eq!("$response1:example.com", taken[0].as_str());
+    assert_eq!("$response3:example.com", taken[1].as_str());
+    assert!(storage
+        .take_responses(event_id!("$trigger:example.com"))
+        .is_empty());
+    assert_eq!(1, storage.responses.len())
+}
+#[test]
+fn first_response_to_trigger() {
+    let mut storage = ResponderStorage::default();
+    assert_eq!(None, storage.response_to(event_id!("$trigger:example.com")));
+    storage.record_response(
+        event_id!("$trigger:example.com").to_owned(),
+        event_id!("$response1:example.com").to_owned(),
+    );
+    storage.record_response(
+        event_id!("$trigger:example.com").to_owned(),
+        event_id!("$response2:example.com").to_owned(),
+    );
+    assert_eq!(
+        Some(&event_id!("$response1:example.com").to_owned()),
+        storage.response_to(event_id!("$trigger:example.com"))
+    )
+}
+#[test]
+fn responses_bounded() {
+    let mut storage = ResponderStorage::default();
+    for i in 0..600 {
+        storage.record_response(
+            EventId::parse(format!("$trigger{}:example.com", i)).unwrap(),
+            EventId::parse(format!("$response{}:example.com", i)).unwrap(),
+        );
+    }
+    assert_eq!(500, storage.responses.len());
+    assert!(storage
+        .take_responses(event_id!("$trigger0:example.com"))
+        .is_empty())
+}
+#[test]
+fn reply_budget_depletes() {
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    let now = Instant::now();
+    assert!(!storage.reply_budget_exhausted(user, 2, now));
+    storage.record_replies(user, 1, now);
+    assert!(!storage.reply_budget_exhausted(user, 2, now));
+    storage.record_replies(user, 1, now + Duration::from_secs(60));
+    assert!(storage.reply_budget_exhausted(user, 2, now + Duration::from_secs(120)));
+    assert!(!storage.reply_budget_exhausted(user_id!("@other:example.com"), 2, now))
+}
+#[test]
+fn reply_budget_resets() {
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    let now = Instant::now();
+    storage.record_replies(user, 2, now);
+    let later = now + Duration::from_secs(3600);
+    assert!(!storage.reply_budget_exhausted(user, 2, later));
+    storage.record_replies(user, 1, later);
+    assert!(!storage.reply_budget_exhausted(user, 2, later));
+    storage.record_replies(user, 1, later);
+    assert!(storage.reply_budget_exhausted(user, 2, later))
+}
+#[test]
+fn karma_cooldown_expires() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    let giver = user_id!("@giver:example.com");
+    let receiver = user_id!("@receiver:example.com");
+    let now = Instant::now();
+    assert!(storage.change_karma(room, giver, receiver, 1, now));
+    assert!(!storage.change_karma(room, giver, receiver, 1, now + Duration::from_secs(299)));
+    assert!(storage.change_karma(room, giver, receiver, 1, now + Duration::from_secs(300)));
+    assert_eq!(2, storage.karma(room, receiver))
+}
+#[test]
+fn help_cooldown_expires() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    let cooldown = Duration::from_secs(60);
+    let now = Instant::now();
+    assert!(storage.help_cooldown(room, "", cooldown, now));
+    assert!(!storage.help_cooldown(room, "", cooldown, now + Duration::from_secs(59)));
+    assert!(storage.help_cooldown(room, "ping", cooldown, now + Duration::from_secs(59)));
+    assert!(storage.help_cooldown(room, "", cooldown, now + Duration::from_secs(60)))
+}
+#[test]
+fn about_reply_cooldown_per_room() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    let now = Instant::now();
+    assert!(storage.about_reply_cooldown(room, now));
+    assert!(!storage.about_reply_cooldown(room, now + Duration::from_secs(3599)));
+    assert!(storage.about_reply_cooldown(room_id!("!other:example.com"), now));
+    assert!(storage.about_reply_cooldown(room, now + Duration::from_secs(3600)))
+}
+#[test]
+fn duplicate_conversion_window_expires() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    let window = Duration::from_secs(60);
+    let now = Instant::now();
+    assert!(!storage.duplicate_conversion(room, "100km", window, now));
+    assert!(storage.duplicate_conversion(room, "100km", window, now + Duration::from_secs(59)));
+    assert!(!storage.duplicate_conversion(room, "5km", window, now + Duration::from_secs(59)));
+    assert!(!storage.duplicate_conversion(room, "100km", window, now + Duration::from_secs(60)))
+}
+#[test]
+fn correction_stats_bounded() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    for i in 0..100 {
+        storage.record_correction(room, &format!("spelling{}", i));
+    }
+    storage.record_correction(room, "spelling0");
+    let stats = &storage.correction_stats[room];
+    assert_eq!(101, stats.total);
+    assert_eq!(50, stats.spellings.len());
+    assert_eq!(Some(&2), stats.spellings.get("spelling0"))
+}
+#[test]
+fn correction_burst_window_restarts() {
+    let mut storage = ListenerStorage::default();
+    let (room, user) = (room_id!("!room:example.com"), user_id!("@user:example.com"));
+    let window = Duration::from_secs(10);
+    let now = Instant::now();
+    storage.queue_correction(room, user, event_id!("$1:example.com"), "a", window, now);
+    let later = now + Duration::from_secs(8);
+    storage.queue_correction(room, user, event_id!("$2:example.com"), "a", window, later);
+    assert_eq!(Some(later + window), storage.next_correction_due());
+    assert!(storage.take_due_corrections(now + window).is_empty());
+    let due = storage.take_due_corrections(later + window);
+    assert_eq!(1, due.len());
+    assert_eq!(vec!["a".to_string()], due[0].2.spellings);
+    assert_eq!(None, storage.next_correction_due())
+}
+#[test]
+fn github_pause() {
+    let mut storage = ListenerStorage::default();
+    let now = SystemTime::now();
+    assert!(!storage.github_paused(now));
+    storage.pause_github(now + Duration::from_secs(60));
+    assert!(storage.github_paused(now));
+    assert!(storage.github_pause_notice());
+    assert!(!storage.github_pause_notice());
+    assert!(!storage.github_paused(now + Duration::from_secs(60)));
+    storage.pause_github(now + Duration::from_secs(120));
+    assert!(storage.github_pause_notice())
+}
+#[test]
+fn flood_detection() {
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    let window = Duration::from_secs(10);
+    let now = Instant::now();
+    for i in 0..3 {
+        assert!(!storage.record_flood_message(user, 3, window, now + Duration::from_secs(i)));
+    }
+    // The first message has left the window
+    assert!(!storage.record_flood_message(user, 3, window, now + Duration::from_secs(10)));
+    assert!(storage.record_flood_message(user, 3, window, now + Duration::from_millis(10500)));
+    assert!(!storage.recent_messages.contains_key(user))
+}
+#[test]
+fn role_invite_cooldown_per_room_and_user() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!gaming:example.com");
+    let user = user_id!("@user:example.com");
+    let now = Instant::now();
+    assert!(storage.role_invite_cooldown(room, user, now));
+    assert!(!storage.role_invite_cooldown(room, user, now + Duration::from_secs(3599)));
+    assert!(storage.role_invite_cooldown(room_id!("!other:example.com"), user, now));
+    assert!(storage.role_invite_cooldown(room, user_id!("@other:example.com"), now));
+    assert!(storage.role_invite_cooldown(room, user, now + Duration::from_secs(3600)))
+}
+#[test]
+fn warnings_counted_per_scope() {
+    use crate::config::Warning;
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    let warning = |room: &str| Warning {
+        time: SystemTime::now(),
+        room: ruma::RoomId::parse(room).unwrap(),
+        admin: user_id!("@admin:example.com").to_owned(),
+        reason: None,
+    };
+    let room = room_id!("!room:example.com");
+    assert_eq!(
+        1,
+        storage.add_warning(user, warning("!room:example.com"), None)
+    );
+    assert_eq!(
+        1,
+        storage.add_warning(
+            user,
+            warning("!other:example.com"),
+            Some(room_id!("!other:example.com"))
+        )
+    );
+    assert_eq!(2, storage.warning_count(user, None));
+    assert_eq!(1, storage.warning_count(user, Some(room)));
+    assert_eq!(
+        0,
+        storage.warning_count(user_id!("@other:example.com"), None)
+    );
+    let storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    assert_eq!(2, storage.warning_count(user, None))
+}
+#[test]
+fn clear_warnings_per_scope() {
+    use crate::config::Warning;
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    for room in [
+        "!room:example.com",
+        "!room:example.com",
+        "!other:example.com",
+    ]
+    .iter()
+    {
+        let warning = Warning {
+            time: SystemTime::now(),
+            room: ruma::RoomId::parse(*room).unwrap(),
+            admin: user_id!("@admin:example.com").to_owned(),
+            reason: None,
+        };
+        storage.add_warning(user, warning, None);
+    }
+    assert_eq!(
+        2,
+        storage.clear_warnings(user, Some(room_id!("!room:example.com")))
+    );
+    assert_eq!(1, storage.warning_count(user, None));
+    assert_eq!(1, storage.clear_warnings(user, None));
+    assert!(storage.warnings.is_empty());
+    assert_eq!(0, storage.clear_warnings(user, None))
+}
+#[test]
+fn correction_cooldown_per_room() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    assert!(storage.correction_time_cooldown(room, Duration::from_secs(300)));
+    storage.last_correction_time.insert(
+        room.to_owned(),
+        SystemTime::now() - Duration::from_secs(120),
+    );
+    assert!(storage.correction_time_cooldown(room, Duration::from_secs(60)));
+    assert!(!storage.correction_time_cooldown(room, Duration::from_secs(300)))
+}
+#[test]
+fn pending_ban_expires() {
+    use crate::messages::MatrixBanMessage;
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    let ban = || MatrixBanMessage {
+        admin: admin.to_owned(),
+        users: vec![user_id!("@spam:example.com").to_owned()],
+        reason: None,
+        rooms: Default::default(),
+    };
+    let now = Instant::now();
+    storage.set_pending_ban(admin, ban(), now);
+    assert!(storage
+        .take_pending_ban(admin, now + Duration::from_secs(59))
+        .is_some());
+    assert!(storage.take_pending_ban(admin, now).is_none());
+    storage.set_pending_ban(admin, ban(), now);
+    assert!(storage
+        .take_pending_ban(admin, now + Duration::from_secs(60))
+        .is_none())
+}
+#[test]
+fn room_members_expire() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    let now = Instant::now();
+    assert!(storage.room_members(room, now).is_none());
+    storage.set_room_members(
+        room,
+        std::iter::once(user_id!("@user:example.com").to_owned()).collect(),
+        now,
+    );
+    assert_eq!(
+        1,
+        storage
+            .room_members(room, now + Duration::from_secs(59))
+            .unwrap()
+            .len()
+    );
+    assert!(storage
+        .room_members(room, now + Duration::from_secs(60))
+        .is_none())
+}
+#[test]
+fn group_ping_cooldown() {
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    let cooldown = Duration::from_secs(60);
+    let now = SystemTime::now();
+    assert!(storage.group_ping_cooldown(user, cooldown, now));
+    storage.last_group_ping_time.insert(user.to_owned(), now);
+    assert!(!storage.group_ping_cooldown(user, cooldown, now + Duration::from_secs(59)));
+    assert!(storage.group_ping_cooldown(user, cooldown, now + Duration::from_secs(60)))
+}
+/// Returns a storage path in the temp directory that no other test or test run writes to
+fn storage_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("matrix_bot_{}_{}.ron", std::process::id(), name))
+}
+#[test]
+fn smaller_save_replaces_larger_one() {
+    use crate::config::write_storage_file;
+    let path = storage_path("shrinking_storage");
+    let mut storage = ListenerStorage::default();
+    for i in 0..50 {
+        storage
+            .ignored_users
+            .insert(ruma::UserId::parse(format!("@user{}:example.com", i)).unwrap());
+    }
+    write_storage_file(&path, &ron::to_string(&storage).unwrap()).unwrap();
+    storage.ignored_users.clear();
+    write_storage_file(&path, &ron::to_string(&storage).unwrap()).unwrap();
+    let loaded: ListenerStorage = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(loaded.ignored_users.is_empty());
+    std::fs::remove_file(path).unwrap()
+}
+#[test]
+fn shutdown_save_after_responses_taken() {
+    use crate::config::write_storage_file;
+    let path = storage_path("shrinking_responder");
+    let mut storage = ResponderStorage::default();
+    let trigger = event_id!("$trigger:example.com");
+    for i in 0..20 {
+        storage.record_response(
+            trigger.to_owned(),
+            EventId::parse(format!("$response{}:example.com", i)).unwrap(),
+        );
+    }
+    write_storage_file(&path, &ron::to_string(&storage).unwrap()).unwrap();
+    storage.take_responses(trigger);
+    write_storage_file(&path, &ron::to_string(&storage).unwrap()).unwrap();
+    let loaded: ResponderStorage = ron::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert!(loaded.responses.is_empty());
+    // Nothing is left behind next to the storage file
+    let mut temp = path.clone().into_os_string();
+    temp.push(".tmp");
+    assert!(!std::path::Path::new(&temp).exists());
+    std::fs::remove_file(path).unwrap()
+}
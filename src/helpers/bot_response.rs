@@ -1,6 +1,7 @@
 //! Helper type and associated functions to enable simple response building
 
 use super::ConvertedUnit;
+use crate::config::GroupPingFormat;
 use reqwest::Url;
 use ruma::OwnedUserId;
 use std::collections::HashSet;
@@ -11,8 +12,16 @@ use std::fmt;
 pub struct MatrixNoticeResponse {
     /// List of converted units for response building
     conversions: Option<Vec<ConvertedUnit>>,
+    /// Whether converted units are followed by the formula used to convert them
+    show_formulas: bool,
+    /// List of heat indexes and wind chills for response building
+    feels_like: Option<Vec<String>>,
     /// List of gh search results for response building
     gh_results: Option<Vec<Url>>,
+    /// Explanation of why gh searches were not performed for response building
+    gh_notice: Option<String>,
+    /// Searchable repos close to unknown repos that were searched for response building
+    gh_suggestions: Option<Vec<String>>,
     /// List of link results for response building
     links: Option<Vec<Url>>,
     /// Expanded text for response building
@@ -23,6 +32,8 @@ pub struct MatrixNoticeResponse {
 pub struct MatrixFormattedTextResponse {
     /// List of users that will be pinged for response building
     users: Option<HashSet<OwnedUserId>>,
+    /// How users are shown for response building
+    format: GroupPingFormat,
 }
 
 #[derive(Debug, Default)]
@@ -37,12 +48,34 @@ impl MatrixNoticeResponse {
     pub fn set_unit_conversions(&mut self, conversions: Vec<ConvertedUnit>) {
         self.conversions = Some(conversions)
     }
+    /// Sets whether converted units are followed by the formula used to convert them
+    pub fn set_show_formulas(&mut self, show_formulas: bool) {
+        self.show_formulas = show_formulas
+    }
+    /// Sets member feels_like with supplied list of heat indexes and wind chills
+    ///
+    /// Will overwrite if suppled a second time
+    pub fn set_feels_like(&mut self, feels_like: Vec<String>) {
+        self.feels_like = Some(feels_like)
+    }
     /// Sets member gh_results with supplied list of Urls
     ///
     /// Will overwrite if suppled a second time
     pub fn set_gh_results(&mut self, gh_results: Vec<Url>) {
         self.gh_results = Some(gh_results)
     }
+    /// Sets member gh_notice with supplied text
+    ///
+    /// Will overwrite if suppled a second time
+    pub fn set_gh_notice(&mut self, gh_notice: String) {
+        self.gh_notice = Some(gh_notice)
+    }
+    /// Sets member gh_suggestions with supplied list of repos
+    ///
+    /// Will overwrite if suppled a second time
+    pub fn set_gh_suggestions(&mut self, gh_suggestions: Vec<String>) {
+        self.gh_suggestions = Some(gh_suggestions)
+    }
     /// Sets member links with supplied list of Urls
     ///
     /// Will overwrite if suppled a second time
@@ -58,7 +91,10 @@ impl MatrixNoticeResponse {
     /// Returns `true` if any member field is `Some`
     pub fn is_some(&self) -> bool {
         self.conversions.is_some()
+            || self.feels_like.is_some()
             || self.gh_results.is_some()
+            || self.gh_notice.is_some()
+            || self.gh_suggestions.is_some()
             || self.links.is_some()
             || self.expanded_text.is_some()
     }
@@ -71,15 +107,24 @@ impl MatrixFormattedTextResponse {
     pub fn set_users(&mut self, users: HashSet<OwnedUserId>) {
         self.users = Some(users)
     }
+    /// Sets how users are shown
+    pub fn set_format(&mut self, format: GroupPingFormat) {
+        self.format = format
+    }
     /// Returns `true` if any member field is `Some`
     pub fn is_some(&self) -> bool {
         self.users.is_some()
     }
     /// Formats users to be in line with the fancy riot style pings
+    ///
+    /// Returns `None` unless the format is html
     pub fn format_text(&self) -> Option<String> {
-        self.users.as_ref().map(|v| {
+        if self.format != GroupPingFormat::Html {
+            return None;
+        }
+        self.users.as_ref().map(|_| {
             let mut formatted_text = String::new();
-            for user in v {
+            for user in self.sorted_users() {
                 formatted_text.push_str("<a href=\"https://matrix.to/#/");
                 formatted_text.push_str(user.as_ref());
                 formatted_text.push_str("\">");
@@ -89,6 +134,12 @@ impl MatrixFormattedTextResponse {
             formatted_text
         })
     }
+    /// Users in a stable order so replies don't change between pings
+    fn sorted_users(&self) -> Vec<&OwnedUserId> {
+        let mut users: Vec<&OwnedUserId> = self.users.iter().flatten().collect();
+        users.sort();
+        users
+    }
 }
 
 impl MatrixFormattedNoticeResponse {
@@ -123,7 +174,17 @@ impl fmt::Display for MatrixNoticeResponse {
         let mut response = String::new();
         if let Some(v) = &self.conversions {
             for s in v {
-                response.push_str(&s.to_string());
+                if self.show_formulas {
+                    response.push_str(&format!("{:#}", s));
+                } else {
+                    response.push_str(&s.to_string());
+                }
+                response.push('\n')
+            }
+        }
+        if let Some(v) = &self.feels_like {
+            for s in v {
+                response.push_str(s);
                 response.push('\n')
             }
         }
@@ -133,6 +194,16 @@ impl fmt::Display for MatrixNoticeResponse {
                 response.push('\n')
             }
         }
+        if let Some(v) = &self.gh_notice {
+            response.push_str(v);
+            response.push('\n')
+        }
+        if let Some(v) = &self.gh_suggestions {
+            for s in v {
+                response.push_str(&format!("Did you mean {}?", s));
+                response.push('\n')
+            }
+        }
         if let Some(v) = &self.links {
             for s in v {
                 response.push_str(s.as_ref());
@@ -152,14 +223,24 @@ impl fmt::Display for MatrixNoticeResponse {
 
 impl fmt::Display for MatrixFormattedTextResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut response = String::new();
-        if let Some(v) = &self.users {
-            for user in v {
-                response.push_str(user.localpart());
-                response.push(' ')
-            }
-        }
-        let response = response.trim();
+        let users = self.sorted_users();
+        let response = match self.format {
+            GroupPingFormat::Plain => users
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join(" "),
+            GroupPingFormat::Html => users
+                .iter()
+                .map(|v| v.localpart())
+                .collect::<Vec<_>>()
+                .join(" "),
+            GroupPingFormat::List => users
+                .iter()
+                .map(|v| v.as_str())
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
         write!(f, "{}", response)
     }
 }
@@ -0,0 +1,248 @@
+//! Helper functions and associated type to convert colors between hex, rgb(), and hsl() notation
+
+use anyhow::{anyhow, bail};
+use std::fmt;
+
+/// Common color names and their hex value.
+pub const NAMED_COLORS: [(&str, &str); 25] = [
+    ("black", "#000000"),
+    ("white", "#ffffff"),
+    ("red", "#ff0000"),
+    ("lime", "#00ff00"),
+    ("green", "#008000"),
+    ("blue", "#0000ff"),
+    ("yellow", "#ffff00"),
+    ("cyan", "#00ffff"),
+    ("aqua", "#00ffff"),
+    ("magenta", "#ff00ff"),
+    ("fuchsia", "#ff00ff"),
+    ("silver", "#c0c0c0"),
+    ("gray", "#808080"),
+    ("grey", "#808080"),
+    ("maroon", "#800000"),
+    ("olive", "#808000"),
+    ("purple", "#800080"),
+    ("teal", "#008080"),
+    ("navy", "#000080"),
+    ("orange", "#ffa500"),
+    ("pink", "#ffc0cb"),
+    ("brown", "#a52a2a"),
+    ("gold", "#ffd700"),
+    ("indigo", "#4b0082"),
+    ("violet", "#ee82ee"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The notation a color was written in.
+pub enum ColorFormat {
+    Hex,
+    Rgb,
+    Hsl,
+    Named,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A color in the sRGB color space.
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Color {
+    /// Hex notation like "#ff8800".
+    pub fn hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
+    /// rgb() notation like "rgb(255,136,0)".
+    pub fn rgb(&self) -> String {
+        format!("rgb({},{},{})", self.red, self.green, self.blue)
+    }
+
+    /// Hue in degrees, and saturation and lightness in percent, rounded to whole numbers.
+    pub fn to_hsl(self) -> (u16, u8, u8) {
+        let (r, g, b) = (
+            f64::from(self.red) / 255.0,
+            f64::from(self.green) / 255.0,
+            f64::from(self.blue) / 255.0,
+        );
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let lightness = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return (0, 0, (lightness * 100.0).round() as u8);
+        }
+        let saturation = if lightness > 0.5 {
+            delta / (2.0 - max - min)
+        } else {
+            delta / (max + min)
+        };
+        let hue = if max == r {
+            ((g - b) / delta).rem_euclid(6.0)
+        } else if max == g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        } * 60.0;
+        (
+            hue.round() as u16 % 360,
+            (saturation * 100.0).round() as u8,
+            (lightness * 100.0).round() as u8,
+        )
+    }
+
+    /// hsl() notation like "hsl(32,100%,50%)".
+    pub fn hsl(&self) -> String {
+        let (hue, saturation, lightness) = self.to_hsl();
+        format!("hsl({},{}%,{}%)", hue, saturation, lightness)
+    }
+
+    /// Builds a color from a hue in degrees, and saturation and lightness in percent.
+    pub fn from_hsl(hue: f64, saturation: f64, lightness: f64) -> Self {
+        let (s, l) = (saturation / 100.0, lightness / 100.0);
+        let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+        let h = (hue % 360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = l - chroma / 2.0;
+        let channel = |v: f64| ((v + m) * 255.0).round() as u8;
+        Color {
+            red: channel(r),
+            green: channel(g),
+            blue: channel(b),
+        }
+    }
+}
+
+#[derive(Debug)]
+/// A parsed color and the notation it was written in.
+///
+/// Displays the color in every other notation, like "#ff8800 => rgb(255,136,0), hsl(32,100%,50%)"
+pub struct ConvertedColor {
+    pub input: String,
+    pub color: Color,
+    pub format: ColorFormat,
+}
+
+impl fmt::Display for ConvertedColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let others: Vec<String> = match self.format {
+            ColorFormat::Hex => vec![self.color.rgb(), self.color.hsl()],
+            ColorFormat::Rgb => vec![self.color.hex(), self.color.hsl()],
+            ColorFormat::Hsl => vec![self.color.hex(), self.color.rgb()],
+            ColorFormat::Named => vec![self.color.hex(), self.color.rgb(), self.color.hsl()],
+        };
+        write!(f, "{} => {}", self.input, others.join(", "))
+    }
+}
+
+/// Parses a color written as "#f80", "#ff8800", "rgb(255, 136, 0)", "hsl(32, 100%, 50%)", or a common name like "red".
+///
+/// Errors explain which part of the color is invalid.
+pub fn convert_color(input: &str) -> anyhow::Result<ConvertedColor> {
+    let input = input.trim().to_lowercase();
+    let (color, format) = if let Some(hex) = input.strip_prefix('#') {
+        (parse_hex(hex)?, ColorFormat::Hex)
+    } else if let Some(args) = function_args(&input, "rgb") {
+        (parse_rgb(&args)?, ColorFormat::Rgb)
+    } else if let Some(args) = function_args(&input, "hsl") {
+        (parse_hsl(&args)?, ColorFormat::Hsl)
+    } else {
+        match NAMED_COLORS.iter().find(|(name, _)| *name == input) {
+            Some((_, hex)) => (parse_hex(&hex[1..])?, ColorFormat::Named),
+            None => bail!("Unknown color {}", input),
+        }
+    };
+    Ok(ConvertedColor {
+        input,
+        color,
+        format,
+    })
+}
+
+/// Returns the trimmed comma separated arguments of a notation like "rgb(1, 2, 3)".
+fn function_args(input: &str, name: &str) -> Option<Vec<String>> {
+    let args = input
+        .strip_prefix(name)?
+        .trim_start()
+        .strip_prefix('(')?
+        .strip_suffix(')')?;
+    Some(args.split(',').map(|a| a.trim().to_string()).collect())
+}
+
+fn parse_hex(hex: &str) -> anyhow::Result<Color> {
+    let invalid = || {
+        anyhow!(
+            "Invalid hex color #{}. Hex colors have 3 or 6 hex digits",
+            hex
+        )
+    };
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
+    }
+    let digits: Vec<u8> = match hex.len() {
+        // Each digit of a 3 digit color is doubled, so #f80 is #ff8800
+        3 => hex
+            .chars()
+            .map(|c| u8::from_str_radix(&format!("{}{}", c, c), 16))
+            .collect::<Result<_, _>>()?,
+        6 => (0..6)
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<_, _>>()?,
+        _ => return Err(invalid()),
+    };
+    Ok(Color {
+        red: digits[0],
+        green: digits[1],
+        blue: digits[2],
+    })
+}
+
+fn parse_rgb(args: &[String]) -> anyhow::Result<Color> {
+    let channels = match args
+        .iter()
+        .map(|a| a.parse::<u8>())
+        .collect::<Result<Vec<_>, _>>()
+    {
+        Ok(v) if v.len() == 3 => v,
+        _ => bail!("Invalid rgb color. Use rgb(red, green, blue) with values between 0 and 255"),
+    };
+    Ok(Color {
+        red: channels[0],
+        green: channels[1],
+        blue: channels[2],
+    })
+}
+
+fn parse_hsl(args: &[String]) -> anyhow::Result<Color> {
+    let invalid = || {
+        anyhow!("Invalid hsl color. Use hsl(hue, saturation%, lightness%) with a hue between 0 and 360 and percentages between 0% and 100%")
+    };
+    if args.len() != 3 {
+        return Err(invalid());
+    }
+    let hue: f64 = args[0].parse().map_err(|_| invalid())?;
+    let percent = |arg: &str| -> Option<f64> {
+        let value: f64 = arg.trim_end_matches('%').trim().parse().ok()?;
+        if (0.0..=100.0).contains(&value) {
+            Some(value)
+        } else {
+            None
+        }
+    };
+    let (saturation, lightness) = match (percent(&args[1]), percent(&args[2])) {
+        (Some(s), Some(l)) if (0.0..=360.0).contains(&hue) => (s, l),
+        _ => return Err(invalid()),
+    };
+    Ok(Color::from_hsl(hue, saturation, lightness))
+}
@@ -0,0 +1,308 @@
+//! Helper functions to describe standard 5 field cron expressions and build them from simple descriptions
+
+use anyhow::{anyhow, bail, Context};
+
+const MONTHS: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const WEEKDAYS: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// A cron field with the values it accepts.
+struct Field {
+    /// Name used in descriptions and errors.
+    unit: &'static str,
+    min: u32,
+    max: u32,
+    /// Names the values can be written as, starting at min. Named values are described by name.
+    names: Option<&'static [&'static str]>,
+}
+
+const FIELDS: [Field; 5] = [
+    Field {
+        unit: "minute",
+        min: 0,
+        max: 59,
+        names: None,
+    },
+    Field {
+        unit: "hour",
+        min: 0,
+        max: 23,
+        names: None,
+    },
+    Field {
+        unit: "day-of-month",
+        min: 1,
+        max: 31,
+        names: None,
+    },
+    Field {
+        unit: "month",
+        min: 1,
+        max: 12,
+        names: Some(&MONTHS),
+    },
+    Field {
+        unit: "day-of-week",
+        min: 0,
+        max: 7,
+        names: Some(&WEEKDAYS),
+    },
+];
+
+/// One comma separated item of a cron field.
+#[derive(Debug, PartialEq)]
+enum Item {
+    /// Every value, optionally with a step.
+    All(Option<u32>),
+    Value(u32),
+    /// Inclusive range of values, optionally with a step.
+    Range(u32, u32, Option<u32>),
+}
+
+impl Field {
+    fn parse_value(&self, value: &str) -> anyhow::Result<u32> {
+        if let Some(names) = self.names {
+            if let Some(i) = names
+                .iter()
+                .position(|n| n[..3].eq_ignore_ascii_case(value))
+            {
+                return Ok(self.min + i as u32);
+            }
+        }
+        let number = value
+            .parse()
+            .with_context(|| format!("Invalid {} value {}", self.unit, value))?;
+        if number < self.min || number > self.max {
+            bail!(
+                "Invalid {} value {}. Must be between {} and {}",
+                self.unit,
+                value,
+                self.min,
+                self.max
+            )
+        }
+        Ok(number)
+    }
+
+    fn parse_item(&self, item: &str) -> anyhow::Result<Item> {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => match step.parse() {
+                Ok(0) | Err(_) => bail!("Invalid {} step {}", self.unit, step),
+                Ok(v) => (range, Some(v)),
+            },
+            None => (item, None),
+        };
+        if range == "*" {
+            return Ok(Item::All(step));
+        }
+        match range.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (self.parse_value(start)?, self.parse_value(end)?);
+                if start > end {
+                    bail!(
+                        "Invalid {} range {}. Start must not be after end",
+                        self.unit,
+                        range
+                    )
+                }
+                Ok(Item::Range(start, end, step))
+            }
+            None => {
+                let value = self.parse_value(range)?;
+                match step {
+                    Some(_) => Ok(Item::Range(value, self.max, step)),
+                    None => Ok(Item::Value(value)),
+                }
+            }
+        }
+    }
+
+    fn parse(&self, field: &str) -> anyhow::Result<Vec<Item>> {
+        field.split(',').map(|item| self.parse_item(item)).collect()
+    }
+
+    fn name(&self, value: u32) -> String {
+        match self.names {
+            // Day of week 7 is another way to write Sunday
+            Some(names) => names[(value - self.min) as usize % names.len()].to_string(),
+            None => value.to_string(),
+        }
+    }
+
+    /// Describes the items of a field, like "every 2nd hour" or "Monday and Friday".
+    fn describe(&self, items: &[Item]) -> String {
+        let values: Option<Vec<String>> = items
+            .iter()
+            .map(|item| match item {
+                Item::Value(v) => Some(self.name(*v)),
+                _ => None,
+            })
+            .collect();
+        if let Some(values) = values {
+            return match self.names {
+                Some(_) => join(&values),
+                None => format!("{} {}", self.unit, join(&values)),
+            };
+        }
+        let descriptions: Vec<String> = items
+            .iter()
+            .map(|item| match item {
+                Item::All(None) => format!("every {}", self.unit),
+                Item::All(Some(step)) => format!("every {} {}", ordinal(*step), self.unit),
+                Item::Value(v) => match self.names {
+                    Some(_) => self.name(*v),
+                    None => format!("{} {}", self.unit, v),
+                },
+                Item::Range(start, end, step) => format!(
+                    "every {}{} from {} through {}",
+                    step.map(|s| format!("{} ", ordinal(s))).unwrap_or_default(),
+                    self.unit,
+                    self.name(*start),
+                    self.name(*end)
+                ),
+            })
+            .collect();
+        join(&descriptions)
+    }
+}
+
+/// Joins a list like "1, 2 and 3".
+fn join(items: &[String]) -> String {
+    match items.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+fn ordinal(number: u32) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", number, suffix)
+}
+
+/// Describes a standard 5 field cron expression, like "At 09:00 on Monday" for "0 9 * * 1".
+///
+/// Errors explain which field is invalid.
+pub fn describe_cron(expression: &str) -> anyhow::Result<String> {
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    if fields.len() != 5 {
+        bail!(
+            "Cron expressions need 5 fields (minute hour day-of-month month day-of-week) but {} has {}",
+            expression,
+            fields.len()
+        )
+    }
+    let items = FIELDS
+        .iter()
+        .zip(&fields)
+        .map(|(field, text)| field.parse(text))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let (minute, hour, day, month, weekday) =
+        (&items[0], &items[1], &items[2], &items[3], &items[4]);
+    let every = |items: &[Item]| matches!(items, [Item::All(None)]);
+
+    let mut description = match (minute.as_slice(), hour.as_slice()) {
+        ([Item::Value(m)], [Item::Value(h)]) => format!("At {:02}:{:02}", h, m),
+        _ if every(hour) => format!("At {}", FIELDS[0].describe(minute)),
+        _ => format!(
+            "At {} past {}",
+            FIELDS[0].describe(minute),
+            FIELDS[1].describe(hour)
+        ),
+    };
+    if !every(day) {
+        description.push_str(&format!(" on {}", FIELDS[2].describe(day)));
+    }
+    if !every(weekday) {
+        let joiner = if every(day) { " on " } else { " and on " };
+        description.push_str(joiner);
+        description.push_str(&FIELDS[4].describe(weekday));
+    }
+    if !every(month) {
+        description.push_str(&format!(" in {}", FIELDS[3].describe(month)));
+    }
+    Ok(description)
+}
+
+/// Builds a cron expression from a simple description.
+///
+/// Supports "every minute", "every N minutes", "every hour", "every N hours",
+/// and "at HH:MM", optionally prefixed with "every day" or followed by "on <weekday>".
+pub fn to_cron(description: &str) -> anyhow::Result<String> {
+    let description = description.trim().to_lowercase();
+    let words: Vec<&str> = description.split_whitespace().collect();
+    let unsupported = || {
+        anyhow!(
+            "Unable to build a cron expression for {}. Try \"every 15 minutes\" or \"at 09:00 on monday\"",
+            description
+        )
+    };
+    match words.as_slice() {
+        ["every", "minute"] => Ok("* * * * *".to_string()),
+        ["every", "hour"] => Ok("0 * * * *".to_string()),
+        ["every", n, "minutes"] => Ok(format!("*/{} * * * *", parse_step(n, &FIELDS[0])?)),
+        ["every", n, "hours"] => Ok(format!("0 */{} * * *", parse_step(n, &FIELDS[1])?)),
+        ["every", "day", "at", time] | ["at", time] => {
+            let (hour, minute) = parse_time(time).ok_or_else(unsupported)?;
+            Ok(format!("{} {} * * *", minute, hour))
+        }
+        ["at", time, "on", day] | ["every", day, "at", time] => {
+            let (hour, minute) = parse_time(time).ok_or_else(unsupported)?;
+            let weekday = WEEKDAYS
+                .iter()
+                .position(|w| w.eq_ignore_ascii_case(day.trim_end_matches('s')))
+                .ok_or_else(unsupported)?;
+            Ok(format!("{} {} * * {}", minute, hour, weekday))
+        }
+        _ => Err(unsupported()),
+    }
+}
+
+/// Parses a step between 1 and the largest value of a field.
+fn parse_step(step: &str, field: &Field) -> anyhow::Result<u32> {
+    match step.parse() {
+        Ok(v) if v >= 1 && v <= field.max => Ok(v),
+        _ => bail!(
+            "Invalid {} step {}. Must be between 1 and {}",
+            field.unit,
+            step,
+            field.max
+        ),
+    }
+}
+
+/// Parses a 24 hour HH:MM time.
+fn parse_time(time: &str) -> Option<(u32, u32)> {
+    let (hour, minute) = time.split_once(':')?;
+    let (hour, minute) = (hour.parse().ok()?, minute.parse().ok()?);
+    if hour > 23 || minute > 59 {
+        return None;
+    }
+    Some((hour, minute))
+}
@@ -0,0 +1,69 @@
+//! Helper function and associated type to show a date in alternate calendar representations
+
+use chrono::{Datelike, NaiveDate};
+use std::fmt;
+use tracing::debug;
+
+/// Julian day number of 0001-12-31 in the proleptic Gregorian calendar, which chrono counts as day 0
+const JULIAN_DAY_OFFSET: i32 = 1_721_425;
+
+#[derive(Debug)]
+/// Type used to represent a date alongside its alternate representations.
+///
+/// Displays like "2024-03-15 => Julian day 2460385, ordinal 2024-075, ISO week 2024-W11-5"
+pub struct ConvertedDate {
+    /// Date that was converted.
+    date: NaiveDate,
+}
+
+impl ConvertedDate {
+    /// Julian day number of the date.
+    pub fn julian_day(&self) -> i32 {
+        self.date.num_days_from_ce() + JULIAN_DAY_OFFSET
+    }
+
+    /// Ordinal date in the form "2024-075".
+    pub fn ordinal(&self) -> String {
+        format!("{}-{:03}", self.date.year(), self.date.ordinal())
+    }
+
+    /// ISO week date in the form "2024-W11-5".
+    pub fn iso_week(&self) -> String {
+        let week = self.date.iso_week();
+        format!(
+            "{}-W{:02}-{}",
+            week.year(),
+            week.week(),
+            self.date.weekday().number_from_monday()
+        )
+    }
+}
+
+impl fmt::Display for ConvertedDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} => Julian day {}, ordinal {}, ISO week {}",
+            self.date,
+            self.julian_day(),
+            self.ordinal(),
+            self.iso_week()
+        )
+    }
+}
+
+/// Parses a "YYYY-MM-DD" date into a [ConvertedDate](struct.ConvertedDate.html).
+///
+/// Returns `None` if the date does not exist.
+pub fn convert_date(date: &str) -> Option<ConvertedDate> {
+    match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+        Ok(date) => Some(ConvertedDate { date }),
+        Err(e) => {
+            debug!(
+                "Date unable to be parsed. Error is {:?}, date is {:?}",
+                e, date
+            );
+            None
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Helper functions to convert pixel densities and work out the physical size of pixels
+//!
+//! Formulas used, with an inch being 2.54cm:
+//! - dpcm = dpi / 2.54
+//! - size in inches = pixels / dpi
+//! - ppi of a screen = √(width² + height²) / diagonal in inches
+//! - dot pitch in mm = 25.4 / ppi
+
+/// Centimeters in an inch.
+pub const CM_PER_INCH: f64 = 2.54;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Whether a pixel density counts dots per inch or per centimeter.
+pub enum DensityUnit {
+    /// Dots or pixels per inch, written dpi or ppi.
+    PerInch,
+    /// Dots or pixels per centimeter, written dpcm or ppcm.
+    PerCm,
+}
+
+impl DensityUnit {
+    /// Reads a unit like "dpi", "ppi", "dpcm", or "ppcm".
+    pub fn parse(unit: &str) -> Option<Self> {
+        match unit.to_lowercase().as_str() {
+            "dpi" | "ppi" => Some(Self::PerInch),
+            "dpcm" | "ppcm" => Some(Self::PerCm),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a density to the other unit, so dpi becomes dpcm and dpcm becomes dpi.
+pub fn convert_density(density: f64, unit: DensityUnit) -> f64 {
+    match unit {
+        DensityUnit::PerInch => density / CM_PER_INCH,
+        DensityUnit::PerCm => density * CM_PER_INCH,
+    }
+}
+
+/// Size in inches of a number of pixels printed or shown at `dpi`.
+///
+/// Returns `None` if `dpi` isn't positive.
+pub fn physical_size(pixels: u32, dpi: f64) -> Option<f64> {
+    if dpi <= 0.0 {
+        return None;
+    }
+    Some(f64::from(pixels) / dpi)
+}
+
+/// Pixel density in ppi of a screen with a resolution and a diagonal in inches.
+///
+/// Returns `None` if the resolution is zero or the diagonal isn't positive.
+pub fn screen_ppi(width: u32, height: u32, diagonal: f64) -> Option<f64> {
+    if width == 0 || height == 0 || diagonal <= 0.0 {
+        return None;
+    }
+    Some(f64::from(width).hypot(f64::from(height)) / diagonal)
+}
+
+/// Distance in mm between the centers of neighbouring pixels at `ppi`.
+pub fn dot_pitch(ppi: f64) -> f64 {
+    CM_PER_INCH * 10.0 / ppi
+}
@@ -0,0 +1,68 @@
+//! Helper functions to convert between UK oven gas marks and temperatures
+
+/// Gas marks with their name and oven temperature in °C and °F, from coolest to hottest.
+///
+/// Gas marks aren't linear below gas mark 1, so conversions look marks up instead of calculating them.
+pub const GAS_MARKS: [(f64, &str, u32, u32); 11] = [
+    (0.25, "1/4", 110, 225),
+    (0.5, "1/2", 120, 250),
+    (1.0, "1", 140, 275),
+    (2.0, "2", 150, 300),
+    (3.0, "3", 170, 325),
+    (4.0, "4", 180, 350),
+    (5.0, "5", 190, 375),
+    (6.0, "6", 200, 400),
+    (7.0, "7", 220, 425),
+    (8.0, "8", 230, 450),
+    (9.0, "9", 240, 475),
+];
+
+/// How far in °C a temperature can be outside of the gas mark table and still round to the nearest mark.
+const GAS_MARK_TOLERANCE: f64 = 10.0;
+
+/// Parses a gas mark written as a number like "4", a decimal like "0.5", a fraction like "1/4", or "¼" and "½".
+pub fn parse_gas_mark(mark: &str) -> Option<f64> {
+    match mark.trim() {
+        "¼" => Some(0.25),
+        "½" => Some(0.5),
+        mark => match mark.split_once('/') {
+            Some((numerator, denominator)) => {
+                let denominator: f64 = denominator.trim().parse().ok()?;
+                if denominator == 0.0 {
+                    return None;
+                }
+                Some(numerator.trim().parse::<f64>().ok()? / denominator)
+            }
+            None => mark.parse().ok(),
+        },
+    }
+}
+
+/// Returns the name and oven temperature in °C and °F of a gas mark.
+///
+/// Returns `None` if the mark isn't in [GAS_MARKS](constant.GAS_MARKS.html).
+pub fn gas_mark_temperature(mark: f64) -> Option<(&'static str, u32, u32)> {
+    GAS_MARKS
+        .iter()
+        .find(|(m, ..)| *m == mark)
+        .map(|(_, name, celsius, fahrenheit)| (*name, *celsius, *fahrenheit))
+}
+
+/// Returns the name of the gas mark closest to a temperature in °C, preferring the cooler mark on ties.
+///
+/// Returns `None` if the temperature is too far outside of the gas mark table.
+pub fn nearest_gas_mark(celsius: f64) -> Option<&'static str> {
+    let (coolest, hottest) = (GAS_MARKS[0].2, GAS_MARKS[GAS_MARKS.len() - 1].2);
+    if celsius < f64::from(coolest) - GAS_MARK_TOLERANCE
+        || celsius > f64::from(hottest) + GAS_MARK_TOLERANCE
+    {
+        return None;
+    }
+    let mut nearest = &GAS_MARKS[0];
+    for mark in &GAS_MARKS[1..] {
+        if (f64::from(mark.2) - celsius).abs() < (f64::from(nearest.2) - celsius).abs() {
+            nearest = mark;
+        }
+    }
+    Some(nearest.1)
+}
@@ -0,0 +1,113 @@
+//! Helper functions to describe IPv4 and IPv6 CIDR ranges and convert between netmasks and prefix lengths
+
+use anyhow::{anyhow, bail};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Most host bits an IPv6 range can have for its address count to be shown in full instead of as a power of 2.
+const MAX_FULL_COUNT_BITS: u32 = 64;
+
+/// Describes a range written in CIDR notation like "192.168.1.0/24" or "2001:db8::/32".
+///
+/// IPv4 ranges show their network, broadcast, netmask, and usable hosts. IPv6 ranges show their
+/// network, last address, and number of addresses. Host bits set in the address are ignored.
+pub fn describe_network(input: &str) -> anyhow::Result<String> {
+    let (address, prefix) = input
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| anyhow!("{} is not in CIDR notation like 192.168.1.0/24", input))?;
+    let address: IpAddr = address
+        .parse()
+        .map_err(|_| anyhow!("{} is not a valid IP address", address))?;
+    let prefix: u32 = prefix
+        .parse()
+        .map_err(|_| anyhow!("/{} is not a valid prefix length", prefix))?;
+    match address {
+        IpAddr::V4(v) => describe_ipv4(v, prefix),
+        IpAddr::V6(v) => describe_ipv6(v, prefix),
+    }
+}
+
+fn describe_ipv4(address: Ipv4Addr, prefix: u32) -> anyhow::Result<String> {
+    let mask = prefix_to_netmask(prefix)?;
+    let network = u32::from(address) & u32::from(mask);
+    let last = network | !u32::from(mask);
+    let (network, last) = (Ipv4Addr::from(network), Ipv4Addr::from(last));
+    let input = format!("{}/{}", address, prefix);
+    Ok(match prefix {
+        32 => format!("{} => single host {}", input, network),
+        // Point to point links use both addresses and have no broadcast address
+        31 => format!(
+            "{} => network {}, netmask {}, 2 hosts ({} - {})",
+            input, network, mask, network, last
+        ),
+        _ => format!(
+            "{} => network {}, broadcast {}, netmask {}, {} hosts ({} - {})",
+            input,
+            network,
+            last,
+            mask,
+            (1u64 << (32 - prefix)) - 2,
+            Ipv4Addr::from(u32::from(network) + 1),
+            Ipv4Addr::from(u32::from(last) - 1)
+        ),
+    })
+}
+
+fn describe_ipv6(address: Ipv6Addr, prefix: u32) -> anyhow::Result<String> {
+    if prefix > 128 {
+        bail!(
+            "/{} is too long for an IPv6 address, which has at most /128",
+            prefix
+        )
+    }
+    let host_bits = 128 - prefix;
+    let mask = u128::MAX.checked_shl(host_bits).unwrap_or(0);
+    let network = u128::from(address) & mask;
+    let last = network | !mask;
+    let (network, last) = (Ipv6Addr::from(network), Ipv6Addr::from(last));
+    let input = format!("{}/{}", address, prefix);
+    if host_bits == 0 {
+        return Ok(format!("{} => single host {}", input, network));
+    }
+    let count = if host_bits <= MAX_FULL_COUNT_BITS {
+        (1u128 << host_bits).to_string()
+    } else {
+        format!("2^{}", host_bits)
+    };
+    Ok(format!(
+        "{} => network {}, last address {}, {} addresses",
+        input, network, last, count
+    ))
+}
+
+/// Converts an IPv4 netmask like "255.255.255.0" to its prefix length.
+///
+/// Errors if the netmask isn't a valid address or its bits aren't contiguous.
+pub fn netmask_to_prefix(netmask: &str) -> anyhow::Result<u32> {
+    let mask: Ipv4Addr = netmask
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("{} is not a valid netmask", netmask))?;
+    let bits = u32::from(mask);
+    let prefix = bits.leading_ones();
+    // Every set bit has to come before every unset bit
+    if bits.checked_shl(prefix).unwrap_or(0) != 0 {
+        bail!("{} is not a valid netmask", netmask)
+    }
+    Ok(prefix)
+}
+
+/// Converts a prefix length to an IPv4 netmask, like /24 to 255.255.255.0.
+///
+/// Errors if the prefix length is longer than /32.
+pub fn prefix_to_netmask(prefix: u32) -> anyhow::Result<Ipv4Addr> {
+    if prefix > 32 {
+        bail!(
+            "/{} is too long for an IPv4 address, which has at most /32",
+            prefix
+        )
+    }
+    Ok(Ipv4Addr::from(
+        u32::MAX.checked_shl(32 - prefix).unwrap_or(0),
+    ))
+}
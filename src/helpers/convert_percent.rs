@@ -0,0 +1,159 @@
+//! Helper functions and associated type to convert between percentages, fractions, and ratios
+
+use anyhow::{anyhow, bail};
+
+/// Most decimal places shown for decimals.
+const DECIMAL_PLACES: u32 = 6;
+/// Most decimal places shown for percentages.
+const PERCENT_PLACES: u32 = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The notation a proportion is written in.
+pub enum FractionFormat {
+    Percent,
+    Fraction,
+    Ratio,
+    Decimal,
+}
+
+impl FractionFormat {
+    /// Reads a conversion target like "%", "percent", "fraction", "ratio", or "decimal".
+    pub fn from_target(target: &str) -> Option<Self> {
+        match target.to_lowercase().as_str() {
+            "%" | "percent" | "percentage" => Some(FractionFormat::Percent),
+            "fraction" => Some(FractionFormat::Fraction),
+            "ratio" => Some(FractionFormat::Ratio),
+            "decimal" => Some(FractionFormat::Decimal),
+            _ => None,
+        }
+    }
+}
+
+/// A non-negative fraction reduced to its lowest terms.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Fraction {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+/// Greatest common divisor of two numbers.
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Fraction {
+    /// Builds a fraction reduced with the greatest common divisor.
+    ///
+    /// Errors if the denominator is zero.
+    pub fn new(numerator: u64, denominator: u64) -> anyhow::Result<Self> {
+        if denominator == 0 {
+            bail!("{}/{} divides by zero", numerator, denominator)
+        }
+        let divisor = gcd(numerator, denominator);
+        Ok(Self {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Formats the fraction in a notation.
+    ///
+    /// Improper fractions also show their mixed number, like "9/4 (2 1/4)".
+    /// Decimals and percentages that had to be rounded are prefixed with ≈.
+    pub fn format(&self, format: FractionFormat) -> String {
+        match format {
+            FractionFormat::Percent => format!("{}%", self.decimal(100, PERCENT_PLACES)),
+            FractionFormat::Decimal => self.decimal(1, DECIMAL_PLACES),
+            FractionFormat::Ratio => format!("{}:{}", self.numerator, self.denominator),
+            FractionFormat::Fraction => {
+                let whole = self.numerator / self.denominator;
+                let remainder = self.numerator % self.denominator;
+                if whole > 0 && remainder > 0 {
+                    format!(
+                        "{}/{} ({} {}/{})",
+                        self.numerator, self.denominator, whole, remainder, self.denominator
+                    )
+                } else {
+                    format!("{}/{}", self.numerator, self.denominator)
+                }
+            }
+        }
+    }
+
+    /// The fraction times a scale, rounded half up to at most a number of decimal places.
+    fn decimal(&self, scale: u64, places: u32) -> String {
+        let factor = 10u128.pow(places);
+        let scaled = u128::from(self.numerator) * u128::from(scale) * factor;
+        let denominator = u128::from(self.denominator);
+        let rounded = (scaled + denominator / 2) / denominator;
+        let exact = scaled.is_multiple_of(denominator);
+        let whole = rounded / factor;
+        let fraction = format!("{:0width$}", rounded % factor, width = places as usize);
+        let fraction = fraction.trim_end_matches('0');
+        format!(
+            "{}{}{}{}",
+            if exact { "" } else { "≈" },
+            whole,
+            if fraction.is_empty() { "" } else { "." },
+            fraction
+        )
+    }
+}
+
+/// Parses a proportion written as a percentage like "25%" or "12.5 %", a fraction like "3/8", or a ratio like "3:8".
+pub fn parse_fraction(input: &str) -> anyhow::Result<(Fraction, FractionFormat)> {
+    let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let number = |n: &str| {
+        n.parse::<u64>()
+            .map_err(|_| anyhow!("{} is too large to convert", input))
+    };
+    if let Some(percent) = input.strip_suffix('%') {
+        let (whole, decimals) = percent.split_once('.').unwrap_or((percent, ""));
+        // 12.5% is 125/1000
+        let denominator = 10u64
+            .checked_pow(decimals.len() as u32)
+            .and_then(|v| v.checked_mul(100))
+            .ok_or_else(|| anyhow!("{} has too many decimal places to convert", input))?;
+        let numerator = number(&format!("{}{}", whole, decimals))?;
+        return Ok((
+            Fraction::new(numerator, denominator)?,
+            FractionFormat::Percent,
+        ));
+    }
+    let (parts, format) = match (input.split_once('/'), input.split_once(':')) {
+        (Some(parts), None) => (parts, FractionFormat::Fraction),
+        (None, Some(parts)) => (parts, FractionFormat::Ratio),
+        _ => bail!("{} is not a percentage, fraction, or ratio", input),
+    };
+    let (numerator, denominator) = (number(parts.0)?, number(parts.1)?);
+    if denominator == 0 {
+        bail!("{} divides by zero", input)
+    }
+    Ok((Fraction::new(numerator, denominator)?, format))
+}
+
+/// Converts a percentage, fraction, or ratio to a target notation, or every other notation if there is no target.
+///
+/// The input notation stays in the output when it can be reduced, like "6/8 => 0.75, 75%, 3/4, 3:4".
+pub fn convert_fraction(input: &str, target: Option<FractionFormat>) -> anyhow::Result<String> {
+    let (fraction, format) = parse_fraction(input)?;
+    let input: String = input.split_whitespace().collect();
+    let results: Vec<String> = match target {
+        Some(t) => vec![fraction.format(t)],
+        None => [
+            FractionFormat::Decimal,
+            FractionFormat::Percent,
+            FractionFormat::Fraction,
+            FractionFormat::Ratio,
+        ]
+        .iter()
+        .filter(|f| **f != format || fraction.format(**f) != input)
+        .map(|f| fraction.format(*f))
+        .collect(),
+    };
+    Ok(format!("{} => {}", input, results.join(", ")))
+}
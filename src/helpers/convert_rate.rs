@@ -0,0 +1,88 @@
+//! Helper function and associated type to convert data transfer rates between bits and bytes per second
+
+use std::fmt;
+
+/// Decimal prefixes a data rate can use, with their symbol and name.
+const PREFIXES: [(char, &str); 4] = [('k', "kilo"), ('M', "mega"), ('G', "giga"), ('T', "tera")];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// Whether a data rate counts bits or bytes. A byte is 8 bits.
+pub enum DataUnit {
+    Bits,
+    Bytes,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A data rate unit like Mbps or MBps.
+pub struct RateUnit {
+    /// Symbol of the decimal prefix, like 'M'.
+    prefix: char,
+    /// Name of the decimal prefix, like "mega".
+    prefix_name: &'static str,
+    pub unit: DataUnit,
+}
+
+impl RateUnit {
+    /// Reads a unit like "Mbps", "MB/s", or "gbps". The prefix is case insensitive,
+    /// but a lowercase b means bits and an uppercase B means bytes.
+    pub fn parse(unit: &str) -> Option<Self> {
+        let mut chars = unit.chars();
+        let prefix = chars.next()?;
+        let (prefix, prefix_name) = *PREFIXES
+            .iter()
+            .find(|(p, _)| p.eq_ignore_ascii_case(&prefix))?;
+        let data_unit = match chars.next()? {
+            'b' => DataUnit::Bits,
+            'B' => DataUnit::Bytes,
+            _ => return None,
+        };
+        match chars.as_str() {
+            "ps" | "/s" => Some(Self {
+                prefix,
+                prefix_name,
+                unit: data_unit,
+            }),
+            _ => None,
+        }
+    }
+
+    /// The same prefix in the other data unit.
+    fn other(self) -> Self {
+        Self {
+            unit: match self.unit {
+                DataUnit::Bits => DataUnit::Bytes,
+                DataUnit::Bytes => DataUnit::Bits,
+            },
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for RateUnit {
+    /// Formats as "Mbps", or with the name as "Mbps (megabits per second)" when using the alternate flag.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (symbol, name) = match self.unit {
+            DataUnit::Bits => ('b', "bits"),
+            DataUnit::Bytes => ('B', "bytes"),
+        };
+        write!(f, "{}{}ps", self.prefix, symbol)?;
+        if f.alternate() {
+            write!(f, " ({}{} per second)", self.prefix_name, name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts a data rate between bits and bytes per second, keeping its decimal prefix.
+///
+/// Returns the converted quantity and unit, or `None` if the unit is unknown or the quantity is negative.
+pub fn convert_data_rate(quantity: f64, unit: RateUnit) -> Option<(f64, RateUnit)> {
+    if !(quantity.is_finite() && quantity >= 0.0) {
+        return None;
+    }
+    let converted = match unit.unit {
+        DataUnit::Bits => quantity / 8.0,
+        DataUnit::Bytes => quantity * 8.0,
+    };
+    Some((converted, unit.other()))
+}
@@ -0,0 +1,47 @@
+//! Helper functions to convert between resolutions and aspect ratios
+
+use std::convert::TryFrom;
+
+/// Named resolutions and their height in pixels.
+const NAMED_HEIGHTS: [(&str, u32); 2] = [("4k", 2160), ("8k", 4320)];
+
+/// Greatest common divisor of two numbers.
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Simplifies a resolution like 1920x1080 into its aspect ratio like 16:9.
+///
+/// Returns `None` if either side is zero.
+pub fn aspect_ratio(width: u32, height: u32) -> Option<(u32, u32)> {
+    if width == 0 || height == 0 {
+        return None;
+    }
+    let divisor = gcd(width, height);
+    Some((width / divisor, height / divisor))
+}
+
+/// Calculates the resolution of an aspect ratio at a height, rounding the width to the nearest pixel.
+///
+/// Returns `None` if either side of the ratio or the height is zero.
+pub fn resolution(ratio_width: u32, ratio_height: u32, height: u32) -> Option<(u32, u32)> {
+    if ratio_width == 0 || ratio_height == 0 || height == 0 {
+        return None;
+    }
+    let width = (u64::from(height) * u64::from(ratio_width) + u64::from(ratio_height) / 2)
+        / u64::from(ratio_height);
+    u32::try_from(width).ok().map(|w| (w, height))
+}
+
+/// Reads the height of a resolution written like "1080p", "1080", or "4k".
+pub fn resolution_height(name: &str) -> Option<u32> {
+    let name = name.to_lowercase();
+    match NAMED_HEIGHTS.iter().find(|(n, _)| *n == name) {
+        Some((_, height)) => Some(*height),
+        None => name.trim_end_matches('p').parse().ok(),
+    }
+}
@@ -0,0 +1,60 @@
+//! Helper functions to convert between numbers and Roman numerals
+
+/// Smallest number that can be written as a Roman numeral.
+pub const MIN_ROMAN: u32 = 1;
+/// Largest number that can be written as a standard Roman numeral.
+pub const MAX_ROMAN: u32 = 3999;
+
+/// Numeral values from largest to smallest, including the subtractive pairs.
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"),
+    (900, "CM"),
+    (500, "D"),
+    (400, "CD"),
+    (100, "C"),
+    (90, "XC"),
+    (50, "L"),
+    (40, "XL"),
+    (10, "X"),
+    (9, "IX"),
+    (5, "V"),
+    (4, "IV"),
+    (1, "I"),
+];
+
+/// Writes `number` as a Roman numeral.
+///
+/// Returns `None` if the number is outside of [MIN_ROMAN](constant.MIN_ROMAN.html) to
+/// [MAX_ROMAN](constant.MAX_ROMAN.html).
+pub fn to_roman(mut number: u32) -> Option<String> {
+    if !(MIN_ROMAN..=MAX_ROMAN).contains(&number) {
+        return None;
+    }
+    let mut numeral = String::new();
+    for (value, symbol) in NUMERALS {
+        while number >= value {
+            numeral.push_str(symbol);
+            number -= value;
+        }
+    }
+    Some(numeral)
+}
+
+/// Reads an uppercase Roman numeral as a number.
+///
+/// Only numerals in their standard form are accepted, so "IIII", "VX", and "IC" are rejected.
+pub fn from_roman(numeral: &str) -> Option<u32> {
+    let mut number = 0;
+    let mut rest = numeral;
+    for (value, symbol) in NUMERALS {
+        while let Some(r) = rest.strip_prefix(symbol) {
+            number += value;
+            rest = r;
+        }
+    }
+    // Non standard numerals either have leftover symbols or do not round trip
+    if !rest.is_empty() || to_roman(number).as_deref() != Some(numeral) {
+        return None;
+    }
+    Some(number)
+}
@@ -0,0 +1,42 @@
+//! Helper functions to convert times of day between timezones
+
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone};
+use chrono_tz::{Tz, TZ_VARIANTS};
+
+/// Finds a timezone by its IANA name like "Europe/London", ignoring case.
+pub fn find_timezone(name: &str) -> Option<Tz> {
+    TZ_VARIANTS
+        .iter()
+        .find(|tz| tz.name().eq_ignore_ascii_case(name))
+        .copied()
+}
+
+/// Reads a time of day like "3pm", "3:30 pm", or "15:00". 24 hour times need minutes.
+pub fn parse_time_of_day(time: &str) -> Option<NaiveTime> {
+    let time = time.to_lowercase();
+    let (time, pm) = match (time.strip_suffix("am"), time.strip_suffix("pm")) {
+        (Some(t), _) => (t.trim(), Some(false)),
+        (_, Some(t)) => (t.trim(), Some(true)),
+        _ => (time.as_str(), None),
+    };
+    let (hour, minute) = match (time.split_once(':'), pm) {
+        (Some((h, m)), _) => (h.parse::<u32>().ok()?, m.parse::<u32>().ok()?),
+        (None, Some(_)) => (time.parse().ok()?, 0),
+        (None, None) => return None,
+    };
+    let hour = match pm {
+        Some(pm) if (1..=12).contains(&hour) => hour % 12 + if pm { 12 } else { 0 },
+        Some(_) => return None,
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Converts a time of day on `date` in `from` to the same moment in `to`.
+///
+/// Returns `None` if the time doesn't exist in `from` on that date, like during a daylight saving gap.
+pub fn convert_time(time: NaiveTime, date: NaiveDate, from: Tz, to: Tz) -> Option<DateTime<Tz>> {
+    from.from_local_datetime(&date.and_time(time))
+        .earliest()
+        .map(|t| t.with_timezone(&to))
+}
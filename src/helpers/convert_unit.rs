@@ -1,12 +1,35 @@
 //! Helper function and associated type to enable simple conversion from a list of values to a list of converted values
 
+use crate::config::{CustomConversion, RoundingMode};
+use std::collections::HashMap;
 use std::fmt;
 use tracing::{debug, trace};
 use uom::si::f64::*;
 use uom::si::length::{centimeter, foot, inch, kilometer, meter, mile};
 use uom::si::mass::{kilogram, pound};
+use uom::si::pressure::{bar, kilopascal, pound_force_per_square_inch};
 use uom::si::thermodynamic_temperature::{degree_celsius, degree_fahrenheit};
+use uom::si::torque::{newton_meter, pound_force_foot};
 use uom::si::velocity::{kilometer_per_hour, mile_per_hour};
+use uom::si::volume::{cup, gallon, liter, pint_liquid, quart_liquid};
+
+/// Number of decimal places used for dimensions without a configured precision if none is configured.
+pub const DEFAULT_PRECISION: usize = 2;
+/// Dimensions whose precision can be configured. Custom conversions share the "custom" dimension.
+pub const DIMENSIONS: [&str; 8] = [
+    "length",
+    "temperature",
+    "mass",
+    "speed",
+    "volume",
+    "torque",
+    "pressure",
+    "custom",
+];
+/// Units whose case tells them apart from another unit, like Nm (newton meters) and nm (nanometers).
+const CASE_SENSITIVE_UNITS: [&str; 1] = ["Nm"];
+/// Largest precision that can be configured for a dimension.
+pub const MAX_PRECISION: usize = 10;
 
 #[derive(Debug)]
 /// Type used to represent a successful unit conversion in the form of "100km => 62.41mi"
@@ -15,24 +38,49 @@ pub struct ConvertedUnit {
     from: String,
     /// Converted "to" value. Looks like "62.41mi"
     to: String,
+    /// Formula used for the conversion. Looks like "mi = km × 0.621371"
+    formula: String,
 }
 
 impl fmt::Display for ConvertedUnit {
+    /// Formats as "100km => 62.41mi", or with the formula as "100km => 62.41mi (mi = km × 0.621371)"
+    /// when using the alternate flag.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} => {}", self.from, self.to)
+        if f.alternate() {
+            write!(f, "{} => {} ({})", self.from, self.to, self.formula)
+        } else {
+            write!(f, "{} => {}", self.from, self.to)
+        }
     }
 }
 
 /// Converts supplied values into [ConvertedUnits](struct.ConvertedUnit.html).
 ///
+/// Units not found in the built-in table are looked up in `custom_conversions`, so custom units
+/// only shadow a built-in one with the same name if `prefer_custom` is set.
+///
+/// Values are rounded using `rounding` to the number of decimal places configured for their
+/// dimension in `precisions`, falling back to `default_precision`.
+///
 /// Returns `None` if nothing was able to be converted after parsing and processing.
-pub fn convert_unit(conversions: Vec<(String, String)>) -> Option<Vec<ConvertedUnit>> {
+pub fn convert_unit(
+    conversions: Vec<(String, String)>,
+    custom_conversions: Option<&HashMap<String, CustomConversion>>,
+    prefer_custom: bool,
+    default_precision: usize,
+    precisions: &HashMap<String, usize>,
+    rounding: RoundingMode,
+) -> Option<Vec<ConvertedUnit>> {
+    let format_value = |value: f64, dimension: &str| {
+        format_value(value, dimension, default_precision, precisions, rounding)
+    };
+
     let mut result = Vec::new();
     let mut working_data = Vec::new();
 
     for conversion in conversions {
         let (quantity, unit) = conversion;
-        match quantity.parse::<f64>() {
+        match decimal_comma(&quantity).parse::<f64>() {
             Ok(v) => working_data.push((unit, v)),
             Err(e) => {
                 debug!(
@@ -48,80 +96,383 @@ pub fn convert_unit(conversions: Vec<(String, String)>) -> Option<Vec<ConvertedU
         return None;
     }
 
-    macro_rules! convert_unit {
-        (
-            $unit:expr, $quantity:expr,
-            $(
-                $unit_ty:ident {
-                    $( ( $from_str:expr, $to_str:expr, $from_ty:ty, $to_ty:ty ) ),*
-                    $(,)?
+    for (unit, quantity) in working_data {
+        let custom = custom_conversions.and_then(|c| c.get(unit.as_str()));
+        let builtin = match custom {
+            Some(_) if prefer_custom => None,
+            _ => convert_builtin(unit.as_str(), quantity, &format_value),
+        };
+        if let Some(v) = builtin {
+            result.push(v);
+            continue;
+        }
+        match custom {
+            Some(c) => {
+                let from = format!("{}{}", format_value(quantity, "custom"), unit);
+                let to = format!("{}{}", format_value(quantity * c.factor, "custom"), c.unit);
+                let formula = linear_formula(&unit, &c.unit, c.factor);
+                result.push(ConvertedUnit { from, to, formula });
+            }
+            None => {
+                debug!(
+                    "Attempted unknown conversion for unit {:?}",
+                    unit.trim().to_lowercase()
+                );
+            }
+        }
+    }
+
+    if !result.is_empty() {
+        Some(result)
+    } else {
+        trace!("No units converted");
+        None
+    }
+}
+
+/// Replaces a comma used as a decimal separator, like in "1,5", with a period so the quantity parses.
+///
+/// Only a single comma followed by 1 to 9 digits is replaced. Quantities with more commas, like
+/// "1,000,000", are left alone since they use commas as thousands separators.
+fn decimal_comma(quantity: &str) -> String {
+    match quantity.split_once(',') {
+        Some((whole, fraction))
+            if (1..=9).contains(&fraction.len())
+                && fraction.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            format!("{}.{}", whole, fraction)
+        }
+        _ => quantity.to_string(),
+    }
+}
+
+/// Returns the name `unit` is looked up by, which is lowercase unless its case matters.
+pub fn unit_key(unit: &str) -> String {
+    if CASE_SENSITIVE_UNITS.contains(&unit) {
+        unit.to_string()
+    } else {
+        unit.to_lowercase()
+    }
+}
+
+/// Quantities and their units, like ("100", "km").
+type Conversions = Vec<(String, String)>;
+
+/// Splits adjacent imperial volumes like "1gal 2qt" out of `conversions` into compound volumes.
+///
+/// A compound volume ends at a conversion that isn't an imperial volume or at a repeated unit,
+/// so "1gal 2qt 1gal" is two volumes. Lone volumes are left in the returned conversions.
+pub fn split_compound_volumes(conversions: Conversions) -> (Vec<Conversions>, Conversions) {
+    let mut compounds = Vec::new();
+    let mut rest = Vec::new();
+    let mut current: Conversions = Vec::new();
+    let mut finish = |current: &mut Conversions, rest: &mut Conversions| {
+        if current.len() > 1 {
+            compounds.push(std::mem::take(current));
+        } else {
+            rest.append(current);
+        }
+    };
+    for conversion in conversions {
+        match compound_volume_unit(&conversion.1) {
+            Some((rank, _)) => {
+                if current.iter().any(
+                    |(_, unit)| matches!(compound_volume_unit(unit), Some((r, _)) if r == rank),
+                ) {
+                    finish(&mut current, &mut rest);
                 }
-            )*
-            _ => {
-                $($default_code:tt)*
+                current.push(conversion);
+            }
+            None => {
+                finish(&mut current, &mut rest);
+                rest.push(conversion);
+            }
+        }
+    }
+    finish(&mut current, &mut rest);
+    (compounds, rest)
+}
+
+/// Sums the components of a compound imperial volume and converts the total to liters.
+///
+/// Components can be in any order and are shown largest first, like "1.00gal 2.00qt => 5.68l".
+///
+/// Returns `None` if a quantity can't be parsed or a unit isn't an imperial volume.
+pub fn convert_compound_volume(
+    components: Vec<(String, String)>,
+    default_precision: usize,
+    precisions: &HashMap<String, usize>,
+    rounding: RoundingMode,
+) -> Option<ConvertedUnit> {
+    let format_value =
+        |value: f64| format_value(value, "volume", default_precision, precisions, rounding);
+    let mut parsed = Vec::new();
+    for (quantity, unit) in components {
+        let (rank, volume) = compound_volume_unit(&unit)?;
+        match decimal_comma(&quantity).parse::<f64>() {
+            Ok(v) => parsed.push((rank, unit, v, volume)),
+            Err(e) => {
+                debug!(
+                    "Quantity unable to be parsed. Error is {:?}, quantity is {:?}",
+                    e, quantity
+                );
+                return None;
+            }
+        }
+    }
+    parsed.sort_by_key(|(rank, ..)| *rank);
+
+    let total = parsed.iter().fold(
+        Volume::new::<liter>(0.0),
+        |total, (_, _, quantity, volume)| total + *volume * *quantity,
+    );
+    let from: Vec<String> = parsed
+        .iter()
+        .map(|(_, unit, quantity, _)| format!("{}{}", format_value(*quantity), unit))
+        .collect();
+    let terms: Vec<String> = parsed
+        .iter()
+        .map(|(_, unit, _, volume)| format!("{} × {}", unit, format_factor(volume.get::<liter>())))
+        .collect();
+    Some(ConvertedUnit {
+        from: from.join(" "),
+        to: format!("{}l", format_value(total.get::<liter>())),
+        formula: format!("l = {}", terms.join(" + ")),
+    })
+}
+
+/// Returns the rank of an imperial volume unit that can be part of a compound volume, largest first,
+/// and the volume of one of the unit.
+fn compound_volume_unit(unit: &str) -> Option<(usize, Volume)> {
+    match unit {
+        "gal" => Some((0, Volume::new::<gallon>(1.0))),
+        "qt" => Some((1, Volume::new::<quart_liquid>(1.0))),
+        "pt" => Some((2, Volume::new::<pint_liquid>(1.0))),
+        "cup" | "cups" => Some((3, Volume::new::<cup>(1.0))),
+        _ => None,
+    }
+}
+
+/// Formats `value` with the number of decimal places configured for `dimension` in `precisions`,
+/// or `default_precision` if it has none.
+fn format_value(
+    value: f64,
+    dimension: &str,
+    default_precision: usize,
+    precisions: &HashMap<String, usize>,
+    rounding: RoundingMode,
+) -> String {
+    let precision = precisions
+        .get(dimension)
+        .copied()
+        .unwrap_or(default_precision);
+    format!("{:.*}", precision, round(value, precision, rounding))
+}
+
+/// Rounds `value` to `precision` decimal places using the supplied rounding mode.
+pub fn round(value: f64, precision: usize, mode: RoundingMode) -> f64 {
+    let scale = 10f64.powi(precision as i32);
+    let scaled = value * scale;
+    let rounded = match mode {
+        RoundingMode::Truncate => scaled.trunc(),
+        RoundingMode::HalfUp => scaled.round(),
+        RoundingMode::HalfEven => {
+            if (scaled - scaled.trunc()).abs() == 0.5 {
+                2.0 * (scaled / 2.0).round()
+            } else {
+                scaled.round()
             }
-        ) => {
-            match $unit {
+        }
+    };
+    rounded / scale
+}
+
+/// Builds the formula for a conversion that only multiplies by `factor`, like "mi = km × 0.621371"
+fn linear_formula(from: &str, to: &str, factor: f64) -> String {
+    format!("{} = {} × {}", to, from, format_factor(factor))
+}
+
+/// Formats a conversion factor with up to 6 decimal places, like "0.621371"
+fn format_factor(factor: f64) -> String {
+    let factor = format!("{:.6}", factor);
+    factor
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Returns the unit `unit` converts to, checking built-in units before `custom_conversions`.
+///
+/// Returns `None` if the unit is unknown.
+pub fn conversion_target<'a>(
+    unit: &str,
+    custom_conversions: Option<&'a HashMap<String, CustomConversion>>,
+) -> Option<&'a str> {
+    builtin_target(unit).or_else(|| {
+        custom_conversions
+            .and_then(|c| c.get(unit))
+            .map(|c| c.unit.as_str())
+    })
+}
+
+#[derive(Debug, PartialEq)]
+/// A unit that is both a built-in unit and a custom unit.
+///
+/// Displays both meanings, like "m converts to ft as a length or to h as a custom unit"
+pub struct AmbiguousUnit {
+    pub unit: String,
+    /// Dimension of the built-in unit.
+    pub dimension: &'static str,
+    /// Unit the built-in unit converts to.
+    pub builtin_target: &'static str,
+    /// Unit the custom unit converts to.
+    pub custom_target: String,
+}
+
+impl fmt::Display for AmbiguousUnit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} converts to {} as a {} or to {} as a custom unit",
+            self.unit, self.builtin_target, self.dimension, self.custom_target
+        )
+    }
+}
+
+/// Returns the units of `conversions` that are both a built-in unit and one of `custom_conversions`,
+/// each listed once.
+pub fn ambiguous_units(
+    conversions: &[(String, String)],
+    custom_conversions: Option<&HashMap<String, CustomConversion>>,
+) -> Vec<AmbiguousUnit> {
+    let mut ambiguous: Vec<AmbiguousUnit> = Vec::new();
+    for (_, unit) in conversions {
+        let custom = match custom_conversions.and_then(|c| c.get(unit.as_str())) {
+            Some(v) => v,
+            None => continue,
+        };
+        if let (Some(dimension), Some(builtin_target)) =
+            (builtin_dimension(unit), builtin_target(unit))
+        {
+            if !ambiguous.iter().any(|a| &a.unit == unit) {
+                ambiguous.push(AmbiguousUnit {
+                    unit: unit.clone(),
+                    dimension,
+                    builtin_target,
+                    custom_target: custom.unit.clone(),
+                });
+            }
+        }
+    }
+    ambiguous
+}
+
+/// Generates the functions that convert and look up built-in units from a single table of units.
+macro_rules! unit_table {
+    (
+        $(
+            $unit_ty:ident $dimension:literal {
+                $( ( $from_str:expr, $to_str:expr, $from_ty:ty, $to_ty:ty $(, $formula:expr)? ) ),*
+                $(,)?
+            }
+        )*
+    ) => {
+        /// Built-in units users can convert from, grouped by dimension in the order they're listed.
+        pub const BUILTIN_UNITS: &[(&str, &[&str])] = &[
+            $( ($dimension, &[ $( $from_str ),* ]) ),*
+        ];
+
+        /// Converts a quantity of a built-in unit, formatting values with `format_value` for its dimension.
+        fn convert_builtin(
+            unit: &str,
+            quantity: f64,
+            format_value: &dyn Fn(f64, &str) -> String,
+        ) -> Option<ConvertedUnit> {
+            match unit {
                 $(
                     $(
                         $from_str => {
-                            let unit_value = $unit_ty::new::<$from_ty>($quantity);
+                            let unit_value = $unit_ty::new::<$from_ty>(quantity);
                             let converted_quantity = unit_value.get::<$to_ty>();
-                            let from = format!("{:.2}{}", $quantity, $from_str);
-                            let to = format!("{:.2}{}", converted_quantity, $to_str);
-                            result.push(ConvertedUnit {
-                                from,
-                                to
-                            });
+                            let from = format!("{}{}", format_value(quantity, $dimension), $from_str);
+                            let to = format!("{}{}", format_value(converted_quantity, $dimension), $to_str);
+                            let factor = $unit_ty::new::<$from_ty>(1.0).get::<$to_ty>();
+                            let formula: Option<&str> = None $(.or(Some($formula)))?;
+                            let formula = formula.map_or_else(
+                                || linear_formula($from_str, $to_str, factor),
+                                str::to_string,
+                            );
+                            Some(ConvertedUnit { from, to, formula })
                         }
                     )*
                 )*
-                _ => {
-                    $($default_code)*
-                }
+                _ => None,
             }
         }
-    }
-    for (unit, quantity) in working_data {
-        convert_unit!(unit.as_str(), quantity,
-            Length {
-                ("cm", "in", centimeter, inch),
-                ("m", "ft", meter, foot),
-                ("km", "mi", kilometer, mile),
-                ("in", "cm", inch, centimeter),
-                ("ft", "m", foot, meter),
-                ("mi", "km", mile, kilometer),
-                ("mile", "km", mile, kilometer),
-                ("miles", "km", mile, kilometer),
-            }
-            ThermodynamicTemperature {
-                ("c", "f", degree_celsius, degree_fahrenheit),
-                ("°c", "°f", degree_celsius, degree_fahrenheit),
-                ("f", "c", degree_fahrenheit, degree_celsius),
-                ("°f", "°c", degree_fahrenheit, degree_celsius),
-            }
-            Mass {
-                ("kg", "lbs", kilogram, pound),
-                ("lbs", "kg", pound, kilogram),
-            }
-            Velocity {
-                ("km/h", "mph", kilometer_per_hour, mile_per_hour),
-                ("kmh", "mph", kilometer_per_hour, mile_per_hour),
-                ("kph", "mph", kilometer_per_hour, mile_per_hour),
-                ("kmph", "mph", kilometer_per_hour, mile_per_hour),
-                ("mph", "km/h", mile_per_hour, kilometer_per_hour),
+
+        /// Returns the unit a built-in unit converts to.
+        fn builtin_target(unit: &str) -> Option<&'static str> {
+            match unit {
+                $( $( $from_str => Some($to_str), )* )*
+                _ => None,
             }
-            _ => {
-                debug!("Attempted unknown conversion for unit {:?}",
-                unit.trim().to_lowercase());
+        }
+
+        /// Returns the dimension of a built-in unit.
+        fn builtin_dimension(unit: &str) -> Option<&'static str> {
+            match unit {
+                $( $( $from_str => Some($dimension), )* )*
+                _ => None,
             }
-        );
-    }
+        }
+    };
+}
 
-    if !result.is_empty() {
-        Some(result)
-    } else {
-        trace!("No units converted");
-        None
+unit_table! {
+    Length "length" {
+        ("cm", "in", centimeter, inch),
+        ("m", "ft", meter, foot),
+        ("km", "mi", kilometer, mile),
+        ("in", "cm", inch, centimeter),
+        ("ft", "m", foot, meter),
+        ("mi", "km", mile, kilometer),
+        ("mile", "km", mile, kilometer),
+        ("miles", "km", mile, kilometer),
+    }
+    ThermodynamicTemperature "temperature" {
+        ("c", "f", degree_celsius, degree_fahrenheit, "°F = °C × 9/5 + 32"),
+        ("°c", "°f", degree_celsius, degree_fahrenheit, "°F = °C × 9/5 + 32"),
+        ("f", "c", degree_fahrenheit, degree_celsius, "°C = (°F − 32) × 5/9"),
+        ("°f", "°c", degree_fahrenheit, degree_celsius, "°C = (°F − 32) × 5/9"),
+    }
+    Mass "mass" {
+        ("kg", "lbs", kilogram, pound),
+        ("lbs", "kg", pound, kilogram),
+    }
+    Velocity "speed" {
+        ("km/h", "mph", kilometer_per_hour, mile_per_hour),
+        ("kmh", "mph", kilometer_per_hour, mile_per_hour),
+        ("kph", "mph", kilometer_per_hour, mile_per_hour),
+        ("kmph", "mph", kilometer_per_hour, mile_per_hour),
+        ("mph", "km/h", mile_per_hour, kilometer_per_hour),
+    }
+    Volume "volume" {
+        ("l", "gal", liter, gallon),
+        ("gal", "l", gallon, liter),
+        ("qt", "l", quart_liquid, liter),
+        ("pt", "l", pint_liquid, liter),
+        ("cup", "l", cup, liter),
+        ("cups", "l", cup, liter),
+    }
+    Torque "torque" {
+        ("Nm", "lb-ft", newton_meter, pound_force_foot),
+        ("lb-ft", "Nm", pound_force_foot, newton_meter),
+        ("lbft", "Nm", pound_force_foot, newton_meter),
+        ("ft-lb", "Nm", pound_force_foot, newton_meter),
+    }
+    Pressure "pressure" {
+        ("bar", "psi", bar, pound_force_per_square_inch),
+        ("kpa", "psi", kilopascal, pound_force_per_square_inch),
+        ("psi", "bar", pound_force_per_square_inch, bar),
     }
 }
@@ -0,0 +1,68 @@
+//! Helper function and associated type to convert between the wavelength and frequency of light and sound
+
+use std::fmt;
+
+/// Speed of light in a vacuum in m/s.
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+/// Speed of sound in dry air at 20°C in m/s.
+pub const DEFAULT_SPEED_OF_SOUND: f64 = 343.0;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The kind of wave a unit belongs to.
+pub enum Wave {
+    /// Electromagnetic waves, travelling at the speed of light in a vacuum.
+    Light,
+    /// Acoustic waves, travelling at the configured speed of sound.
+    Sound,
+}
+
+#[derive(Debug)]
+/// Type used to represent a converted wave in the form of "500nm => 599.58THz"
+pub struct ConvertedWave {
+    /// Kind of wave that was converted.
+    pub wave: Wave,
+    /// Converted quantity.
+    pub quantity: f64,
+    /// Unit of the converted quantity.
+    pub unit: &'static str,
+}
+
+impl fmt::Display for Wave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Wave::Light => write!(f, "light"),
+            Wave::Sound => write!(f, "sound"),
+        }
+    }
+}
+
+/// Converts a wavelength to a frequency or a frequency to a wavelength using f = v / λ.
+///
+/// Wavelengths in nm or µm and frequencies in THz are light, frequencies in Hz or kHz are sound.
+/// Returns `None` if the unit is unknown or the quantity is not positive.
+pub fn convert_wave(quantity: f64, unit: &str, speed_of_sound: f64) -> Option<ConvertedWave> {
+    if !(quantity.is_finite() && quantity > 0.0) {
+        return None;
+    }
+    let (wave, quantity, unit) = match unit {
+        "nm" => (
+            Wave::Light,
+            SPEED_OF_LIGHT / (quantity * 1e-9) / 1e12,
+            "THz",
+        ),
+        "um" | "µm" => (
+            Wave::Light,
+            SPEED_OF_LIGHT / (quantity * 1e-6) / 1e12,
+            "THz",
+        ),
+        "thz" => (Wave::Light, SPEED_OF_LIGHT / (quantity * 1e12) * 1e9, "nm"),
+        "hz" => (Wave::Sound, speed_of_sound / quantity, "m"),
+        "khz" => (Wave::Sound, speed_of_sound / (quantity * 1e3), "m"),
+        _ => return None,
+    };
+    Some(ConvertedWave {
+        wave,
+        quantity,
+        unit,
+    })
+}
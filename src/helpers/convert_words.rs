@@ -0,0 +1,188 @@
+//! Helper functions to convert between numbers and English number words
+
+/// Largest number that can be written or read as words.
+pub const MAX_WORDS: u64 = 999_999_999_999;
+
+/// Words for zero through nineteen.
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+/// Words for the multiples of ten from twenty to ninety, starting at twenty.
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+/// Scale words from largest to smallest.
+const SCALES: [(u64, &str); 3] = [
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Writes `number` in English words, hyphenating numbers like "forty-two".
+///
+/// Returns `None` if the number is larger than [MAX_WORDS](constant.MAX_WORDS.html).
+pub fn to_words(number: u64) -> Option<String> {
+    if number > MAX_WORDS {
+        return None;
+    }
+    if number == 0 {
+        return Some(UNITS[0].to_string());
+    }
+    let mut words = Vec::new();
+    let mut rest = number;
+    for (scale, name) in SCALES.iter() {
+        if rest >= *scale {
+            words.push(format!("{} {}", group_to_words(rest / scale), name));
+            rest %= scale;
+        }
+    }
+    if rest > 0 {
+        words.push(group_to_words(rest));
+    }
+    Some(words.join(" "))
+}
+
+/// Writes a number from 1 to 999 in words.
+fn group_to_words(number: u64) -> String {
+    let hundreds = (number / 100) as usize;
+    let rest = (number % 100) as usize;
+    let below_hundred = match rest {
+        0 => None,
+        1..=19 => Some(UNITS[rest].to_string()),
+        _ => match rest % 10 {
+            0 => Some(TENS[rest / 10 - 2].to_string()),
+            unit => Some(format!("{}-{}", TENS[rest / 10 - 2], UNITS[unit])),
+        },
+    };
+    match (hundreds, below_hundred) {
+        (0, Some(v)) => v,
+        (h, None) => format!("{} hundred", UNITS[h]),
+        (h, Some(v)) => format!("{} hundred {}", UNITS[h], v),
+    }
+}
+
+/// Reads English number words like "forty two", "forty-two", or "one hundred and five".
+///
+/// Scales must be in decreasing order and each hundred must be preceded by one to nine,
+/// so ambiguous phrasings like "twelve hundred" are rejected.
+///
+/// Returns `None` if the words aren't a valid number or the number is larger than [MAX_WORDS](constant.MAX_WORDS.html).
+pub fn from_words(text: &str) -> Option<u64> {
+    let text = text.to_lowercase();
+    let mut tokens = Vec::new();
+    for word in text.split_whitespace() {
+        match word.split_once('-') {
+            // Only tens and units can be hyphenated, like "forty-two"
+            Some((tens, unit)) => {
+                if !TENS.contains(&tens) || !matches!(unit_value(unit), Some(1..=9)) {
+                    return None;
+                }
+                tokens.push(tens);
+                tokens.push(unit);
+            }
+            None => tokens.push(word),
+        }
+    }
+    if tokens == [UNITS[0]] {
+        return Some(0);
+    }
+
+    let mut total = 0;
+    let mut last_scale = u64::MAX;
+    let mut i = 0;
+    while i < tokens.len() {
+        let (group, next) = parse_group(&tokens, i)?;
+        i = next;
+        let scale = match tokens.get(i) {
+            Some(word) => {
+                let scale = SCALES.iter().find(|(_, name)| name == word)?.0;
+                i += 1;
+                scale
+            }
+            None => 1,
+        };
+        if scale >= last_scale {
+            return None;
+        }
+        last_scale = scale;
+        total += group * scale;
+    }
+    if total > MAX_WORDS {
+        None
+    } else {
+        Some(total)
+    }
+}
+
+/// Parses a number from 1 to 999 starting at `tokens[i]`, returning it and the index after it.
+fn parse_group(tokens: &[&str], mut i: usize) -> Option<(u64, usize)> {
+    let mut value = 0;
+    let mut has_hundreds = false;
+    if let (Some(unit), Some(&"hundred")) =
+        (tokens.get(i).and_then(|w| unit_value(w)), tokens.get(i + 1))
+    {
+        if !(1..=9).contains(&unit) {
+            return None;
+        }
+        value = unit * 100;
+        has_hundreds = true;
+        i += 2;
+        if tokens.get(i) == Some(&"and") {
+            // "and" must be followed by the rest of the number, like "one hundred and five"
+            i += 1;
+            if !matches!(tokens.get(i), Some(w) if below_hundred_start(w)) {
+                return None;
+            }
+        }
+    }
+    match tokens.get(i) {
+        Some(word) if tens_value(word).is_some() => {
+            value += tens_value(word)?;
+            i += 1;
+            if let Some(unit @ 1..=9) = tokens.get(i).and_then(|w| unit_value(w)) {
+                value += unit;
+                i += 1;
+            }
+        }
+        Some(word) if matches!(unit_value(word), Some(1..=19)) => {
+            value += unit_value(word)?;
+            i += 1;
+        }
+        _ if has_hundreds => (),
+        _ => return None,
+    }
+    Some((value, i))
+}
+
+fn below_hundred_start(word: &str) -> bool {
+    tens_value(word).is_some() || matches!(unit_value(word), Some(1..=19))
+}
+
+fn unit_value(word: &str) -> Option<u64> {
+    UNITS.iter().position(|u| *u == word).map(|v| v as u64)
+}
+
+fn tens_value(word: &str) -> Option<u64> {
+    TENS.iter()
+        .position(|t| *t == word)
+        .map(|v| (v as u64 + 2) * 10)
+}
@@ -0,0 +1,63 @@
+//! Helper functions to encode and decode text as base64, url encoding, or hex
+
+use anyhow::{anyhow, Context};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, NON_ALPHANUMERIC};
+
+/// Longest text in characters that will be encoded or decoded.
+pub const MAX_ENCODING_INPUT: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// A supported string encoding.
+pub enum Encoding {
+    /// Standard base64 with padding.
+    Base64,
+    /// Percent encoding of everything except ASCII letters and digits.
+    Url,
+    /// Lowercase hexadecimal bytes.
+    Hex,
+}
+
+impl Encoding {
+    /// Parses an encoding name. Accepts base64, url, and hex in any case.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "base64" => Some(Encoding::Base64),
+            "url" => Some(Encoding::Url),
+            "hex" => Some(Encoding::Hex),
+            _ => None,
+        }
+    }
+}
+
+/// Encodes the UTF-8 bytes of `text`.
+pub fn encode(encoding: Encoding, text: &str) -> String {
+    match encoding {
+        Encoding::Base64 => base64::encode(text),
+        Encoding::Url => utf8_percent_encode(text, NON_ALPHANUMERIC).to_string(),
+        Encoding::Hex => text.bytes().map(|b| format!("{:02x}", b)).collect(),
+    }
+}
+
+/// Decodes `text` back into a string.
+///
+/// Fails if `text` isn't valid for the encoding or doesn't decode to valid UTF-8.
+pub fn decode(encoding: Encoding, text: &str) -> anyhow::Result<String> {
+    let bytes = match encoding {
+        Encoding::Base64 => base64::decode(text).context("Invalid base64")?,
+        Encoding::Url => percent_decode_str(text).collect(),
+        Encoding::Hex => decode_hex(text)?,
+    };
+    String::from_utf8(bytes).context("Decoded text is not valid UTF-8")
+}
+
+fn decode_hex(text: &str) -> anyhow::Result<Vec<u8>> {
+    text.as_bytes()
+        .chunks(2)
+        .map(|pair| match std::str::from_utf8(pair) {
+            Ok(digits) if digits.len() == 2 => {
+                u8::from_str_radix(digits, 16).context("Invalid hex")
+            }
+            _ => Err(anyhow!("Invalid hex")),
+        })
+        .collect()
+}
@@ -0,0 +1,50 @@
+//! Helper functions to calculate how hot or cold a temperature feels
+
+/// Lowest temperature in °C the heat index is calculated for. Equal to 80°F.
+pub const HEAT_INDEX_MIN_TEMPERATURE: f64 = 26.7;
+/// Highest temperature in °C the heat index is calculated for.
+pub const HEAT_INDEX_MAX_TEMPERATURE: f64 = 50.0;
+/// Highest temperature in °C the wind chill is calculated for.
+pub const WIND_CHILL_MAX_TEMPERATURE: f64 = 10.0;
+/// Lowest wind speed in km/h the wind chill is calculated for.
+pub const WIND_CHILL_MIN_SPEED: f64 = 4.8;
+
+/// Calculates the heat index in °C of a temperature in °C at a relative humidity in percent,
+/// using the Rothfusz regression of the US National Weather Service.
+///
+/// Returns `None` if the temperature is outside of
+/// [HEAT_INDEX_MIN_TEMPERATURE](constant.HEAT_INDEX_MIN_TEMPERATURE.html) to
+/// [HEAT_INDEX_MAX_TEMPERATURE](constant.HEAT_INDEX_MAX_TEMPERATURE.html) or the humidity is not
+/// between 0% and 100%.
+pub fn heat_index(celsius: f64, humidity: f64) -> Option<f64> {
+    if !(HEAT_INDEX_MIN_TEMPERATURE..=HEAT_INDEX_MAX_TEMPERATURE).contains(&celsius)
+        || !(0.0..=100.0).contains(&humidity)
+    {
+        return None;
+    }
+    let t = celsius * 9.0 / 5.0 + 32.0;
+    let rh = humidity;
+    let fahrenheit = -42.379 + 2.049_015_23 * t + 10.143_331_27 * rh
+        - 0.224_755_41 * t * rh
+        - 0.006_837_83 * t * t
+        - 0.054_817_17 * rh * rh
+        + 0.001_228_74 * t * t * rh
+        + 0.000_852_82 * t * rh * rh
+        - 0.000_001_99 * t * t * rh * rh;
+    Some((fahrenheit - 32.0) * 5.0 / 9.0)
+}
+
+/// Calculates the wind chill in °C of a temperature in °C at a wind speed in km/h,
+/// using the formula of Environment Canada and the US National Weather Service.
+///
+/// Returns `None` if the temperature is above
+/// [WIND_CHILL_MAX_TEMPERATURE](constant.WIND_CHILL_MAX_TEMPERATURE.html) or the wind speed is
+/// below [WIND_CHILL_MIN_SPEED](constant.WIND_CHILL_MIN_SPEED.html).
+pub fn wind_chill(celsius: f64, speed: f64) -> Option<f64> {
+    if celsius > WIND_CHILL_MAX_TEMPERATURE || !(speed >= WIND_CHILL_MIN_SPEED && speed.is_finite())
+    {
+        return None;
+    }
+    let v = speed.powf(0.16);
+    Some(13.12 + 0.6215 * celsius - 11.37 * v + 0.3965 * celsius * v)
+}
@@ -0,0 +1,65 @@
+//! Helper functions used to find close matches for misremembered or misspelled keywords
+
+/// Computes the Levenshtein edit distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            let insertion = current[j] + 1;
+            let deletion = previous[j + 1] + 1;
+            current.push(substitution.min(insertion).min(deletion));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns the candidate one edit away from `query`, to suggest as the correction of a likely typo
+///
+/// Exact matches aren't suggested. If several candidates are one edit away, the alphabetically first is returned.
+pub fn typo_correction<'a, I>(query: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let query = query.to_lowercase();
+    candidates
+        .into_iter()
+        .filter(|c| edit_distance(&query, &c.to_lowercase()) == 1)
+        .map(String::as_str)
+        .min()
+}
+
+/// Returns the candidates that closely match `query`, best matches first
+///
+/// Exact matches rank first, followed by candidates containing the query and then
+/// candidates within a small edit distance relative to the query length.
+pub fn fuzzy_matches<'a, I>(query: &str, candidates: I) -> Vec<&'a str>
+where
+    I: IntoIterator<Item = &'a String>,
+{
+    let query = query.to_lowercase();
+    let max_distance = (query.chars().count() / 3).max(1);
+    let mut matches: Vec<(usize, &str)> = candidates
+        .into_iter()
+        .filter_map(|c| {
+            let candidate = c.to_lowercase();
+            if candidate == query {
+                Some((0, c.as_str()))
+            } else if candidate.contains(&query) {
+                Some((1, c.as_str()))
+            } else {
+                let distance = edit_distance(&query, &candidate);
+                if distance <= max_distance {
+                    Some((1 + distance, c.as_str()))
+                } else {
+                    None
+                }
+            }
+        })
+        .collect();
+    matches.sort();
+    matches.into_iter().map(|(_, c)| c).collect()
+}
@@ -1,9 +1,31 @@
 //! Exports various helper functions and types
+//!
+//! Relevant tests are in a test submodule
+
+#[cfg(test)]
+mod tests;
 
 mod bot_response;
 mod check_format;
 mod clean_text;
+mod convert_color;
+mod convert_cron;
+mod convert_date;
+mod convert_dpi;
+mod convert_gas_mark;
+mod convert_ip;
+mod convert_percent;
+mod convert_rate;
+mod convert_ratio;
+mod convert_roman;
+mod convert_time;
 mod convert_unit;
+mod convert_wave;
+mod convert_words;
+mod encoding;
+mod feels_like;
+mod fuzzy_match;
+mod template;
 
 // Public re-exports
 pub use bot_response::{
@@ -11,7 +33,30 @@ pub use bot_response::{
 };
 pub use check_format::check_format;
 pub use clean_text::clean_text;
-pub use convert_unit::convert_unit;
+pub use convert_color::{convert_color, NAMED_COLORS};
+pub use convert_cron::{describe_cron, to_cron};
+pub use convert_date::convert_date;
+pub use convert_dpi::{
+    convert_density, dot_pitch, physical_size, screen_ppi, DensityUnit, CM_PER_INCH,
+};
+pub use convert_gas_mark::{gas_mark_temperature, nearest_gas_mark, parse_gas_mark, GAS_MARKS};
+pub use convert_ip::{describe_network, netmask_to_prefix, prefix_to_netmask};
+pub use convert_percent::{convert_fraction, FractionFormat};
+pub use convert_rate::{convert_data_rate, RateUnit};
+pub use convert_ratio::{aspect_ratio, resolution, resolution_height};
+pub use convert_roman::{from_roman, to_roman, MAX_ROMAN, MIN_ROMAN};
+pub use convert_time::{convert_time, find_timezone, parse_time_of_day};
+pub use convert_unit::{
+    ambiguous_units, conversion_target, convert_compound_volume, convert_unit, round,
+    split_compound_volumes, unit_key, AmbiguousUnit, BUILTIN_UNITS, DEFAULT_PRECISION, DIMENSIONS,
+    MAX_PRECISION,
+};
+pub use convert_wave::{convert_wave, Wave, DEFAULT_SPEED_OF_SOUND};
+pub use convert_words::{from_words, to_words, MAX_WORDS};
+pub use encoding::{decode, encode, Encoding, MAX_ENCODING_INPUT};
+pub use feels_like::{heat_index, wind_chill};
+pub use fuzzy_match::{fuzzy_matches, typo_correction};
+pub use template::{render_template, TemplateData};
 
 // Private re-exports
 use convert_unit::ConvertedUnit;
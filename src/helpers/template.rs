@@ -0,0 +1,42 @@
+//! Helper function and associated type to substitute dynamic data into configured messages
+
+use crate::regex::TEMPLATE_PLACEHOLDER;
+use chrono::{DateTime, Utc};
+use ruma::UserId;
+use std::time::SystemTime;
+
+#[derive(Debug, Default)]
+/// Data substituted into message templates.
+///
+/// Available placeholders are `{time}`, `{room}`, `{membercount}`, and `{sender}`.
+/// Placeholders without data are left untouched.
+pub struct TemplateData<'a> {
+    /// Time the message is sent, substituted for `{time}` as "2022-01-31 14:05 UTC".
+    pub time: Option<SystemTime>,
+    /// Name of the room the message is sent to, substituted for `{room}`.
+    pub room: Option<&'a str>,
+    /// Number of users joined to the room, substituted for `{membercount}`.
+    pub member_count: Option<u64>,
+    /// User that triggered the message, substituted for `{sender}`.
+    pub sender: Option<&'a UserId>,
+}
+
+/// Substitutes the placeholders in `template` with `data`, leaving unknown placeholders untouched.
+pub fn render_template(template: &str, data: &TemplateData<'_>) -> String {
+    TEMPLATE_PLACEHOLDER
+        .replace_all(template, |cap: &regex::Captures<'_>| {
+            let value = match &cap[1] {
+                "time" => data.time.map(|t| {
+                    DateTime::<Utc>::from(t)
+                        .format("%Y-%m-%d %H:%M UTC")
+                        .to_string()
+                }),
+                "room" => data.room.map(str::to_string),
+                "membercount" => data.member_count.map(|c| c.to_string()),
+                "sender" => data.sender.map(|s| s.to_string()),
+                _ => None,
+            };
+            value.unwrap_or_else(|| cap[0].to_string())
+        })
+        .into_owned()
+}
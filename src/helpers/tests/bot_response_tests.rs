@@ -0,0 +1,52 @@
+use crate::config::GroupPingFormat;
+use crate::helpers::MatrixFormattedTextResponse;
+use ruma::{user_id, OwnedUserId};
+use std::collections::HashSet;
+
+fn response(format: GroupPingFormat) -> MatrixFormattedTextResponse {
+    let users: HashSet<OwnedUserId> = vec![
+        user_id!("@bob:example.com").to_owned(),
+        user_id!("@alice:example.com").to_owned(),
+    ]
+    .into_iter()
+    .collect();
+    let mut response = MatrixFormattedTextResponse::default();
+    response.set_format(format);
+    response.set_users(users);
+    response
+}
+
+#[test]
+fn plain_group_ping() {
+    let response = response(GroupPingFormat::Plain);
+    assert_eq!("@alice:example.com @bob:example.com", response.to_string());
+    assert_eq!(None, response.format_text())
+}
+#[test]
+fn html_group_ping() {
+    let response = response(GroupPingFormat::Html);
+    assert_eq!("alice bob", response.to_string());
+    assert_eq!(
+        Some(
+            "<a href=\"https://matrix.to/#/@alice:example.com\">alice</a>\n<a href=\"https://matrix.to/#/@bob:example.com\">bob</a>\n"
+                .to_string()
+        ),
+        response.format_text()
+    )
+}
+#[test]
+fn list_group_ping() {
+    let response = response(GroupPingFormat::List);
+    assert_eq!("@alice:example.com, @bob:example.com", response.to_string());
+    assert_eq!(None, response.format_text())
+}
+#[test]
+fn html_is_default() {
+    let mut response = MatrixFormattedTextResponse::default();
+    response.set_users(
+        vec![user_id!("@alice:example.com").to_owned()]
+            .into_iter()
+            .collect(),
+    );
+    assert!(response.format_text().is_some())
+}
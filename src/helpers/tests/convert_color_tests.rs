@@ -0,0 +1,78 @@
+use crate::helpers::convert_color;
+use crate::helpers::convert_color::Color;
+
+#[test]
+fn hex_to_others() {
+    assert_eq!(
+        "#ff8800 => rgb(255,136,0), hsl(32,100%,50%)",
+        convert_color("#FF8800").unwrap().to_string()
+    );
+    assert_eq!(
+        "#f80 => rgb(255,136,0), hsl(32,100%,50%)",
+        convert_color("#f80").unwrap().to_string()
+    )
+}
+#[test]
+fn rgb_to_others() {
+    assert_eq!(
+        "rgb(0, 128, 128) => #008080, hsl(180,100%,25%)",
+        convert_color("rgb(0, 128, 128)").unwrap().to_string()
+    )
+}
+#[test]
+fn hsl_to_others() {
+    assert_eq!(
+        "hsl(240, 100%, 50%) => #0000ff, rgb(0,0,255)",
+        convert_color("hsl(240, 100%, 50%)").unwrap().to_string()
+    );
+    assert_eq!(
+        "hsl(0,0%,50%) => #808080, rgb(128,128,128)",
+        convert_color("hsl(0,0%,50%)").unwrap().to_string()
+    )
+}
+#[test]
+fn named() {
+    assert_eq!(
+        "red => #ff0000, rgb(255,0,0), hsl(0,100%,50%)",
+        convert_color("Red").unwrap().to_string()
+    )
+}
+#[test]
+fn round_trips() {
+    for hex in [
+        "#ff8800", "#000000", "#ffffff", "#ff00ff", "#00ff00", "#336699",
+    ]
+    .iter()
+    {
+        let color = convert_color(hex).unwrap().color;
+        assert_eq!(*hex, color.hex());
+        assert_eq!(color, convert_color(&color.rgb()).unwrap().color);
+        assert_eq!(color, convert_color(&color.hsl()).unwrap().color)
+    }
+}
+#[test]
+fn hsl_components() {
+    let color = Color {
+        red: 255,
+        green: 136,
+        blue: 0,
+    };
+    assert_eq!((32, 100, 50), color.to_hsl());
+    assert_eq!(color, Color::from_hsl(32.0, 100.0, 50.0))
+}
+#[test]
+fn invalid() {
+    assert_eq!(
+        "Invalid hex color #ff88. Hex colors have 3 or 6 hex digits",
+        convert_color("#ff88").unwrap_err().to_string()
+    );
+    assert!(convert_color("#ff88zz").is_err());
+    assert!(convert_color("rgb(256, 0, 0)").is_err());
+    assert!(convert_color("rgb(1, 2)").is_err());
+    assert!(convert_color("hsl(400, 50%, 50%)").is_err());
+    assert!(convert_color("hsl(40, 150%, 50%)").is_err());
+    assert_eq!(
+        "Unknown color chartreuse",
+        convert_color("chartreuse").unwrap_err().to_string()
+    )
+}
@@ -0,0 +1,86 @@
+use crate::helpers::{describe_cron, to_cron};
+
+#[test]
+fn describe() {
+    assert_eq!("At 09:00 on Monday", describe_cron("0 9 * * 1").unwrap());
+    assert_eq!("At every minute", describe_cron("* * * * *").unwrap());
+    assert_eq!(
+        "At every 15th minute",
+        describe_cron("*/15 * * * *").unwrap()
+    );
+    assert_eq!(
+        "At minute 0 past every 2nd hour",
+        describe_cron("0 */2 * * *").unwrap()
+    );
+    assert_eq!(
+        "At 22:00 on every day-of-week from Monday through Friday",
+        describe_cron("0 22 * * 1-5").unwrap()
+    );
+    assert_eq!(
+        "At 04:30 on day-of-month 1 and 15 and on Friday",
+        describe_cron("30 4 1,15 * 5").unwrap()
+    );
+    assert_eq!("At 00:05 in August", describe_cron("5 0 * 8 *").unwrap());
+    assert_eq!(
+        "At minute 0, 15 and 30 past hour 9",
+        describe_cron("0,15,30 9 * * *").unwrap()
+    )
+}
+#[test]
+fn describe_names() {
+    assert_eq!(
+        "At 12:00 on Sunday and Saturday in every month from January through March",
+        describe_cron("0 12 * jan-MAR sun,SAT").unwrap()
+    );
+    assert_eq!("At 12:00 on Sunday", describe_cron("0 12 * * 7").unwrap())
+}
+#[test]
+fn invalid() {
+    assert_eq!(
+        "Cron expressions need 5 fields (minute hour day-of-month month day-of-week) but 0 9 * * has 4",
+        describe_cron("0 9 * *").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Invalid hour value 24. Must be between 0 and 23",
+        describe_cron("0 24 * * *").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Invalid minute step 0",
+        describe_cron("*/0 * * * *").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Invalid day-of-week range 5-1. Start must not be after end",
+        describe_cron("0 0 * * 5-1").unwrap_err().to_string()
+    );
+    assert_eq!(
+        "Invalid month value foo",
+        describe_cron("0 0 * foo *").unwrap_err().to_string()
+    )
+}
+#[test]
+fn from_description() {
+    assert_eq!("* * * * *", to_cron("every minute").unwrap());
+    assert_eq!("*/15 * * * *", to_cron("every 15 minutes").unwrap());
+    assert_eq!("0 */2 * * *", to_cron("Every 2 hours").unwrap());
+    assert_eq!("30 9 * * *", to_cron("every day at 09:30").unwrap());
+    assert_eq!("0 9 * * 1", to_cron("at 09:00 on Monday").unwrap());
+    assert_eq!("0 17 * * 5", to_cron("every friday at 17:00").unwrap())
+}
+#[test]
+fn unsupported_description() {
+    assert!(to_cron("every 61 minutes").is_err());
+    assert!(to_cron("at 25:00").is_err());
+    assert!(to_cron("twice a fortnight").is_err())
+}
+#[test]
+fn round_trip() {
+    for description in [
+        "every 5 minutes",
+        "at 09:00 on monday",
+        "every day at 23:59",
+    ]
+    .iter()
+    {
+        assert!(describe_cron(&to_cron(description).unwrap()).is_ok())
+    }
+}
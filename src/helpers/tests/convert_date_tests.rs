@@ -0,0 +1,33 @@
+use crate::helpers::convert_date;
+
+#[test]
+fn julian_day() {
+    assert_eq!(2460385, convert_date("2024-03-15").unwrap().julian_day())
+}
+#[test]
+fn julian_day_epoch() {
+    assert_eq!(2451545, convert_date("2000-01-01").unwrap().julian_day())
+}
+#[test]
+fn iso_week() {
+    assert_eq!("2024-W11-5", convert_date("2024-03-15").unwrap().iso_week())
+}
+#[test]
+fn iso_week_previous_year() {
+    assert_eq!("2020-W53-5", convert_date("2021-01-01").unwrap().iso_week())
+}
+#[test]
+fn ordinal() {
+    assert_eq!("2024-075", convert_date("2024-03-15").unwrap().ordinal())
+}
+#[test]
+fn display() {
+    assert_eq!(
+        "2024-03-15 => Julian day 2460385, ordinal 2024-075, ISO week 2024-W11-5",
+        convert_date("2024-03-15").unwrap().to_string()
+    )
+}
+#[test]
+fn invalid_date() {
+    assert!(convert_date("2023-02-29").is_none())
+}
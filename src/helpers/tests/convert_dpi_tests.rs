@@ -0,0 +1,32 @@
+use crate::helpers::{convert_density, dot_pitch, physical_size, screen_ppi, DensityUnit};
+
+#[test]
+fn dpi_to_dpcm() {
+    assert!((convert_density(300.0, DensityUnit::PerInch) - 118.110236).abs() < 1e-6)
+}
+#[test]
+fn dpcm_to_dpi() {
+    assert!((convert_density(118.110236, DensityUnit::PerCm) - 300.0).abs() < 1e-6)
+}
+#[test]
+fn density_units() {
+    assert_eq!(Some(DensityUnit::PerInch), DensityUnit::parse("PPI"));
+    assert_eq!(Some(DensityUnit::PerCm), DensityUnit::parse("dpcm"));
+    assert_eq!(None, DensityUnit::parse("dpm"))
+}
+#[test]
+fn print_size() {
+    assert_eq!(Some(8.0), physical_size(2400, 300.0));
+    assert_eq!(None, physical_size(2400, 0.0))
+}
+#[test]
+fn full_hd_27_inch_dot_pitch() {
+    let ppi = screen_ppi(1920, 1080, 27.0).unwrap();
+    assert!((ppi - 81.589).abs() < 1e-3);
+    assert!((dot_pitch(ppi) - 0.3113).abs() < 1e-4)
+}
+#[test]
+fn invalid_screen() {
+    assert!(screen_ppi(1920, 0, 27.0).is_none());
+    assert!(screen_ppi(1920, 1080, 0.0).is_none())
+}
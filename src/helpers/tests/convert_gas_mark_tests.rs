@@ -0,0 +1,49 @@
+use crate::helpers::{gas_mark_temperature, nearest_gas_mark, parse_gas_mark, GAS_MARKS};
+
+#[test]
+fn gas_marks_to_temperatures() {
+    assert_eq!(Some(("1", 140, 275)), gas_mark_temperature(1.0));
+    assert_eq!(Some(("4", 180, 350)), gas_mark_temperature(4.0));
+    assert_eq!(Some(("9", 240, 475)), gas_mark_temperature(9.0))
+}
+#[test]
+fn fractional_gas_marks() {
+    assert_eq!(Some(("1/4", 110, 225)), gas_mark_temperature(0.25));
+    assert_eq!(Some(("1/2", 120, 250)), gas_mark_temperature(0.5))
+}
+#[test]
+fn out_of_range_gas_marks() {
+    assert!(gas_mark_temperature(10.0).is_none());
+    assert!(gas_mark_temperature(0.0).is_none());
+    assert!(gas_mark_temperature(4.5).is_none())
+}
+#[test]
+fn parse_notations() {
+    assert_eq!(Some(4.0), parse_gas_mark("4"));
+    assert_eq!(Some(0.5), parse_gas_mark("0.5"));
+    assert_eq!(Some(0.25), parse_gas_mark("1/4"));
+    assert_eq!(Some(0.25), parse_gas_mark("¼"));
+    assert_eq!(Some(0.5), parse_gas_mark("½"));
+    assert!(parse_gas_mark("1/0").is_none())
+}
+#[test]
+fn temperatures_to_gas_marks() {
+    assert_eq!(Some("4"), nearest_gas_mark(180.0));
+    assert_eq!(Some("6"), nearest_gas_mark(204.0));
+    assert_eq!(Some("1/4"), nearest_gas_mark(105.0))
+}
+#[test]
+fn ties_prefer_cooler_mark() {
+    assert_eq!(Some("3"), nearest_gas_mark(175.0))
+}
+#[test]
+fn temperatures_out_of_range() {
+    assert!(nearest_gas_mark(50.0).is_none());
+    assert!(nearest_gas_mark(300.0).is_none())
+}
+#[test]
+fn round_trip() {
+    for (_, name, celsius, _) in GAS_MARKS {
+        assert_eq!(Some(name), nearest_gas_mark(f64::from(celsius)))
+    }
+}
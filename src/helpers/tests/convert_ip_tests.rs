@@ -0,0 +1,98 @@
+use crate::helpers::{describe_network, netmask_to_prefix, prefix_to_netmask};
+use std::net::Ipv4Addr;
+
+#[test]
+fn ipv4_range() {
+    assert_eq!(
+        "192.168.1.0/24 => network 192.168.1.0, broadcast 192.168.1.255, netmask 255.255.255.0, 254 hosts (192.168.1.1 - 192.168.1.254)",
+        describe_network("192.168.1.0/24").unwrap()
+    );
+    assert_eq!(
+        "10.0.0.0/8 => network 10.0.0.0, broadcast 10.255.255.255, netmask 255.0.0.0, 16777214 hosts (10.0.0.1 - 10.255.255.254)",
+        describe_network("10.0.0.0/8").unwrap()
+    )
+}
+#[test]
+fn host_bits_ignored() {
+    assert_eq!(
+        "172.16.5.77/20 => network 172.16.0.0, broadcast 172.16.15.255, netmask 255.255.240.0, 4094 hosts (172.16.0.1 - 172.16.15.254)",
+        describe_network("172.16.5.77/20").unwrap()
+    )
+}
+#[test]
+fn small_ipv4_ranges() {
+    assert_eq!(
+        "192.168.1.6/31 => network 192.168.1.6, netmask 255.255.255.254, 2 hosts (192.168.1.6 - 192.168.1.7)",
+        describe_network("192.168.1.6/31").unwrap()
+    );
+    assert_eq!(
+        "192.168.1.7/32 => single host 192.168.1.7",
+        describe_network("192.168.1.7/32").unwrap()
+    )
+}
+#[test]
+fn whole_ipv4_space() {
+    assert_eq!(
+        "0.0.0.0/0 => network 0.0.0.0, broadcast 255.255.255.255, netmask 0.0.0.0, 4294967294 hosts (0.0.0.1 - 255.255.255.254)",
+        describe_network("0.0.0.0/0").unwrap()
+    )
+}
+#[test]
+fn ipv6_range() {
+    assert_eq!(
+        "2001:db8::/32 => network 2001:db8::, last address 2001:db8:ffff:ffff:ffff:ffff:ffff:ffff, 2^96 addresses",
+        describe_network("2001:db8::/32").unwrap()
+    );
+    assert_eq!(
+        "2001:db8::1/120 => network 2001:db8::, last address 2001:db8::ff, 256 addresses",
+        describe_network("2001:db8::1/120").unwrap()
+    );
+    assert_eq!(
+        "::1/128 => single host ::1",
+        describe_network("::1/128").unwrap()
+    );
+    assert!(describe_network("::/0").is_ok())
+}
+#[test]
+fn invalid_ranges() {
+    assert!(describe_network("192.168.1.0").is_err());
+    assert!(describe_network("192.168.1/24").is_err());
+    assert!(describe_network("192.168.1.256/24").is_err());
+    assert!(describe_network("192.168.1.0/33").is_err());
+    assert!(describe_network("2001:db8::/129").is_err());
+    assert!(describe_network("2001:db8:::1/64").is_err());
+    assert!(describe_network("192.168.1.0/99999999999").is_err())
+}
+#[test]
+fn netmask_to_prefix_length() {
+    assert_eq!(24, netmask_to_prefix("255.255.255.0").unwrap());
+    assert_eq!(20, netmask_to_prefix("255.255.240.0").unwrap());
+    assert_eq!(32, netmask_to_prefix("255.255.255.255").unwrap());
+    assert_eq!(0, netmask_to_prefix("0.0.0.0").unwrap())
+}
+#[test]
+fn invalid_netmasks() {
+    assert!(netmask_to_prefix("255.0.255.0").is_err());
+    assert!(netmask_to_prefix("255.255.255.1").is_err());
+    assert!(netmask_to_prefix("255.255.256.0").is_err())
+}
+#[test]
+fn prefix_length_to_netmask() {
+    assert_eq!(
+        Ipv4Addr::new(255, 255, 255, 0),
+        prefix_to_netmask(24).unwrap()
+    );
+    assert_eq!(Ipv4Addr::new(0, 0, 0, 0), prefix_to_netmask(0).unwrap());
+    assert_eq!(
+        Ipv4Addr::new(255, 255, 255, 255),
+        prefix_to_netmask(32).unwrap()
+    );
+    assert!(prefix_to_netmask(33).is_err())
+}
+#[test]
+fn round_trip() {
+    for prefix in 0..=32 {
+        let mask = prefix_to_netmask(prefix).unwrap().to_string();
+        assert_eq!(prefix, netmask_to_prefix(&mask).unwrap())
+    }
+}
@@ -0,0 +1,99 @@
+use crate::helpers::convert_fraction;
+use crate::helpers::convert_percent::{parse_fraction, Fraction, FractionFormat};
+
+#[test]
+fn percent_to_others() {
+    assert_eq!(
+        "25% => 0.25, 1/4, 1:4",
+        convert_fraction("25%", None).unwrap()
+    );
+    assert_eq!(
+        "12.5% => 0.125, 1/8, 1:8",
+        convert_fraction("12.5 %", None).unwrap()
+    )
+}
+#[test]
+fn fraction_to_percent() {
+    assert_eq!(
+        "3/8 => 37.5%",
+        convert_fraction("3/8", Some(FractionFormat::Percent)).unwrap()
+    );
+    assert_eq!(
+        "3/8 => 0.375, 37.5%, 3:8",
+        convert_fraction("3/8", None).unwrap()
+    )
+}
+#[test]
+fn ratio_to_others() {
+    assert_eq!(
+        "2:6 => ≈0.333333, ≈33.3333%, 1/3, 1:3",
+        convert_fraction("2 : 6", None).unwrap()
+    );
+    assert_eq!(
+        "2:6 => 1:3",
+        convert_fraction("2:6", Some(FractionFormat::Ratio)).unwrap()
+    )
+}
+#[test]
+fn reduces_fractions() {
+    assert_eq!(
+        "6/8 => 0.75, 75%, 3/4, 3:4",
+        convert_fraction("6/8", None).unwrap()
+    );
+    assert_eq!(
+        Fraction {
+            numerator: 3,
+            denominator: 4
+        },
+        Fraction::new(75, 100).unwrap()
+    )
+}
+#[test]
+fn improper_fractions() {
+    assert_eq!(
+        "9/4 => 2.25, 225%, 9/4 (2 1/4), 9:4",
+        convert_fraction("9/4", None).unwrap()
+    );
+    assert_eq!(
+        "150% => 1.5, 3/2 (1 1/2), 3:2",
+        convert_fraction("150%", None).unwrap()
+    );
+    assert_eq!(
+        "8/4 => 2, 200%, 2/1, 2:1",
+        convert_fraction("8/4", None).unwrap()
+    )
+}
+#[test]
+fn zero() {
+    assert_eq!("0% => 0, 0/1, 0:1", convert_fraction("0%", None).unwrap())
+}
+#[test]
+fn divide_by_zero() {
+    assert_eq!(
+        "3/0 divides by zero",
+        convert_fraction("3/0", None).unwrap_err().to_string()
+    );
+    assert_eq!(
+        "3:0 divides by zero",
+        parse_fraction("3:0").unwrap_err().to_string()
+    );
+    assert!(parse_fraction("0/0").is_err());
+    assert!(Fraction::new(1, 0).is_err())
+}
+#[test]
+fn too_large() {
+    assert!(parse_fraction("99999999999999999999/2").is_err());
+    assert!(parse_fraction("1.00000000000000000001%").is_err())
+}
+#[test]
+fn targets() {
+    assert_eq!(
+        Some(FractionFormat::Percent),
+        FractionFormat::from_target("%")
+    );
+    assert_eq!(
+        Some(FractionFormat::Decimal),
+        FractionFormat::from_target("Decimal")
+    );
+    assert_eq!(None, FractionFormat::from_target("km"))
+}
@@ -0,0 +1,41 @@
+use crate::helpers::convert_rate::DataUnit;
+use crate::helpers::{convert_data_rate, RateUnit};
+
+#[test]
+fn megabits_to_megabytes() {
+    let (quantity, unit) = convert_data_rate(100.0, RateUnit::parse("Mbps").unwrap()).unwrap();
+    assert!((quantity - 12.5).abs() < 1e-9);
+    assert_eq!(DataUnit::Bytes, unit.unit);
+    assert_eq!("MBps (megabytes per second)", format!("{:#}", unit))
+}
+#[test]
+fn megabytes_to_megabits() {
+    let (quantity, unit) = convert_data_rate(12.5, RateUnit::parse("MB/s").unwrap()).unwrap();
+    assert!((quantity - 100.0).abs() < 1e-9);
+    assert_eq!("Mbps (megabits per second)", format!("{:#}", unit))
+}
+#[test]
+fn gigabits_to_gigabytes() {
+    let (quantity, unit) = convert_data_rate(1.0, RateUnit::parse("gbps").unwrap()).unwrap();
+    assert!((quantity - 0.125).abs() < 1e-9);
+    assert_eq!("GBps", unit.to_string())
+}
+#[test]
+fn gigabytes_to_gigabits() {
+    let (quantity, unit) = convert_data_rate(2.5, RateUnit::parse("GBps").unwrap()).unwrap();
+    assert!((quantity - 20.0).abs() < 1e-9);
+    assert_eq!("Gbps", unit.to_string())
+}
+#[test]
+fn prefix_case_keeps_bits_and_bytes() {
+    assert_eq!(DataUnit::Bits, RateUnit::parse("mbps").unwrap().unit);
+    assert_eq!(DataUnit::Bytes, RateUnit::parse("mBps").unwrap().unit);
+    assert_eq!("kbps", RateUnit::parse("Kbps").unwrap().to_string())
+}
+#[test]
+fn invalid() {
+    assert!(RateUnit::parse("Mbit").is_none());
+    assert!(RateUnit::parse("Xbps").is_none());
+    assert!(RateUnit::parse("M").is_none());
+    assert!(convert_data_rate(-1.0, RateUnit::parse("Mbps").unwrap()).is_none())
+}
@@ -0,0 +1,39 @@
+use crate::helpers::{aspect_ratio, resolution, resolution_height};
+
+#[test]
+fn full_hd_ratio() {
+    assert_eq!(Some((16, 9)), aspect_ratio(1920, 1080))
+}
+#[test]
+fn ultrawide_ratio() {
+    assert_eq!(Some((43, 18)), aspect_ratio(3440, 1440))
+}
+#[test]
+fn simplified_ratio() {
+    assert_eq!(Some((4, 3)), aspect_ratio(4, 3))
+}
+#[test]
+fn zero_ratio() {
+    assert!(aspect_ratio(1920, 0).is_none())
+}
+#[test]
+fn full_hd_resolution() {
+    assert_eq!(Some((1920, 1080)), resolution(16, 9, 1080))
+}
+#[test]
+fn rounded_resolution() {
+    assert_eq!(Some((1280, 720)), resolution(16, 9, 720));
+    assert_eq!(Some((2520, 1080)), resolution(21, 9, 1080));
+    assert_eq!(Some((853, 480)), resolution(16, 9, 480))
+}
+#[test]
+fn zero_resolution() {
+    assert!(resolution(16, 0, 1080).is_none());
+    assert!(resolution(16, 9, 0).is_none())
+}
+#[test]
+fn named_heights() {
+    assert_eq!(Some(1080), resolution_height("1080p"));
+    assert_eq!(Some(720), resolution_height("720"));
+    assert_eq!(Some(2160), resolution_height("4K"))
+}
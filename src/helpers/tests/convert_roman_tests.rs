@@ -0,0 +1,44 @@
+use crate::helpers::{from_roman, to_roman};
+
+#[test]
+fn numeral_to_number() {
+    assert_eq!(Some(42), from_roman("XLII"))
+}
+#[test]
+fn subtractive_numerals() {
+    assert_eq!(Some(1994), from_roman("MCMXCIV"))
+}
+#[test]
+fn largest_numeral() {
+    assert_eq!(Some(3999), from_roman("MMMCMXCIX"))
+}
+#[test]
+fn number_to_numeral() {
+    assert_eq!(Some("XLII".to_string()), to_roman(42))
+}
+#[test]
+fn round_trip() {
+    for n in 1..=3999 {
+        assert_eq!(Some(n), from_roman(&to_roman(n).unwrap()))
+    }
+}
+#[test]
+fn repeated_symbols_rejected() {
+    assert!(from_roman("IIII").is_none());
+    assert!(from_roman("VV").is_none())
+}
+#[test]
+fn invalid_subtraction_rejected() {
+    assert!(from_roman("VX").is_none());
+    assert!(from_roman("IC").is_none());
+    assert!(from_roman("IXIX").is_none())
+}
+#[test]
+fn too_large_numeral_rejected() {
+    assert!(from_roman("MMMM").is_none())
+}
+#[test]
+fn out_of_range_number() {
+    assert!(to_roman(0).is_none());
+    assert!(to_roman(4000).is_none())
+}
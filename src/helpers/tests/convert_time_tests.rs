@@ -0,0 +1,43 @@
+use crate::helpers::{convert_time, find_timezone, parse_time_of_day};
+use chrono::{NaiveDate, NaiveTime};
+use chrono_tz::{America::New_York, Asia::Tokyo, Europe::London};
+
+fn date(month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2024, month, day).unwrap()
+}
+fn time(hour: u32, minute: u32) -> NaiveTime {
+    NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+}
+
+#[test]
+fn timezone_names_ignore_case() {
+    assert_eq!(Some(London), find_timezone("europe/london"));
+    assert_eq!(None, find_timezone("Europe/Nowhere"))
+}
+#[test]
+fn twelve_hour_times() {
+    assert_eq!(Some(time(15, 0)), parse_time_of_day("3pm"));
+    assert_eq!(Some(time(0, 30)), parse_time_of_day("12:30 AM"));
+    assert_eq!(Some(time(12, 0)), parse_time_of_day("12pm"));
+    assert_eq!(None, parse_time_of_day("13pm"))
+}
+#[test]
+fn twenty_four_hour_times() {
+    assert_eq!(Some(time(15, 45)), parse_time_of_day("15:45"));
+    assert_eq!(None, parse_time_of_day("15"));
+    assert_eq!(None, parse_time_of_day("24:00"))
+}
+#[test]
+fn summer_time_offsets() {
+    let converted = convert_time(time(15, 0), date(7, 1), London, New_York).unwrap();
+    assert_eq!("2024-07-01 10:00", converted.format("%F %H:%M").to_string())
+}
+#[test]
+fn next_day() {
+    let converted = convert_time(time(20, 0), date(1, 15), London, Tokyo).unwrap();
+    assert_eq!("2024-01-16 05:00", converted.format("%F %H:%M").to_string())
+}
+#[test]
+fn daylight_saving_gap() {
+    assert!(convert_time(time(1, 30), date(3, 31), London, Tokyo).is_none())
+}
@@ -0,0 +1,513 @@
+use crate::config::{CustomConversion, RoundingMode};
+use crate::helpers::{ambiguous_units, convert_unit, DEFAULT_PRECISION};
+use std::collections::HashMap;
+
+fn conversion(quantity: &str, unit: &str) -> Vec<(String, String)> {
+    vec![(quantity.to_string(), unit.to_string())]
+}
+
+fn pizzas() -> HashMap<String, CustomConversion> {
+    let mut custom = HashMap::new();
+    custom.insert(
+        "pizzas".to_string(),
+        CustomConversion {
+            factor: 8.0,
+            unit: "slices".to_string(),
+        },
+    );
+    custom
+}
+
+mod custom_conversions {
+    use super::*;
+
+    #[test]
+    fn custom_unit() {
+        let result = convert_unit(
+            conversion("3", "pizzas"),
+            Some(&pizzas()),
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("3.00pizzas => 24.00slices", result[0].to_string())
+    }
+    #[test]
+    fn custom_unit_without_room_config() {
+        assert!(convert_unit(
+            conversion("3", "pizzas"),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default()
+        )
+        .is_none())
+    }
+    #[test]
+    fn builtin_not_shadowed() {
+        let mut custom = pizzas();
+        custom.insert(
+            "km".to_string(),
+            CustomConversion {
+                factor: 1000.0,
+                unit: "m".to_string(),
+            },
+        );
+        let result = convert_unit(
+            conversion("100", "km"),
+            Some(&custom),
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("100.00km => 62.14mi", result[0].to_string())
+    }
+    #[test]
+    fn custom_preferred() {
+        let mut custom = pizzas();
+        custom.insert(
+            "m".to_string(),
+            CustomConversion {
+                factor: 1.0 / 60.0,
+                unit: "h".to_string(),
+            },
+        );
+        let result = convert_unit(
+            conversion("90", "m"),
+            Some(&custom),
+            true,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("90.00m => 1.50h", result[0].to_string())
+    }
+    #[test]
+    fn ambiguous() {
+        let mut custom = pizzas();
+        custom.insert(
+            "m".to_string(),
+            CustomConversion {
+                factor: 1.0 / 60.0,
+                unit: "h".to_string(),
+            },
+        );
+        let conversions = vec![
+            ("5".to_string(), "m".to_string()),
+            ("3".to_string(), "pizzas".to_string()),
+            ("10".to_string(), "m".to_string()),
+        ];
+        let ambiguous = ambiguous_units(&conversions, Some(&custom));
+        assert_eq!(1, ambiguous.len());
+        assert_eq!(
+            "m converts to ft as a length or to h as a custom unit",
+            ambiguous[0].to_string()
+        );
+        assert!(ambiguous_units(&conversions, None).is_empty())
+    }
+}
+
+mod precision {
+    use super::*;
+
+    fn precisions() -> HashMap<String, usize> {
+        let mut precisions = HashMap::new();
+        precisions.insert("temperature".to_string(), 1);
+        precisions.insert("mass".to_string(), 3);
+        precisions
+    }
+
+    #[test]
+    fn per_dimension_precision() {
+        let conversions = vec![
+            ("20".to_string(), "c".to_string()),
+            ("10".to_string(), "kg".to_string()),
+        ];
+        let result = convert_unit(
+            conversions,
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &precisions(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("20.0c => 68.0f", result[0].to_string());
+        assert_eq!("10.000kg => 22.046lbs", result[1].to_string())
+    }
+    #[test]
+    fn unconfigured_dimension_uses_default() {
+        let result = convert_unit(
+            conversion("100", "km"),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &precisions(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("100.00km => 62.14mi", result[0].to_string())
+    }
+    #[test]
+    fn configured_default_precision() {
+        let result = convert_unit(
+            conversion("0.1", "in"),
+            None,
+            false,
+            4,
+            &precisions(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("0.1000in => 0.2540cm", result[0].to_string())
+    }
+    #[test]
+    fn custom_dimension() {
+        let mut precisions = HashMap::new();
+        precisions.insert("custom".to_string(), 0);
+        let result = convert_unit(
+            conversion("3", "pizzas"),
+            Some(&pizzas()),
+            false,
+            DEFAULT_PRECISION,
+            &precisions,
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("3pizzas => 24slices", result[0].to_string())
+    }
+}
+
+mod rounding {
+    use super::*;
+    use crate::helpers::round;
+
+    #[test]
+    fn truncate() {
+        assert_eq!(2.12, round(2.125, 2, RoundingMode::Truncate));
+        assert_eq!(2.12, round(2.129, 2, RoundingMode::Truncate))
+    }
+    #[test]
+    fn half_up() {
+        assert_eq!(2.13, round(2.125, 2, RoundingMode::HalfUp));
+        assert_eq!(-2.13, round(-2.125, 2, RoundingMode::HalfUp))
+    }
+    #[test]
+    fn half_even() {
+        assert_eq!(2.12, round(2.125, 2, RoundingMode::HalfEven));
+        assert_eq!(2.38, round(2.375, 2, RoundingMode::HalfEven));
+        assert_eq!(2.13, round(2.126, 2, RoundingMode::HalfEven))
+    }
+    #[test]
+    fn applied_to_conversion() {
+        let mut custom = HashMap::new();
+        custom.insert(
+            "halves".to_string(),
+            CustomConversion {
+                factor: 0.5,
+                unit: "wholes".to_string(),
+            },
+        );
+        let convert = |mode| {
+            convert_unit(
+                conversion("4.25", "halves"),
+                Some(&custom),
+                false,
+                DEFAULT_PRECISION,
+                &HashMap::new(),
+                mode,
+            )
+            .unwrap()[0]
+                .to_string()
+        };
+        assert_eq!("4.25halves => 2.12wholes", convert(RoundingMode::HalfEven));
+        assert_eq!("4.25halves => 2.13wholes", convert(RoundingMode::HalfUp));
+        assert_eq!("4.25halves => 2.12wholes", convert(RoundingMode::Truncate))
+    }
+}
+
+mod formulas {
+    use super::*;
+
+    fn explain(quantity: &str, unit: &str) -> String {
+        let result = convert_unit(
+            conversion(quantity, unit),
+            Some(&pizzas()),
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        format!("{:#}", result[0])
+    }
+
+    #[test]
+    fn temperature_formula() {
+        assert_eq!("20.00c => 68.00f (°F = °C × 9/5 + 32)", explain("20", "c"));
+        assert_eq!(
+            "68.00f => 20.00c (°C = (°F − 32) × 5/9)",
+            explain("68", "f")
+        )
+    }
+    #[test]
+    fn linear_formula() {
+        assert_eq!(
+            "100.00km => 62.14mi (mi = km × 0.621371)",
+            explain("100", "km")
+        );
+        assert_eq!("1.00in => 2.54cm (cm = in × 2.54)", explain("1", "in"))
+    }
+    #[test]
+    fn custom_formula() {
+        assert_eq!(
+            "3.00pizzas => 24.00slices (slices = pizzas × 8)",
+            explain("3", "pizzas")
+        )
+    }
+}
+
+mod compound_volumes {
+    use super::*;
+    use crate::helpers::{convert_compound_volume, split_compound_volumes};
+
+    fn convert(text: &[(&str, &str)]) -> Vec<String> {
+        let conversions = text
+            .iter()
+            .map(|(q, u)| (q.to_string(), u.to_string()))
+            .collect();
+        let (compounds, rest) = split_compound_volumes(conversions);
+        assert!(rest.is_empty());
+        compounds
+            .into_iter()
+            .map(|c| {
+                format!(
+                    "{:#}",
+                    convert_compound_volume(
+                        c,
+                        DEFAULT_PRECISION,
+                        &HashMap::new(),
+                        RoundingMode::default()
+                    )
+                    .unwrap()
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn gallons_and_quarts() {
+        assert_eq!(
+            vec!["1.00gal 2.00qt => 5.68l (l = gal × 3.785412 + qt × 0.946353)"],
+            convert(&[("1", "gal"), ("2", "qt")])
+        )
+    }
+    #[test]
+    fn any_order() {
+        assert_eq!(
+            vec!["1.00gal 2.00qt => 5.68l (l = gal × 3.785412 + qt × 0.946353)"],
+            convert(&[("2", "qt"), ("1", "gal")])
+        )
+    }
+    #[test]
+    fn missing_components() {
+        assert_eq!(
+            vec!["1.00gal 1.00cup => 4.02l (l = gal × 3.785412 + cup × 0.236588)"],
+            convert(&[("1", "gal"), ("1", "cup")])
+        );
+        assert_eq!(
+            vec!["1.00pt 2.00cups => 0.95l (l = pt × 0.473176 + cups × 0.236588)"],
+            convert(&[("2", "cups"), ("1", "pt")])
+        )
+    }
+    #[test]
+    fn all_components() {
+        assert_eq!(
+            vec!["1.00gal 2.00qt 1.00pt 1.00cup => 6.39l (l = gal × 3.785412 + qt × 0.946353 + pt × 0.473176 + cup × 0.236588)"],
+            convert(&[("1", "gal"), ("2", "qt"), ("1", "pt"), ("1", "cup")])
+        )
+    }
+    #[test]
+    fn repeated_unit_starts_new_volume() {
+        assert_eq!(
+            vec![
+                "1.00gal 2.00qt => 5.68l (l = gal × 3.785412 + qt × 0.946353)",
+                "1.00gal 1.00qt => 4.73l (l = gal × 3.785412 + qt × 0.946353)"
+            ],
+            convert(&[("1", "gal"), ("2", "qt"), ("1", "gal"), ("1", "qt")])
+        )
+    }
+    #[test]
+    fn lone_volumes_not_combined() {
+        let conversions = vec![
+            ("1".to_string(), "gal".to_string()),
+            ("5".to_string(), "km".to_string()),
+            ("2".to_string(), "qt".to_string()),
+        ];
+        let (compounds, rest) = split_compound_volumes(conversions.clone());
+        assert!(compounds.is_empty());
+        assert_eq!(conversions, rest)
+    }
+}
+
+mod torque {
+    use super::*;
+    use crate::helpers::unit_key;
+
+    fn convert(quantity: &str, unit: &str) -> String {
+        let mut precisions = HashMap::new();
+        precisions.insert("torque".to_string(), 6);
+        let result = convert_unit(
+            conversion(quantity, unit),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &precisions,
+            RoundingMode::default(),
+        )
+        .unwrap();
+        result[0].to_string()
+    }
+
+    #[test]
+    fn newton_meters_to_pound_feet() {
+        assert_eq!("200.000000Nm => 147.512417lb-ft", convert("200", "Nm"))
+    }
+    #[test]
+    fn round_trip() {
+        assert_eq!(
+            "147.512417lb-ft => 200.000000Nm",
+            convert("147.512417", "lb-ft")
+        );
+        assert_eq!("1.000000lb-ft => 1.355818Nm", convert("1", "lb-ft"));
+        assert_eq!("1.355818Nm => 1.000000lb-ft", convert("1.355818", "Nm"))
+    }
+    #[test]
+    fn spelling_variants() {
+        assert_eq!("1.000000lbft => 1.355818Nm", convert("1", "lbft"));
+        assert_eq!("1.000000ft-lb => 1.355818Nm", convert("1", "ft-lb"))
+    }
+    #[test]
+    fn newton_meters_keep_case() {
+        assert_eq!("Nm", unit_key("Nm"));
+        assert_eq!("nm", unit_key("nm"));
+        assert_eq!("lb-ft", unit_key("LB-FT"))
+    }
+}
+
+mod scientific_notation {
+    use super::*;
+
+    fn convert(quantity: &str, unit: &str) -> String {
+        let result = convert_unit(
+            conversion(quantity, unit),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        result[0].to_string()
+    }
+
+    #[test]
+    fn kilometers_to_miles() {
+        let result = convert_unit(
+            vec![("1.5e3".to_string(), "km".to_string())],
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        assert_eq!("1500.00km => 932.06mi", result[0].to_string())
+    }
+    #[test]
+    fn negative_exponent() {
+        assert_eq!("0.01c => 32.02f", convert("1e-2", "c"));
+        assert_eq!("-0.01c => 31.98f", convert("-1e-2", "c"))
+    }
+}
+
+mod pressure {
+    use super::*;
+
+    fn convert(quantity: &str, unit: &str) -> String {
+        let result = convert_unit(
+            conversion(quantity, unit),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .unwrap();
+        result[0].to_string()
+    }
+
+    #[test]
+    fn bar_to_psi() {
+        assert_eq!("1.00bar => 14.50psi", convert("1", "bar"))
+    }
+    #[test]
+    fn psi_to_bar() {
+        assert_eq!("30.00psi => 2.07bar", convert("30", "psi"))
+    }
+    #[test]
+    fn kilopascals_to_psi() {
+        assert_eq!("220.00kpa => 31.91psi", convert("220", "kpa"));
+        assert_eq!("kpa", crate::helpers::unit_key("kPa"))
+    }
+}
+
+mod decimal_comma {
+    use super::*;
+
+    fn convert(quantity: &str, unit: &str) -> Option<String> {
+        convert_unit(
+            conversion(quantity, unit),
+            None,
+            false,
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        )
+        .map(|r| r[0].to_string())
+    }
+
+    #[test]
+    fn comma_as_decimal_separator() {
+        assert_eq!(Some("1.50km => 0.93mi".to_string()), convert("1,5", "km"));
+        assert_eq!(Some("-2.25c => 27.95f".to_string()), convert("-2,25", "c"))
+    }
+    #[test]
+    fn thousands_separators_left_alone() {
+        assert_eq!(None, convert("1,000,000", "km"))
+    }
+    #[test]
+    fn too_many_decimals_left_alone() {
+        assert_eq!(None, convert("1,0000000001", "km"))
+    }
+    #[test]
+    fn compound_volume() {
+        use crate::helpers::convert_compound_volume;
+        let result = convert_compound_volume(
+            vec![
+                ("1".to_string(), "gal".to_string()),
+                ("0,5".to_string(), "qt".to_string()),
+            ],
+            DEFAULT_PRECISION,
+            &HashMap::new(),
+            RoundingMode::default(),
+        );
+        assert!(result.is_some())
+    }
+}
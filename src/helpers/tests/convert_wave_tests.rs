@@ -0,0 +1,40 @@
+use crate::helpers::{convert_wave, Wave, DEFAULT_SPEED_OF_SOUND};
+
+#[test]
+fn light_wavelength_to_frequency() {
+    let converted = convert_wave(500.0, "nm", DEFAULT_SPEED_OF_SOUND).unwrap();
+    assert_eq!(Wave::Light, converted.wave);
+    assert_eq!("THz", converted.unit);
+    assert!((converted.quantity - 599.584_916).abs() < 1e-6)
+}
+#[test]
+fn light_micrometers() {
+    let converted = convert_wave(0.5, "µm", DEFAULT_SPEED_OF_SOUND).unwrap();
+    assert!((converted.quantity - 599.584_916).abs() < 1e-6)
+}
+#[test]
+fn light_frequency_to_wavelength() {
+    let converted = convert_wave(599.584_916, "thz", DEFAULT_SPEED_OF_SOUND).unwrap();
+    assert_eq!("nm", converted.unit);
+    assert!((converted.quantity - 500.0).abs() < 1e-6)
+}
+#[test]
+fn sound_frequency_to_wavelength() {
+    let converted = convert_wave(440.0, "hz", DEFAULT_SPEED_OF_SOUND).unwrap();
+    assert_eq!(Wave::Sound, converted.wave);
+    assert_eq!("m", converted.unit);
+    assert!((converted.quantity - 0.779_545).abs() < 1e-6)
+}
+#[test]
+fn sound_in_water() {
+    let converted = convert_wave(1.0, "khz", 1481.0).unwrap();
+    assert!((converted.quantity - 1.481).abs() < 1e-9)
+}
+#[test]
+fn non_positive_quantity() {
+    assert!(convert_wave(0.0, "hz", DEFAULT_SPEED_OF_SOUND).is_none())
+}
+#[test]
+fn unknown_unit() {
+    assert!(convert_wave(500.0, "km", DEFAULT_SPEED_OF_SOUND).is_none())
+}
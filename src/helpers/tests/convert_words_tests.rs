@@ -0,0 +1,48 @@
+use crate::helpers::{from_words, to_words, MAX_WORDS};
+
+#[test]
+fn words_to_number() {
+    assert_eq!(Some(42), from_words("forty two"));
+    assert_eq!(Some(42), from_words("Forty-Two"));
+    assert_eq!(Some(0), from_words("zero"));
+    assert_eq!(Some(105), from_words("one hundred and five"));
+    assert_eq!(Some(1_000_019), from_words("one million nineteen"));
+    assert_eq!(
+        Some(3_200_100_070),
+        from_words("three billion two hundred million one hundred thousand seventy")
+    )
+}
+#[test]
+fn number_to_words() {
+    assert_eq!(Some("forty-two".to_string()), to_words(42));
+    assert_eq!(Some("zero".to_string()), to_words(0));
+    assert_eq!(Some("one hundred".to_string()), to_words(100));
+    assert_eq!(Some("two thousand twenty-four".to_string()), to_words(2024));
+    assert_eq!(
+        Some("one billion one million one thousand one".to_string()),
+        to_words(1_001_001_001)
+    )
+}
+#[test]
+fn round_trip() {
+    for n in (0..=MAX_WORDS).step_by(987_654_321) {
+        assert_eq!(Some(n), from_words(&to_words(n).unwrap()))
+    }
+    for n in 0..=1000 {
+        assert_eq!(Some(n), from_words(&to_words(n).unwrap()))
+    }
+}
+#[test]
+fn too_large() {
+    assert!(to_words(MAX_WORDS + 1).is_none())
+}
+#[test]
+fn invalid_words_rejected() {
+    assert!(from_words("two three").is_none());
+    assert!(from_words("twelve hundred").is_none());
+    assert!(from_words("thousand").is_none());
+    assert!(from_words("one thousand one million").is_none());
+    assert!(from_words("forty-hundred").is_none());
+    assert!(from_words("one hundred and").is_none());
+    assert!(from_words("zero zero").is_none())
+}
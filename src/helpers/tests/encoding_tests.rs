@@ -0,0 +1,38 @@
+use crate::helpers::{decode, encode, Encoding};
+
+#[test]
+fn base64_round_trip() {
+    assert_eq!("aGVsbG8=", encode(Encoding::Base64, "hello"));
+    assert_eq!("hello", decode(Encoding::Base64, "aGVsbG8=").unwrap())
+}
+#[test]
+fn url_round_trip() {
+    assert_eq!("a%20b%26c%3D%C3%A9", encode(Encoding::Url, "a b&c=é"));
+    assert_eq!(
+        "a b&c=é",
+        decode(Encoding::Url, "a%20b%26c%3D%C3%A9").unwrap()
+    )
+}
+#[test]
+fn hex_round_trip() {
+    assert_eq!("68656c6c6f", encode(Encoding::Hex, "hello"));
+    assert_eq!("hello", decode(Encoding::Hex, "68656C6C6F").unwrap())
+}
+#[test]
+fn invalid_base64() {
+    assert!(decode(Encoding::Base64, "aGVs*G8=").is_err())
+}
+#[test]
+fn invalid_hex() {
+    assert!(decode(Encoding::Hex, "6g").is_err());
+    assert!(decode(Encoding::Hex, "686").is_err())
+}
+#[test]
+fn invalid_utf8() {
+    assert!(decode(Encoding::Hex, "ff").is_err())
+}
+#[test]
+fn encoding_names() {
+    assert_eq!(Some(Encoding::Base64), Encoding::parse("Base64"));
+    assert_eq!(None, Encoding::parse("rot13"))
+}
@@ -0,0 +1,38 @@
+use crate::helpers::{heat_index, wind_chill};
+
+fn fahrenheit_to_celsius(f: f64) -> f64 {
+    (f - 32.0) * 5.0 / 9.0
+}
+
+#[test]
+fn known_heat_index() {
+    // 90°F at 60% humidity feels like 100°F according to the NWS heat index chart
+    let result = heat_index(fahrenheit_to_celsius(90.0), 60.0).unwrap();
+    assert!((result - fahrenheit_to_celsius(99.68)).abs() < 0.01)
+}
+#[test]
+fn heat_index_too_cold() {
+    assert!(heat_index(20.0, 60.0).is_none())
+}
+#[test]
+fn heat_index_too_hot() {
+    assert!(heat_index(60.0, 60.0).is_none())
+}
+#[test]
+fn heat_index_invalid_humidity() {
+    assert!(heat_index(30.0, 120.0).is_none())
+}
+#[test]
+fn known_wind_chill() {
+    // -10°C with 20km/h wind feels like -18°C according to the Environment Canada wind chill chart
+    let result = wind_chill(-10.0, 20.0).unwrap();
+    assert!((result + 17.86).abs() < 0.01)
+}
+#[test]
+fn wind_chill_too_warm() {
+    assert!(wind_chill(15.0, 20.0).is_none())
+}
+#[test]
+fn wind_chill_too_calm() {
+    assert!(wind_chill(-10.0, 2.0).is_none())
+}
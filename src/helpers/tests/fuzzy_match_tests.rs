@@ -0,0 +1,42 @@
+use crate::helpers::{fuzzy_matches, typo_correction};
+
+fn keys() -> Vec<String> {
+    vec![
+        "hwa".to_string(),
+        "network".to_string(),
+        "networking".to_string(),
+        "troubleshooting".to_string(),
+    ]
+}
+
+#[test]
+fn exact_match_first() {
+    assert_eq!(
+        vec!["network", "networking"],
+        fuzzy_matches("network", &keys())
+    )
+}
+#[test]
+fn misspelling() {
+    assert_eq!(
+        vec!["troubleshooting"],
+        fuzzy_matches("trubleshooting", &keys())
+    )
+}
+#[test]
+fn no_match() {
+    assert!(fuzzy_matches("kodi", &keys()).is_empty())
+}
+#[test]
+fn typo_corrected() {
+    assert_eq!(Some("network"), typo_correction("netwrk", &keys()));
+    assert_eq!(Some("hwa"), typo_correction("HWX", &keys()))
+}
+#[test]
+fn exact_match_not_a_typo() {
+    assert_eq!(None, typo_correction("hwa", &keys()))
+}
+#[test]
+fn unrelated_not_a_typo() {
+    assert_eq!(None, typo_correction("kodi", &keys()))
+}
@@ -0,0 +1,19 @@
+mod bot_response_tests;
+mod convert_color_tests;
+mod convert_cron_tests;
+mod convert_date_tests;
+mod convert_dpi_tests;
+mod convert_gas_mark_tests;
+mod convert_ip_tests;
+mod convert_percent_tests;
+mod convert_rate_tests;
+mod convert_ratio_tests;
+mod convert_roman_tests;
+mod convert_time_tests;
+mod convert_unit_tests;
+mod convert_wave_tests;
+mod convert_words_tests;
+mod encoding_tests;
+mod feels_like_tests;
+mod fuzzy_match_tests;
+mod template_tests;
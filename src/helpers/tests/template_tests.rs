@@ -0,0 +1,66 @@
+use crate::helpers::{render_template, TemplateData};
+use ruma::user_id;
+use std::time::{Duration, SystemTime};
+
+#[test]
+fn time() {
+    let data = TemplateData {
+        time: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_643_637_900)),
+        ..TemplateData::default()
+    };
+    assert_eq!(
+        "It is 2022-01-31 14:05 UTC",
+        render_template("It is {time}", &data)
+    )
+}
+#[test]
+fn room() {
+    let data = TemplateData {
+        room: Some("Jellyfin"),
+        ..TemplateData::default()
+    };
+    assert_eq!(
+        "Welcome to Jellyfin",
+        render_template("Welcome to {room}", &data)
+    )
+}
+#[test]
+fn member_count() {
+    let data = TemplateData {
+        member_count: Some(42),
+        ..TemplateData::default()
+    };
+    assert_eq!(
+        "42 members",
+        render_template("{membercount} members", &data)
+    )
+}
+#[test]
+fn sender() {
+    let data = TemplateData {
+        sender: Some(user_id!("@user:example.com")),
+        ..TemplateData::default()
+    };
+    assert_eq!(
+        "Hi @user:example.com, hi again @user:example.com",
+        render_template("Hi {sender}, hi again {sender}", &data)
+    )
+}
+#[test]
+fn unknown_placeholder_untouched() {
+    let data = TemplateData {
+        room: Some("Jellyfin"),
+        ..TemplateData::default()
+    };
+    assert_eq!(
+        "{weather} in {room",
+        render_template("{weather} in {room", &data)
+    )
+}
+#[test]
+fn placeholder_without_data_untouched() {
+    assert_eq!(
+        "Welcome to {room}",
+        render_template("Welcome to {room}", &TemplateData::default())
+    )
+}
@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::env;
 use std::fmt;
-use tracing_subscriber::FmtSubscriber;
+use tracing::error;
+use tracing_subscriber::{fmt::Formatter, reload::Handle, EnvFilter, FmtSubscriber};
 
 // TODO: Output to stdout+file for logging
 // let file = tracing_appender::rolling::daily("/var/log", "myapp.log");
@@ -81,11 +83,148 @@ impl From<String> for Filter {
     }
 }
 
+/// Handle used to replace the log filter after the config is loaded
+pub type LogHandle = Handle<EnvFilter, Formatter>;
+
+/// Log levels that can be configured for a feature
+pub const LOG_LEVELS: [&str; 6] = ["off", "error", "warn", "info", "debug", "trace"];
+
+/// Features whose log level can be configured and the modules that belong to them
+pub const FEATURES: [(&str, &[&str]); 10] = [
+    (
+        "conversions",
+        &[
+            "matrix_bot::helpers::convert_color",
+            "matrix_bot::helpers::convert_cron",
+            "matrix_bot::helpers::convert_date",
+            "matrix_bot::helpers::convert_dpi",
+            "matrix_bot::helpers::convert_gas_mark",
+            "matrix_bot::helpers::convert_ip",
+            "matrix_bot::helpers::convert_percent",
+            "matrix_bot::helpers::convert_rate",
+            "matrix_bot::helpers::convert_ratio",
+            "matrix_bot::helpers::convert_roman",
+            "matrix_bot::helpers::convert_time",
+            "matrix_bot::helpers::convert_unit",
+            "matrix_bot::helpers::convert_wave",
+            "matrix_bot::helpers::convert_words",
+            "matrix_bot::helpers::feels_like",
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::feels_like",
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::unit_conversion",
+            "matrix_bot::matrix_handlers::listeners::color_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::cron_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::data_rate_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::date_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::dpi_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::gas_mark_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::ip_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::percent_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::ratio_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::roman_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::timezone_handler",
+            "matrix_bot::matrix_handlers::listeners::unit_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::wave_conversion_handler",
+            "matrix_bot::matrix_handlers::listeners::words_conversion_handler",
+        ],
+    ),
+    (
+        "corrections",
+        &[
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::spellcheck",
+            "matrix_bot::matrix_handlers::listeners::correction_handler",
+            "matrix_bot::matrix_handlers::listeners::stats_handler",
+        ],
+    ),
+    (
+        "github_search",
+        &[
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::github_search",
+            "matrix_bot::queries",
+        ],
+    ),
+    (
+        "links",
+        &[
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::link_url",
+            "matrix_bot::matrix_handlers::listeners::link_handler",
+        ],
+    ),
+    (
+        "group_pings",
+        &[
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::group_ping",
+            "matrix_bot::matrix_handlers::listeners::pingtest_handler",
+        ],
+    ),
+    (
+        "text_expansions",
+        &["matrix_bot::matrix_handlers::listeners::commandless_handler::text_expansion"],
+    ),
+    (
+        "karma",
+        &[
+            "matrix_bot::matrix_handlers::listeners::commandless_handler::karma",
+            "matrix_bot::matrix_handlers::listeners::karma_handler",
+        ],
+    ),
+    (
+        "moderation",
+        &[
+            "matrix_bot::matrix_handlers::listeners::audit_handler",
+            "matrix_bot::matrix_handlers::listeners::ban_handler",
+            "matrix_bot::matrix_handlers::listeners::flood_handler",
+            "matrix_bot::matrix_handlers::listeners::grant_handler",
+            "matrix_bot::matrix_handlers::listeners::unban_handler",
+            "matrix_bot::matrix_handlers::responders::audit",
+            "matrix_bot::matrix_handlers::responders::confirmation",
+            "matrix_bot::matrix_handlers::responders::mute",
+        ],
+    ),
+    (
+        "announcements",
+        &[
+            "matrix_bot::matrix_handlers::listeners::announce_handler",
+            "matrix_bot::matrix_handlers::responders::pin",
+        ],
+    ),
+    (
+        "webhooks",
+        &["matrix_bot::webhook", "matrix_bot::webhook_handlers"],
+    ),
+];
+
+/// Returns the modules belonging to a feature, or the name itself if it is a module path
+pub fn feature_targets(feature: &str) -> Option<Vec<&str>> {
+    if feature.contains("::") {
+        return Some(vec![feature]);
+    }
+    FEATURES
+        .iter()
+        .find(|(name, _)| *name == feature)
+        .map(|(_, targets)| targets.to_vec())
+}
+
 fn log_filter(level: Level, filter: Filter) -> String {
     filter.to_string() + &level.to_string()
 }
 
-pub fn init() {
+/// Builds the log filter from the environment plus a directive for every module of the configured features
+pub fn feature_filter(base: &str, log_levels: &HashMap<String, String>) -> String {
+    let mut features: Vec<_> = log_levels.iter().collect();
+    features.sort();
+    let mut filter = base.to_string();
+    for (feature, level) in features {
+        for target in feature_targets(feature).unwrap_or_default() {
+            filter.push(',');
+            filter.push_str(target);
+            filter.push('=');
+            filter.push_str(level);
+        }
+    }
+    filter
+}
+
+fn env_filter() -> String {
     let level = match env::var("MATRIX_BOT_LOG_LEVEL") {
         Ok(v) => Level::from(v),
         Err(_) => Level::default(),
@@ -94,7 +233,34 @@ pub fn init() {
         Ok(v) => Filter::from(v),
         Err(_) => Filter::default(),
     };
-    FmtSubscriber::builder()
-        .with_env_filter(log_filter(level, filter))
-        .init();
+    log_filter(level, filter)
+}
+
+pub fn init() -> LogHandle {
+    let builder = FmtSubscriber::builder()
+        .with_env_filter(env_filter())
+        .with_filter_reloading();
+    let handle = builder.reload_handle();
+    builder.init();
+    handle
+}
+
+/// Replaces the log filter with one that includes the configured per feature log levels
+pub fn set_feature_levels(handle: &LogHandle, log_levels: &HashMap<String, String>) {
+    if log_levels.is_empty() {
+        return;
+    }
+    let filter = match EnvFilter::try_new(feature_filter(&env_filter(), log_levels)) {
+        Ok(v) => v,
+        Err(e) => {
+            error!(
+                "Unable to build log filter from configured log levels. {}",
+                e
+            );
+            return;
+        }
+    };
+    if let Err(e) = handle.reload(filter) {
+        error!("Unable to apply configured log levels. {}", e);
+    }
 }
@@ -57,6 +57,9 @@
 //!
 //! `./matrix-bot` to run
 //!
+//! `./matrix-bot --config <path> --data-dir <path>` uses another config file and storage directory,
+//! taking precedence over the `MATRIX_BOT_CONFIG_DIR` and `MATRIX_BOT_DATA_DIR` environment variables
+//!
 //! I hope you enjoy your experience and please report and issues or feature requests you might have
 
 #![forbid(unsafe_code)]
@@ -79,7 +82,8 @@ mod webhook_handlers;
 #[tokio::main]
 #[allow(clippy::missing_docs_in_private_items)]
 async fn main() -> anyhow::Result<()> {
-    logging::init();
-    bot::init().await?;
+    config::Args::parse(std::env::args().skip(1))?.set();
+    let log_handle = logging::init();
+    bot::init(log_handle).await?;
     Ok(())
 }
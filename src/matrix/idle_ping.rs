@@ -0,0 +1,34 @@
+//! Struct used to schedule pings that keep the homeserver session alive while the bot is idle
+
+use std::time::{Duration, Instant};
+
+/// Tracks how long the bot has gone without sync activity.
+pub struct IdlePing {
+    interval: Duration,
+    last_activity: Instant,
+}
+
+impl IdlePing {
+    pub fn new(interval: Duration, now: Instant) -> Self {
+        Self {
+            interval,
+            last_activity: now,
+        }
+    }
+
+    /// Records sync activity, postponing the next ping.
+    pub fn record_activity(&mut self, now: Instant) {
+        self.last_activity = now;
+    }
+
+    /// Returns true if the bot has been idle for the whole interval.
+    ///
+    /// A due ping counts as activity, so pings are spaced one interval apart while the bot stays idle.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if now.saturating_duration_since(self.last_activity) < self.interval {
+            return false;
+        }
+        self.last_activity = now;
+        true
+    }
+}
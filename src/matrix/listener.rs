@@ -1,24 +1,43 @@
 //! Structs and functions that represent functional bots and allow for easy loading
 //! plus main loop initialization.
 
+use super::idle_ping::IdlePing;
+use super::power_levels::{fetch_power_level_admins, PowerLevelRefresh};
+use super::room_members::{fetch_alias_members, fetch_joined_members};
+use super::sync_filter::{sync_event_types, sync_filter_definition};
+use super::sync_save::SyncSave;
 use super::MatrixClient;
 use crate::config::{Config, ListenerStorage, MatrixListenerConfig};
-use crate::matrix_handlers::listeners::{handle_invite_event, handle_text_event};
+use crate::matrix_handlers::listeners::{
+    handle_edit_event, handle_invite_event, handle_pending_corrections, handle_reaction_event,
+    handle_redaction_event, handle_stats_report, handle_text_event, is_stale_event,
+    needs_room_members, stale_group_rooms,
+};
 use crate::messages::MatrixMessage;
 use ruma::{
-    api::client::sync::sync_events,
+    api::client::{
+        account::whoami,
+        sync::sync_events::{self, v3::Filter},
+    },
     events::{
-        room::message::{
-            MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
-            SyncRoomMessageEvent,
+        reaction::{OriginalSyncReactionEvent, ReactionEventContent, SyncReactionEvent},
+        room::{
+            encryption::SyncRoomEncryptionEvent,
+            message::{
+                MessageType, OriginalSyncRoomMessageEvent, Relation, RoomMessageEventContent,
+                SyncRoomMessageEvent,
+            },
+            name::{OriginalSyncRoomNameEvent, RoomNameEventContent, SyncRoomNameEvent},
+            redaction::{OriginalSyncRoomRedactionEvent, SyncRoomRedactionEvent},
         },
-        AnyStrippedStateEvent, AnySyncMessageLikeEvent, AnySyncTimelineEvent,
+        AnyStrippedStateEvent, AnySyncMessageLikeEvent, AnySyncStateEvent, AnySyncTimelineEvent,
     },
     presence::PresenceState,
+    RoomId,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime};
 use tokio::sync::mpsc::Sender;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, info, trace};
 
 /// Struct representing all required data for a functioning bot instance.
 pub struct MatrixListener {
@@ -46,37 +65,110 @@ impl MatrixListener {
     }
 
     /// Used to start main program loop for the bot.
-    /// Will login then loop forever while waiting on new sync data from the homeserver.
+    /// Will login then loop while waiting on new sync data from the homeserver until the bot is shut down.
     pub async fn start(&mut self, client: MatrixClient) {
+        let event_types = sync_event_types(&self.config);
+        let filter = Filter::FilterDefinition(sync_filter_definition(&event_types));
+        if self.config.enable_sync_filter {
+            debug!("Filtering syncs to event types {:?}", event_types);
+        }
+        if self.config.dry_run {
+            info!("Dry run enabled. Responses will be logged instead of sent.");
+        }
+        let mut idle_ping = self
+            .config
+            .idle_ping_interval
+            .map(|interval| IdlePing::new(interval, Instant::now()));
+        let mut sync_save = SyncSave::new(self.config.sync_save_interval);
+        let mut power_level_refresh = self
+            .config
+            .power_level_admins
+            .as_ref()
+            .map(|v| PowerLevelRefresh::new(v.refresh_interval));
+        let shutdown = shutdown_signal();
+        tokio::pin!(shutdown);
         loop {
+            if let (Some(power_level_admins), Some(refresh)) =
+                (&self.config.power_level_admins, &mut power_level_refresh)
+            {
+                if refresh.due(Instant::now()) {
+                    if let Some(v) = fetch_power_level_admins(power_level_admins, &client).await {
+                        self.storage.power_level_admins = v;
+                    }
+                }
+            }
             let mut req = sync_events::v3::Request::new();
-            req.filter = None;
+            req.filter = if self.config.enable_sync_filter {
+                Some(&filter)
+            } else {
+                None
+            };
             req.since = match &self.storage.last_sync {
                 Some(v) => Some(v.as_str()),
                 None => None,
             };
             req.full_state = false;
             req.set_presence = &PresenceState::Unavailable;
-            req.timeout = Some(Duration::new(30, 0));
+            // Syncs return early enough to send batched corrections when their burst ends
+            let timeout = Duration::new(30, 0);
+            req.timeout = Some(match self.storage.next_correction_due() {
+                Some(due) => due.saturating_duration_since(Instant::now()).min(timeout),
+                None => timeout,
+            });
 
-            let response = match client.send_request(req).await {
-                Ok(v) => Some(v),
-                Err(e) => {
-                    debug!("Line 65: {:?}", e);
-                    None
+            let response = tokio::select! {
+                response = client.send_request(req) => match response {
+                    Ok(v) => Some(v),
+                    Err(e) => {
+                        debug!("Line 65: {:?}", e);
+                        self.storage.activity.errors += 1;
+                        None
+                    }
+                },
+                _ = &mut shutdown => {
+                    info!("Shutting down matrix listener...");
+                    break;
                 }
             };
 
             match response {
                 Some(v) => {
+                    if let Some(idle_ping) = &mut idle_ping {
+                        if !v.rooms.is_empty() {
+                            idle_ping.record_activity(Instant::now());
+                        } else if idle_ping.due(Instant::now()) {
+                            ping_homeserver(&client).await;
+                        }
+                    }
                     self.storage.last_sync = Some(v.next_batch.clone());
-                    if let Err(e) = self.storage.save_storage() {
+                    self.storage.count_bans();
+                    if !sync_save.due(Instant::now()) {
+                        trace!("Sync token was saved recently. Skipping save...");
+                    } else if let Err(e) = self.storage.save_storage() {
                         error!(
                             "Unable to save matrix_listener.ron during normal operation. {}",
                             e
                         )
                     };
                     for (room_id, joined_room) in &v.rooms.join {
+                        if let Some(count) = joined_room.summary.joined_member_count {
+                            self.storage
+                                .joined_member_counts
+                                .insert(room_id.clone(), count.into());
+                        }
+                        for raw_event in &joined_room.state.events {
+                            match raw_event.deserialize() {
+                                Ok(AnySyncStateEvent::RoomName(name)) => {
+                                    self.update_room_name(room_id, name)
+                                }
+                                Ok(AnySyncStateEvent::RoomEncryption(
+                                    SyncRoomEncryptionEvent::Original(_),
+                                )) => {
+                                    self.storage.encrypted_rooms.insert(room_id.clone());
+                                }
+                                _ => {}
+                            }
+                        }
                         for raw_event in &joined_room.timeline.events {
                             let event = raw_event.deserialize();
                             match event {
@@ -90,19 +182,77 @@ impl MatrixListener {
                                                         relates_to,
                                                         ..
                                                     },
+                                                event_id,
                                                 sender,
+                                                origin_server_ts,
                                                 ..
                                             },
                                         ),
                                     ),
                                 )) => {
-                                    if matches!(relates_to, Some(Relation::Replacement(_))) {
-                                        debug!("Message is an edit, skipping handling");
+                                    if is_stale_event(
+                                        &t,
+                                        room_id,
+                                        origin_server_ts,
+                                        &self.config,
+                                        SystemTime::now(),
+                                    ) {
+                                        debug!("Event {} is too old. Skipping...", event_id);
                                         continue;
                                     }
+                                    if let Some(Relation::Replacement(r)) = &relates_to {
+                                        handle_edit_event(
+                                            r,
+                                            &event_id,
+                                            origin_server_ts,
+                                            &sender,
+                                            room_id,
+                                            &mut self.storage,
+                                            &self.config,
+                                            &self.api_client,
+                                            &mut self.send,
+                                        )
+                                        .await;
+                                        continue;
+                                    }
+                                    if needs_room_members(
+                                        &t,
+                                        &sender,
+                                        room_id,
+                                        &self.storage,
+                                        &self.config,
+                                        Instant::now(),
+                                    ) {
+                                        if let Some(v) =
+                                            fetch_joined_members(room_id, &client).await
+                                        {
+                                            self.storage.set_room_members(
+                                                room_id,
+                                                v,
+                                                Instant::now(),
+                                            );
+                                        }
+                                    }
+                                    for room in stale_group_rooms(
+                                        &t,
+                                        &sender,
+                                        &self.storage,
+                                        &self.config,
+                                        Instant::now(),
+                                    ) {
+                                        if let Some(v) = fetch_alias_members(&room, &client).await {
+                                            self.storage.set_alias_members(
+                                                &room,
+                                                v,
+                                                Instant::now(),
+                                            );
+                                        }
+                                    }
                                     handle_text_event(
                                         &t,
                                         relates_to.as_ref(),
+                                        &event_id,
+                                        origin_server_ts,
                                         &sender,
                                         room_id,
                                         &mut self.storage,
@@ -112,6 +262,53 @@ impl MatrixListener {
                                     )
                                     .await;
                                 }
+                                Ok(AnySyncTimelineEvent::MessageLike(
+                                    AnySyncMessageLikeEvent::RoomRedaction(
+                                        SyncRoomRedactionEvent::Original(
+                                            OriginalSyncRoomRedactionEvent {
+                                                redacts, sender, ..
+                                            },
+                                        ),
+                                    ),
+                                )) => {
+                                    handle_redaction_event(
+                                        &redacts,
+                                        &sender,
+                                        room_id,
+                                        &self.config,
+                                        &mut self.send,
+                                    )
+                                    .await;
+                                }
+                                Ok(AnySyncTimelineEvent::MessageLike(
+                                    AnySyncMessageLikeEvent::Reaction(SyncReactionEvent::Original(
+                                        OriginalSyncReactionEvent {
+                                            content: ReactionEventContent { relates_to, .. },
+                                            sender,
+                                            ..
+                                        },
+                                    )),
+                                )) => {
+                                    handle_reaction_event(
+                                        &relates_to,
+                                        &sender,
+                                        room_id,
+                                        &mut self.storage,
+                                        &self.config,
+                                        &mut self.send,
+                                    )
+                                    .await;
+                                }
+                                Ok(AnySyncTimelineEvent::State(AnySyncStateEvent::RoomName(
+                                    name,
+                                ))) => self.update_room_name(room_id, name),
+                                Ok(AnySyncTimelineEvent::State(
+                                    AnySyncStateEvent::RoomEncryption(
+                                        SyncRoomEncryptionEvent::Original(_),
+                                    ),
+                                )) => {
+                                    self.storage.encrypted_rooms.insert(room_id.clone());
+                                }
                                 Ok(_) => {}
                                 Err(e) => {
                                     debug!("{:?}", e);
@@ -150,6 +347,68 @@ impl MatrixListener {
                 }
                 None => debug!("Response deserialization failed. Doing nothing this loop."),
             }
+            handle_pending_corrections(
+                &mut self.storage,
+                &self.config,
+                &mut self.send,
+                Instant::now(),
+            )
+            .await;
+            handle_stats_report(
+                &mut self.storage,
+                &self.config,
+                &mut self.send,
+                SystemTime::now(),
+            )
+            .await;
         }
     }
+
+    /// Records the name of a room so it can be used in message templates.
+    fn update_room_name(&mut self, room_id: &RoomId, event: SyncRoomNameEvent) {
+        match event {
+            SyncRoomNameEvent::Original(OriginalSyncRoomNameEvent {
+                content: RoomNameEventContent { name: Some(n), .. },
+                ..
+            }) => {
+                self.storage.room_names.insert(room_id.to_owned(), n);
+            }
+            _ => {
+                self.storage.room_names.remove(room_id);
+            }
+        }
+    }
+}
+
+/// Resolves once the bot is asked to stop with Ctrl+C, or SIGTERM on unix.
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                return;
+            }
+            Err(e) => error!("Unable to listen for SIGTERM. {:?}", e),
+        }
+    }
+    if let Err(e) = tokio::signal::ctrl_c().await {
+        error!(
+            "Unable to listen for Ctrl+C. Graceful shutdown is disabled. {:?}",
+            e
+        );
+        std::future::pending::<()>().await
+    }
+}
+
+/// Sends a no-op request to keep the session alive and surface disconnects while the bot is idle.
+async fn ping_homeserver(client: &MatrixClient) {
+    match client.send_request(whoami::v3::Request::new()).await {
+        Ok(_) => trace!("Idle ping succeeded"),
+        Err(e) => error!("Idle ping to the homeserver failed. {:?}", e),
+    }
 }
@@ -1,4 +1,13 @@
+#[cfg(test)]
+mod tests;
+
+pub mod idle_ping;
 pub mod listener;
+pub mod power_levels;
+pub mod profile;
 pub mod responder;
+pub mod room_members;
+pub mod sync_filter;
+pub mod sync_save;
 
 pub type MatrixClient = ruma::client::Client<ruma::client::http_client::Reqwest>;
@@ -0,0 +1,93 @@
+//! Functions used to treat users with enough power in a room as admins
+
+use super::MatrixClient;
+use crate::config::PowerLevelAdmins;
+use ruma::{
+    api::client::state::get_state_events_for_key,
+    events::{room::power_levels::RoomPowerLevelsEventContent, StateEventType},
+    OwnedUserId,
+};
+use std::collections::HashSet;
+use std::time::{Duration, Instant};
+use tracing::{debug, error};
+
+/// Tracks when power level admins were last refreshed.
+pub struct PowerLevelRefresh {
+    interval: Duration,
+    last_refresh: Option<Instant>,
+}
+
+impl PowerLevelRefresh {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_refresh: None,
+        }
+    }
+
+    /// Returns true if power level admins should be refreshed.
+    ///
+    /// The first check is always due so admins are known right after startup.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if let Some(last_refresh) = self.last_refresh {
+            if now.saturating_duration_since(last_refresh) < self.interval {
+                return false;
+            }
+        }
+        self.last_refresh = Some(now);
+        true
+    }
+}
+
+/// Returns the users with at least `min_power_level` in a room's power levels.
+///
+/// Only users listed in the power levels are returned, even if users_default is high enough.
+pub fn power_level_admins(
+    power_levels: &RoomPowerLevelsEventContent,
+    min_power_level: i64,
+) -> HashSet<OwnedUserId> {
+    power_levels
+        .users
+        .iter()
+        .filter(|(_, level)| i64::from(**level) >= min_power_level)
+        .map(|(user, _)| user.clone())
+        .collect()
+}
+
+/// Fetches the power levels of the configured room and returns the users with enough power to be admins.
+///
+/// Returns None if the power levels can't be fetched, so the previous admins are kept.
+pub async fn fetch_power_level_admins(
+    config: &PowerLevelAdmins,
+    client: &MatrixClient,
+) -> Option<HashSet<OwnedUserId>> {
+    let req = get_state_events_for_key::v3::Request::new(
+        &config.room,
+        StateEventType::RoomPowerLevels,
+        "",
+    );
+    let response = match client.send_request(req).await {
+        Ok(v) => v,
+        Err(e) => {
+            error!(
+                "Unable to fetch power levels of {} due to error {:?}",
+                config.room, e
+            );
+            return None;
+        }
+    };
+    match response
+        .content
+        .deserialize_as::<RoomPowerLevelsEventContent>()
+    {
+        Ok(v) => {
+            let admins = power_level_admins(&v, config.min_power_level);
+            debug!("Power level admins are {:?}", admins);
+            Some(admins)
+        }
+        Err(e) => {
+            error!("Unable to read power levels of {}. {:?}", config.room, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Functions used to keep the bot account profile in line with the configured profile
+
+use super::MatrixClient;
+use crate::config::Config;
+use ruma::{
+    api::client::profile::{get_profile, set_avatar_url, set_display_name},
+    MxcUri,
+};
+use tracing::{debug, error, info};
+
+#[derive(Debug, Default, PartialEq)]
+/// Profile fields that differ from the configured profile and need to be set
+pub struct ProfileUpdate<'a> {
+    /// Display name to set, if it needs changing
+    pub display_name: Option<&'a str>,
+    /// Avatar to set, if it needs changing
+    pub avatar_url: Option<&'a MxcUri>,
+}
+
+/// Compares the configured profile against the current one and returns only the fields that need to change
+///
+/// Fields that are not configured are never changed
+pub fn profile_update<'a>(
+    display_name: Option<&'a str>,
+    avatar_url: Option<&'a MxcUri>,
+    current_display_name: Option<&str>,
+    current_avatar_url: Option<&MxcUri>,
+) -> ProfileUpdate<'a> {
+    ProfileUpdate {
+        display_name: display_name.filter(|v| Some(*v) != current_display_name),
+        avatar_url: avatar_url.filter(|v| Some(*v) != current_avatar_url),
+    }
+}
+
+/// Fetches the current bot profile and updates the display name and avatar if they differ from config
+pub async fn sync_profile(config: &Config, client: &MatrixClient) {
+    if config.display_name.is_none() && config.avatar_url.is_none() {
+        debug!("No profile configured. Leaving profile unchanged.");
+        return;
+    }
+    let current = match client
+        .send_request(get_profile::v3::Request::new(&config.mx_uname))
+        .await
+    {
+        Ok(v) => v,
+        Err(e) => {
+            error!("Unable to fetch current profile due to error {:?}", e);
+            return;
+        }
+    };
+    let update = profile_update(
+        config.display_name.as_deref(),
+        config.avatar_url.as_deref(),
+        current.displayname.as_deref(),
+        current.avatar_url.as_deref(),
+    );
+    if config.dry_run {
+        info!("Dry run, not updating profile: {:?}", update);
+        return;
+    }
+    if let Some(v) = update.display_name {
+        let req = set_display_name::v3::Request::new(&config.mx_uname, Some(v));
+        match client.send_request(req).await {
+            Ok(_) => info!("Set display name to {}", v),
+            Err(e) => error!("Unable to set display name due to error {:?}", e),
+        }
+    }
+    if let Some(v) = update.avatar_url {
+        let req = set_avatar_url::v3::Request::new(&config.mx_uname, Some(v));
+        match client.send_request(req).await {
+            Ok(_) => info!("Set avatar to {}", v),
+            Err(e) => error!("Unable to set avatar due to error {:?}", e),
+        }
+    }
+}
@@ -2,27 +2,59 @@
 //! plus main loop initialization.
 
 use super::MatrixClient;
-use crate::config::ResponderStorage;
+use crate::config::{Config, Emoji, ResponderStorage};
 use crate::matrix_handlers::responders::{
-    accept_invite, reject_invite, send_ban_message, send_formatted_notice, send_formatted_text,
-    send_notice, send_plain_text,
+    accept_invite, announce, audit_tail, message_content, redact_responses, reject_invite,
+    send_ban_message, send_edit, send_formatted_notice, send_formatted_text, send_in_thread,
+    send_mute_message, send_notice, send_plain_text, send_raw_event, send_reaction, send_typing,
+    send_unban_message, send_user_invite, unannounce, Confirmation, PendingActions, Throttle,
 };
-use crate::messages::{MatrixInviteType, MatrixMessage, MatrixMessageType};
+use crate::messages::{MatrixBanMessage, MatrixInviteType, MatrixMessage, MatrixMessageType};
+use ruma::OwnedUserId;
+use std::time::Instant;
 use tokio::sync::mpsc::Receiver;
-use tracing::info;
+use tracing::{debug, error, info};
 
 /// Struct representing all required data for a functioning bot instance.
 pub struct MatrixResponder {
     /// Storage data.
     pub storage: ResponderStorage,
+    /// Bot account, used to show it as typing.
+    user_id: OwnedUserId,
+    /// Reaction admins confirm actions with.
+    confirmation_reaction: Emoji,
+    /// Bans waiting on an admin to confirm them.
+    pending_bans: PendingActions<MatrixBanMessage>,
+    /// Logs messages instead of sending them if true.
+    dry_run: bool,
     recv: Receiver<MatrixMessage>,
 }
 
 impl MatrixResponder {
     /// Loads storage data, config data, and then creates a reqwest client and then returns a Bot instance.
-    pub fn new(recv: Receiver<MatrixMessage>) -> anyhow::Result<Self> {
-        let storage = ResponderStorage::load_storage()?;
-        Ok(Self { storage, recv })
+    pub fn new(config: &Config, recv: Receiver<MatrixMessage>) -> anyhow::Result<Self> {
+        Ok(Self::with_storage(
+            config,
+            ResponderStorage::load_storage()?,
+            recv,
+        ))
+    }
+
+    /// Creates a Bot instance from already loaded storage data.
+    pub fn with_storage(
+        config: &Config,
+        mut storage: ResponderStorage,
+        recv: Receiver<MatrixMessage>,
+    ) -> Self {
+        storage.throttle = Throttle::new(config.messages_per_second);
+        Self {
+            storage,
+            user_id: config.mx_uname.clone(),
+            confirmation_reaction: config.confirmation_reaction.clone(),
+            pending_bans: PendingActions::default(),
+            dry_run: config.dry_run,
+            recv,
+        }
     }
 
     /// Used to start main program loop for the bot.
@@ -30,45 +62,186 @@ impl MatrixResponder {
     pub async fn start(&mut self, client: MatrixClient) {
         loop {
             match self.recv.recv().await {
-                Some(v) => match v.message {
-                    MatrixMessageType::Notice(m) => {
-                        send_notice(&client, v.room_id, &mut self.storage, m).await
+                Some(v) => {
+                    if self.dry_run {
+                        info!(
+                            "Dry run, not sending to room {:?}: {:?}",
+                            v.room_id, v.message
+                        );
+                        continue;
                     }
-                    MatrixMessageType::FormattedText(m) => {
-                        send_formatted_text(
+                    if let (Some(root), Some(content)) =
+                        (&v.thread_root, message_content(&v.message))
+                    {
+                        let latest = v.trigger_event.as_deref().unwrap_or(root);
+                        let response = send_in_thread(
                             v.room_id,
                             &mut self.storage,
-                            m.plain_text,
-                            m.formatted_text,
+                            content,
+                            root,
+                            latest,
                             &client,
                         )
-                        .await
-                    }
-                    MatrixMessageType::Text(m) => {
-                        send_plain_text(v.room_id, &mut self.storage, m, &client).await
+                        .await;
+                        if let (Some(trigger), Some(response)) = (v.trigger_event, response) {
+                            self.storage.record_response(trigger, response);
+                        }
+                        continue;
                     }
-                    MatrixMessageType::Invite(m) => match m.kind {
-                        MatrixInviteType::Accept => {
-                            accept_invite(&m.sender, v.room_id, &client).await
+                    let response = match v.message {
+                        MatrixMessageType::Notice(m) => {
+                            send_notice(&client, v.room_id, &mut self.storage, m).await
                         }
-                        MatrixInviteType::Reject => {
-                            reject_invite(&m.sender, v.room_id, &client).await
+                        MatrixMessageType::FormattedText(m) => {
+                            send_formatted_text(
+                                v.room_id,
+                                &mut self.storage,
+                                m.plain_text,
+                                m.formatted_text,
+                                &client,
+                            )
+                            .await
                         }
-                    },
-                    MatrixMessageType::FormattedNotice(m) => {
-                        send_formatted_notice(
-                            v.room_id,
-                            &mut self.storage,
-                            m.plain_text,
-                            m.formatted_text,
-                            &client,
-                        )
-                        .await
-                    }
-                    MatrixMessageType::Ban(m) => {
-                        send_ban_message(&m.user, m.reason, m.rooms, &client).await
+                        MatrixMessageType::Text(m) => {
+                            send_plain_text(v.room_id, &mut self.storage, m, &client).await
+                        }
+                        MatrixMessageType::Edit(e, m) => {
+                            send_edit(v.room_id, &mut self.storage, &e, m, &client).await
+                        }
+                        MatrixMessageType::Invite(m) => {
+                            match m.kind {
+                                MatrixInviteType::Accept => {
+                                    accept_invite(&m.sender, v.room_id, &client).await
+                                }
+                                MatrixInviteType::Reject => {
+                                    reject_invite(&m.sender, v.room_id, &client).await
+                                }
+                            };
+                            None
+                        }
+                        MatrixMessageType::FormattedNotice(m) => {
+                            send_formatted_notice(
+                                v.room_id,
+                                &mut self.storage,
+                                m.plain_text,
+                                m.formatted_text,
+                                &client,
+                            )
+                            .await
+                        }
+                        MatrixMessageType::Ban(m) => {
+                            send_ban_message(v.room_id, m, &mut self.storage, &client).await
+                        }
+                        MatrixMessageType::Unban(m) => {
+                            send_unban_message(v.room_id, m, &mut self.storage, &client).await
+                        }
+                        MatrixMessageType::Mute(m) => {
+                            send_mute_message(m, &mut self.storage, &client).await;
+                            None
+                        }
+                        MatrixMessageType::ConfirmBan(m) => {
+                            let prompt = format!(
+                                "React {} to confirm banning {} users: {}",
+                                self.confirmation_reaction,
+                                m.users.len(),
+                                m.users
+                                    .iter()
+                                    .map(|u| u.as_str())
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            );
+                            let response =
+                                send_notice(&client, v.room_id.clone(), &mut self.storage, prompt)
+                                    .await;
+                            if let (Some(prompt), Some(room_id)) = (&response, &v.room_id) {
+                                self.pending_bans.insert(prompt.clone(), m, Instant::now());
+                                // Admins can confirm by tapping the bot's own reaction
+                                send_reaction(
+                                    room_id,
+                                    &mut self.storage,
+                                    prompt,
+                                    &self.confirmation_reaction,
+                                    &client,
+                                )
+                                .await;
+                            }
+                            response
+                        }
+                        MatrixMessageType::ConfirmAction(e) => {
+                            match self.pending_bans.confirm(&e, Instant::now()) {
+                                Confirmation::Confirmed(m) => {
+                                    send_ban_message(v.room_id, m, &mut self.storage, &client).await
+                                }
+                                Confirmation::Expired => {
+                                    send_notice(
+                                        &client,
+                                        v.room_id,
+                                        &mut self.storage,
+                                        "Confirmation timed out. Ban aborted.".to_string(),
+                                    )
+                                    .await;
+                                    None
+                                }
+                                Confirmation::Unknown => {
+                                    debug!("No pending action for event {}. Doing nothing.", e);
+                                    None
+                                }
+                            }
+                        }
+                        MatrixMessageType::Announce(m) => {
+                            announce(v.room_id, &mut self.storage, m, &client).await
+                        }
+                        MatrixMessageType::Unannounce => {
+                            unannounce(v.room_id, &mut self.storage, &client).await;
+                            None
+                        }
+                        MatrixMessageType::AuditLog(n) => {
+                            let tail = audit_tail(&self.storage.audit_log, n);
+                            send_notice(&client, v.room_id, &mut self.storage, tail).await
+                        }
+                        MatrixMessageType::RawEvent(e) => {
+                            send_raw_event(v.room_id, &mut self.storage, &e, &client).await
+                        }
+                        MatrixMessageType::Typing(typing) => {
+                            send_typing(v.room_id, &self.user_id, typing, &client).await;
+                            None
+                        }
+                        MatrixMessageType::React { event_id, key } => match &v.room_id {
+                            // Recorded like other responses so the reaction is redacted with its trigger
+                            Some(room_id) => {
+                                send_reaction(room_id, &mut self.storage, &event_id, &key, &client)
+                                    .await
+                            }
+                            None => {
+                                error!("Reaction was not provided with room_id");
+                                None
+                            }
+                        },
+                        MatrixMessageType::InviteUser(u) => {
+                            send_user_invite(v.room_id, &mut self.storage, &u, &client).await;
+                            None
+                        }
+                        MatrixMessageType::RoleMessage(m, emoji) => {
+                            let response =
+                                send_notice(&client, v.room_id.clone(), &mut self.storage, m).await;
+                            if let (Some(message), Some(room_id)) = (&response, &v.room_id) {
+                                // Users can pick a role by tapping the bot's own reaction
+                                for e in &emoji {
+                                    send_reaction(room_id, &mut self.storage, message, e, &client)
+                                        .await;
+                                }
+                            }
+                            response
+                        }
+                        MatrixMessageType::RedactResponses(e) => {
+                            redact_responses(v.room_id, &mut self.storage, &e, &client).await;
+                            None
+                        }
+                    };
+                    if let (Some(trigger), Some(response)) = (v.trigger_event, response) {
+                        self.storage.record_response(trigger, response);
                     }
-                },
+                }
                 None => {
                     info!("Matrix channel closed and empty. Exiting thread.");
                     break;
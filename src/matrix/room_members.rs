@@ -0,0 +1,56 @@
+//! Functions used to find who is in a room for %here and room group pings
+
+use super::MatrixClient;
+use ruma::{
+    api::client::{alias::get_alias, membership::joined_members},
+    OwnedUserId, RoomAliasId, RoomId,
+};
+use std::collections::HashSet;
+use tracing::{debug, warn};
+
+/// Fetches the users currently joined to a room.
+///
+/// Returns None if the members can't be fetched, so the ping skips %here instead of pinging nobody by mistake.
+pub async fn fetch_joined_members(
+    room_id: &RoomId,
+    client: &MatrixClient,
+) -> Option<HashSet<OwnedUserId>> {
+    match client
+        .send_request(joined_members::v3::Request::new(room_id))
+        .await
+    {
+        Ok(v) => {
+            debug!("{} has {} joined members", room_id, v.joined.len());
+            Some(v.joined.into_keys().collect())
+        }
+        Err(e) => {
+            warn!(
+                "Unable to fetch joined members of {} due to error {:?}",
+                room_id, e
+            );
+            None
+        }
+    }
+}
+
+/// Resolves a room alias used in a group ping and fetches the users currently joined to its room.
+///
+/// Returns None if the alias can't be resolved or the members can't be fetched.
+pub async fn fetch_alias_members(
+    alias: &RoomAliasId,
+    client: &MatrixClient,
+) -> Option<HashSet<OwnedUserId>> {
+    match client
+        .send_request(get_alias::v3::Request::new(alias))
+        .await
+    {
+        Ok(v) => fetch_joined_members(&v.room_id, client).await,
+        Err(e) => {
+            warn!(
+                "Unable to resolve room alias {} due to error {:?}",
+                alias, e
+            );
+            None
+        }
+    }
+}
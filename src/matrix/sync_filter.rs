@@ -0,0 +1,44 @@
+//! Functions used to build the sync filter from the features enabled in config
+
+use crate::config::MatrixListenerConfig;
+use ruma::api::client::filter::{Filter, FilterDefinition, RoomEventFilter};
+
+/// Returns the room event types needed by the enabled features
+///
+/// Messages, members, and room names are always needed for commands and message templates
+pub fn sync_event_types(config: &MatrixListenerConfig) -> Vec<String> {
+    let mut types = vec![
+        "m.room.message".to_string(),
+        "m.room.member".to_string(),
+        "m.room.name".to_string(),
+    ];
+    if config.moderation_requires_encryption {
+        types.push("m.room.encryption".to_string());
+    }
+    if !config.ban_rooms.is_empty() {
+        types.push("m.reaction".to_string());
+    }
+    if config.enable_response_redaction {
+        types.push("m.room.redaction".to_string());
+    }
+    types
+}
+
+/// Builds a filter that only syncs room events of the given types
+///
+/// Presence, account data, and ephemeral events like typing notifications are never used and always left out
+pub fn sync_filter_definition(types: &[String]) -> FilterDefinition<'_> {
+    let mut timeline = RoomEventFilter::empty();
+    timeline.types = Some(types);
+    let mut state = RoomEventFilter::empty();
+    state.types = Some(types);
+
+    let mut definition = FilterDefinition::empty();
+    definition.presence = Filter::ignore_all();
+    definition.account_data = Filter::ignore_all();
+    definition.room.account_data = RoomEventFilter::ignore_all();
+    definition.room.ephemeral = RoomEventFilter::ignore_all();
+    definition.room.timeline = timeline;
+    definition.room.state = state;
+    definition
+}
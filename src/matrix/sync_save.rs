@@ -0,0 +1,31 @@
+//! Struct used to space out saves of the sync token
+
+use std::time::{Duration, Instant};
+
+/// Tracks when the sync token was last saved.
+pub struct SyncSave {
+    interval: Duration,
+    last_save: Option<Instant>,
+}
+
+impl SyncSave {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_save: None,
+        }
+    }
+
+    /// Returns true if the sync token should be saved.
+    ///
+    /// The first sync is always saved, and an interval of zero saves every sync.
+    pub fn due(&mut self, now: Instant) -> bool {
+        if let Some(last_save) = self.last_save {
+            if now.saturating_duration_since(last_save) < self.interval {
+                return false;
+            }
+        }
+        self.last_save = Some(now);
+        true
+    }
+}
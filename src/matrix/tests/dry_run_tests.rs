@@ -0,0 +1,38 @@
+use crate::config::tests::common::load_config;
+use crate::config::ResponderStorage;
+use crate::matrix::responder::MatrixResponder;
+use crate::matrix::MatrixClient;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{api::MatrixVersion, room_id};
+use std::io::ErrorKind;
+use std::net::TcpListener;
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn dry_run_sends_nothing() {
+    let homeserver = TcpListener::bind("127.0.0.1:0").unwrap();
+    homeserver.set_nonblocking(true).unwrap();
+    let client: MatrixClient = ruma::client::Client::builder()
+        .homeserver_url(format!("http://{}", homeserver.local_addr().unwrap()))
+        .supported_matrix_versions(vec![MatrixVersion::V1_2])
+        .build()
+        .await
+        .unwrap();
+    let config = load_config("dry_run = true", "").unwrap();
+    let (send, recv) = mpsc::channel(1);
+    let mut responder = MatrixResponder::with_storage(&config, ResponderStorage::default(), recv);
+    send.send(MatrixMessage {
+        room_id: Some(room_id!("!room:example.com").to_owned()),
+        message: MatrixMessageType::Text("Hello".to_string()),
+        trigger_event: None,
+        thread_root: None,
+    })
+    .await
+    .unwrap();
+    drop(send);
+    responder.start(client).await;
+    assert_eq!(
+        ErrorKind::WouldBlock,
+        homeserver.accept().unwrap_err().kind()
+    );
+}
@@ -0,0 +1,33 @@
+use crate::matrix::idle_ping::IdlePing;
+use std::time::{Duration, Instant};
+
+#[test]
+fn not_due_before_interval() {
+    let start = Instant::now();
+    let mut ping = IdlePing::new(Duration::from_secs(300), start);
+    assert!(!ping.due(start));
+    assert!(!ping.due(start + Duration::from_secs(299)))
+}
+#[test]
+fn due_after_interval() {
+    let start = Instant::now();
+    let mut ping = IdlePing::new(Duration::from_secs(300), start);
+    assert!(ping.due(start + Duration::from_secs(300)))
+}
+#[test]
+fn ping_restarts_interval() {
+    let start = Instant::now();
+    let mut ping = IdlePing::new(Duration::from_secs(300), start);
+    assert!(ping.due(start + Duration::from_secs(300)));
+    assert!(!ping.due(start + Duration::from_secs(301)));
+    assert!(!ping.due(start + Duration::from_secs(599)));
+    assert!(ping.due(start + Duration::from_secs(600)))
+}
+#[test]
+fn activity_postpones_ping() {
+    let start = Instant::now();
+    let mut ping = IdlePing::new(Duration::from_secs(300), start);
+    ping.record_activity(start + Duration::from_secs(200));
+    assert!(!ping.due(start + Duration::from_secs(300)));
+    assert!(ping.due(start + Duration::from_secs(500)))
+}
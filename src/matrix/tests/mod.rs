@@ -0,0 +1,6 @@
+mod dry_run_tests;
+mod idle_ping_tests;
+mod power_level_tests;
+mod profile_tests;
+mod sync_filter_tests;
+mod sync_save_tests;
@@ -0,0 +1,46 @@
+use crate::matrix::power_levels::{power_level_admins, PowerLevelRefresh};
+use ruma::events::room::power_levels::RoomPowerLevelsEventContent;
+use ruma::{int, user_id};
+use std::time::{Duration, Instant};
+
+fn power_levels() -> RoomPowerLevelsEventContent {
+    let mut power_levels = RoomPowerLevelsEventContent::new();
+    power_levels
+        .users
+        .insert(user_id!("@owner:example.com").to_owned(), int!(100));
+    power_levels
+        .users
+        .insert(user_id!("@moderator:example.com").to_owned(), int!(50));
+    power_levels
+        .users
+        .insert(user_id!("@user:example.com").to_owned(), int!(0));
+    power_levels
+}
+
+#[test]
+fn admins_at_min_power_level() {
+    let admins = power_level_admins(&power_levels(), 50);
+    assert_eq!(2, admins.len());
+    assert!(admins.contains(user_id!("@owner:example.com")));
+    assert!(admins.contains(user_id!("@moderator:example.com")))
+}
+#[test]
+fn admins_below_min_power_level_excluded() {
+    let admins = power_level_admins(&power_levels(), 100);
+    assert_eq!(1, admins.len());
+    assert!(admins.contains(user_id!("@owner:example.com")))
+}
+#[test]
+fn users_default_not_admins() {
+    let mut power_levels = power_levels();
+    power_levels.users_default = int!(100);
+    assert_eq!(1, power_level_admins(&power_levels, 100).len())
+}
+#[test]
+fn refresh_due_at_start_and_after_interval() {
+    let start = Instant::now();
+    let mut refresh = PowerLevelRefresh::new(Duration::from_secs(300));
+    assert!(refresh.due(start));
+    assert!(!refresh.due(start + Duration::from_secs(299)));
+    assert!(refresh.due(start + Duration::from_secs(300)))
+}
@@ -0,0 +1,42 @@
+use crate::matrix::profile::{profile_update, ProfileUpdate};
+use ruma::mxc_uri;
+
+#[test]
+fn unchanged() {
+    let avatar = mxc_uri!("mxc://example.com/avatar");
+    assert_eq!(
+        ProfileUpdate::default(),
+        profile_update(Some("Bot"), Some(avatar), Some("Bot"), Some(avatar))
+    )
+}
+#[test]
+fn changed() {
+    let avatar = mxc_uri!("mxc://example.com/avatar");
+    let old_avatar = mxc_uri!("mxc://example.com/old");
+    assert_eq!(
+        ProfileUpdate {
+            display_name: Some("Bot"),
+            avatar_url: Some(avatar),
+        },
+        profile_update(Some("Bot"), Some(avatar), Some("Old Bot"), Some(old_avatar))
+    )
+}
+#[test]
+fn unset_current_profile() {
+    let avatar = mxc_uri!("mxc://example.com/avatar");
+    assert_eq!(
+        ProfileUpdate {
+            display_name: Some("Bot"),
+            avatar_url: Some(avatar),
+        },
+        profile_update(Some("Bot"), Some(avatar), None, None)
+    )
+}
+#[test]
+fn not_configured() {
+    let avatar = mxc_uri!("mxc://example.com/avatar");
+    assert_eq!(
+        ProfileUpdate::default(),
+        profile_update(None, None, Some("Old Bot"), Some(avatar))
+    )
+}
@@ -0,0 +1,52 @@
+use crate::config::tests::common::listener_config;
+use crate::matrix::sync_filter::{sync_event_types, sync_filter_definition};
+use serde_json::json;
+
+#[test]
+fn default_types() {
+    assert_eq!(
+        vec!["m.room.message", "m.room.member", "m.room.name"],
+        sync_event_types(&listener_config("", ""))
+    )
+}
+#[test]
+fn all_feature_types() {
+    let config = listener_config(
+        "ban_rooms = ['!room:example.com']
+moderation_requires_encryption = true
+enable_response_redaction = true",
+        "",
+    );
+    assert_eq!(
+        vec![
+            "m.room.message",
+            "m.room.member",
+            "m.room.name",
+            "m.room.encryption",
+            "m.reaction",
+            "m.room.redaction"
+        ],
+        sync_event_types(&config)
+    )
+}
+#[test]
+fn filter_json() {
+    let types = sync_event_types(&listener_config("enable_response_redaction = true", ""));
+    assert_eq!(
+        json!({
+            "presence": { "types": [] },
+            "account_data": { "types": [] },
+            "room": {
+                "account_data": { "types": [] },
+                "ephemeral": { "types": [] },
+                "timeline": {
+                    "types": ["m.room.message", "m.room.member", "m.room.name", "m.room.redaction"]
+                },
+                "state": {
+                    "types": ["m.room.message", "m.room.member", "m.room.name", "m.room.redaction"]
+                }
+            }
+        }),
+        serde_json::to_value(sync_filter_definition(&types)).unwrap()
+    )
+}
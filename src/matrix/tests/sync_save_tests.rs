@@ -0,0 +1,27 @@
+use crate::matrix::sync_save::SyncSave;
+use std::time::{Duration, Instant};
+
+#[test]
+fn first_sync_saved() {
+    let mut save = SyncSave::new(Duration::from_secs(60));
+    assert!(save.due(Instant::now()))
+}
+#[test]
+fn saves_once_per_interval() {
+    let start = Instant::now();
+    let mut save = SyncSave::new(Duration::from_secs(60));
+    assert!(save.due(start));
+    assert!(!save.due(start + Duration::from_secs(30)));
+    assert!(!save.due(start + Duration::from_secs(59)));
+    assert!(save.due(start + Duration::from_secs(60)));
+    assert!(!save.due(start + Duration::from_secs(90)));
+    assert!(save.due(start + Duration::from_secs(125)))
+}
+#[test]
+fn zero_interval_saves_every_sync() {
+    let start = Instant::now();
+    let mut save = SyncSave::new(Duration::ZERO);
+    assert!(save.due(start));
+    assert!(save.due(start));
+    assert!(save.due(start + Duration::from_millis(1)))
+}
@@ -0,0 +1,40 @@
+//! Handler for greetings in direct messages
+
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{EventId, RoomId};
+use std::time::Instant;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Replies to a greeting in a direct message with the configured about text
+///
+/// The about text is sent at most once an hour in each room.
+pub(super) async fn about_handler(
+    event_id: &EventId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let about = match &config.direct_messages.about {
+        Some(v) => v.clone(),
+        None => return,
+    };
+    if !storage.about_reply_cooldown(room_id, Instant::now()) {
+        debug!("About text was sent recently. Skipping...");
+        return;
+    }
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(about),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
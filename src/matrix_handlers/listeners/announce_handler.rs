@@ -0,0 +1,84 @@
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::helpers::render_template;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+pub(super) async fn announce_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for announcements. Skipping...");
+        return;
+    }
+
+    let message = match text.body.split_once(' ') {
+        Some((_, m)) if !m.trim().is_empty() => {
+            render_template(m.trim(), &storage.template_data(room_id, sender))
+        }
+        _ => {
+            debug!("Announce command doesnt include a message. Skipping...");
+            return;
+        }
+    };
+
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Announce(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+pub(super) async fn unannounce_handler(
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(authorized, None, sender, room_id, storage, config, send).await {
+        debug!("Unauthorized user for announcements. Skipping...");
+        return;
+    }
+
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Unannounce,
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
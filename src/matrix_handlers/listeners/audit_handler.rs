@@ -0,0 +1,66 @@
+//! Handler for the audit log command
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Number of moderation actions shown when none is requested
+pub(super) const DEFAULT_AUDIT_TAIL: usize = 5;
+/// Largest number of moderation actions that can be requested
+pub(super) const MAX_AUDIT_TAIL: usize = 20;
+
+/// Asks the responder for the most recent moderation actions
+pub(super) async fn audit_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for audit log. Skipping...");
+        return;
+    }
+    let count = match text.body.split_whitespace().nth(1) {
+        Some(v) => match v.parse::<usize>() {
+            Ok(n) if n > 0 => n.min(MAX_AUDIT_TAIL),
+            _ => {
+                debug!(
+                    "Audit log count {} is not a positive number. Skipping...",
+                    v
+                );
+                return;
+            }
+        },
+        None => DEFAULT_AUDIT_TAIL,
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::AuditLog(count),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
@@ -1,14 +1,21 @@
-use crate::config::MatrixListenerConfig;
+use super::check_authorized;
+use crate::config::{
+    Emoji, ListenerStorage, MatrixListenerConfig, ACKNOWLEDGEMENT_REACTION, BAN_CONFIRMATION_WINDOW,
+};
 use crate::messages::{MatrixBanMessage, MatrixMessage, MatrixMessageType};
 use crate::regex::FORMATTED_USERNAME;
-use ruma::{events::room::message::TextMessageEventContent, OwnedUserId, UserId};
+use ruma::{events::room::message::TextMessageEventContent, EventId, OwnedUserId, RoomId, UserId};
+use std::time::{Instant, SystemTime};
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error, trace, warn};
 
 pub(super) async fn ban_handler(
     text: &TextMessageEventContent,
+    event_id: &EventId,
+    storage: &mut ListenerStorage,
     config: &MatrixListenerConfig,
     sender: &UserId,
+    room_id: &RoomId,
     send: &mut Sender<MatrixMessage>,
 ) {
     if config.ban_rooms.is_empty() {
@@ -16,14 +23,61 @@ pub(super) async fn ban_handler(
         return;
     }
 
-    if !config.admins.contains(sender) {
+    let authorized = ban_authorized(sender, storage, config);
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
         debug!("Unauthorized user for banning. Skipping...");
         return;
     }
 
-    let user: OwnedUserId = match text.body.split(' ').nth(1) {
+    if !moderation_allowed(room_id, storage, config) {
+        debug!("Moderation commands require an encrypted room. Rejecting...");
+        if send
+            .send(MatrixMessage {
+                room_id: Some(room_id.to_owned()),
+                message: MatrixMessageType::Notice(
+                    "Moderation commands are only accepted in encrypted rooms".to_string(),
+                ),
+                trigger_event: None,
+                thread_root: None,
+            })
+            .await
+            .is_err()
+        {
+            error!("Channel closed, unable to send mesage.")
+        }
+        return;
+    }
+
+    let args = text
+        .body
+        .trim()
+        .split_once(char::is_whitespace)
+        .map(|(_, args)| args)
+        .unwrap_or_default();
+    if args.trim() == "--confirm" {
+        let message = match storage.take_pending_ban(sender, Instant::now()) {
+            Some(v) => {
+                debug!("Pending ban confirmed. Banning...");
+                ban_message(v, config)
+            }
+            None => MatrixMessageType::Notice("No pending ban to confirm".to_string()),
+        };
+        send_ban_reply(message, event_id, room_id, config, send).await;
+        return;
+    }
+    let (users, reason) = match args.split_whitespace().next() {
         Some(v) => match UserId::parse(v) {
-            Ok(u) => u,
+            Ok(_) => ban_targets(args),
             Err(_) => {
                 error!("User was invalid format, unable to continue to ban handler");
                 return;
@@ -46,7 +100,7 @@ pub(super) async fn ban_handler(
                             return;
                         }
                     };
-                    username
+                    (vec![username], None)
                 }
                 None => {
                     warn!("No formatted body present, unable to attempt parse of user for ban. Unable to continue.");
@@ -56,35 +110,120 @@ pub(super) async fn ban_handler(
         }
     };
 
-    let reason: String = text
-        .body
-        .split(' ')
-        .skip(2)
-        .map(|s| s.to_owned() + " ")
-        .collect();
-
-    let reason = {
-        if reason.trim().is_empty() {
-            None
-        } else {
-            Some(reason)
-        }
-    };
-
     let message = MatrixBanMessage {
-        user,
+        admin: sender.to_owned(),
+        users,
         reason,
         rooms: config.ban_rooms.clone(),
     };
 
-    if send
-        .send(MatrixMessage {
-            room_id: None,
-            message: MatrixMessageType::Ban(message),
-        })
-        .await
-        .is_err()
-    {
-        error!("Channel closed, unable to send mesage.")
+    let message = hold_or_ban(message, sender, storage, config);
+    send_ban_reply(message, event_id, room_id, config, send).await;
+}
+
+/// Holds a ban until the admin confirms it if it affects more rooms than the room confirmation threshold
+pub(super) fn hold_or_ban(
+    message: MatrixBanMessage,
+    admin: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> MatrixMessageType {
+    match config.ban_room_confirmation_threshold {
+        Some(threshold) if message.rooms.len() > threshold => {
+            debug!("Ban exceeds room confirmation threshold. Holding it until confirmed...");
+            let notice = format!(
+                "This ban affects {} rooms. Send !ban --confirm within {} seconds to perform it",
+                message.rooms.len(),
+                BAN_CONFIRMATION_WINDOW.as_secs()
+            );
+            storage.set_pending_ban(admin, message, Instant::now());
+            MatrixMessageType::Notice(notice)
+        }
+        _ => ban_message(message, config),
+    }
+}
+
+/// Sends the reply to a ban command, reacting to the command if it bans right away and reactions are enabled
+async fn send_ban_reply(
+    message: MatrixMessageType,
+    event_id: &EventId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let acknowledge = config.use_reactions && matches!(message, MatrixMessageType::Ban(_));
+    let mut messages = vec![message];
+    if acknowledge {
+        messages.push(MatrixMessageType::React {
+            event_id: event_id.to_owned(),
+            key: Emoji::Unicode(ACKNOWLEDGEMENT_REACTION.to_string()),
+        });
+    }
+    for message in messages {
+        if send
+            .send(MatrixMessage {
+                room_id: Some(room_id.to_owned()),
+                message,
+                trigger_event: None,
+                thread_root: None,
+            })
+            .await
+            .is_err()
+        {
+            error!("Channel closed, unable to send mesage.")
+        }
     }
 }
+
+/// Asks for confirmation of a ban if it targets more users than the confirmation threshold
+fn ban_message(message: MatrixBanMessage, config: &MatrixListenerConfig) -> MatrixMessageType {
+    if message.users.len() > config.ban_confirmation_threshold {
+        debug!("Ban exceeds confirmation threshold. Asking for confirmation...");
+        MatrixMessageType::ConfirmBan(message)
+    } else {
+        MatrixMessageType::Ban(message)
+    }
+}
+
+/// Splits the arguments of a ban command into the user IDs it starts with and the reason after them
+///
+/// The reason keeps its internal spacing and is None if nothing follows the user IDs.
+fn ban_targets(args: &str) -> (Vec<OwnedUserId>, Option<String>) {
+    let mut users = Vec::new();
+    let mut rest = args.trim_start();
+    while let Some(word) = rest.split_whitespace().next() {
+        match UserId::parse(word) {
+            Ok(u) => users.push(u),
+            Err(_) => break,
+        }
+        rest = rest[word.len()..].trim_start();
+    }
+    let reason = rest.trim_end();
+    let reason = if reason.is_empty() {
+        None
+    } else {
+        Some(reason.to_string())
+    };
+    (users, reason)
+}
+
+/// Checks if a user can ban, which is limited to the configured ban authorized users if there are any
+pub(super) fn ban_authorized(
+    user: &UserId,
+    storage: &ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> bool {
+    match &config.ban_authorized_users {
+        Some(v) => v.contains(user),
+        None => storage.is_admin(user, &config.admins, SystemTime::now()),
+    }
+}
+
+/// Checks if moderation commands sent in the supplied room meet the configured requirements
+pub(super) fn moderation_allowed(
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> bool {
+    !config.moderation_requires_encryption || storage.encrypted_rooms.contains(room_id)
+}
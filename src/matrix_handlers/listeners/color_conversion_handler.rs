@@ -0,0 +1,31 @@
+//! Handler for the color conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::convert_color;
+use crate::messages::MatrixMessage;
+use crate::regex::COLOR_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based color conversion handler that replies with a color in hex, rgb(), and hsl() notation
+pub(super) async fn color_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let color = match COLOR_CONVERSION.captures(&text.body) {
+        Some(v) => v[1].to_string(),
+        None => {
+            debug!("No color found. No reply will be constructed.");
+            return;
+        }
+    };
+    let result = convert_color(&color).map(|v| v.to_string());
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,87 @@
+//! Builds the index of commands the bot posts and pins when it joins a room
+
+use crate::config::MatrixListenerConfig;
+use ruma::RoomId;
+
+/// Commands anyone can use and what they do.
+const COMMANDS: [(&str, &str); 12] = [
+    ("convert", "converts units, dates, times, colors, and more"),
+    ("encode", "encodes text as base64, hex, or URL encoding"),
+    ("decode", "decodes base64, hex, or URL encoded text"),
+    ("help", "shows help for a topic"),
+    ("link", "links a configured URL"),
+    ("ping", "shows the bot's latency and uptime"),
+    ("version", "shows which build of the bot is running"),
+    ("pingtest", "shows who a group ping would ping"),
+    ("karma", "shows the karma of users in the room"),
+    ("tz", "sets your timezone"),
+    ("time", "shows the time for you or another user"),
+    (
+        "correctme",
+        "turns spelling corrections of your messages off or on",
+    ),
+];
+
+/// Commands only admins can use.
+const ADMIN_COMMANDS: [&str; 20] = [
+    "announce",
+    "unannounce",
+    "correction",
+    "stats",
+    "auditlog",
+    "grant",
+    "revoke",
+    "reload",
+    "configcheck",
+    "raw",
+    "maintenance",
+    "ignore",
+    "unignore",
+    "ignorelist",
+    "roles",
+    "warn",
+    "warnings",
+    "clearwarnings",
+    "ban",
+    "unban",
+];
+
+/// Checks if the feature behind a command is enabled in the room.
+fn feature_enabled(command: &str, room_id: &RoomId, config: &MatrixListenerConfig) -> bool {
+    match command {
+        "help" | "ping" => config.help_rooms.is_empty() || config.help_rooms.contains(room_id),
+        "karma" => config.enable_karma,
+        "correctme" | "correction" => {
+            config.enable_corrections && !config.correction_exclusion.contains(room_id)
+        }
+        "ban" | "unban" => !config.ban_rooms.is_empty(),
+        "roles" => config.reaction_roles.is_some(),
+        "warn" | "warnings" | "clearwarnings" => config.warnings.is_some(),
+        _ => true,
+    }
+}
+
+/// Lists the commands that can be used in a room, leaving out commands that aren't allowed there
+/// and commands whose feature is disabled, with the room's command prefix.
+pub(super) fn command_index(room_id: &RoomId, config: &MatrixListenerConfig) -> String {
+    let prefix = config.command_prefix(room_id);
+    let available = |command: &str| {
+        config.command_allowed(room_id, command) && feature_enabled(command, room_id, config)
+    };
+    let mut index = String::from("Commands in this room:");
+    for (command, description) in COMMANDS.iter().filter(|(c, _)| available(c)) {
+        index.push_str(&format!("\n{}{} {}", prefix, command, description));
+    }
+    let admin_commands: Vec<String> = ADMIN_COMMANDS
+        .iter()
+        .filter(|c| available(c))
+        .map(|c| format!("{}{}", prefix, c))
+        .collect();
+    if !admin_commands.is_empty() {
+        index.push_str(&format!(
+            "\nAdmins can also use {}",
+            admin_commands.join(", ")
+        ));
+    }
+    index
+}
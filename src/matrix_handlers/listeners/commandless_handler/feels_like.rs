@@ -0,0 +1,84 @@
+//! Calculates how hot or cold temperatures with humidity or wind feel and adds them to response data
+
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{clean_text, heat_index, round, wind_chill, MatrixNoticeResponse};
+use crate::regex::FEELS_LIKE;
+use ruma::events::room::message::TextMessageEventContent;
+use tracing::{debug, trace};
+
+/// Adds the heat index or wind chill of temperatures paired with a humidity or wind speed to the supplied BotResponseNotice
+pub fn feels_like(
+    text: &TextMessageEventContent,
+    config: &MatrixListenerConfig,
+    notice_response: &mut MatrixNoticeResponse,
+) {
+    let body = match &text.formatted {
+        Some(v) => clean_text(&v.body),
+        None => text.body.clone(),
+    };
+    let results: Vec<String> = FEELS_LIKE
+        .captures_iter(&body.to_lowercase())
+        .filter_map(|cap| process_capture(&cap, config))
+        .collect();
+    if results.is_empty() {
+        debug!("No temperatures within the supported ranges. Doing nothing.");
+    } else {
+        notice_response.set_feels_like(results);
+    }
+}
+
+/// Calculates the heat index or wind chill of a feels like regex capture and formats it in the unit of the temperature
+fn process_capture(capture: &regex::Captures, config: &MatrixListenerConfig) -> Option<String> {
+    trace!("Capture being processed is {:?}", capture);
+    let temperature = capture[1].parse::<f64>().ok()?;
+    let value = capture[3].parse::<f64>().ok()?;
+    let fahrenheit = capture[2].ends_with('f');
+    let celsius = if fahrenheit {
+        (temperature - 32.0) * 5.0 / 9.0
+    } else {
+        temperature
+    };
+    let (condition, result) = match capture.get(4).map(|m| m.as_str()) {
+        Some(unit) => {
+            let speed = if unit == "mph" {
+                value * 1.609_344
+            } else {
+                value
+            };
+            (
+                format!("{}{} wind", value, unit),
+                wind_chill(celsius, speed),
+            )
+        }
+        None => (format!("{}% humidity", value), heat_index(celsius, value)),
+    };
+    let result = match result {
+        Some(v) => v,
+        None => {
+            debug!(
+                "Capture {:?} is outside of the supported range",
+                &capture[0]
+            );
+            return None;
+        }
+    };
+    let (result, unit) = if fahrenheit {
+        (result * 9.0 / 5.0 + 32.0, "°F")
+    } else {
+        (result, "°C")
+    };
+    let precision = config
+        .unit_conversion_precision
+        .get("temperature")
+        .copied()
+        .unwrap_or(config.default_unit_conversion_precision);
+    Some(format!(
+        "{}{} with {} feels like {:.*}{}",
+        temperature,
+        unit,
+        condition,
+        precision,
+        round(result, precision, config.rounding_mode),
+        unit
+    ))
+}
@@ -1,21 +1,35 @@
 //! Performs search of issues and pulls in message text and builds proper response
 
-use crate::config::MatrixListenerConfig;
-use crate::helpers::{clean_text, MatrixNoticeResponse};
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::helpers::{clean_text, typo_correction, MatrixNoticeResponse};
+use crate::matrix_handlers::listeners::send_typing;
+use crate::messages::MatrixMessage;
 use crate::queries::issue_or_pull::IssueOrPullRepositoryIssueOrPullRequest::{Issue, PullRequest};
 use crate::queries::*;
 use crate::regex::GITHUB_SEARCH;
 use graphql_client::GraphQLQuery;
-use reqwest::{header, Url};
-use ruma::events::room::message::TextMessageEventContent;
-use tracing::{debug, error, trace};
+use reqwest::{header, RequestBuilder, Url};
+use ruma::{events::room::message::TextMessageEventContent, RoomId};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tokio::task::JoinSet;
+use tracing::{debug, error, trace, warn};
+
+/// Reply sent once per pause when GitHub searches are paused by a rate limit
+const RATE_LIMITED_NOTICE: &str = "GitHub rate limited, try later";
 
 /// Searches and links found issues or pulls requested and builds response text
+///
+/// Shows the bot as typing in the room while searching.
 pub async fn github_search(
     text: &TextMessageEventContent,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
     config: &MatrixListenerConfig,
     api_client: &reqwest::Client,
     notice_response: &mut MatrixNoticeResponse,
+    send: &mut Sender<MatrixMessage>,
 ) {
     let mut repos_to_search = Vec::new();
     match &text.formatted {
@@ -38,118 +52,200 @@ pub async fn github_search(
         }
     }
     let repos_to_search = repos_to_search;
-    let mut searches = Vec::new();
-    for (repo, number) in repos_to_search {
-        match number.parse::<i64>() {
-            Ok(n) => match config.repos.get(&repo.to_lowercase()) {
-                Some(r) => {
-                    let index = match r.find('/') {
-                        Some(v) => v,
-                        None => {
-                            debug!("No / was found in repo/owner pair {:?}. Unable to search such a thing.", r);
-                            continue;
+    // The lock is released before searching so it isn't held across requests
+    let (searches, suggestions) = {
+        let mut searches = Vec::new();
+        let mut suggestions = Vec::new();
+        let settings = match config.link_settings.read() {
+            Ok(v) => v,
+            Err(_) => {
+                error!("Link lock is poisoned. Ignoring...");
+                return;
+            }
+        };
+        for (repo, number) in repos_to_search {
+            match number.parse::<i64>() {
+                Ok(n) => match settings.repos.get(&repo.to_lowercase()) {
+                    Some(r) => {
+                        let index = match r.find('/') {
+                            Some(v) => v,
+                            None => {
+                                debug!("No / was found in repo/owner pair {:?}. Unable to search such a thing.", r);
+                                continue;
+                            }
+                        };
+                        let (owner, repo) = r.split_at(index);
+                        let repo = repo.replace('/', "");
+                        searches.push((owner.to_string(), repo.to_string(), n))
+                    }
+                    None => {
+                        debug!("Repo {:?} not found", repo);
+                        // Close misses are only suggested so a typo never links the wrong repo
+                        if let Some(v) = typo_correction(&repo, settings.repos.keys()) {
+                            if !suggestions.iter().any(|s| s == v) {
+                                suggestions.push(v.to_string());
+                            }
                         }
-                    };
-                    let (owner, repo) = r.split_at(index);
-                    let repo = repo.replace('/', "");
-                    searches.push((owner.to_string(), repo.to_string(), n))
-                }
-                None => {
-                    debug!("Repo {:?} not found", repo);
-                    continue;
+                        continue;
+                    }
+                },
+                Err(e) => {
+                    error!(
+                        "Issue or pull number unable to be parsed. Error is {:?}, quantity is {:?}",
+                        e, number
+                    );
                 }
-            },
-            Err(e) => {
-                error!(
-                    "Issue or pull number unable to be parsed. Error is {:?}, quantity is {:?}",
-                    e, number
-                );
             }
         }
+        (searches, suggestions)
+    };
+    if !suggestions.is_empty() {
+        debug!("Suggesting repos {:?} for unknown repos", suggestions);
+        notice_response.set_gh_suggestions(suggestions);
     }
-    let searches = searches;
     debug!("Queued searches: {:?}", searches);
     if searches.is_empty() {
         debug!("No searches found after parsing numbers. No searches will be built.");
         return;
     }
-    let mut results = Vec::new();
-    for (owner, name, number) in searches {
+    if storage.github_paused(SystemTime::now()) {
+        debug!("GitHub searches are paused by a rate limit. Skipping...");
+        if storage.github_pause_notice() {
+            notice_response.set_gh_notice(RATE_LIMITED_NOTICE.to_string());
+        }
+        return;
+    }
+    send_typing(room_id, true, send).await;
+    let results = search(searches, storage, config, api_client, notice_response).await;
+    // Typing is stopped before handling results so failed searches stop it as well
+    send_typing(room_id, false, send).await;
+    if results.is_empty() {
+        error!("No search resulted returned. Doing nothing");
+    } else {
+        notice_response.set_gh_results(results)
+    }
+}
+
+/// Looks up each owner, repo, and number, returning the urls of the issues and pulls found
+///
+/// Searches are made at the same time, and identical searches share a single request.
+async fn search(
+    searches: Vec<IssueOrPullSearch>,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    api_client: &reqwest::Client,
+    notice_response: &mut MatrixNoticeResponse,
+) -> Vec<Url> {
+    let mut lookups = JoinSet::new();
+    for (index, search) in searches.into_iter().enumerate() {
+        let (owner, name, number) = search.clone();
         let query = IssueOrPull::build_query(issue_or_pull::Variables {
             name,
             owner,
             number,
         });
-        let response_body = match api_client
+        let request = api_client
             .post("https://api.github.com/graphql")
             .bearer_auth(config.gh_access_token.clone())
             .header(header::USER_AGENT, config.user_agent.clone())
-            .json(&query)
-            .send()
-            .await
-        {
-            Ok(r) => {
-                let response_body: graphql_client::Response<issue_or_pull::ResponseData> =
-                    match r.json().await {
-                        Ok(b) => b,
-                        Err(e) => {
-                            error!("No response body found. Error is {:?}", e);
-                            continue;
-                        }
-                    };
-                response_body
+            .json(&query);
+        let in_flight = Arc::clone(&storage.github_lookups);
+        lookups.spawn(async move { (index, in_flight.run(search, || lookup(request)).await) });
+    }
+    let mut finished = Vec::new();
+    while let Some(v) = lookups.join_next().await {
+        match v {
+            Ok(v) => finished.push(v),
+            Err(e) => error!("Search task failed. Error is {:?}", e),
+        }
+    }
+    // Results are reported in the order they were asked for
+    finished.sort_by_key(|(index, _)| *index);
+
+    let mut results = Vec::new();
+    for (_, lookup) in finished {
+        if let Some(reset) = lookup.rate_limit_reset {
+            storage.pause_github(reset);
+            if lookup.rate_limited {
+                warn!(
+                    "GitHub rate limited the bot. Pausing searches until {:?}",
+                    reset
+                );
+                if storage.github_pause_notice() {
+                    notice_response.set_gh_notice(RATE_LIMITED_NOTICE.to_string());
+                }
+                break;
             }
-            Err(e) => {
-                error!("Query failed, Error is {:?}", e);
-                continue;
+            warn!(
+                "GitHub rate limit used up. Pausing searches until {:?}",
+                reset
+            );
+        }
+        if let Some(v) = lookup.url {
+            results.push(v)
+        }
+    }
+    results
+}
+
+/// Sends a prepared issue or pull query and reads the url of the result from the response
+async fn lookup(request: RequestBuilder) -> IssueOrPullLookup {
+    let mut lookup = IssueOrPullLookup::default();
+    let response_body = match request.send().await {
+        Ok(r) => {
+            if let Some(reset) = rate_limit_reset(r.status(), r.headers(), SystemTime::now()) {
+                lookup.rate_limit_reset = Some(reset);
+                if r.status().is_client_error() {
+                    lookup.rate_limited = true;
+                    return lookup;
+                }
             }
-        };
-        let response_data = match response_body.data {
-            Some(d) => match d.repository {
-                Some(r) => match r.issue_or_pull_request {
-                    Some(v) => v,
-                    None => {
-                        error!("Missing issue or pull request data");
-                        continue;
+            let response_body: graphql_client::Response<issue_or_pull::ResponseData> =
+                match r.json().await {
+                    Ok(b) => b,
+                    Err(e) => {
+                        error!("No response body found. Error is {:?}", e);
+                        return lookup;
                     }
-                },
+                };
+            response_body
+        }
+        Err(e) => {
+            error!("Query failed, Error is {:?}", e);
+            return lookup;
+        }
+    };
+    let response_data = match response_body.data {
+        Some(d) => match d.repository {
+            Some(r) => match r.issue_or_pull_request {
+                Some(v) => v,
                 None => {
-                    error!("Missing repository data");
-                    continue;
+                    error!("Missing issue or pull request data");
+                    return lookup;
                 }
             },
             None => {
-                error!("Missing response data");
-                continue;
-            }
-        };
-
-        match response_data {
-            Issue(v) => {
-                let result = "https://github.com".to_string() + &v.resource_path + "\n";
-                match Url::parse(&result) {
-                    Ok(v) => results.push(v),
-                    Err(e) => error!(
-                        "Unable to parse result {:?} to Url due to error {:?}",
-                        result, e
-                    ),
-                }
-            }
-            PullRequest(v) => {
-                let result = "https://github.com".to_string() + &v.resource_path + "\n";
-                match Url::parse(&result) {
-                    Ok(v) => results.push(v),
-                    Err(e) => error!(
-                        "Unable to parse result {:?} to Url due to error {:?}",
-                        result, e
-                    ),
-                }
+                error!("Missing repository data");
+                return lookup;
             }
+        },
+        None => {
+            error!("Missing response data");
+            return lookup;
         }
+    };
+
+    let resource_path = match response_data {
+        Issue(v) => v.resource_path,
+        PullRequest(v) => v.resource_path,
+    };
+    let result = "https://github.com".to_string() + &resource_path + "\n";
+    match Url::parse(&result) {
+        Ok(v) => lookup.url = Some(v),
+        Err(e) => error!(
+            "Unable to parse result {:?} to Url due to error {:?}",
+            result, e
+        ),
     }
-    if results.is_empty() {
-        error!("No search resulted returned. Doing nothing");
-    } else {
-        notice_response.set_gh_results(results)
-    }
+    lookup
 }
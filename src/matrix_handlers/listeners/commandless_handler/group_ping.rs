@@ -1,36 +1,56 @@
 //! Performs group pings based on message text and builds proper response
+//!
+//! The reserved group %here pings the members currently joined to the room. They're fetched by the
+//! listener before the message is handled and cached in storage for a short time, so %here pings
+//! nobody if they couldn't be fetched. Groups can also include the members of rooms, which are
+//! fetched and cached the same way when the group is pinged.
 
-use crate::config::MatrixListenerConfig;
+use crate::config::{GroupPings, ListenerStorage, MatrixListenerConfig};
 use crate::helpers::{clean_text, MatrixFormattedTextResponse};
 use crate::regex::GROUP_PING;
-use ruma::{events::room::message::TextMessageEventContent, OwnedUserId, UserId};
+use ruma::{
+    events::room::message::TextMessageEventContent, OwnedRoomAliasId, OwnedUserId, RoomId, UserId,
+};
 use std::collections::HashSet;
-use tracing::{debug, error, trace};
+use std::time::Instant;
+use tracing::{debug, error, trace, warn};
 
 /// Finds requested users to ping and builds response text
 pub fn group_ping(
     text: &TextMessageEventContent,
     sender: &UserId,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
     config: &MatrixListenerConfig,
     text_response: &mut MatrixFormattedTextResponse,
 ) {
     let mut users: HashSet<OwnedUserId> = HashSet::new();
-    if !config.group_ping_users.contains(sender) {
+    let group_pings = match config.group_pings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Group ping lock is poisoned. Ignoring...");
+            return;
+        }
+    };
+    if !group_pings.users.contains(sender) {
         debug!("User not authorized for group pings. Ignoring...");
         return;
     }
+    let now = Instant::now();
     match &text.formatted {
         Some(v) => {
             let clean_text = clean_text(&v.body);
             if GROUP_PING.is_match(&clean_text) {
-                determine_users(config, &clean_text, &mut users)
+                determine_users(&group_pings, room_id, storage, now, &clean_text, &mut users)
             } else {
                 debug!("There are no remaining matches after cleaning tags. Doing nothing.");
                 return;
             }
         }
-        None => determine_users(config, &text.body, &mut users),
+        None => determine_users(&group_pings, room_id, storage, now, &text.body, &mut users),
     }
+    // The bot is a member of the rooms whose members it pings, but never pings itself
+    users.remove(&config.mx_uname);
     if users.is_empty() {
         debug!("No users to ping after processing.");
     } else {
@@ -42,21 +62,74 @@ pub fn group_ping(
     }
 }
 
-fn determine_users(config: &MatrixListenerConfig, text: &str, users: &mut HashSet<OwnedUserId>) {
+/// Returns true if an authorized user pings %here, so the room's members are needed
+pub fn requests_here(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    config: &MatrixListenerConfig,
+) -> bool {
+    pinged_groups(text, sender, config)
+        .iter()
+        .any(|group| group == "here")
+}
+
+/// Returns the rooms whose members are part of the groups an authorized user pings
+pub fn pinged_rooms(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    config: &MatrixListenerConfig,
+) -> HashSet<OwnedRoomAliasId> {
+    let groups = pinged_groups(text, sender, config);
+    match config.group_pings.read() {
+        Ok(v) => groups.iter().flat_map(|group| v.rooms_of(group)).collect(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+/// Returns the names of the groups pinged in a message, or none if the sender can't group ping
+fn pinged_groups(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    config: &MatrixListenerConfig,
+) -> Vec<String> {
+    let authorized = match config.group_pings.read() {
+        Ok(v) => v.users.contains(sender),
+        Err(_) => false,
+    };
+    if !authorized {
+        return Vec::new();
+    }
+    GROUP_PING
+        .captures_iter(&text.body.to_lowercase())
+        .map(|cap| cap[1].to_string())
+        .collect()
+}
+
+fn determine_users(
+    group_pings: &GroupPings,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    now: Instant,
+    text: &str,
+    users: &mut HashSet<OwnedUserId>,
+) {
     for cap in GROUP_PING.captures_iter(&text.to_lowercase()) {
         trace!("{:?}", cap);
-        if cap[1].eq("all") {
-            for user in config.group_pings.values().flatten() {
-                users.insert(user.clone());
+        if &cap[1] == "here" {
+            match storage.room_members(room_id, now) {
+                Some(v) => users.extend(v.iter().cloned()),
+                None => debug!("Members of the room are unknown. Skipping %here..."),
             }
-        } else {
-            match config.group_pings.get(&cap[1]) {
-                Some(v) => {
-                    for user in v {
-                        users.insert(user.clone());
-                    }
-                }
-                None => error!("Somehow lost group between regex match and insertion!"),
+            continue;
+        }
+        match group_pings.members(&cap[1]) {
+            Some(v) => users.extend(v),
+            None => error!("Somehow lost group between regex match and insertion!"),
+        }
+        for room in group_pings.rooms_of(&cap[1]) {
+            match storage.alias_members(&room, now) {
+                Some(v) => users.extend(v.iter().cloned()),
+                None => warn!("Members of {} are unknown. Skipping them...", room),
             }
         }
     }
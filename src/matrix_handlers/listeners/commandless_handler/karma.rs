@@ -0,0 +1,55 @@
+//! Tracks karma given to users with "@user++", "@user--" and "thanks @user"
+
+use crate::config::ListenerStorage;
+use crate::regex::{KARMA, THANKS};
+use ruma::{events::room::message::TextMessageEventContent, OwnedUserId, RoomId, UserId};
+use std::time::Instant;
+use tracing::{debug, trace};
+
+/// Maximum number of users whose karma a single message can change
+const MAX_KARMA_CHANGES: usize = 3;
+
+/// Changes the karma of users mentioned in the message, at most once per user
+pub fn karma(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    now: Instant,
+) {
+    let mut changes: Vec<(OwnedUserId, i64)> = Vec::new();
+    let karma = KARMA
+        .captures_iter(&text.body)
+        .map(|cap| (cap[1].to_string(), if &cap[2] == "++" { 1 } else { -1 }));
+    let thanks = THANKS
+        .captures_iter(&text.body)
+        .map(|cap| (cap[1].to_string(), 1));
+    for (user, change) in karma.chain(thanks) {
+        let user = match UserId::parse(user.as_str()) {
+            Ok(v) => v,
+            Err(_) => {
+                debug!("Karma target {} is not a valid user id. Skipping...", user);
+                continue;
+            }
+        };
+        if user == sender {
+            trace!("User attempted to change their own karma. Skipping...");
+            continue;
+        }
+        if changes.iter().any(|(u, _)| *u == user) {
+            continue;
+        }
+        if changes.len() == MAX_KARMA_CHANGES {
+            debug!("Too many karma changes in one message. Ignoring the rest.");
+            break;
+        }
+        changes.push((user, change));
+    }
+    for (user, change) in changes {
+        if storage.change_karma(room_id, sender, &user, change, now) {
+            debug!("Changed karma of {} by {}", user, change);
+        } else {
+            debug!("Karma change for {} is on cooldown. Skipping...", user);
+        }
+    }
+}
@@ -13,6 +13,13 @@ pub fn link_url(
     config: &MatrixListenerConfig,
     notice_response: &mut MatrixNoticeResponse,
 ) {
+    let settings = match config.link_settings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Link lock is poisoned. Ignoring...");
+            return;
+        }
+    };
     let mut links: Vec<String> = Vec::new();
     match &text.formatted {
         Some(v) => {
@@ -20,8 +27,8 @@ pub fn link_url(
             if LINK_URL.is_match(&clean_text) {
                 for cap in LINK_URL.captures_iter(&clean_text.to_lowercase()) {
                     trace!("{:?}", cap);
-                    if config.linkers.contains(&cap[1].to_lowercase()) {
-                        match config.links.get(&cap[2].to_string()) {
+                    if settings.linkers.contains(&cap[1].to_lowercase()) {
+                        match settings.links.get(&cap[2].to_string()) {
                             Some(v) => {
                                 links.push(v.to_string())
                             }
@@ -39,8 +46,8 @@ pub fn link_url(
         None => {
             for cap in LINK_URL.captures_iter(&text.body.to_lowercase()) {
                 trace!("{:?}", cap);
-                if config.linkers.contains(&cap[1].to_lowercase()) {
-                    match config.links.get(&cap[2].to_string()) {
+                if settings.linkers.contains(&cap[1].to_lowercase()) {
+                    match settings.links.get(&cap[2].to_string()) {
                         Some(v) => {
                             links.push(v.to_string())
                         }
@@ -1,7 +1,9 @@
 //! Contains handlers and response functions for text based non-command events
 
+mod feels_like;
 mod github_search;
 mod group_ping;
+mod karma;
 mod link_url;
 mod spellcheck;
 mod text_expansion;
@@ -10,26 +12,91 @@ mod unit_conversion;
 use crate::config::{ListenerStorage, MatrixListenerConfig};
 use crate::helpers::{check_format, MatrixFormattedTextResponse, MatrixNoticeResponse};
 use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
-use crate::regex::{GITHUB_SEARCH, GROUP_PING, LINK_URL, TEXT_EXPANSION, UNIT_CONVERSION};
+use crate::regex::{
+    FEELS_LIKE, GITHUB_SEARCH, GROUP_PING, KARMA, LINK_URL, TEXT_EXPANSION, THANKS, UNIT_CONVERSION,
+};
+use feels_like::feels_like;
 use github_search::github_search;
 use group_ping::group_ping;
+pub(super) use group_ping::{pinged_rooms, requests_here};
+use karma::karma;
 use link_url::link_url;
 use ruma::{
-    events::room::message::{Relation, TextMessageEventContent},
-    RoomId, UserId,
+    events::room::message::{Relation, RoomMessageEventContent, TextMessageEventContent},
+    EventId, RoomId, UserId,
 };
-use spellcheck::spellcheck;
-use std::time::SystemTime;
+use spellcheck::{correction, spellcheck};
+use std::time::{Instant, SystemTime};
 use text_expansion::text_expansion;
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error, trace};
 use unit_conversion::unit_conversion;
 
+/// Handler for edited text based non-command events. Only performs unit conversions
+///
+/// Edits the conversion the bot sent for the original message instead of sending another one.
+/// Conversions are limited like they are for new messages.
+pub(super) async fn commandless_edit_handler(
+    text: &TextMessageEventContent,
+    original_event_id: &EventId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if storage.is_direct_message(room_id) && !config.direct_messages.unit_conversions {
+        trace!("Unit conversions are disabled in direct messages");
+        return;
+    }
+    let passive = config.passive_conversions.contains(room_id);
+    let conversions_enabled = if passive {
+        storage.passive_conversion_cooldown(room_id, config.passive_conversion_cooldown)
+    } else {
+        config.enable_unit_conversions
+    };
+    if !conversions_enabled || !UNIT_CONVERSION.is_match(&text.body) {
+        trace!("No conversions to perform on edit");
+        return;
+    }
+    if let Err(e) = check_format(text.formatted.as_ref().map(|f| &f.format)) {
+        error!("{}", e);
+        return;
+    }
+    let mut notice_response = MatrixNoticeResponse::default();
+    debug!("Entering commandless unit conversion path for edit");
+    unit_conversion(text, room_id, Some(storage), config, &mut notice_response);
+    if notice_response.is_some() {
+        storage.activity.conversions += 1;
+        if passive {
+            storage
+                .last_passive_conversion_time
+                .insert(room_id.to_owned(), SystemTime::now());
+        }
+    }
+    if notice_response.is_some()
+        && send
+            .send(MatrixMessage {
+                room_id: Some(room_id.to_owned()),
+                message: MatrixMessageType::Edit(
+                    original_event_id.to_owned(),
+                    RoomMessageEventContent::notice_plain(notice_response.to_string()),
+                ),
+                trigger_event: Some(original_event_id.to_owned()),
+                thread_root: None,
+            })
+            .await
+            .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
 /// Handler for all text based non-command events
 #[allow(clippy::too_many_arguments)]
 pub(super) async fn commandless_handler(
     text: &TextMessageEventContent,
     relates_to: Option<&Relation>,
+    event_id: &EventId,
     sender: &UserId,
     room_id: &RoomId,
     storage: &mut ListenerStorage,
@@ -45,28 +112,90 @@ pub(super) async fn commandless_handler(
             Ok(_) => {
                 let mut notice_response = MatrixNoticeResponse::default();
                 let mut text_response = MatrixFormattedTextResponse::default();
-                if UNIT_CONVERSION.is_match(&text.body) && config.enable_unit_conversions {
+                text_response.set_format(config.group_ping_format);
+                let dm = storage.is_direct_message(room_id);
+                let dm_policy = &config.direct_messages;
+                let (repos_configured, links_configured) = match config.link_settings.read() {
+                    Ok(v) => (
+                        !v.repos.is_empty(),
+                        !v.links.is_empty() && !v.linkers.is_empty(),
+                    ),
+                    Err(_) => {
+                        error!("Link lock is poisoned. Ignoring...");
+                        (false, false)
+                    }
+                };
+                let passive = config.passive_conversions.contains(room_id);
+                let conversions_enabled = if passive {
+                    storage.passive_conversion_cooldown(room_id, config.passive_conversion_cooldown)
+                } else {
+                    config.enable_unit_conversions
+                };
+                if UNIT_CONVERSION.is_match(&text.body)
+                    && conversions_enabled
+                    && (!dm || dm_policy.unit_conversions)
+                {
                     debug!("Entering commandless unit conversion path");
-                    unit_conversion(text, config, &mut notice_response);
+                    unit_conversion(text, room_id, Some(storage), config, &mut notice_response);
+                    if FEELS_LIKE.is_match(&text.body.to_lowercase()) {
+                        debug!("Entering commandless feels like path");
+                        feels_like(text, config, &mut notice_response);
+                    }
+                    if notice_response.is_some() {
+                        storage.activity.conversions += 1;
+                    }
+                    if passive && notice_response.is_some() {
+                        storage
+                            .last_passive_conversion_time
+                            .insert(room_id.to_owned(), SystemTime::now());
+                    }
                 }
-                if GITHUB_SEARCH.is_match(&text.body) && !config.repos.is_empty() {
+                if GITHUB_SEARCH.is_match(&text.body)
+                    && repos_configured
+                    && (!dm || dm_policy.github_search)
+                {
                     debug!("Entering commandless github search path");
-                    github_search(text, config, api_client, &mut notice_response).await;
+                    github_search(
+                        text,
+                        room_id,
+                        storage,
+                        config,
+                        api_client,
+                        &mut notice_response,
+                        send,
+                    )
+                    .await;
                 }
-                if LINK_URL.is_match(&text.body)
-                    && !config.links.is_empty()
-                    && !config.linkers.is_empty()
-                {
+                if LINK_URL.is_match(&text.body) && links_configured && (!dm || dm_policy.links) {
                     debug!("Entering commandless url linking path");
                     link_url(text, config, &mut notice_response);
                 }
-                if GROUP_PING.is_match(&text.body) {
-                    debug!("Entering commandless group ping path");
-                    group_ping(text, sender, config, &mut text_response);
+                if GROUP_PING.is_match(&text.body) && (!dm || dm_policy.group_pings) {
+                    let now = SystemTime::now();
+                    if config
+                        .group_ping_cooldown
+                        .is_none_or(|v| storage.group_ping_cooldown(sender, v, now))
+                    {
+                        debug!("Entering commandless group ping path");
+                        group_ping(text, sender, room_id, storage, config, &mut text_response);
+                        if text_response.is_some() {
+                            storage.last_group_ping_time.insert(sender.to_owned(), now);
+                        }
+                    } else {
+                        debug!("User is on group ping cooldown. Ignoring...");
+                    }
                 }
-                if TEXT_EXPANSION.is_match(&text.body) {
+                if TEXT_EXPANSION.is_match(&text.body) && (!dm || dm_policy.text_expansions) {
                     debug!("Entering commandless text expansion path");
-                    text_expansion(text, config, &mut notice_response);
+                    let data = storage.template_data(room_id, sender);
+                    text_expansion(text, &data, config, &mut notice_response);
+                }
+                if config.enable_karma
+                    && (!dm || dm_policy.karma)
+                    && (KARMA.is_match(&text.body) || THANKS.is_match(&text.body))
+                {
+                    debug!("Entering commandless karma path");
+                    karma(text, sender, room_id, storage, Instant::now());
                 }
 
                 let notice_response = notice_response;
@@ -77,6 +206,8 @@ pub(super) async fn commandless_handler(
                         .send(MatrixMessage {
                             room_id: Some(room_id.to_owned()),
                             message: MatrixMessageType::Notice(notice_response.to_string()),
+                            trigger_event: Some(event_id.to_owned()),
+                            thread_root: None,
                         })
                         .await
                         .is_err()
@@ -93,6 +224,8 @@ pub(super) async fn commandless_handler(
                         .send(MatrixMessage {
                             room_id: Some(room_id.to_owned()),
                             message: MatrixMessageType::FormattedText(message),
+                            trigger_event: Some(event_id.to_owned()),
+                            thread_root: None,
                         })
                         .await
                         .is_err()
@@ -101,17 +234,35 @@ pub(super) async fn commandless_handler(
                     }
                 }
                 if config.enable_corrections
+                    && (!dm || dm_policy.corrections)
                     && relates_to.is_none()
-                    && storage.correction_time_cooldown(room_id)
+                    && !storage.correction_opt_outs.contains(sender)
+                    && storage
+                        .correction_time_cooldown(room_id, config.correction_cooldown(room_id))
                     && !config.correction_exclusion.contains(room_id)
                     && !notice_response.is_some()
                     && !text_response.is_some()
                 {
-                    if let Some(v) = spellcheck(text, sender, config) {
+                    if let Some((v, spelling)) = spellcheck(text, sender, config) {
+                        if let Some(window) = config.correction_batch_window {
+                            debug!("Batching correction of {} for {}", spelling, sender);
+                            storage.queue_correction(
+                                room_id,
+                                sender,
+                                event_id,
+                                &spelling,
+                                window,
+                                Instant::now(),
+                            );
+                            return;
+                        }
+                        let now = Instant::now();
                         match send
                             .send(MatrixMessage {
                                 room_id: Some(room_id.to_owned()),
-                                message: MatrixMessageType::Text(v),
+                                message: correction_message(storage, room_id, sender, v, now),
+                                trigger_event: Some(event_id.to_owned()),
+                                thread_root: None,
                             })
                             .await
                         {
@@ -119,6 +270,8 @@ pub(super) async fn commandless_handler(
                                 storage
                                     .last_correction_time
                                     .insert(room_id.to_owned(), SystemTime::now());
+                                storage.record_correction(room_id, &spelling);
+                                storage.record_sent_correction(room_id, sender, event_id, now);
                             }
                             Err(_) => error!("Channel closed. Unable to send message."),
                         };
@@ -131,3 +284,60 @@ pub(super) async fn commandless_handler(
         }
     }
 }
+
+/// Builds a correction of a user, editing the bot's recent correction of them in the room instead if there is one
+fn correction_message(
+    storage: &ListenerStorage,
+    room_id: &RoomId,
+    user: &UserId,
+    correction: String,
+    now: Instant,
+) -> MatrixMessageType {
+    match storage.recent_correction(room_id, user, now) {
+        Some(v) => {
+            debug!("Editing the recent correction of {}", user);
+            MatrixMessageType::Edit(v.clone(), RoomMessageEventContent::text_plain(correction))
+        }
+        None => MatrixMessageType::Text(correction),
+    }
+}
+
+/// Sends one correction for each burst of misspellings that has ended, covering every spelling in the burst
+pub(super) async fn send_pending_corrections(
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+    now: Instant,
+) {
+    for (room_id, user, pending) in storage.take_due_corrections(now) {
+        // Another burst in the room may have been corrected since this one started
+        if !storage.correction_time_cooldown(&room_id, config.correction_cooldown(&room_id)) {
+            debug!(
+                "Correction cooldown active. Dropping batched correction for {}",
+                user
+            );
+            continue;
+        }
+        let text = correction(&user, &pending.spellings, config);
+        match send
+            .send(MatrixMessage {
+                room_id: Some(room_id.clone()),
+                message: correction_message(storage, &room_id, &user, text, now),
+                trigger_event: Some(pending.event_id.clone()),
+                thread_root: None,
+            })
+            .await
+        {
+            Ok(_) => {
+                storage
+                    .last_correction_time
+                    .insert(room_id.clone(), SystemTime::now());
+                for spelling in &pending.spellings {
+                    storage.record_correction(&room_id, spelling);
+                }
+                storage.record_sent_correction(&room_id, &user, &pending.event_id, now);
+            }
+            Err(_) => error!("Channel closed. Unable to send message."),
+        }
+    }
+}
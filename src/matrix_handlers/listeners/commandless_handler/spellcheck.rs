@@ -2,30 +2,36 @@
 
 use crate::config::{MatrixListenerConfig, SpellCheckKind};
 use ruma::{events::room::message::TextMessageEventContent, UserId};
+use tracing::error;
 
 /// Checks that provided string contains an incorrect spelling and returns a formatted string that corrects the user
+/// along with the configured spelling that triggered it
 pub fn spellcheck(
     text: &TextMessageEventContent,
     sender: &UserId,
     config: &MatrixListenerConfig,
-) -> Option<String> {
+) -> Option<(String, String)> {
     let mut result = String::new();
-    for i in config.incorrect_spellings.iter() {
+    let mut spelling = String::new();
+    let incorrect_spellings = match config.incorrect_spellings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Correction lock is poisoned. Ignoring...");
+            return None;
+        }
+    };
+    for i in incorrect_spellings.iter() {
         match i {
             SpellCheckKind::SpellCheckInsensitive(v) => {
                 if text.body.contains(&v.to_string().to_lowercase()) {
-                    result = config
-                        .correction_text
-                        .replacen("{}", sender.localpart(), 1)
-                        .replacen("{}", &v.to_string(), 1);
+                    spelling = v.to_string();
+                    result = correction(sender, &[spelling.clone()], config);
                 }
             }
             SpellCheckKind::SpellCheckSensitive(v) => {
                 if text.body.contains(&v.to_string()) {
-                    result = config
-                        .correction_text
-                        .replacen("{}", sender.localpart(), 1)
-                        .replacen("{}", &v.to_string(), 1);
+                    spelling = v.to_string();
+                    result = correction(sender, &[spelling.clone()], config);
                 }
             }
         }
@@ -34,6 +40,20 @@ pub fn spellcheck(
     if result.is_empty() {
         None
     } else {
-        Some(result)
+        Some((result, spelling))
     }
 }
+
+/// Builds the correction text for a user that misspelled one or more configured spellings,
+/// joining several spellings like "Jellyfish, Jelly Fin and JellyFin"
+pub fn correction(sender: &UserId, spellings: &[String], config: &MatrixListenerConfig) -> String {
+    let spellings = match spellings.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    };
+    config
+        .correction_text
+        .replacen("{}", sender.localpart(), 1)
+        .replacen("{}", &spellings, 1)
+}
@@ -1,14 +1,15 @@
 //! Performs expansion of text in messages and builds proper response
 
 use crate::config::MatrixListenerConfig;
-use crate::helpers::{clean_text, MatrixNoticeResponse};
+use crate::helpers::{clean_text, render_template, MatrixNoticeResponse, TemplateData};
 use crate::regex::TEXT_EXPANSION;
 use ruma::events::room::message::TextMessageEventContent;
 use tracing::{debug, error, trace};
 
-/// Finds and expands text plus builds response text
+/// Finds and expands text plus builds response text, substituting `data` into the expanded text
 pub fn text_expansion(
     text: &TextMessageEventContent,
+    data: &TemplateData<'_>,
     config: &MatrixListenerConfig,
     notice_response: &mut MatrixNoticeResponse,
 ) {
@@ -17,13 +18,13 @@ pub fn text_expansion(
         Some(v) => {
             let clean_text = clean_text(&v.body);
             if TEXT_EXPANSION.is_match(&clean_text) {
-                determine_expanded_text(config, &clean_text, &mut expanded_text)
+                determine_expanded_text(config, data, &clean_text, &mut expanded_text)
             } else {
                 debug!("There are no remaining matches after cleaning tags. Doing nothing.");
                 return;
             }
         }
-        None => determine_expanded_text(config, &text.body, &mut expanded_text),
+        None => determine_expanded_text(config, data, &text.body, &mut expanded_text),
     }
     if expanded_text.is_empty() {
         debug!("No text to send after processing.");
@@ -34,6 +35,7 @@ pub fn text_expansion(
 
 fn determine_expanded_text(
     config: &MatrixListenerConfig,
+    data: &TemplateData<'_>,
     text: &str,
     expanded_text: &mut Vec<String>,
 ) {
@@ -41,7 +43,7 @@ fn determine_expanded_text(
         trace!("{:?}", cap);
         match config.text_expansions.get(&cap[1]) {
             Some(v) => {
-                expanded_text.push(v.to_string());
+                expanded_text.push(render_template(v, data));
             }
             None => error!("Somehow lost expanded text between regex match and insertion!"),
         }
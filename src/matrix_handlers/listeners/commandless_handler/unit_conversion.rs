@@ -1,14 +1,19 @@
 //! Performs unit conversions and adds them to response data
 
-use crate::config::MatrixListenerConfig;
-use crate::helpers::{clean_text, convert_unit, MatrixNoticeResponse};
+use crate::config::{AmbiguousUnits, ListenerStorage, MatrixListenerConfig};
+use crate::helpers::{clean_text, convert_unit, unit_key, MatrixNoticeResponse};
+use crate::matrix_handlers::listeners::is_duplicate_conversion;
 use crate::regex::UNIT_CONVERSION;
-use ruma::events::room::message::TextMessageEventContent;
+use ruma::{events::room::message::TextMessageEventContent, RoomId};
 use tracing::{debug, trace};
 
 /// Adds unit conversions to the supplied BotResponseNotice
+///
+/// Conversions already answered in the room are skipped if storage is supplied.
 pub fn unit_conversion(
     text: &TextMessageEventContent,
+    room_id: &RoomId,
+    storage: Option<&mut ListenerStorage>,
     config: &MatrixListenerConfig,
     notice_response: &mut MatrixNoticeResponse,
 ) {
@@ -17,8 +22,8 @@ pub fn unit_conversion(
         Some(v) => {
             let clean_text = clean_text(&v.body);
             if UNIT_CONVERSION.is_match(&clean_text) {
-                for cap in UNIT_CONVERSION.captures_iter(&clean_text.to_lowercase()) {
-                    process_capture(&cap, config, &mut conversions)
+                for cap in UNIT_CONVERSION.captures_iter(&clean_text) {
+                    process_capture(&cap, &clean_text, config, &mut conversions)
                 }
             } else {
                 debug!("There are no remaining matches after cleaning tags. Doing nothing.");
@@ -26,14 +31,28 @@ pub fn unit_conversion(
             }
         }
         None => {
-            for cap in UNIT_CONVERSION.captures_iter(&text.body.to_lowercase()) {
-                process_capture(&cap, config, &mut conversions)
+            for cap in UNIT_CONVERSION.captures_iter(&text.body) {
+                process_capture(&cap, &text.body, config, &mut conversions)
             }
         }
     }
     let conversions = conversions;
-    match convert_unit(conversions) {
-        Some(v) => notice_response.set_unit_conversions(v),
+    if !conversions.is_empty() && is_duplicate_conversion(&conversions, room_id, storage, config) {
+        debug!("Conversions were recently answered in this room. Skipping...");
+        return;
+    }
+    match convert_unit(
+        conversions,
+        config.custom_conversions.get(room_id),
+        config.ambiguous_units == AmbiguousUnits::Custom,
+        config.default_unit_conversion_precision,
+        &config.unit_conversion_precision,
+        config.rounding_mode,
+    ) {
+        Some(v) => {
+            notice_response.set_unit_conversions(v);
+            notice_response.set_show_formulas(config.verbose_conversions);
+        }
         None => debug!("No convertable units found. No unit conversions will be performed."),
     }
 }
@@ -41,18 +60,26 @@ pub fn unit_conversion(
 /// Processes a unit conversion regex capture into a Vec
 fn process_capture(
     capture: &regex::Captures,
+    text: &str,
     config: &MatrixListenerConfig,
     conversions: &mut Vec<(String, String)>,
 ) {
     trace!("Capture being processed is {:?}", capture);
+    if let Some(marker) = &config.conversion_marker {
+        let end = capture.get(0).map_or(0, |m| m.end());
+        if !text[end..].starts_with(marker.as_str()) {
+            trace!("Capture is not followed by the conversion marker");
+            return;
+        }
+    }
     if !config.unit_conversion_exclusion.is_empty() {
         if capture_not_excluded(capture, config) {
-            conversions.push((capture[1].to_string(), capture[2].to_string()))
+            conversions.push((capture[1].to_string(), unit_key(&capture[2])))
         } else {
             trace!("Capture excluded due to exclusion rules");
         }
     } else {
-        conversions.push((capture[1].to_string(), capture[2].to_string()))
+        conversions.push((capture[1].to_string(), unit_key(&capture[2])))
     }
 }
 
@@ -0,0 +1,67 @@
+//! Handler for the config check command
+
+use super::check_authorized;
+use crate::config::{Config, ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+/// Validates the config file on disk without applying it and replies with the result
+///
+/// Only accepted in direct messages and help rooms.
+pub(super) async fn config_check_handler(
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for checking config. Skipping...");
+        return;
+    }
+    let message = if storage.is_direct_message(room_id) || config.help_rooms.contains(room_id) {
+        let report = config_report(Config::read_config_file());
+        info!("Config check requested by {}: {}", sender, report);
+        report
+    } else {
+        debug!("Config checks are only accepted in direct messages and help rooms. Rejecting...");
+        "Config checks are only accepted in direct messages and help rooms".to_string()
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Validates config file contents the same way they are validated at startup
+///
+/// Validation stops at the first problem found.
+pub(super) fn config_report(contents: anyhow::Result<String>) -> String {
+    match contents.and_then(|c| Config::parse_config(&c)) {
+        Ok(_) => "Config OK. Nothing was applied".to_string(),
+        Err(e) => format!("Config problem: {:#}", e),
+    }
+}
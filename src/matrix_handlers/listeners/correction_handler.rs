@@ -0,0 +1,124 @@
+//! Handler for the correction command
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig, SpellCheckKind};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+const USAGE: &str =
+    "Usage: !correction add insensitive|sensitive <spelling>, !correction remove <spelling>, or !correction list";
+
+/// Lists, adds, or removes the incorrect spellings the bot corrects without changing the config file
+pub(super) async fn correction_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for changing corrections. Skipping...");
+        return;
+    }
+    let message = if config.enable_corrections {
+        change_corrections(&text.body, config)
+    } else {
+        "Corrections are disabled in the config".to_string()
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Performs the correction command in `body` and returns the reply
+fn change_corrections(body: &str, config: &MatrixListenerConfig) -> String {
+    let mut incorrect_spellings = match config.incorrect_spellings.write() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Correction lock is poisoned");
+            return "Corrections are unavailable".to_string();
+        }
+    };
+    let mut words = body.split_whitespace().skip(1);
+    match words.next().map(|w| w.to_lowercase()).as_deref() {
+        Some("list") => {
+            let list = |sensitive: bool| {
+                let mut spellings: Vec<String> = incorrect_spellings
+                    .iter()
+                    .filter(|s| matches!(s, SpellCheckKind::SpellCheckSensitive(_)) == sensitive)
+                    .map(|s| s.to_string())
+                    .collect();
+                spellings.sort();
+                spellings.join(", ")
+            };
+            format!(
+                "Case insensitive: {}\nCase sensitive: {}",
+                list(false),
+                list(true)
+            )
+        }
+        Some("add") => {
+            let kind = words.next().map(|w| w.to_lowercase());
+            let spelling = words.collect::<Vec<_>>().join(" ");
+            if spelling.is_empty() {
+                return USAGE.to_string();
+            }
+            let correction = match kind.as_deref() {
+                Some("insensitive") => {
+                    SpellCheckKind::SpellCheckInsensitive(spelling.as_str().into())
+                }
+                Some("sensitive") => SpellCheckKind::SpellCheckSensitive(spelling.as_str().into()),
+                _ => return USAGE.to_string(),
+            };
+            if incorrect_spellings
+                .iter()
+                .any(|s| s.to_string() == spelling)
+            {
+                return format!("{} is already corrected", spelling);
+            }
+            info!("Adding correction {}", spelling);
+            incorrect_spellings.push(correction);
+            format!("Added correction {}", spelling)
+        }
+        Some("remove") => {
+            let spelling = words.collect::<Vec<_>>().join(" ");
+            if spelling.is_empty() {
+                return USAGE.to_string();
+            }
+            let count = incorrect_spellings.len();
+            incorrect_spellings.retain(|s| s.to_string() != spelling);
+            if incorrect_spellings.len() == count {
+                format!("{} is not corrected", spelling)
+            } else {
+                info!("Removing correction {}", spelling);
+                format!("Removed correction {}", spelling)
+            }
+        }
+        _ => USAGE.to_string(),
+    }
+}
@@ -0,0 +1,53 @@
+//! Handler for the command users opt out of corrections with
+
+use crate::config::ListenerStorage;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use tokio::sync::mpsc::Sender;
+use tracing::{error, info};
+
+const USAGE: &str = "Usage: !correctme off or !correctme on";
+
+/// Opts the sender out of corrections in every room, or back in
+pub(super) async fn correctme_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let switch = text
+        .body
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .to_lowercase();
+    let message = match switch.as_str() {
+        "off" => {
+            if storage.correction_opt_outs.insert(sender.to_owned()) {
+                info!("{} opted out of corrections", sender);
+            }
+            "You won't be corrected anymore. Use !correctme on to be corrected again".to_string()
+        }
+        "on" => {
+            if storage.correction_opt_outs.remove(sender) {
+                info!("{} opted back in to corrections", sender);
+            }
+            "You'll be corrected again".to_string()
+        }
+        _ => USAGE.to_string(),
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
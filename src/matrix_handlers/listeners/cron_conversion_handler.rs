@@ -0,0 +1,38 @@
+//! Handler for the cron expression conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{describe_cron, to_cron};
+use crate::messages::MatrixMessage;
+use crate::regex::CRON_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based cron conversion handler that replies with a description of a cron expression,
+/// or the cron expression for a simple description like "every 15 minutes"
+pub(super) async fn cron_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let input = match CRON_CONVERSION.captures(&text.body) {
+        Some(v) => v[1].to_string(),
+        None => {
+            debug!("No cron expression found. No reply will be constructed.");
+            return;
+        }
+    };
+    // Minutes are never written with letters, so anything starting with one is a description
+    let converted = if input.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        to_cron(&input)
+    } else {
+        describe_cron(&input)
+    };
+    let result = converted.map(|v| format!("{} => {}", input, v));
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,48 @@
+//! Handler for the data transfer rate conversion command
+
+use super::send_conversion_reply;
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{convert_data_rate, round, RateUnit, DEFAULT_PRECISION};
+use crate::messages::MatrixMessage;
+use crate::regex::DATA_RATE_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based data rate conversion handler that replies with a rate in bits per second converted to bytes per second or the reverse
+pub(super) async fn data_rate_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    // Not lowercased since b is bits and B is bytes
+    let cap = match DATA_RATE_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No data rate found. No reply will be constructed.");
+            return;
+        }
+    };
+    let quantity = cap[1].parse::<f64>().unwrap_or(0.0);
+    let converted = RateUnit::parse(&cap[2]).and_then(|unit| {
+        convert_data_rate(quantity, unit)
+            .map(|(converted, converted_unit)| (unit, converted, converted_unit))
+    });
+    let result = match converted {
+        Some((unit, converted, converted_unit)) => Ok(format!(
+            "{}{:#} => {}{:#}",
+            &cap[1],
+            unit,
+            round(converted, DEFAULT_PRECISION, config.rounding_mode),
+            converted_unit
+        )),
+        None => Err(format!("{}{} is not a valid data rate", &cap[1], &cap[2])),
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,34 @@
+//! Handler for the date conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::convert_date;
+use crate::messages::MatrixMessage;
+use crate::regex::DATE_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based date conversion handler that replies with alternate representations of a date
+pub(super) async fn date_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let body = text.body.to_lowercase();
+    let date = match DATE_CONVERSION.captures(&body) {
+        Some(v) => v[1].to_string(),
+        None => {
+            debug!("No date found. No reply will be constructed.");
+            return;
+        }
+    };
+    let result = convert_date(&date)
+        .map(|v| v.to_string())
+        .ok_or_else(|| format!("{} is not a valid date", date));
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
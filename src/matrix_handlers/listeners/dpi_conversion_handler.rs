@@ -0,0 +1,100 @@
+//! Handler for the pixel density conversion command
+
+use super::send_conversion_reply;
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{
+    convert_density, dot_pitch, physical_size, round, screen_ppi, DensityUnit, CM_PER_INCH,
+    DEFAULT_PRECISION,
+};
+use crate::messages::MatrixMessage;
+use crate::regex::DPI_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Decimal places dot pitches are rounded to, since they're fractions of a millimeter
+const DOT_PITCH_PRECISION: usize = 3;
+
+/// Command based pixel density conversion handler that replies with a density converted between dpi and dpcm,
+/// the printed size of a number of pixels, or the density and dot pitch of a screen
+pub(super) async fn dpi_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let body = text.body.to_lowercase();
+    let cap = match DPI_CONVERSION.captures(&body) {
+        Some(v) => v,
+        None => {
+            debug!("No pixel density found. No reply will be constructed.");
+            return;
+        }
+    };
+    let rounded = |value: f64, precision: usize| {
+        format!(
+            "{:.*}",
+            precision,
+            round(value, precision, config.rounding_mode)
+        )
+    };
+    let number = |i: usize| cap[i].parse::<f64>().unwrap_or(0.0);
+    let result = if let Some(unit) = cap.get(2) {
+        let (density, unit) = (number(1), unit.as_str());
+        match DensityUnit::parse(unit) {
+            Some(DensityUnit::PerInch) => Ok(format!(
+                "{}{} => {}dpcm",
+                &cap[1],
+                unit,
+                rounded(
+                    convert_density(density, DensityUnit::PerInch),
+                    DEFAULT_PRECISION
+                )
+            )),
+            Some(DensityUnit::PerCm) => Ok(format!(
+                "{}{} => {}dpi",
+                &cap[1],
+                unit,
+                rounded(
+                    convert_density(density, DensityUnit::PerCm),
+                    DEFAULT_PRECISION
+                )
+            )),
+            None => Err(format!("{}{} is not a valid pixel density", &cap[1], unit)),
+        }
+    } else if cap.get(3).is_some() {
+        let pixels = cap[3].parse::<u32>().unwrap_or(0);
+        match physical_size(pixels, number(4)) {
+            Some(inches) => Ok(format!(
+                "{}px at {}dpi => {}in ({}cm)",
+                &cap[3],
+                &cap[4],
+                rounded(inches, DEFAULT_PRECISION),
+                rounded(inches * CM_PER_INCH, DEFAULT_PRECISION)
+            )),
+            None => Err(format!("{}dpi is not a valid pixel density", &cap[4])),
+        }
+    } else {
+        let size = |i: usize| cap[i].parse::<u32>().unwrap_or(0);
+        match screen_ppi(size(5), size(6), number(7)) {
+            Some(ppi) => Ok(format!(
+                "{}x{} at {}in => {}ppi, {}mm dot pitch",
+                &cap[5],
+                &cap[6],
+                &cap[7],
+                rounded(ppi, DEFAULT_PRECISION),
+                rounded(dot_pitch(ppi), DOT_PITCH_PRECISION)
+            )),
+            None => Err(format!(
+                "{}x{} at {}in is not a valid screen",
+                &cap[5], &cap[6], &cap[7]
+            )),
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
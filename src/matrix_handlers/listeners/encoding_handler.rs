@@ -0,0 +1,66 @@
+//! Handler for the encode and decode commands
+
+use crate::helpers::{decode, encode, Encoding, MatrixFormattedNoticeResponse, MAX_ENCODING_INPUT};
+use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+const USAGE: &str = "Usage: !encode|!decode base64|url|hex <text>";
+
+/// Command based encoding handler that replies with the encoded or decoded text
+pub(super) async fn encoding_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let message = match convert(&text.body) {
+        Ok(v) => MatrixFormattedMessage {
+            plain_text: v,
+            formatted_text: None,
+        },
+        Err(e) => {
+            debug!("Unable to encode or decode text: {}", e);
+            let mut response = MatrixFormattedNoticeResponse::default();
+            response.add_errrors(vec![e]);
+            MatrixFormattedMessage {
+                plain_text: response.to_string(),
+                formatted_text: response.format_text(),
+            }
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::FormattedNotice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+fn convert(body: &str) -> Result<String, String> {
+    let (command, rest) = body.split_once(' ').ok_or(USAGE)?;
+    let (format, input) = rest.trim_start().split_once(' ').ok_or(USAGE)?;
+    let encoding = Encoding::parse(format).ok_or(USAGE)?;
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(USAGE.to_string());
+    }
+    if input.chars().count() > MAX_ENCODING_INPUT {
+        return Err(format!(
+            "Text must be at most {} characters",
+            MAX_ENCODING_INPUT
+        ));
+    }
+    if command.eq_ignore_ascii_case("!encode") {
+        Ok(encode(encoding, input))
+    } else {
+        decode(encoding, input).map_err(|e| format!("Unable to decode {}: {}", format, e))
+    }
+}
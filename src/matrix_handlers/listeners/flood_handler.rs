@@ -0,0 +1,69 @@
+//! Handler that detects users flooding ban rooms and bans or mutes them
+
+use crate::config::{FloodAction, ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixBanMessage, MatrixMessage, MatrixMessageType};
+use ruma::{RoomId, UserId};
+use std::time::{Instant, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tracing::{error, trace, warn};
+
+/// Counts a message sent in a ban room and acts against its sender if they are flooding.
+///
+/// Returns `true` if the sender was caught flooding. Admins are never caught.
+pub(super) async fn flood_handler(
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) -> bool {
+    let flood_protection = match &config.flood_protection {
+        Some(v) => v,
+        None => return false,
+    };
+    if !config.ban_rooms.contains(room_id) {
+        trace!("Room is not a ban room. Skipping flood detection...");
+        return false;
+    }
+    if storage.is_admin(sender, &config.admins, SystemTime::now()) {
+        trace!("Admins are exempt from flood detection. Skipping...");
+        return false;
+    }
+    if !storage.record_flood_message(
+        sender,
+        flood_protection.message_limit,
+        flood_protection.window,
+        Instant::now(),
+    ) {
+        return false;
+    }
+
+    warn!("User {} is flooding room {}", sender, room_id);
+    let message = MatrixBanMessage {
+        admin: config.mx_uname.clone(),
+        users: vec![sender.to_owned()],
+        reason: Some(format!(
+            "Flooding: more than {} messages in {} seconds",
+            flood_protection.message_limit,
+            flood_protection.window.as_secs()
+        )),
+        rooms: config.ban_rooms.clone(),
+    };
+    let message = match flood_protection.action {
+        FloodAction::Ban => MatrixMessageType::Ban(message),
+        FloodAction::Mute => MatrixMessageType::Mute(message),
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message,
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed, unable to send mesage.")
+    }
+    true
+}
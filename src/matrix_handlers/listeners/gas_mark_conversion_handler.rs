@@ -0,0 +1,77 @@
+//! Handler for the gas mark conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{gas_mark_temperature, nearest_gas_mark, parse_gas_mark, GAS_MARKS};
+use crate::messages::MatrixMessage;
+use crate::regex::GAS_MARK_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Command based gas mark conversion handler that replies with the oven temperature or gas mark
+pub(super) async fn gas_mark_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match GAS_MARK_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No gas mark or temperature found. No reply will be constructed.");
+            return;
+        }
+    };
+    let (coolest, hottest) = (&GAS_MARKS[0], &GAS_MARKS[GAS_MARKS.len() - 1]);
+    let result = match (cap.get(1), cap.get(2), cap.get(3)) {
+        (Some(mark), _, _) => match parse_gas_mark(mark.as_str()).and_then(gas_mark_temperature) {
+            Some((name, celsius, fahrenheit)) => Ok(format!(
+                "gas mark {} => {}°C ({}°F)",
+                name, celsius, fahrenheit
+            )),
+            None => Err(format!(
+                "Gas mark {} is out of range. Gas marks go from {} to {}",
+                mark.as_str(),
+                coolest.1,
+                hottest.1
+            )),
+        },
+        (None, Some(temperature), Some(unit)) => {
+            let unit = unit.as_str().to_uppercase();
+            let celsius = match temperature.as_str().parse::<f64>() {
+                Ok(v) if unit == "F" => (v - 32.0) * 5.0 / 9.0,
+                Ok(v) => v,
+                Err(e) => {
+                    error!("Temperature unable to be parsed. Error is {:?}", e);
+                    return;
+                }
+            };
+            match nearest_gas_mark(celsius) {
+                Some(v) => Ok(format!(
+                    "{}°{} => gas mark {}",
+                    temperature.as_str(),
+                    unit,
+                    v
+                )),
+                None => Err(format!(
+                    "{}°{} is out of range. Gas marks go from {}°C ({}°F) to {}°C ({}°F)",
+                    temperature.as_str(),
+                    unit,
+                    coolest.2,
+                    coolest.3,
+                    hottest.2,
+                    hottest.3
+                )),
+            }
+        }
+        _ => {
+            error!("Somehow lost gas mark between regex match and conversion!");
+            return;
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,109 @@
+//! Handlers for the grant and revoke commands
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+/// Longest time admin can be granted for
+pub(super) const MAX_GRANT_DURATION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+const USAGE: &str = "Usage: !grant @user:server <duration> or !revoke @user:server. Durations are minutes (30m), hours (1h), or days (2d), up to 7d";
+
+/// Temporarily grants admin to a user or revokes a grant. Only super admins can use it.
+///
+/// Granted admins are never super admins, so they can't grant admin to anyone else.
+pub(super) async fn grant_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = config.super_admins.contains(sender);
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for granting admin. Skipping...");
+        return;
+    }
+    let message = change_grants(&text.body, storage, config, SystemTime::now());
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+fn change_grants(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    now: SystemTime,
+) -> String {
+    let mut args = body.split_whitespace();
+    let command = args.next().unwrap_or_default().to_lowercase();
+    let user = match args.next().map(UserId::parse) {
+        Some(Ok(v)) => v,
+        _ => return USAGE.to_string(),
+    };
+    match (command.as_str(), args.next(), args.next()) {
+        ("!grant", Some(d), None) => match parse_duration(d) {
+            Some(duration) => {
+                if config.admins.contains(&user) {
+                    return format!("{} is already an admin", user);
+                }
+                storage.grant_admin(&user, duration, now);
+                info!("Granted admin to {} for {}", user, d);
+                format!("Granted admin to {} for {}", user, d)
+            }
+            None => USAGE.to_string(),
+        },
+        ("!revoke", None, None) => {
+            if storage.revoke_admin(&user) {
+                info!("Revoked admin from {}", user);
+                format!("Revoked admin from {}", user)
+            } else {
+                format!("{} has not been granted admin", user)
+            }
+        }
+        _ => USAGE.to_string(),
+    }
+}
+
+/// Parses durations like "30m", "1h", or "2d". Returns `None` for zero or durations over `MAX_GRANT_DURATION`.
+fn parse_duration(text: &str) -> Option<Duration> {
+    let unit = match text.to_lowercase().chars().last()? {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    let quantity = text[..text.len() - 1].parse::<u64>().ok()?;
+    let duration = Duration::from_secs(quantity.checked_mul(unit)?);
+    if duration.is_zero() || duration > MAX_GRANT_DURATION {
+        None
+    } else {
+        Some(duration)
+    }
+}
@@ -1,8 +1,13 @@
-use crate::config::MatrixListenerConfig;
+use super::thread_root;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
 use crate::helpers::MatrixFormattedNoticeResponse;
 use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
-use ruma::{events::room::message::TextMessageEventContent, RoomId};
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
 use std::convert::From;
+use std::time::Instant;
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error, trace};
 
@@ -35,11 +40,30 @@ impl From<&str> for HelpType {
 
 pub(super) async fn help_handler(
     text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
     room_id: &RoomId,
+    storage: &mut ListenerStorage,
     config: &MatrixListenerConfig,
     send: &mut Sender<MatrixMessage>,
 ) {
     if config.help_rooms.is_empty() || config.help_rooms.contains(room_id) {
+        let thread_root = thread_root(relates_to);
+        let topic = text
+            .body
+            .split(' ')
+            .nth(1)
+            .unwrap_or_default()
+            .to_lowercase();
+        if let Some(cooldown) = config.help_cooldown {
+            if !storage.help_cooldown(room_id, &topic, cooldown, Instant::now()) {
+                debug!(
+                    "Help for {:?} was recently sent in room {}. Skipping...",
+                    topic, room_id
+                );
+                return;
+            }
+        }
         trace!("Room is allowed, building help message");
         let mut message = String::new();
         match text.body.split(' ').nth(1).map(HelpType::from) {
@@ -63,6 +87,8 @@ pub(super) async fn help_handler(
                 .send(MatrixMessage {
                     room_id: Some(room_id.to_owned()),
                     message: MatrixMessageType::Notice(message),
+                    trigger_event: Some(event_id.to_owned()),
+                    thread_root: thread_root.clone(),
                 })
                 .await
                 .is_err()
@@ -86,6 +112,8 @@ pub(super) async fn help_handler(
                         plain_text: response.to_string(),
                         formatted_text,
                     }),
+                    trigger_event: Some(event_id.to_owned()),
+                    thread_root: thread_root.clone(),
                 })
                 .await
                 .is_err()
@@ -150,8 +178,15 @@ EXAMPLES:
 }
 
 async fn group_ping_help_message(config: &MatrixListenerConfig) -> String {
+    let group_pings = match config.group_pings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Group ping lock is poisoned");
+            return "Group pings are unavailable".to_string();
+        }
+    };
     let mut groups = Vec::new();
-    for group in config.group_pings.keys() {
+    for group in group_pings.groups.keys() {
         groups.push(group);
     }
     groups.sort();
@@ -168,6 +203,8 @@ This action is only available as commandless. It will trigger on anything that m
 
 If the group exists and you are authorized to make a group ping, a message pinging everyone in the group will be made in a bot message.
 
+To see who a group would ping without pinging them, use \"!pingtest group\".
+
 USAGE:
 \tHey there %server can you look at this for me?
 \t%server
@@ -178,8 +215,15 @@ AVAILABLE GROUPS:
 }
 
 async fn github_search_help_message(config: &MatrixListenerConfig) -> String {
+    let settings = match config.link_settings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Link lock is poisoned");
+            return "Github search is unavailable".to_string();
+        }
+    };
     let mut repos = Vec::new();
-    for repo in config.repos.keys() {
+    for repo in settings.repos.keys() {
         repos.push(repo);
     }
     repos.sort();
@@ -205,8 +249,15 @@ AVAILABLE REPOS:
 }
 
 async fn link_help_message(config: &MatrixListenerConfig) -> String {
+    let settings = match config.link_settings.read() {
+        Ok(v) => v,
+        Err(_) => {
+            error!("Link lock is poisoned");
+            return "Links are unavailable".to_string();
+        }
+    };
     let mut keywords = Vec::new();
-    for keyword in &config.linkers {
+    for keyword in &settings.linkers {
         keywords.push(keyword);
     }
     keywords.sort();
@@ -218,7 +269,7 @@ async fn link_help_message(config: &MatrixListenerConfig) -> String {
     available_keywords.pop();
     let available_keywords = available_keywords.replace('|', " | ");
     let mut links = Vec::new();
-    for link in config.links.keys() {
+    for link in settings.links.keys() {
         links.push(link);
     }
     links.sort();
@@ -231,13 +282,19 @@ async fn link_help_message(config: &MatrixListenerConfig) -> String {
     let available_links = available_links.replace('|', " | ");
     format!("Link
 
-This action is only available as commandless. It will trigger on anything that matches \"link@hwa\" where \"link\" is a configured keyword and \"hwa\" is a linkable item.
+This action is available as both a command and commandless. As commandless, it will trigger on anything that matches \"link@hwa\" where \"link\" is a configured keyword and \"hwa\" is a linkable item.
 
 if the keyword and item exist, there will be a link provided in a bot message.
 
+As a command, it will search all linkable items for the closest matches to what you typed and list them.
+
 USAGE:
-\tI think you might want to look at link@hwa
-\tlink@hwa
+\tCOMMAND:
+\t\t!link troubleshooting
+
+\tCOMMANDLESS:
+\t\tI think you might want to look at link@hwa
+\t\tlink@hwa
 
 AVAILABLE KEYWORDS:
 {}
@@ -288,6 +345,10 @@ async fn unit_conversion_help_message(config: &MatrixListenerConfig) -> String {
     }
     space_excluded_units.pop();
     let space_excluded_units = space_excluded_units.replace('|', " | ");
+    let marker = match &config.conversion_marker {
+        Some(v) => format!("\n\nCommandless conversions only happen for units directly followed by {}, like 45mph{}", v, v),
+        None => String::new(),
+    };
     format!("Unit Conversion
 
 This action is available as both a command and commanless. It will convert common converstation units Imperial <-> Metric to help ease international chat. There can be a space between the quantity and unit except for the units excluded by configuration (listed below).
@@ -295,6 +356,9 @@ This action is available as both a command and commanless. It will convert commo
 USAGE:
 \tCOMMAND:
 \t\t!convert 20c
+\t\t!convert 20c -v (shows the formula used)
+\t\t!convert 5m as custom (uses the room's custom unit when it has the same name as a built-in unit)
+\t\t!convert help (lists the supported units)
 
 \tCOMMANDLESS:
 \t\tIt's weird that the speed limit here is 45mph
@@ -309,8 +373,43 @@ WEIGHT:
 kg | lbs
 SPEED:
 km/h | kmh | kph | kmph | mph
+VOLUME:
+l | gal | qt | pt | cup | cups (adjacent imperial volumes like 1gal 2qt are summed if compound volumes are enabled)
+TORQUE:
+Nm | lb-ft | lbft | ft-lb (Nm is case sensitive, nm is nanometers)
+PRESSURE:
+bar | kpa | psi (bar and kpa convert to psi, psi converts to bar)
+DATE (command only):
+YYYY-MM-DD shows the Julian day, ordinal date, and ISO week date
+TIMEZONES (command only):
+3pm to @user:example.com converts from your timezone to theirs, and 15:00 Europe/London to America/New_York converts between named timezones. Set your timezone with !tz set Europe/London and see someone's time with !time @user:example.com
+ASPECT RATIOS (command only):
+1920x1080 ratio converts to 16:9 and 16:9 at 1080p converts to 1920x1080. Heights can also be 4k or 8k
+PERCENTAGES AND FRACTIONS (command only):
+25% converts to 0.25, 1/4, and 1:4. Fractions like 3/8 and ratios like 3:8 convert as well. Add to %|fraction|ratio|decimal for a single notation
+ROMAN NUMERALS (command only):
+XLII converts to 42 and 42 roman converts to XLII. Numerals that are also units need roman after them, like MM roman. Numerals must be uppercase and between I and MMMCMXCIX (3999)
+GAS MARKS (command only):
+gas4 converts to 180°C (350°F) and 180c gas converts to gas mark 4. Gas marks go from 1/4 to 9
+IP ADDRESSES (command only):
+192.168.1.0/24 shows the network, broadcast address, netmask, and usable hosts, 255.255.255.0 to cidr converts to /24, and /24 to netmask converts to 255.255.255.0. IPv6 ranges like 2001:db8::/32 show their network, last address, and number of addresses
+WAVELENGTH AND FREQUENCY (command only):
+500nm converts light to 599.58THz and 440hz converts sound to 0.78m. Light units are nm | um | µm | thz, sound units are hz | khz
+DATA RATES (command only):
+100mbps converts to 12.5MBps (megabytes per second) and back. Prefixes are decimal and can be k | M | G | T. A lowercase b is bits and an uppercase B is bytes
+PIXEL DENSITY (command only):
+300dpi converts to 118.11dpcm and back, 2400px at 300dpi converts to a printed size of 8in (20.32cm), and 1920x1080 27in shows the screen's ppi and dot pitch. ppi and ppcm work like dpi and dpcm
+COLORS (command only):
+#ff8800, rgb(255, 136, 0), and hsl(32, 100%, 50%) convert to the other two notations. Common names like red convert as well
+CRON EXPRESSIONS (command only):
+cron \"0 9 * * 1\" converts to At 09:00 on Monday and cron every 15 minutes converts to */15 * * * *. Descriptions can be every N minutes|hours or at HH:MM on a weekday
+NUMBER WORDS (command only):
+forty-two converts to 42 and 42 words converts to forty-two. Numbers can be up to 999999999999
+FEELS LIKE (commandless only):
+25c 60% humidity shows the heat index from 27c (80f) to 50c (122f)
+-5c 20km/h wind shows the wind chill at 10c (50f) and below with at least 4.8km/h (3mph) wind
 
 SPACE EXCLUDED UNITS:
-{}
-    ", space_excluded_units)
+{}{}
+    ", space_excluded_units, marker)
 }
@@ -0,0 +1,111 @@
+//! Handler for the commands admins manage ignored users with
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, OwnedUserId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+const USAGE: &str = "Usage: !ignore @user:server, !unignore @user:server, or !ignorelist";
+
+/// Ignores or unignores a user, or lists ignored users and users that opted out of corrections.
+/// Only admins can use it.
+///
+/// The bot doesn't respond to ignored users at all. Admins can't be ignored.
+pub(super) async fn ignore_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for managing ignored users. Skipping...");
+        return;
+    }
+    let message = change_ignores(&text.body, storage, config, SystemTime::now());
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+fn change_ignores(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    now: SystemTime,
+) -> String {
+    let mut args = body.split_whitespace();
+    let command = args.next().unwrap_or_default().to_lowercase();
+    if command == "!ignorelist" {
+        return ignore_list(storage);
+    }
+    let user = match (args.next().map(UserId::parse), args.next()) {
+        (Some(Ok(v)), None) => v,
+        _ => return USAGE.to_string(),
+    };
+    match command.as_str() {
+        "!ignore" => {
+            if storage.is_admin(&user, &config.admins, now) {
+                return format!("{} is an admin and can't be ignored", user);
+            }
+            if storage.ignored_users.insert(user.clone()) {
+                info!("Ignoring {}", user);
+                format!("Ignoring {}", user)
+            } else {
+                format!("{} is already ignored", user)
+            }
+        }
+        "!unignore" => {
+            if storage.ignored_users.remove(&user) {
+                info!("No longer ignoring {}", user);
+                format!("No longer ignoring {}", user)
+            } else {
+                format!("{} is not ignored", user)
+            }
+        }
+        _ => USAGE.to_string(),
+    }
+}
+
+/// Lists ignored users and users that opted out of corrections, sorted so the list is stable
+fn ignore_list(storage: &ListenerStorage) -> String {
+    let list = |users: Vec<&OwnedUserId>| -> String {
+        let mut users: Vec<&str> = users.into_iter().map(|u| u.as_str()).collect();
+        users.sort_unstable();
+        if users.is_empty() {
+            "none".to_string()
+        } else {
+            users.join(", ")
+        }
+    };
+    format!(
+        "Ignored users: {}\nOpted out of corrections: {}",
+        list(storage.ignored_users.iter().collect()),
+        list(storage.correction_opt_outs.iter().collect())
+    )
+}
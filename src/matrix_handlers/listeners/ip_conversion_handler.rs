@@ -0,0 +1,47 @@
+//! Handler for the IP range and netmask conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{describe_network, netmask_to_prefix, prefix_to_netmask};
+use crate::messages::MatrixMessage;
+use crate::regex::IP_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Command based IP conversion handler that replies with a CIDR range's details, a netmask's
+/// prefix length, or a prefix length's netmask
+pub(super) async fn ip_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match IP_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No IP range, netmask, or prefix length found. No reply will be constructed.");
+            return;
+        }
+    };
+    let result = match (cap.get(1), cap.get(2), cap.get(3)) {
+        (Some(range), _, _) => describe_network(range.as_str()),
+        (None, Some(netmask), _) => netmask_to_prefix(netmask.as_str())
+            .map(|prefix| format!("{} => /{}", netmask.as_str(), prefix)),
+        (None, None, Some(prefix)) => match prefix.as_str().parse() {
+            Ok(v) => prefix_to_netmask(v).map(|mask| format!("/{} => {}", v, mask)),
+            Err(_) => Err(anyhow::anyhow!(
+                "/{} is not a valid prefix length",
+                prefix.as_str()
+            )),
+        },
+        _ => {
+            error!("Somehow lost IP address between regex match and conversion!");
+            return;
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
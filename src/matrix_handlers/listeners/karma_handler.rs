@@ -0,0 +1,49 @@
+//! Handler for the karma command
+
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, trace};
+
+/// Replies with the karma of the mentioned user, or the sender if nobody is mentioned
+pub(super) async fn karma_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if !config.enable_karma {
+        trace!("Karma is disabled. Skipping...");
+        return;
+    }
+    let user = match text.body.split_whitespace().nth(1) {
+        Some(v) => match UserId::parse(v) {
+            Ok(u) => u,
+            Err(_) => {
+                debug!(
+                    "Karma command user {} is not a valid user id. Skipping...",
+                    v
+                );
+                return;
+            }
+        },
+        None => sender.to_owned(),
+    };
+    let message = format!("{} has {} karma", user, storage.karma(room_id, &user));
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
@@ -0,0 +1,87 @@
+//! Handler for the link search command
+
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{fuzzy_matches, MatrixFormattedNoticeResponse};
+use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
+use axum::http::Uri;
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId};
+use std::collections::HashMap;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, trace};
+
+/// Maximum number of candidate links listed in a reply
+const MAX_CANDIDATES: usize = 5;
+
+/// Command based link search that fuzzy matches the query against configured links and replies with the best matches
+pub(super) async fn link_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let query = match text.body.split_whitespace().nth(1) {
+        Some(v) => v,
+        None => {
+            debug!("Link command has no query. Skipping...");
+            return;
+        }
+    };
+    let message = {
+        let settings = match config.link_settings.read() {
+            Ok(v) => v,
+            Err(_) => {
+                error!("Link lock is poisoned. Ignoring...");
+                return;
+            }
+        };
+        if settings.links.is_empty() {
+            trace!("No links configured, link search is disabled. Skipping...");
+            return;
+        }
+        build_link_search_message(query, &settings.links)
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::FormattedNotice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Builds a formatted message listing the links best matching `query`, or an error if there are none
+fn build_link_search_message(query: &str, links: &HashMap<String, Uri>) -> MatrixFormattedMessage {
+    let candidates: Vec<&str> = fuzzy_matches(query, links.keys())
+        .into_iter()
+        .take(MAX_CANDIDATES)
+        .collect();
+    if candidates.is_empty() {
+        let mut response = MatrixFormattedNoticeResponse::default();
+        response.add_errrors(vec![format!("No links found matching {}", query)]);
+        return MatrixFormattedMessage {
+            plain_text: response.to_string(),
+            formatted_text: response.format_text(),
+        };
+    }
+    let mut plain_text = String::new();
+    let mut formatted_text = String::from("<ul>");
+    for candidate in candidates {
+        let url = &links[candidate];
+        plain_text.push_str(&format!("{}: {}\n", candidate, url));
+        formatted_text.push_str(&format!(
+            "<li>{}: <a href=\"{}\">{}</a></li>",
+            candidate, url, url
+        ));
+    }
+    formatted_text.push_str("</ul>");
+    MatrixFormattedMessage {
+        plain_text: plain_text.trim().to_string(),
+        formatted_text: Some(formatted_text),
+    }
+}
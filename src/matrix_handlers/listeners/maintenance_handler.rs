@@ -0,0 +1,107 @@
+//! Handler for the maintenance command and the banner it adds to command replies
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MaintenanceBanner, MatrixListenerConfig};
+use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+const USAGE: &str = "Usage: !maintenance on <message> or !maintenance off";
+
+/// Turns maintenance mode on with a message shown on command replies, or turns it off. Only admins can use it.
+pub(super) async fn maintenance_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for changing maintenance mode. Skipping...");
+        return;
+    }
+    let message = change_maintenance(&text.body, storage);
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+fn change_maintenance(body: &str, storage: &mut ListenerStorage) -> String {
+    let args = body
+        .split_once(char::is_whitespace)
+        .map(|(_, v)| v.trim())
+        .unwrap_or_default();
+    let (switch, message) = match args.split_once(char::is_whitespace) {
+        Some((switch, message)) => (switch.to_lowercase(), message.trim()),
+        None => (args.to_lowercase(), ""),
+    };
+    match (switch.as_str(), message) {
+        ("on", "") => USAGE.to_string(),
+        ("on", message) => {
+            info!("Maintenance mode turned on with message {:?}", message);
+            storage.maintenance = Some(message.to_string());
+            format!(
+                "Maintenance mode is on. Command replies will show: {}",
+                message
+            )
+        }
+        ("off", "") => match storage.maintenance.take() {
+            Some(_) => {
+                info!("Maintenance mode turned off");
+                "Maintenance mode is off".to_string()
+            }
+            None => "Maintenance mode is not on".to_string(),
+        },
+        _ => USAGE.to_string(),
+    }
+}
+
+/// Adds the maintenance message to a reply, placing it the way `placement` says
+///
+/// Only text and notices are changed. Everything else is returned as is.
+pub(super) fn with_banner(
+    message: MatrixMessageType,
+    banner: &str,
+    placement: MaintenanceBanner,
+) -> MatrixMessageType {
+    let place = |reply: String, separator: &str| match placement {
+        MaintenanceBanner::Prepend => format!("{}{}{}", banner, separator, reply),
+        MaintenanceBanner::Append => format!("{}{}{}", reply, separator, banner),
+        MaintenanceBanner::Replace => banner.to_string(),
+    };
+    let formatted = |message: MatrixFormattedMessage| MatrixFormattedMessage {
+        plain_text: place(message.plain_text, "\n"),
+        formatted_text: message.formatted_text.map(|v| place(v, "<br>")),
+    };
+    match message {
+        MatrixMessageType::Text(v) => MatrixMessageType::Text(place(v, "\n")),
+        MatrixMessageType::Notice(v) => MatrixMessageType::Notice(place(v, "\n")),
+        MatrixMessageType::FormattedText(v) => MatrixMessageType::FormattedText(formatted(v)),
+        MatrixMessageType::FormattedNotice(v) => MatrixMessageType::FormattedNotice(formatted(v)),
+        other => other,
+    }
+}
@@ -2,30 +2,199 @@
 //!
 //! Sub modules exist for performing various processes such as unit conversion
 //! and searching github
+//!
+//! Relevant tests are in a test submodule
+
+#[cfg(test)]
+mod tests;
 
+mod about_handler;
+mod announce_handler;
+mod audit_handler;
 mod ban_handler;
+mod color_conversion_handler;
+mod command_index;
 mod commandless_handler;
+mod config_check_handler;
+mod correction_handler;
+mod correctme_handler;
+mod cron_conversion_handler;
+mod data_rate_conversion_handler;
+mod date_conversion_handler;
+mod dpi_conversion_handler;
+mod encoding_handler;
+mod flood_handler;
+mod gas_mark_conversion_handler;
+mod grant_handler;
 mod help_handler;
+mod ignore_handler;
+mod ip_conversion_handler;
+mod karma_handler;
+mod link_handler;
+mod maintenance_handler;
+mod percent_conversion_handler;
+mod ping_handler;
+mod pingtest_handler;
+mod ratio_conversion_handler;
+mod raw_handler;
+mod reaction_roles_handler;
+mod reload_handler;
+mod roman_conversion_handler;
+mod stats_handler;
+mod timezone_handler;
+mod unban_handler;
 mod unit_conversion_handler;
+mod version_handler;
+mod warn_handler;
+mod wave_conversion_handler;
+mod words_conversion_handler;
 
-use self::ban_handler::ban_handler;
-use self::commandless_handler::commandless_handler;
+use self::about_handler::about_handler;
+use self::announce_handler::{announce_handler, unannounce_handler};
+use self::audit_handler::audit_handler;
+use self::ban_handler::{ban_authorized, ban_handler};
+use self::color_conversion_handler::color_conversion_handler;
+use self::command_index::command_index;
+use self::commandless_handler::{
+    commandless_edit_handler, commandless_handler, pinged_rooms, requests_here,
+    send_pending_corrections,
+};
+use self::config_check_handler::config_check_handler;
+use self::correction_handler::correction_handler;
+use self::correctme_handler::correctme_handler;
+use self::cron_conversion_handler::cron_conversion_handler;
+use self::data_rate_conversion_handler::data_rate_conversion_handler;
+use self::date_conversion_handler::date_conversion_handler;
+use self::dpi_conversion_handler::dpi_conversion_handler;
+use self::encoding_handler::encoding_handler;
+use self::flood_handler::flood_handler;
+use self::gas_mark_conversion_handler::gas_mark_conversion_handler;
+use self::grant_handler::grant_handler;
 use self::help_handler::help_handler;
+use self::ignore_handler::ignore_handler;
+use self::ip_conversion_handler::ip_conversion_handler;
+use self::karma_handler::karma_handler;
+use self::link_handler::link_handler;
+use self::maintenance_handler::{maintenance_handler, with_banner};
+use self::percent_conversion_handler::percent_conversion_handler;
+use self::ping_handler::ping_handler;
+use self::pingtest_handler::pingtest_handler;
+use self::ratio_conversion_handler::ratio_conversion_handler;
+use self::raw_handler::raw_handler;
+use self::reaction_roles_handler::{role_reaction_handler, roles_handler};
+use self::reload_handler::reload_handler;
+use self::roman_conversion_handler::{is_roman_conversion, roman_conversion_handler};
+use self::stats_handler::{send_stats_report, stats_handler};
+use self::timezone_handler::{time_conversion_handler, time_handler, tz_handler};
+use self::unban_handler::unban_handler;
 use self::unit_conversion_handler::unit_conversion_handler;
+use self::version_handler::version_handler;
+use self::warn_handler::warn_handler;
+use self::wave_conversion_handler::wave_conversion_handler;
+use self::words_conversion_handler::words_conversion_handler;
 use crate::config::{ListenerStorage, MatrixListenerConfig};
-use crate::messages::{MatrixInviteMessage, MatrixInviteType, MatrixMessage, MatrixMessageType};
+use crate::helpers::MatrixFormattedNoticeResponse;
+use crate::messages::{
+    MatrixFormattedMessage, MatrixInviteMessage, MatrixInviteType, MatrixMessage, MatrixMessageType,
+};
+use crate::regex::{
+    COLOR_CONVERSION, CRON_CONVERSION, DATA_RATE_CONVERSION, DATE_CONVERSION, DPI_CONVERSION,
+    GAS_MARK_CONVERSION, GREETING, IP_CONVERSION, PERCENT_CONVERSION, RATIO_CONVERSION,
+    TIME_CONVERSION, WAVE_CONVERSION, WORDS_CONVERSION,
+};
 use ruma::{
-    events::room::message::{Relation, TextMessageEventContent},
-    RoomId, UserId,
+    events::{
+        reaction::Relation as Annotation,
+        room::message::{MessageType, Relation, Replacement, TextMessageEventContent},
+    },
+    EventId, MilliSecondsSinceUnixEpoch, OwnedEventId, OwnedRoomAliasId, RoomId, UserId,
 };
-use tokio::sync::mpsc::Sender;
-use tracing::{debug, error, trace};
+use std::borrow::Cow;
+use std::fmt::Display;
+use std::time::{Instant, SystemTime};
+use tokio::sync::mpsc::{channel, Sender};
+use tracing::{debug, error, info, trace, warn};
 
-/// Dispatches incoming text events to a number of different handlers depending on various conditions
+/// Dispatches incoming text events, enforcing the reply budget of non-admin users if one is configured
 #[allow(clippy::too_many_arguments)]
 pub async fn handle_text_event(
     text: &TextMessageEventContent,
     relates_to: Option<&Relation>,
+    event_id: &EventId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    api_client: &reqwest::Client,
+    send: &mut Sender<MatrixMessage>,
+) {
+    handle_message(
+        text,
+        relates_to,
+        None,
+        event_id,
+        origin_server_ts,
+        sender,
+        room_id,
+        storage,
+        config,
+        api_client,
+        send,
+    )
+    .await
+}
+
+/// Dispatches edited text events through the same checks as new messages. Only unit conversions are performed on edits
+#[allow(clippy::too_many_arguments)]
+pub async fn handle_edit_event(
+    replacement: &Replacement,
+    event_id: &EventId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    api_client: &reqwest::Client,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if !config.convert_on_edit {
+        debug!("Message is an edit and conversions on edit are disabled, skipping handling");
+        return;
+    }
+    let text = match &replacement.new_content.msgtype {
+        MessageType::Text(t) => t,
+        _ => {
+            debug!("Edit is not a text message, skipping handling");
+            return;
+        }
+    };
+    handle_message(
+        text,
+        None,
+        Some(&replacement.event_id),
+        event_id,
+        origin_server_ts,
+        sender,
+        room_id,
+        storage,
+        config,
+        api_client,
+        send,
+    )
+    .await
+}
+
+/// Checks a new or edited message against the flood limit, ignore list, reply budget and maintenance mode before dispatching it
+///
+/// `edited` is the event an edit replaces, or None for new messages.
+#[allow(clippy::too_many_arguments)]
+async fn handle_message(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    edited: Option<&EventId>,
+    event_id: &EventId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
     sender: &UserId,
     room_id: &RoomId,
     storage: &mut ListenerStorage,
@@ -33,26 +202,598 @@ pub async fn handle_text_event(
     api_client: &reqwest::Client,
     send: &mut Sender<MatrixMessage>,
 ) {
-    if !&text.body.starts_with('!') {
+    if is_own_event(sender, config) {
+        trace!("Message is from self, doing nothing");
+        return;
+    }
+    if edited.is_none() {
+        storage.activity.messages += 1;
+    }
+    if flood_handler(sender, room_id, storage, config, send).await {
+        debug!("User {} was caught flooding. Skipping...", sender);
+        return;
+    }
+    if is_ignored(sender, storage, config) {
+        debug!("User {} is ignored. Skipping...", sender);
+        return;
+    }
+    let reply = match relates_to {
+        Some(Relation::Reply { .. }) => strip_reply_fallback(text),
+        _ => None,
+    };
+    let text = reply.as_ref().unwrap_or(text);
+    let command = normalize_command(text, room_id, config);
+    let is_command = command.is_some();
+    let text = command.as_deref().unwrap_or(text);
+    let limit = match config.max_replies_per_user {
+        Some(v) if !storage.is_admin(sender, &config.admins, SystemTime::now()) => Some(v),
+        _ => None,
+    };
+    let now = Instant::now();
+    if let Some(limit) = limit {
+        if storage.reply_budget_exhausted(sender, limit, now) {
+            debug!(
+                "User {} has exhausted their reply budget. Skipping...",
+                sender
+            );
+            return;
+        }
+    }
+    // Replies to the maintenance command itself never carry the banner
+    let banner = match &storage.maintenance {
+        Some(v) if is_command && command_name(&text.body) != "maintenance" => Some(v.clone()),
+        _ => None,
+    };
+    if limit.is_none() && banner.is_none() {
+        dispatch_text_event(
+            text,
+            is_command,
+            relates_to,
+            edited,
+            event_id,
+            origin_server_ts,
+            sender,
+            room_id,
+            storage,
+            config,
+            api_client,
+            send,
+        )
+        .await;
+        return;
+    }
+
+    // Replies are routed through a local channel so they can be counted against the budget
+    // and carry the maintenance banner
+    let (mut replies, mut recv) = channel(1);
+    let dispatch = {
+        let storage = &mut *storage;
+        async move {
+            dispatch_text_event(
+                text,
+                is_command,
+                relates_to,
+                edited,
+                event_id,
+                origin_server_ts,
+                sender,
+                room_id,
+                storage,
+                config,
+                api_client,
+                &mut replies,
+            )
+            .await
+        }
+    };
+    let forward = async {
+        let mut count = 0;
+        while let Some(mut message) = recv.recv().await {
+            // Typing isn't a reply, so it doesn't count against the budget
+            if !matches!(message.message, MatrixMessageType::Typing(_)) {
+                count += 1;
+            }
+            if let Some(banner) = &banner {
+                message.message = with_banner(message.message, banner, config.maintenance_banner);
+            }
+            if send.send(message).await.is_err() {
+                error!("Channel closed. Unable to send message.");
+            }
+        }
+        count
+    };
+    let ((), count) = tokio::join!(dispatch, forward);
+    if let (Some(limit), true) = (limit, count > 0) {
+        storage.record_replies(sender, count, now);
+        if storage.reply_budget_exhausted(sender, limit, now) {
+            info!(
+                "User {} reached the reply budget of {} replies per hour",
+                sender, limit
+            );
+        }
+    }
+}
+
+/// Dispatches incoming text events to a number of different handlers depending on various conditions
+#[allow(clippy::too_many_arguments)]
+async fn dispatch_text_event(
+    text: &TextMessageEventContent,
+    is_command: bool,
+    relates_to: Option<&Relation>,
+    edited: Option<&EventId>,
+    event_id: &EventId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    api_client: &reqwest::Client,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if let Some(original) = edited {
+        dispatch_edit_event(
+            text, is_command, original, event_id, room_id, storage, config, send,
+        )
+        .await;
+        return;
+    }
+    if !is_command
+        && config.direct_messages.about.is_some()
+        && storage.is_direct_message(room_id)
+        && GREETING.is_match(&text.body)
+    {
+        debug!("Entering about path...");
+        about_handler(event_id, room_id, storage, config, send).await;
+        return;
+    }
+    if !is_command {
         debug!("Entering no command path...");
         commandless_handler(
-            text, relates_to, sender, room_id, storage, config, api_client, send,
+            text, relates_to, event_id, sender, room_id, storage, config, api_client, send,
+        )
+        .await;
+        return;
+    }
+    let command = command_name(&text.body);
+    if storage.is_direct_message(room_id) && !config.direct_messages.commands {
+        debug!("Commands are disabled in direct messages. Skipping...");
+        return;
+    }
+    if !config.command_allowed(room_id, &command) {
+        debug!("Command is not allowed in room {}. Skipping...", room_id);
+        return;
+    }
+    if command == "convert" {
+        storage.activity.conversions += 1;
+    }
+    if IP_CONVERSION.is_match(&text.body) {
+        debug!("Entering IP conversion path...");
+        ip_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if DATE_CONVERSION.is_match(&text.body.to_lowercase()) {
+        debug!("Entering date conversion path...");
+        date_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if TIME_CONVERSION.is_match(&text.body) {
+        debug!("Entering time conversion path...");
+        let now = SystemTime::now().into();
+        time_conversion_handler(
+            text, relates_to, event_id, sender, room_id, storage, now, send,
         )
         .await
-    } else if text.body.to_lowercase().starts_with("!convert ") {
+    } else if RATIO_CONVERSION.is_match(&text.body.to_lowercase()) {
+        debug!("Entering aspect ratio conversion path...");
+        ratio_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if PERCENT_CONVERSION.is_match(&text.body.to_lowercase()) {
+        debug!("Entering percentage conversion path...");
+        percent_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if is_roman_conversion(&text.body, room_id, config) {
+        debug!("Entering Roman numeral conversion path...");
+        roman_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if GAS_MARK_CONVERSION.is_match(&text.body) {
+        debug!("Entering gas mark conversion path...");
+        gas_mark_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if WAVE_CONVERSION.is_match(&text.body) {
+        debug!("Entering wave conversion path...");
+        wave_conversion_handler(text, relates_to, event_id, room_id, config, send).await
+    } else if DATA_RATE_CONVERSION.is_match(&text.body) {
+        debug!("Entering data rate conversion path...");
+        data_rate_conversion_handler(text, relates_to, event_id, room_id, config, send).await
+    } else if DPI_CONVERSION.is_match(&text.body.to_lowercase()) {
+        debug!("Entering pixel density conversion path...");
+        dpi_conversion_handler(text, relates_to, event_id, room_id, config, send).await
+    } else if COLOR_CONVERSION.is_match(&text.body) {
+        debug!("Entering color conversion path...");
+        color_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if CRON_CONVERSION.is_match(&text.body) {
+        debug!("Entering cron conversion path...");
+        cron_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if WORDS_CONVERSION.is_match(&text.body) {
+        debug!("Entering number word conversion path...");
+        words_conversion_handler(text, relates_to, event_id, room_id, send).await
+    } else if text.body.to_lowercase().starts_with("!convert ")
+        || text.body.trim().eq_ignore_ascii_case("!convert")
+    {
         debug!("Entering unit conversion path...");
-        unit_conversion_handler(text, relates_to, room_id, send).await
+        unit_conversion_handler(
+            text,
+            relates_to,
+            event_id,
+            room_id,
+            Some(storage),
+            config,
+            send,
+        )
+        .await
+    } else if text.body.to_lowercase().starts_with("!encode ")
+        || text.body.to_lowercase().starts_with("!decode ")
+    {
+        debug!("Entering encoding path...");
+        encoding_handler(text, event_id, room_id, send).await
     } else if text.body.to_lowercase().starts_with("!help") {
         debug!("Entering help path...");
-        help_handler(text, room_id, config, send).await
+        help_handler(text, relates_to, event_id, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!link") {
+        debug!("Entering link search path...");
+        link_handler(text, event_id, room_id, config, send).await
+    } else if command == "version" {
+        debug!("Entering version path...");
+        version_handler(event_id, room_id, send).await
+    } else if command == "ping" {
+        debug!("Entering ping path...");
+        ping_handler(event_id, origin_server_ts, room_id, config, send).await
+    } else if text.body.to_lowercase().starts_with("!pingtest") {
+        debug!("Entering group ping test path...");
+        pingtest_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!tz") {
+        debug!("Entering timezone path...");
+        tz_handler(text, event_id, sender, room_id, storage, send).await
+    } else if text.body.to_lowercase().starts_with("!time") {
+        debug!("Entering time path...");
+        let now = SystemTime::now().into();
+        time_handler(text, event_id, sender, room_id, storage, now, send).await
+    } else if text.body.to_lowercase().starts_with("!karma") {
+        debug!("Entering karma path...");
+        karma_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!announce ") {
+        debug!("Entering announce path...");
+        announce_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!unannounce") {
+        debug!("Entering unannounce path...");
+        unannounce_handler(sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!correctme") {
+        debug!("Entering correction opt-out path...");
+        correctme_handler(text, event_id, sender, room_id, storage, send).await
+    } else if text.body.to_lowercase().starts_with("!correction") {
+        debug!("Entering correction path...");
+        correction_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!stats") {
+        debug!("Entering stats path...");
+        stats_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!auditlog") {
+        debug!("Entering audit log path...");
+        audit_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!grant ")
+        || text.body.to_lowercase().starts_with("!revoke ")
+    {
+        debug!("Entering grant path...");
+        grant_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!reload") {
+        debug!("Entering reload path...");
+        reload_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!configcheck") {
+        debug!("Entering config check path...");
+        config_check_handler(event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!raw") {
+        debug!("Entering raw event path...");
+        raw_handler(relates_to, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!ignore")
+        || text.body.to_lowercase().starts_with("!unignore")
+    {
+        debug!("Entering ignore path...");
+        ignore_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!roles") {
+        debug!("Entering reaction roles path...");
+        roles_handler(event_id, sender, room_id, storage, config, send).await
+    } else if matches!(command.as_str(), "warn" | "warnings" | "clearwarnings") {
+        debug!("Entering warning path...");
+        warn_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!maintenance") {
+        debug!("Entering maintenance path...");
+        maintenance_handler(text, event_id, sender, room_id, storage, config, send).await
+    } else if text.body.to_lowercase().starts_with("!unban") {
+        debug!("Entering unban path...");
+        unban_handler(text, event_id, sender, room_id, storage, config, send).await
     } else if text.body.to_lowercase().starts_with("!ban") {
-        debug!("Entering help path...");
-        ban_handler(text, config, sender, send).await;
+        debug!("Entering ban path...");
+        ban_handler(text, event_id, storage, config, sender, room_id, send).await;
     } else {
         debug!("Doing nothing...");
     }
 }
 
+/// Dispatches edited text events. Only unit conversions are performed on edits
+#[allow(clippy::too_many_arguments)]
+async fn dispatch_edit_event(
+    text: &TextMessageEventContent,
+    is_command: bool,
+    original: &EventId,
+    event_id: &EventId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if !is_command {
+        debug!("Entering edited no command path...");
+        commandless_edit_handler(text, original, room_id, storage, config, send).await;
+        return;
+    }
+    if storage.is_direct_message(room_id) && !config.direct_messages.commands {
+        debug!("Commands are disabled in direct messages. Skipping...");
+        return;
+    }
+    if text.body.to_lowercase().starts_with("!convert ")
+        && config.command_allowed(room_id, "convert")
+    {
+        debug!("Entering edited unit conversion path...");
+        storage.activity.conversions += 1;
+        unit_conversion_handler(text, None, event_id, room_id, Some(storage), config, send).await
+    } else {
+        debug!("Edited message is not a conversion. Doing nothing...")
+    }
+}
+
+/// Returns the root of the thread a message was sent in, so replies can be sent in the same thread.
+fn thread_root(relates_to: Option<&Relation>) -> Option<OwnedEventId> {
+    match relates_to {
+        Some(Relation::Thread(t)) => Some(t.event_id.clone()),
+        _ => None,
+    }
+}
+
+/// Sends the result of a conversion command as a notice, listing the error if the conversion failed
+async fn send_conversion_reply(
+    result: Result<String, impl Display>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    relates_to: Option<&Relation>,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let message = match result {
+        Ok(v) => MatrixFormattedMessage {
+            plain_text: v,
+            formatted_text: None,
+        },
+        Err(e) => {
+            let mut response = MatrixFormattedNoticeResponse::default();
+            response.add_errrors(vec![e.to_string()]);
+            MatrixFormattedMessage {
+                plain_text: response.to_string(),
+                formatted_text: response.format_text(),
+            }
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::FormattedNotice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: thread_root(relates_to),
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Checks if an event was sent by the bot itself.
+///
+/// Events from the bot are never handled so its own messages, edits, and reactions can't trigger it again.
+fn is_own_event(sender: &UserId, config: &MatrixListenerConfig) -> bool {
+    sender == config.mx_uname
+}
+
+/// Checks if the bot should ignore a user. Admins are never ignored.
+fn is_ignored(sender: &UserId, storage: &ListenerStorage, config: &MatrixListenerConfig) -> bool {
+    storage.ignored_users.contains(sender)
+        && !storage.is_admin(sender, &config.admins, SystemTime::now())
+}
+
+/// Commands that are still performed when their event is older than the configured maximum age
+const MODERATION_COMMANDS: [&str; 3] = ["ban", "unban", "warn"];
+
+/// Checks if an event is older than the configured maximum event age and should be skipped.
+///
+/// Moderation commands are never skipped, whatever prefix they use in the room.
+pub fn is_stale_event(
+    text: &TextMessageEventContent,
+    room_id: &RoomId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    config: &MatrixListenerConfig,
+    now: SystemTime,
+) -> bool {
+    let max_age = match config.max_event_age {
+        Some(v) => v,
+        None => return false,
+    };
+    if normalize_command(text, room_id, config)
+        .is_some_and(|t| MODERATION_COMMANDS.contains(&command_name(&t.body).as_str()))
+    {
+        return false;
+    }
+    match origin_server_ts
+        .to_system_time()
+        .and_then(|t| now.duration_since(t).ok())
+    {
+        Some(age) => age > max_age,
+        None => false,
+    }
+}
+
+/// Checks if a message pings %here in a room whose members haven't been fetched recently.
+///
+/// The listener fetches the members before handling the message so the ping can include them.
+pub fn needs_room_members(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    config: &MatrixListenerConfig,
+    now: Instant,
+) -> bool {
+    requests_here(text, sender, config) && storage.room_members(room_id, now).is_none()
+}
+
+/// Returns the rooms of the groups a message pings whose members haven't been fetched recently.
+///
+/// The listener fetches their members before handling the message so the ping can include them.
+pub fn stale_group_rooms(
+    text: &TextMessageEventContent,
+    sender: &UserId,
+    storage: &ListenerStorage,
+    config: &MatrixListenerConfig,
+    now: Instant,
+) -> Vec<OwnedRoomAliasId> {
+    pinged_rooms(text, sender, config)
+        .into_iter()
+        .filter(|room| storage.alias_members(room, now).is_none())
+        .collect()
+}
+
+/// Returns the lowercased name of the command in `body` without its leading `!`
+fn command_name(body: &str) -> String {
+    body.trim_start_matches('!')
+        .split_whitespace()
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+/// Removes the quote of the replied to message that clients put at the start of a reply's body.
+///
+/// Returns `None` if the body doesn't start with a quote.
+fn strip_reply_fallback(text: &TextMessageEventContent) -> Option<TextMessageEventContent> {
+    let (quote, reply) = text.body.split_once("\n\n")?;
+    if quote.is_empty() || !quote.lines().all(|l| l.starts_with('>')) {
+        return None;
+    }
+    let mut text = text.clone();
+    text.body = reply.to_string();
+    Some(text)
+}
+
+/// Rewrites a command using the room's command prefix, or a configured slash command like "/convert 20c",
+/// into its bang form "!convert 20c".
+///
+/// Returns `None` if the message isn't a command in the room.
+fn normalize_command<'a>(
+    text: &'a TextMessageEventContent,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+) -> Option<Cow<'a, TextMessageEventContent>> {
+    let prefix = config.command_prefix(room_id);
+    let command = match text.body.strip_prefix(prefix) {
+        Some(_) if prefix == "!" => return Some(Cow::Borrowed(text)),
+        Some(v) => {
+            trace!(
+                "Treating {:?} command {:?} as a bang command",
+                prefix,
+                text.body
+            );
+            v
+        }
+        None => {
+            let command = text.body.strip_prefix('/')?;
+            if !config.slash_commands.contains(&command_name(command)) {
+                return None;
+            }
+            trace!("Treating slash command {:?} as a bang command", text.body);
+            command
+        }
+    };
+    let mut text = text.clone();
+    text.body = format!("!{}", command);
+    Some(Cow::Owned(text))
+}
+
+/// Checks if the same conversions were already answered in the room within the duplicate conversion window.
+///
+/// Nothing is a duplicate without storage, so edits are always converted again.
+fn is_duplicate_conversion(
+    conversions: &[(String, String)],
+    room_id: &RoomId,
+    storage: Option<&mut ListenerStorage>,
+    config: &MatrixListenerConfig,
+) -> bool {
+    let (storage, window) = match (storage, config.duplicate_conversion_window) {
+        (Some(s), Some(w)) => (s, w),
+        _ => return false,
+    };
+    let request: Vec<String> = conversions
+        .iter()
+        .map(|(quantity, unit)| format!("{}{}", quantity, unit))
+        .collect();
+    storage.duplicate_conversion(room_id, &request.join(" "), window, Instant::now())
+}
+
+/// Shows the bot as typing in a room while it works on a reply, or stops showing it
+async fn send_typing(room_id: &RoomId, typing: bool, send: &mut Sender<MatrixMessage>) {
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Typing(typing),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Returns `authorized`, first telling the sender they can't use the command if it is false
+/// and unauthorized replies are enabled.
+///
+/// Shared by every admin command so unauthorized users are treated the same everywhere.
+/// Each user is told at most once per cooldown.
+async fn check_authorized(
+    authorized: bool,
+    trigger_event: Option<&EventId>,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) -> bool {
+    if authorized {
+        return true;
+    }
+    if !config.enable_unauthorized_reply {
+        return false;
+    }
+    if !storage.unauthorized_reply_cooldown(sender, Instant::now()) {
+        debug!(
+            "{} was recently told they are unauthorized. Skipping reply...",
+            sender
+        );
+        return false;
+    }
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(config.unauthorized_reply.clone()),
+            trigger_event: trigger_event.map(|e| e.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+    false
+}
+
 /// Accepts or rejects invites to rooms from matrix users
 pub async fn handle_invite_event(
     sender: &UserId,
@@ -61,30 +802,50 @@ pub async fn handle_invite_event(
     send: &mut Sender<MatrixMessage>,
 ) {
     trace!("Invited by {} to room {} ", &sender, &room_id);
-    if config.admins.contains(sender) {
-        let message = MatrixInviteMessage {
-            kind: MatrixInviteType::Accept,
-            sender: sender.to_owned(),
-        };
-        if send
-            .send(MatrixMessage {
-                room_id: Some(room_id.to_owned()),
-                message: MatrixMessageType::Invite(message),
-            })
-            .await
-            .is_err()
-        {
-            error!("Channel closed. Unable to send message.");
-        }
+    let kind = if config.blocked_servers.contains(room_id.server_name())
+        || config.blocked_servers.contains(sender.server_name())
+    {
+        warn!(
+            "Rejecting invite from {} to room {} because it involves a blocked server",
+            sender, room_id
+        );
+        MatrixInviteType::Reject
+    } else if config.admins.contains(sender) {
+        MatrixInviteType::Accept
     } else {
-        let message = MatrixInviteMessage {
-            kind: MatrixInviteType::Reject,
-            sender: sender.to_owned(),
-        };
+        MatrixInviteType::Reject
+    };
+    // The index is posted after the bot joins since messages are sent in order
+    let index = match kind {
+        MatrixInviteType::Accept if config.enable_command_index => {
+            Some(command_index(room_id, config))
+        }
+        _ => None,
+    };
+    let message = MatrixInviteMessage {
+        kind,
+        sender: sender.to_owned(),
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Invite(message),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+        return;
+    }
+    if let Some(index) = index {
         if send
             .send(MatrixMessage {
                 room_id: Some(room_id.to_owned()),
-                message: MatrixMessageType::Invite(message),
+                message: MatrixMessageType::Announce(index),
+                trigger_event: None,
+                thread_root: None,
             })
             .await
             .is_err()
@@ -93,3 +854,101 @@ pub async fn handle_invite_event(
         }
     }
 }
+
+/// Asks the responder to redact its responses to a message that has been redacted
+pub async fn handle_redaction_event(
+    redacts: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if !config.enable_response_redaction {
+        trace!("Response redaction is disabled. Skipping...");
+        return;
+    }
+    if is_own_event(sender, config) {
+        trace!("Redaction is from self, doing nothing");
+        return;
+    }
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::RedactResponses(redacts.to_owned()),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Forwards an admin's confirmation reaction so the responder can perform the action awaiting confirmation
+///
+/// Reactions with a role's emoji in the reaction roles room invite the user to the role's room instead.
+pub async fn handle_reaction_event(
+    relates_to: &Annotation,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if is_own_event(sender, config) {
+        trace!("Reaction is from self, doing nothing");
+        return;
+    }
+    if is_ignored(sender, storage, config) {
+        trace!("Reaction is from an ignored user, doing nothing");
+        return;
+    }
+    if role_reaction_handler(&relates_to.key, sender, room_id, storage, config, send).await {
+        return;
+    }
+    if !config.confirmation_reaction.matches(&relates_to.key) {
+        trace!("Reaction is not a confirmation. Skipping...");
+        return;
+    }
+    // Only bans wait on confirmation, so only users that can ban can confirm
+    if !ban_authorized(sender, storage, config) {
+        debug!("Unauthorized user for confirming actions. Skipping...");
+        return;
+    }
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::ConfirmAction(relates_to.event_id.clone()),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Sends the batched corrections of users whose burst of misspellings has ended
+pub async fn handle_pending_corrections(
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+    now: Instant,
+) {
+    if storage.pending_corrections.is_empty() {
+        return;
+    }
+    send_pending_corrections(storage, config, send, now).await
+}
+
+/// Posts the periodic stats report to the configured stats room when it is due
+pub async fn handle_stats_report(
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+    now: SystemTime,
+) {
+    send_stats_report(storage, config, send, now).await
+}
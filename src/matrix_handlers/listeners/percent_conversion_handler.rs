@@ -0,0 +1,35 @@
+//! Handler for the percentage, fraction, and ratio conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{convert_fraction, FractionFormat};
+use crate::messages::MatrixMessage;
+use crate::regex::PERCENT_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based percentage conversion handler that replies with a proportion as a decimal, percentage, fraction, and ratio
+pub(super) async fn percent_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let body = text.body.to_lowercase();
+    let cap = match PERCENT_CONVERSION.captures(&body) {
+        Some(v) => v,
+        None => {
+            debug!("No percentage, fraction, or ratio found. No reply will be constructed.");
+            return;
+        }
+    };
+    let target = cap
+        .get(2)
+        .and_then(|m| FractionFormat::from_target(m.as_str()));
+    let result = convert_fraction(&cap[1], target);
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,76 @@
+//! Handler for the command that shows the bot is up and how long it takes to see messages
+
+use crate::config::{MatrixListenerConfig, NAME, VERSION};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{EventId, MilliSecondsSinceUnixEpoch, RoomId};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tracing::{error, trace};
+
+/// Replies with the time between the command being sent and the bot seeing it, plus the bot's uptime.
+/// Limited to the help rooms.
+pub(super) async fn ping_handler(
+    event_id: &EventId,
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if !config.help_rooms.is_empty() && !config.help_rooms.contains(room_id) {
+        trace!(
+            "Rooms are limited and room {} is not in the allowed list of help command rooms",
+            room_id
+        );
+        return;
+    }
+    let message = ping_message(origin_server_ts, config.start_time, SystemTime::now());
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Builds the ping reply. Latency is 0 if the homeserver's clock is ahead of the bot's.
+pub(super) fn ping_message(
+    origin_server_ts: MilliSecondsSinceUnixEpoch,
+    start_time: SystemTime,
+    now: SystemTime,
+) -> String {
+    let latency = origin_server_ts
+        .to_system_time()
+        .and_then(|t| now.duration_since(t).ok())
+        .unwrap_or_default();
+    let uptime = now.duration_since(start_time).unwrap_or_default();
+    format!(
+        "{} v{} is up. Latency: {}ms, uptime: {}",
+        NAME,
+        VERSION,
+        latency.as_millis(),
+        format_uptime(uptime)
+    )
+}
+
+/// Formats a duration as days, hours, minutes, and seconds, leaving out leading zero units
+fn format_uptime(uptime: Duration) -> String {
+    let secs = uptime.as_secs();
+    let units = [
+        (secs / 86400, "d"),
+        (secs / 3600 % 24, "h"),
+        (secs / 60 % 60, "m"),
+        (secs % 60, "s"),
+    ];
+    let first = units.iter().position(|(v, _)| *v > 0).unwrap_or(3);
+    units[first..]
+        .iter()
+        .map(|(v, unit)| format!("{}{}", v, unit))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,98 @@
+//! Handler for previewing group pings
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Replies with the users a group ping would notify, without mentioning them
+///
+/// Replies are notices, which clients do not notify for, so the listed users are not pinged
+pub(super) async fn pingtest_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = match config.group_pings.read() {
+        Ok(v) => v.users.contains(sender),
+        Err(_) => {
+            error!("Group ping lock is poisoned. Ignoring...");
+            return;
+        }
+    } || storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("User not authorized for group ping tests. Ignoring...");
+        return;
+    }
+    let message = {
+        let group_pings = match config.group_pings.read() {
+            Ok(v) => v,
+            Err(_) => {
+                error!("Group ping lock is poisoned. Ignoring...");
+                return;
+            }
+        };
+        match text.body.split_whitespace().nth(1) {
+            Some(v) => {
+                let group = v.trim_start_matches('%').to_lowercase();
+                match group_pings.members(&group) {
+                    Some(users) => {
+                        let mut users: Vec<_> = users.iter().map(|u| u.as_str()).collect();
+                        users.sort_unstable();
+                        let mut message = format!(
+                            "%{} would ping {} user{}",
+                            group,
+                            users.len(),
+                            if users.len() == 1 { "" } else { "s" }
+                        );
+                        if !users.is_empty() {
+                            message.push_str(&format!(":\n{}", users.join("\n")));
+                        }
+                        let rooms = group_pings.rooms_of(&group);
+                        if !rooms.is_empty() {
+                            let mut rooms: Vec<_> = rooms.iter().map(|r| r.as_str()).collect();
+                            rooms.sort_unstable();
+                            message
+                                .push_str(&format!("\nplus the members of {}", rooms.join(", ")));
+                        }
+                        message
+                    }
+                    None if group == "here" => {
+                        "%here would ping the members currently in the room".to_string()
+                    }
+                    None => format!("Unknown group %{}", group),
+                }
+            }
+            None => "Usage: !pingtest <group>".to_string(),
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
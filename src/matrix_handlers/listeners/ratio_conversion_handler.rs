@@ -0,0 +1,56 @@
+//! Handler for the aspect ratio conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{aspect_ratio, resolution, resolution_height};
+use crate::messages::MatrixMessage;
+use crate::regex::RATIO_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based aspect ratio conversion handler that replies with the simplified ratio or the resolution
+pub(super) async fn ratio_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let body = text.body.to_lowercase();
+    let cap = match RATIO_CONVERSION.captures(&body) {
+        Some(v) => v,
+        None => {
+            debug!("No resolution or aspect ratio found. No reply will be constructed.");
+            return;
+        }
+    };
+    let number = |i: usize| cap.get(i).and_then(|m| m.as_str().parse::<u32>().ok());
+    let result = if cap.get(1).is_some() {
+        match (number(1), number(2)) {
+            (Some(w), Some(h)) => match aspect_ratio(w, h) {
+                Some((rw, rh)) => Ok(format!("{}x{} => {}:{}", w, h, rw, rh)),
+                None => Err(format!("{}x{} is not a valid resolution", w, h)),
+            },
+            _ => Err(format!("{}x{} is not a valid resolution", &cap[1], &cap[2])),
+        }
+    } else {
+        let height = &cap[5];
+        match (number(3), number(4), resolution_height(height)) {
+            (Some(rw), Some(rh), Some(h)) => match resolution(rw, rh, h) {
+                Some((w, h)) => Ok(format!("{}:{} at {} => {}x{}", rw, rh, height, w, h)),
+                None => Err(format!(
+                    "{}:{} at {} is not a valid resolution",
+                    rw, rh, height
+                )),
+            },
+            _ => Err(format!(
+                "{}:{} at {} is not a valid resolution",
+                &cap[3], &cap[4], height
+            )),
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,57 @@
+//! Handler for the raw event command
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::Relation, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Shows the redacted raw JSON of the message the command replies to. Only admins can use it.
+pub(super) async fn raw_handler(
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for showing raw events. Skipping...");
+        return;
+    }
+    let message = match relates_to {
+        Some(Relation::Reply { in_reply_to }) => {
+            MatrixMessageType::RawEvent(in_reply_to.event_id.clone())
+        }
+        _ => {
+            debug!("Raw event command is not a reply. Sending usage...");
+            MatrixMessageType::Notice("Reply to a message with !raw to see its JSON".to_string())
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message,
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
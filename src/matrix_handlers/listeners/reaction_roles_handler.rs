@@ -0,0 +1,110 @@
+//! Handlers for reaction roles, which invite users to rooms when they react with a configured emoji
+
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig, ReactionRoles};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{EventId, RoomId, UserId};
+use std::time::{Instant, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info, trace};
+
+/// Builds the message listing the emoji users can react with and the room each one invites to.
+pub(super) fn role_message(roles: &ReactionRoles) -> String {
+    let mut message = String::from("React to be invited to a room:");
+    for (emoji, room) in &roles.roles {
+        message.push_str(&format!("\n{} {}", emoji, room));
+    }
+    message
+}
+
+/// Posts the role message in the reaction roles room and reacts to it with every role's emoji.
+/// Only admins can use it.
+pub(super) async fn roles_handler(
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for posting the role message. Skipping...");
+        return;
+    }
+    let roles = match &config.reaction_roles {
+        Some(v) => v,
+        None => {
+            debug!("Reaction roles are disabled. Skipping...");
+            return;
+        }
+    };
+    let emoji = roles.roles.iter().map(|(e, _)| e.clone()).collect();
+    if send
+        .send(MatrixMessage {
+            room_id: Some(roles.room.clone()),
+            message: MatrixMessageType::RoleMessage(role_message(roles), emoji),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Invites a user to the room of the role they reacted with in the reaction roles room.
+///
+/// Returns true if the reaction was a role, whether or not an invite was sent.
+pub(super) async fn role_reaction_handler(
+    key: &str,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) -> bool {
+    let target = match &config.reaction_roles {
+        Some(roles) if roles.room == room_id => match roles.room_for(key) {
+            Some(v) => v,
+            None => return false,
+        },
+        _ => return false,
+    };
+    if !storage.role_invite_cooldown(target, sender, Instant::now()) {
+        trace!(
+            "User {} was recently invited to {}. Skipping...",
+            sender,
+            target
+        );
+        return true;
+    }
+    info!(
+        "Inviting {} to {} for reacting with {}",
+        sender, target, key
+    );
+    if send
+        .send(MatrixMessage {
+            room_id: Some(target.clone()),
+            message: MatrixMessageType::InviteUser(sender.to_owned()),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+    true
+}
@@ -0,0 +1,78 @@
+//! Handler for the reload command
+
+use super::check_authorized;
+use crate::config::{Config, ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+/// Reloads the requested part of the config file and replies with the result
+pub(super) async fn reload_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for reloading config. Skipping...");
+        return;
+    }
+    let message = match text.body.split_whitespace().nth(1) {
+        Some(v) if v.eq_ignore_ascii_case("pings") => {
+            match Config::read_config_file().and_then(|c| config.reload_group_pings(&c)) {
+                Ok(count) => {
+                    info!("Reloaded {} group pings", count);
+                    format!("Reloaded {} group pings", count)
+                }
+                Err(e) => {
+                    error!("Unable to reload group pings: {:?}", e);
+                    format!("Unable to reload group pings: {}", e)
+                }
+            }
+        }
+        Some(v) if v.eq_ignore_ascii_case("links") => {
+            match Config::read_config_file().and_then(|c| config.reload_links(&c)) {
+                Ok((repos, links)) => {
+                    info!("Reloaded {} searchable repos and {} links", repos, links);
+                    format!("Reloaded {} searchable repos and {} links", repos, links)
+                }
+                Err(e) => {
+                    error!("Unable to reload links: {:?}", e);
+                    format!("Unable to reload links: {}", e)
+                }
+            }
+        }
+        _ => {
+            debug!("Reload command doesnt name something reloadable. Skipping...");
+            return;
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
@@ -0,0 +1,72 @@
+//! Handler for the Roman numeral conversion command
+
+use super::send_conversion_reply;
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{conversion_target, from_roman, to_roman, unit_key, MAX_ROMAN, MIN_ROMAN};
+use crate::messages::MatrixMessage;
+use crate::regex::ROMAN_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Checks if a conversion command is for a Roman numeral or number.
+///
+/// Numerals that are also unit names, like C for Celsius, are left to the unit conversion handler
+/// unless they're followed by "roman".
+pub(super) fn is_roman_conversion(
+    body: &str,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+) -> bool {
+    match ROMAN_CONVERSION.captures(body) {
+        Some(cap) => match (cap.get(1), cap.get(2)) {
+            (Some(numeral), None) => conversion_target(
+                &unit_key(numeral.as_str()),
+                config.custom_conversions.get(room_id),
+            )
+            .is_none(),
+            _ => true,
+        },
+        None => false,
+    }
+}
+
+/// Command based Roman numeral conversion handler that replies with the number or numeral
+pub(super) async fn roman_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match ROMAN_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No Roman numeral or number found. No reply will be constructed.");
+            return;
+        }
+    };
+    let result = match (cap.get(1), cap.get(3)) {
+        (Some(numeral), _) => match from_roman(numeral.as_str()) {
+            Some(v) => Ok(format!("{} => {}", numeral.as_str(), v)),
+            None => Err(format!("{} is not a valid Roman numeral", numeral.as_str())),
+        },
+        (None, Some(number)) => match number.as_str().parse().ok().and_then(to_roman) {
+            Some(v) => Ok(format!("{} => {}", number.as_str(), v)),
+            None => Err(format!(
+                "{} is out of range. Roman numerals can only represent {} to {}",
+                number.as_str(),
+                MIN_ROMAN,
+                MAX_ROMAN
+            )),
+        },
+        (None, None) => {
+            error!("Somehow lost Roman numeral between regex match and conversion!");
+            return;
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
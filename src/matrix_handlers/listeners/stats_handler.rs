@@ -0,0 +1,125 @@
+//! Handler for the stats command
+
+use super::check_authorized;
+use crate::config::{ActivityStats, CorrectionStats, ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+/// Number of spellings listed in correction stats
+const TOP_SPELLINGS: usize = 5;
+
+/// Replies with statistics about a feature in the current room
+pub(super) async fn stats_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for stats. Skipping...");
+        return;
+    }
+    let message = match text.body.split_whitespace().nth(1) {
+        Some(v) if v.eq_ignore_ascii_case("corrections") => {
+            correction_stats(storage.correction_stats.get(room_id))
+        }
+        _ => "Usage: !stats corrections".to_string(),
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Renders the total corrections in a room and the spellings corrected most often
+fn correction_stats(stats: Option<&CorrectionStats>) -> String {
+    let stats = match stats {
+        Some(v) if v.total > 0 => v,
+        _ => return "No corrections made in this room".to_string(),
+    };
+    let mut spellings: Vec<_> = stats.spellings.iter().collect();
+    spellings.sort_by(|(a, a_count), (b, b_count)| b_count.cmp(a_count).then(a.cmp(b)));
+    let top = spellings
+        .iter()
+        .take(TOP_SPELLINGS)
+        .map(|(spelling, count)| format!("{} ({})", spelling, count))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "{} corrections made in this room\nMost corrected: {}",
+        stats.total, top
+    )
+}
+
+/// Posts a report of the activity since the last report to the stats room once the report period has ended
+pub(super) async fn send_stats_report(
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+    now: SystemTime,
+) {
+    let room_id = match &config.stats_room {
+        Some(v) => v,
+        None => return,
+    };
+    if !storage.stats_report_due(config.stats_report_interval, now) {
+        return;
+    }
+    let activity = storage.take_activity(now);
+    info!("Posting stats report to {}", room_id);
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.clone()),
+            message: MatrixMessageType::Notice(stats_report(
+                &activity,
+                config.stats_report_interval,
+            )),
+            trigger_event: None,
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Renders the activity counted over a report period
+pub(super) fn stats_report(activity: &ActivityStats, period: Duration) -> String {
+    let hours = period.as_secs() / 3600;
+    format!(
+        "Stats for the last {} hour{}\nMessages processed: {}\nCorrections: {}\nConversions: {}\nUsers banned: {}\nSync errors: {}",
+        hours,
+        if hours == 1 { "" } else { "s" },
+        activity.messages,
+        activity.corrections,
+        activity.conversions,
+        activity.bans,
+        activity.errors
+    )
+}
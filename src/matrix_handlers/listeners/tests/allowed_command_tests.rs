@@ -0,0 +1,34 @@
+use super::common::first_reply;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::MatrixMessage;
+use ruma::{room_id, user_id};
+
+async fn dispatch(body: &str, config: &MatrixListenerConfig) -> Option<MatrixMessage> {
+    first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        config,
+    )
+    .await
+}
+
+fn config() -> MatrixListenerConfig {
+    listener_config("", "[allowed_commands]\n'!room:example.com' = ['!help']")
+}
+
+#[tokio::test]
+async fn allowed_command_runs() {
+    assert!(dispatch("!help", &config()).await.is_some())
+}
+#[tokio::test]
+async fn command_not_in_allowlist_skipped() {
+    assert!(dispatch("!convert 22km", &config()).await.is_none())
+}
+#[tokio::test]
+async fn room_without_allowlist() {
+    let config = listener_config("", "");
+    assert!(dispatch("!convert 22km", &config).await.is_some())
+}
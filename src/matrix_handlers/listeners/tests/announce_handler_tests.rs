@@ -0,0 +1,74 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::announce_handler::{announce_handler, unannounce_handler};
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+async fn announce(body: &str, sender: &UserId) -> Option<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    storage.room_names.insert(
+        room_id!("!room:example.com").to_owned(),
+        "Jellyfin".to_string(),
+    );
+    announce_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        &mut storage,
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok().map(|m| m.message)
+}
+
+#[tokio::test]
+async fn admin_announces() {
+    match announce("!announce Release is out", user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::Announce(m)) => assert_eq!("Release is out", m),
+        _ => panic!("Did not get an announcement like expected"),
+    }
+}
+#[tokio::test]
+async fn announcement_template() {
+    match announce(
+        "!announce Welcome to {room}",
+        user_id!("@admin:example.com"),
+    )
+    .await
+    {
+        Some(MatrixMessageType::Announce(m)) => assert_eq!("Welcome to Jellyfin", m),
+        _ => panic!("Did not get an announcement like expected"),
+    }
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert!(
+        announce("!announce Release is out", user_id!("@user:example.com"))
+            .await
+            .is_none()
+    )
+}
+#[tokio::test]
+async fn empty_announcement_ignored() {
+    assert!(announce("!announce ", user_id!("@admin:example.com"))
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn non_admin_unannounce_ignored() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    unannounce_handler(
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
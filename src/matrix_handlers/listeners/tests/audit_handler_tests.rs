@@ -0,0 +1,58 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::audit_handler::{
+    audit_handler, DEFAULT_AUDIT_TAIL, MAX_AUDIT_TAIL,
+};
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+async fn audit(body: &str, sender: &UserId) -> Option<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    audit_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok().map(|m| m.message)
+}
+
+#[tokio::test]
+async fn default_count() {
+    match audit("!auditlog", user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::AuditLog(n)) => assert_eq!(DEFAULT_AUDIT_TAIL, n),
+        _ => panic!("Did not get an audit log request like expected"),
+    }
+}
+#[tokio::test]
+async fn requested_count() {
+    match audit("!auditlog 3", user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::AuditLog(n)) => assert_eq!(3, n),
+        _ => panic!("Did not get an audit log request like expected"),
+    }
+}
+#[tokio::test]
+async fn count_capped() {
+    match audit("!auditlog 500", user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::AuditLog(n)) => assert_eq!(MAX_AUDIT_TAIL, n),
+        _ => panic!("Did not get an audit log request like expected"),
+    }
+}
+#[tokio::test]
+async fn invalid_count_ignored() {
+    assert!(audit("!auditlog lots", user_id!("@admin:example.com"))
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert!(audit("!auditlog", user_id!("@user:example.com"))
+        .await
+        .is_none())
+}
@@ -0,0 +1,339 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{Emoji, ListenerStorage};
+use crate::matrix_handlers::listeners::ban_handler::{ban_authorized, ban_handler};
+use crate::messages::{MatrixBanMessage, MatrixMessageType};
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+async fn ban(body: &str, general: &str) -> MatrixMessageType {
+    ban_in(body, general, &mut ListenerStorage::default()).await
+}
+
+async fn ban_in(body: &str, general: &str, storage: &mut ListenerStorage) -> MatrixMessageType {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        storage,
+        &listener_config(
+            &format!("ban_rooms = ['!room:example.com']\n{}", general),
+            "",
+        ),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().unwrap().message
+}
+
+fn users(message: &MatrixBanMessage) -> Vec<&str> {
+    message.users.iter().map(|u| u.as_str()).collect()
+}
+
+#[tokio::test]
+async fn single_user_bans_immediately() {
+    match ban("!ban @spam:example.com spamming", "").await {
+        MatrixMessageType::Ban(m) => {
+            assert_eq!(vec!["@spam:example.com"], users(&m));
+            assert_eq!(Some("spamming"), m.reason.as_deref())
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn multiple_users_need_confirmation() {
+    let mut storage = ListenerStorage::default();
+    match ban_in(
+        "!ban @spam:example.com @eggs:example.com spamming",
+        "",
+        &mut storage,
+    )
+    .await
+    {
+        MatrixMessageType::ConfirmBan(m) => {
+            assert_eq!(vec!["@spam:example.com", "@eggs:example.com"], users(&m));
+            assert_eq!(Some("spamming"), m.reason.as_deref())
+        }
+        _ => panic!("Did not get a ban confirmation like expected"),
+    }
+    // Unconfirmed bans aren't counted
+    storage.count_bans();
+    assert_eq!(0, storage.activity.bans)
+}
+#[tokio::test]
+async fn configured_threshold() {
+    match ban(
+        "!ban @spam:example.com @eggs:example.com",
+        "ban_confirmation_threshold = 2",
+    )
+    .await
+    {
+        MatrixMessageType::Ban(_) => (),
+        _ => panic!("Did not get a ban like expected"),
+    }
+    match ban("!ban @spam:example.com", "ban_confirmation_threshold = 0").await {
+        MatrixMessageType::ConfirmBan(_) => (),
+        _ => panic!("Did not get a ban confirmation like expected"),
+    }
+}
+#[tokio::test]
+async fn unencrypted_room_rejected() {
+    match ban(
+        "!ban @spam:example.com",
+        "moderation_requires_encryption = true",
+    )
+    .await
+    {
+        MatrixMessageType::Notice(m) => {
+            assert_eq!(
+                "Moderation commands are only accepted in encrypted rooms",
+                m
+            )
+        }
+        _ => panic!("Did not get a rejection like expected"),
+    }
+}
+#[tokio::test]
+async fn encrypted_room_accepted() {
+    let mut storage = ListenerStorage::default();
+    storage
+        .encrypted_rooms
+        .insert(room_id!("!room:example.com").to_owned());
+    match ban_in(
+        "!ban @spam:example.com",
+        "moderation_requires_encryption = true",
+        &mut storage,
+    )
+    .await
+    {
+        MatrixMessageType::Ban(_) => (),
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn unencrypted_room_accepted_without_requirement() {
+    match ban("!ban @spam:example.com", "").await {
+        MatrixMessageType::Ban(_) => (),
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn multi_word_reason_keeps_spaces() {
+    match ban("!ban @spam:example.com posting  scam links", "").await {
+        MatrixMessageType::Ban(m) => {
+            assert_eq!(vec!["@spam:example.com"], users(&m));
+            assert_eq!(Some("posting  scam links"), m.reason.as_deref())
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn no_reason() {
+    match ban("!ban @spam:example.com ", "").await {
+        MatrixMessageType::Ban(m) => {
+            assert_eq!(vec!["@spam:example.com"], users(&m));
+            assert_eq!(None, m.reason)
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn malformed_user_sends_nothing() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain("!ban spam:example.com spamming"),
+        event_id!("$event:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("ban_rooms = ['!room:example.com']", ""),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
+#[test]
+fn admins_ban_without_ban_authorized_users() {
+    let config = listener_config("ban_rooms = ['!room:example.com']", "");
+    assert!(ban_authorized(
+        user_id!("@admin:example.com"),
+        &ListenerStorage::default(),
+        &config
+    ));
+    assert!(!ban_authorized(
+        user_id!("@user:example.com"),
+        &ListenerStorage::default(),
+        &config
+    ))
+}
+#[tokio::test]
+async fn admin_outside_ban_authorized_users_denied() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain("!ban @spam:example.com"),
+        event_id!("$event:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            "ban_rooms = ['!room:example.com']\nban_authorized_users = ['@mod:example.com']",
+            "",
+        ),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    while let Ok(v) = recv.try_recv() {
+        assert!(!matches!(v.message, MatrixMessageType::Ban(_)))
+    }
+}
+#[tokio::test]
+async fn ban_authorized_user_bans() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain("!ban @spam:example.com"),
+        event_id!("$event:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            "ban_rooms = ['!room:example.com']\nban_authorized_users = ['@mod:example.com']",
+            "",
+        ),
+        user_id!("@mod:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Ban(m) => assert_eq!(vec!["@spam:example.com"], users(&m)),
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+
+async fn ban_by(body: &str, sender: &UserId, storage: &mut ListenerStorage) -> MatrixMessageType {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        storage,
+        &listener_config(
+            "ban_rooms = ['!room:example.com', '!other:example.com']
+            ban_authorized_users = ['@admin:example.com', '@mod:example.com']
+            ban_room_confirmation_threshold = 1",
+            "",
+        ),
+        sender,
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().unwrap().message
+}
+
+#[tokio::test]
+async fn room_threshold_asks_for_confirmation() {
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    match ban_by("!ban @spam:example.com spamming", admin, &mut storage).await {
+        MatrixMessageType::Notice(m) => assert_eq!(
+            "This ban affects 2 rooms. Send !ban --confirm within 60 seconds to perform it",
+            m
+        ),
+        _ => panic!("Did not get a confirmation prompt like expected"),
+    }
+    assert!(storage.pending_bans.contains_key(admin))
+}
+#[tokio::test]
+async fn confirm_performs_pending_ban() {
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    ban_by("!ban @spam:example.com spamming", admin, &mut storage).await;
+    match ban_by("!ban --confirm", admin, &mut storage).await {
+        MatrixMessageType::Ban(m) => {
+            assert_eq!(vec!["@spam:example.com"], users(&m));
+            assert_eq!(Some("spamming"), m.reason.as_deref());
+            assert_eq!(2, m.rooms.len())
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+    assert!(storage.pending_bans.is_empty())
+}
+#[tokio::test]
+async fn confirmed_multi_user_ban_asks_for_confirmation() {
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    ban_by(
+        "!ban @spam:example.com @bot:example.org spamming",
+        admin,
+        &mut storage,
+    )
+    .await;
+    match ban_by("!ban --confirm", admin, &mut storage).await {
+        MatrixMessageType::ConfirmBan(m) => assert_eq!(2, m.users.len()),
+        _ => panic!("Did not get a ban confirmation like expected"),
+    }
+}
+#[tokio::test]
+async fn confirm_only_performs_own_pending_ban() {
+    let mut storage = ListenerStorage::default();
+    ban_by(
+        "!ban @spam:example.com",
+        user_id!("@admin:example.com"),
+        &mut storage,
+    )
+    .await;
+    match ban_by("!ban --confirm", user_id!("@mod:example.com"), &mut storage).await {
+        MatrixMessageType::Notice(m) => assert_eq!("No pending ban to confirm", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+    assert!(storage
+        .pending_bans
+        .contains_key(user_id!("@admin:example.com")))
+}
+#[tokio::test]
+async fn ban_acknowledged_with_reaction() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain("!ban @spam:example.com"),
+        event_id!("$event:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            "ban_rooms = ['!room:example.com']\nuse_reactions = true",
+            "",
+        ),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::Ban(_)
+    ));
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::React { event_id, key } => {
+            assert_eq!("$event:example.com", event_id);
+            assert_eq!(Emoji::Unicode("✅".to_string()), key)
+        }
+        _ => panic!("Did not get a reaction like expected"),
+    }
+}
+#[tokio::test]
+async fn ban_not_acknowledged_without_reactions() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ban_handler(
+        &TextMessageEventContent::plain("!ban @spam:example.com"),
+        event_id!("$event:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("ban_rooms = ['!room:example.com']", ""),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::Ban(_)
+    ));
+    assert!(recv.try_recv().is_err())
+}
@@ -0,0 +1,40 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::color_conversion_handler::color_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    color_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn hex() {
+    assert_eq!(
+        "#ff8800 => rgb(255,136,0), hsl(32,100%,50%)",
+        convert("!convert #ff8800").await
+    )
+}
+#[tokio::test]
+async fn named() {
+    assert_eq!(
+        "navy => #000080, rgb(0,0,128), hsl(240,100%,25%)",
+        convert("!convert navy").await
+    )
+}
+#[tokio::test]
+async fn invalid_rgb() {
+    assert_eq!(
+        "Invalid rgb color. Use rgb(red, green, blue) with values between 0 and 255",
+        convert("!convert rgb(300, 0, 0)").await
+    )
+}
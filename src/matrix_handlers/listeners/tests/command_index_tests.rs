@@ -0,0 +1,74 @@
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::command_index::command_index;
+use crate::matrix_handlers::listeners::handle_invite_event;
+use crate::messages::{MatrixInviteType, MatrixMessageType};
+use ruma::{room_id, user_id};
+use tokio::sync::mpsc;
+
+#[test]
+fn index_lists_allowed_commands() {
+    let config = listener_config(
+        "",
+        "[allowed_commands]\n'!room:example.com' = ['convert', 'help', 'announce']",
+    );
+    assert_eq!(
+        "Commands in this room:
+!convert converts units, dates, times, colors, and more
+!help shows help for a topic
+Admins can also use !announce",
+        command_index(room_id!("!room:example.com"), &config)
+    )
+}
+#[test]
+fn index_leaves_out_disabled_features() {
+    let config = listener_config("help_rooms = ['!help:example.com']", "");
+    let index = command_index(room_id!("!room:example.com"), &config);
+    assert!(index.contains("!convert"));
+    assert!(!index.contains("!help"));
+    assert!(!index.contains("!karma"));
+    assert!(!index.contains("!correctme"));
+    assert!(!index.contains("!ban"))
+}
+#[test]
+fn index_uses_room_prefix() {
+    let config = listener_config(
+        "enable_karma = true",
+        "[command_prefixes]\n'!room:example.com' = '.'",
+    );
+    let index = command_index(room_id!("!room:example.com"), &config);
+    assert!(index.contains("\n.karma shows the karma of users in the room"));
+    assert!(!index.contains("!convert"))
+}
+#[tokio::test]
+async fn index_pinned_after_join() {
+    let config = listener_config("enable_command_index = true", "");
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_invite_event(
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &config,
+        &mut send,
+    )
+    .await;
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::Invite(m) if matches!(m.kind, MatrixInviteType::Accept)
+    ));
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Announce(m) => assert!(m.starts_with("Commands in this room:")),
+        _ => panic!("Did not get an announcement like expected"),
+    }
+}
+#[tokio::test]
+async fn no_index_unless_enabled() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_invite_event(
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_ok());
+    assert!(recv.try_recv().is_err())
+}
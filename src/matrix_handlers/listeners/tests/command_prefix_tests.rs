@@ -0,0 +1,103 @@
+use super::common::first_reply;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_edit_event;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::{Replacement, RoomMessageEventContent};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch, RoomId};
+use tokio::sync::mpsc;
+
+async fn dispatch(
+    body: &str,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+) -> Option<MatrixMessageType> {
+    first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id,
+        &mut ListenerStorage::default(),
+        config,
+    )
+    .await
+    .map(|m| m.message)
+}
+
+fn config() -> MatrixListenerConfig {
+    // Commandless conversions are disabled so only the convert command can reply
+    listener_config(
+        "enable_unit_conversions = false\ncommand_prefix = '?'",
+        "[command_prefixes]
+'!dots:example.com' = '.'
+'!bangs:example.com' = '!'",
+    )
+}
+
+#[tokio::test]
+async fn room_prefix_triggers() {
+    let dots = room_id!("!dots:example.com");
+    match dispatch(".convert 100km", dots, &config()).await {
+        Some(MatrixMessageType::Notice(m)) => assert_eq!("100.00km => 62.14mi", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+    assert!(
+        dispatch("!convert 100km", room_id!("!bangs:example.com"), &config())
+            .await
+            .is_some()
+    )
+}
+#[tokio::test]
+async fn other_room_prefix_ignored() {
+    assert!(
+        dispatch(".convert 100km", room_id!("!bangs:example.com"), &config())
+            .await
+            .is_none()
+    );
+    assert!(
+        dispatch("!convert 100km", room_id!("!dots:example.com"), &config())
+            .await
+            .is_none()
+    );
+    assert!(
+        dispatch("?convert 100km", room_id!("!dots:example.com"), &config())
+            .await
+            .is_none()
+    )
+}
+#[tokio::test]
+async fn global_prefix_fallback() {
+    let room_id = room_id!("!room:example.com");
+    assert!(dispatch("?convert 100km", room_id, &config())
+        .await
+        .is_some());
+    assert!(dispatch("!convert 100km", room_id, &config())
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn edits_use_room_prefix() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let config = listener_config(
+        "enable_unit_conversions = false\nconvert_on_edit = true",
+        "[command_prefixes]\n'!dots:example.com' = '.'",
+    );
+    for body in [".convert 100km", "!convert 100km"].iter() {
+        handle_edit_event(
+            &Replacement::new(
+                event_id!("$original:example.com").to_owned(),
+                Box::new(RoomMessageEventContent::text_plain(*body)),
+            ),
+            event_id!("$event:example.com"),
+            MilliSecondsSinceUnixEpoch::now(),
+            user_id!("@user:example.com"),
+            room_id!("!dots:example.com"),
+            &mut ListenerStorage::default(),
+            &config,
+            &reqwest::Client::new(),
+            &mut send,
+        )
+        .await;
+    }
+    assert!(recv.try_recv().is_ok());
+    assert!(recv.try_recv().is_err())
+}
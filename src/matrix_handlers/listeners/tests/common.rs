@@ -0,0 +1,81 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_text_event;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, MilliSecondsSinceUnixEpoch, RoomId, UserId};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+/// Sends `body` from `sender` in `room_id` through the listener as a new text message.
+pub(super) async fn text_event(
+    body: &str,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    handle_text_event(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        sender,
+        room_id,
+        storage,
+        config,
+        &reqwest::Client::new(),
+        send,
+    )
+    .await
+}
+
+/// Sends `body` through the listener and returns the first message it replied with, if any.
+pub(super) async fn first_reply(
+    body: &str,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<MatrixMessage> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(body, sender, room_id, storage, config, &mut send).await;
+    recv.try_recv().ok()
+}
+
+/// Returns the text of a notice reply, failing the test on any other kind of reply.
+pub(super) fn notice(reply: Option<MatrixMessage>) -> Option<String> {
+    match reply.map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) => Some(m),
+        Some(_) => panic!("Did not get a notice like expected"),
+        None => None,
+    }
+}
+
+/// Returns the text of a notice or plain text reply, failing the test on any other kind of reply.
+pub(super) fn text_reply(reply: Option<MatrixMessage>) -> Option<String> {
+    match reply.map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) | Some(MatrixMessageType::Text(m)) => Some(m),
+        Some(_) => panic!("Did not get a text reply like expected"),
+        None => None,
+    }
+}
+
+/// Returns the plain text of the formatted notice a handler sent.
+pub(super) fn formatted_notice(recv: &mut Receiver<MatrixMessage>) -> String {
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::FormattedNotice(m) => m.plain_text,
+        _ => panic!("Did not get a formatted notice like expected"),
+    }
+}
+
+/// Config with corrections enabled for one case insensitive and one case sensitive word.
+pub(super) fn corrections_config() -> MatrixListenerConfig {
+    listener_config(
+        "enable_corrections = true
+insensitive_corrections = ['jellyfish']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'",
+        "",
+    )
+}
@@ -0,0 +1,67 @@
+use super::common::{first_reply, notice};
+use crate::config::tests::common::{config_text, listener_config};
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::config_check_handler::config_report;
+use anyhow::anyhow;
+use ruma::{room_id, user_id, UserId};
+
+#[test]
+fn good_config() {
+    assert_eq!(
+        "Config OK. Nothing was applied",
+        config_report(Ok(config_text("", "")))
+    )
+}
+#[test]
+fn bad_config() {
+    assert_eq!(
+        "Config problem: Flood message limit must be at least 1 message",
+        config_report(Ok(config_text(
+            "ban_rooms = ['!room:example.com']\nenable_flood_protection = true\nflood_message_limit = 0",
+            ""
+        )))
+    )
+}
+#[test]
+fn invalid_toml() {
+    assert!(config_report(Ok("[general".to_string())).starts_with("Config problem: Invalid toml: "))
+}
+#[test]
+fn unreadable_config() {
+    assert_eq!(
+        "Config problem: Unable to open config file",
+        config_report(Err(anyhow!("Unable to open config file")))
+    )
+}
+
+async fn check(sender: &UserId, general: &str) -> Option<String> {
+    notice(
+        first_reply(
+            "!configcheck",
+            sender,
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default(),
+            &listener_config(general, ""),
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn outside_help_rooms_rejected() {
+    assert_eq!(
+        Some("Config checks are only accepted in direct messages and help rooms".to_string()),
+        check(user_id!("@admin:example.com"), "").await
+    )
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert_eq!(
+        None,
+        check(
+            user_id!("@user:example.com"),
+            "help_rooms = ['!room:example.com']"
+        )
+        .await
+    )
+}
@@ -0,0 +1,49 @@
+use super::common::{first_reply, notice};
+use crate::config::tests::common::{listener_config, load_config};
+use crate::config::ListenerStorage;
+use ruma::{room_id, user_id};
+
+async fn chat(body: &str) -> Option<String> {
+    notice(
+        first_reply(
+            body,
+            user_id!("@user:example.com"),
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default(),
+            &listener_config("conversion_marker = '?'", ""),
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn marker_present() {
+    assert_eq!(
+        Some("22.00km => 13.67mi".to_string()),
+        chat("its 22km? away").await
+    )
+}
+#[tokio::test]
+async fn marker_absent() {
+    assert_eq!(None, chat("its 22km away").await)
+}
+#[tokio::test]
+async fn only_marked_units() {
+    assert_eq!(
+        Some("22.00km => 13.67mi".to_string()),
+        chat("22km? at 50mph").await
+    )
+}
+#[tokio::test]
+async fn command_ignores_marker() {
+    assert_eq!(
+        Some("22.00km => 13.67mi".to_string()),
+        chat("!convert 22km").await
+    )
+}
+#[test]
+fn invalid_marker() {
+    assert!(load_config("conversion_marker = ''", "").is_err());
+    assert!(load_config("conversion_marker = 'x'", "").is_err());
+    assert!(load_config("conversion_marker = '? '", "").is_err())
+}
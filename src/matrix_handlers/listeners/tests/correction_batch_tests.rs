@@ -0,0 +1,210 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::{handle_pending_corrections, handle_text_event};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, EventId, MilliSecondsSinceUnixEpoch, UserId};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+fn batch_config() -> MatrixListenerConfig {
+    listener_config(
+        "enable_corrections = true
+insensitive_corrections = ['Jellyfish', 'Jelly Fin']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'
+correction_batch_seconds = 10",
+        "",
+    )
+}
+
+async fn chat(
+    body: &str,
+    event_id: &EventId,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    handle_text_event(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id,
+        MilliSecondsSinceUnixEpoch::now(),
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &reqwest::Client::new(),
+        send,
+    )
+    .await
+}
+
+fn corrections(recv: &mut Receiver<MatrixMessage>) -> Vec<String> {
+    let mut corrections = Vec::new();
+    while let Ok(m) = recv.try_recv() {
+        match m.message {
+            MatrixMessageType::Text(t) => corrections.push(t),
+            _ => panic!("Did not get a text message like expected"),
+        }
+    }
+    corrections
+}
+
+#[tokio::test]
+async fn burst_produces_one_correction() {
+    let config = batch_config();
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    let user = user_id!("@user:example.com");
+    chat(
+        "i use jellyfish",
+        event_id!("$1:example.com"),
+        user,
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    chat(
+        "jelly fin is great",
+        event_id!("$2:example.com"),
+        user,
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    chat(
+        "really, jellyfish",
+        event_id!("$3:example.com"),
+        user,
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    assert!(corrections(&mut recv).is_empty());
+
+    handle_pending_corrections(&mut storage, &config, &mut send, Instant::now()).await;
+    assert!(corrections(&mut recv).is_empty());
+
+    let later = Instant::now() + Duration::from_secs(11);
+    handle_pending_corrections(&mut storage, &config, &mut send, later).await;
+    let message = recv.try_recv().unwrap();
+    assert_eq!(
+        Some(event_id!("$3:example.com").to_owned()),
+        message.trigger_event
+    );
+    match message.message {
+        MatrixMessageType::Text(t) => assert_eq!("user said Jellyfish and Jelly Fin", t),
+        _ => panic!("Did not get a text message like expected"),
+    }
+    assert!(corrections(&mut recv).is_empty());
+    assert_eq!(
+        2,
+        storage.correction_stats[room_id!("!room:example.com")].total
+    )
+}
+#[tokio::test]
+async fn room_cooldown_applies_to_batches() {
+    let config = batch_config();
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    chat(
+        "i use jellyfish",
+        event_id!("$1:example.com"),
+        user_id!("@user1:example.com"),
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    chat(
+        "i use JellyFin",
+        event_id!("$2:example.com"),
+        user_id!("@user2:example.com"),
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+
+    let later = Instant::now() + Duration::from_secs(11);
+    handle_pending_corrections(&mut storage, &config, &mut send, later).await;
+    assert_eq!(1, corrections(&mut recv).len())
+}
+#[tokio::test]
+async fn unbatched_by_default() {
+    let config = listener_config(
+        "enable_corrections = true
+insensitive_corrections = ['Jellyfish']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'",
+        "",
+    );
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    chat(
+        "i use jellyfish",
+        event_id!("$1:example.com"),
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    assert_eq!(
+        vec!["user said Jellyfish".to_string()],
+        corrections(&mut recv)
+    );
+    assert!(storage.pending_corrections.is_empty())
+}
+#[tokio::test]
+async fn repeat_correction_edits_previous_one() {
+    let config = listener_config(
+        "enable_corrections = true
+insensitive_corrections = ['Jellyfish', 'Jelly Fin']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'",
+        "",
+    );
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    let user = user_id!("@user:example.com");
+    chat(
+        "i use jellyfish",
+        event_id!("$1:example.com"),
+        user,
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    assert_eq!(
+        vec!["user said Jellyfish".to_string()],
+        corrections(&mut recv)
+    );
+
+    storage.last_correction_time.insert(
+        room_id!("!room:example.com").to_owned(),
+        SystemTime::now() - Duration::from_secs(600),
+    );
+    chat(
+        "i use jelly fin",
+        event_id!("$2:example.com"),
+        user,
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Edit(trigger, content) => {
+            assert_eq!(event_id!("$1:example.com"), trigger);
+            assert_eq!("user said Jelly Fin", content.body())
+        }
+        _ => panic!("Did not get an edit like expected"),
+    }
+}
@@ -0,0 +1,89 @@
+use super::common::{corrections_config, first_reply, text_reply};
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use ruma::{room_id, user_id, UserId};
+
+async fn chat(body: &str, sender: &UserId, config: &MatrixListenerConfig) -> Option<String> {
+    text_reply(
+        first_reply(
+            body,
+            sender,
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default(),
+            config,
+        )
+        .await,
+    )
+}
+
+async fn admin(body: &str, config: &MatrixListenerConfig) -> Option<String> {
+    chat(body, user_id!("@admin:example.com"), config).await
+}
+
+#[tokio::test]
+async fn list() {
+    assert_eq!(
+        Some("Case insensitive: jellyfish\nCase sensitive: JellyFin".to_string()),
+        admin("!correction list", &corrections_config()).await
+    )
+}
+#[tokio::test]
+async fn add() {
+    let config = corrections_config();
+    assert_eq!(
+        Some("Added correction emby".to_string()),
+        admin("!correction add insensitive emby", &config).await
+    );
+    assert_eq!(
+        Some("Case insensitive: emby, jellyfish\nCase sensitive: JellyFin".to_string()),
+        admin("!correction list", &config).await
+    )
+}
+#[tokio::test]
+async fn add_duplicate() {
+    assert_eq!(
+        Some("JellyFin is already corrected".to_string()),
+        admin("!correction add sensitive JellyFin", &corrections_config()).await
+    )
+}
+#[tokio::test]
+async fn add_invalid_kind() {
+    assert!(admin("!correction add loud emby", &corrections_config())
+        .await
+        .unwrap()
+        .starts_with("Usage:"))
+}
+#[tokio::test]
+async fn remove() {
+    let config = corrections_config();
+    assert_eq!(
+        Some("Removed correction jellyfish".to_string()),
+        admin("!correction remove jellyfish", &config).await
+    );
+    assert_eq!(
+        Some("jellyfish is not corrected".to_string()),
+        admin("!correction remove jellyfish", &config).await
+    )
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert!(chat(
+        "!correction add insensitive emby",
+        user_id!("@user:example.com"),
+        &corrections_config()
+    )
+    .await
+    .is_none())
+}
+#[tokio::test]
+async fn live_change_corrects() {
+    let config = corrections_config();
+    let user = user_id!("@user:example.com");
+    assert!(chat("i use emby", user, &config).await.is_none());
+    admin("!correction add insensitive emby", &config).await;
+    assert_eq!(
+        Some("user said emby".to_string()),
+        chat("i use emby", user, &config).await
+    );
+    admin("!correction remove jellyfish", &config).await;
+    assert!(chat("i love jellyfish", user, &config).await.is_none())
+}
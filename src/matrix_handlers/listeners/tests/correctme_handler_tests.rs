@@ -0,0 +1,67 @@
+use super::common::{corrections_config, first_reply, text_reply};
+use crate::config::ListenerStorage;
+use ruma::{room_id, user_id, RoomId};
+
+async fn chat(body: &str, room_id: &RoomId, storage: &mut ListenerStorage) -> Option<String> {
+    text_reply(
+        first_reply(
+            body,
+            user_id!("@user:example.com"),
+            room_id,
+            storage,
+            &corrections_config(),
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn opt_out_honored_in_every_room() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    assert!(chat("!correctme off", room, &mut storage).await.is_some());
+    assert_eq!(None, chat("i love jellyfish", room, &mut storage).await);
+    assert_eq!(
+        None,
+        chat(
+            "i love jellyfish",
+            room_id!("!other:example.com"),
+            &mut storage
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn opt_out_persists_across_restart() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    chat("!correctme off", room, &mut storage).await;
+    let mut storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    assert_eq!(None, chat("i love jellyfish", room, &mut storage).await)
+}
+#[tokio::test]
+async fn opt_back_in() {
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    chat("!correctme off", room, &mut storage).await;
+    assert_eq!(
+        Some("You'll be corrected again".to_string()),
+        chat("!correctme on", room, &mut storage).await
+    );
+    assert_eq!(
+        Some("user said jellyfish".to_string()),
+        chat("i love jellyfish", room, &mut storage).await
+    )
+}
+#[tokio::test]
+async fn usage() {
+    assert_eq!(
+        Some("Usage: !correctme off or !correctme on".to_string()),
+        chat(
+            "!correctme",
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default()
+        )
+        .await
+    )
+}
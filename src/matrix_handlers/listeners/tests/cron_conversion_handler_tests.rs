@@ -0,0 +1,47 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::cron_conversion_handler::cron_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    cron_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn quoted_expression() {
+    assert_eq!(
+        "0 9 * * 1 => At 09:00 on Monday",
+        convert("!convert cron \"0 9 * * 1\"").await
+    )
+}
+#[tokio::test]
+async fn unquoted_expression() {
+    assert_eq!(
+        "*/15 * * * * => At every 15th minute",
+        convert("!convert cron */15 * * * *").await
+    )
+}
+#[tokio::test]
+async fn description() {
+    assert_eq!(
+        "every 15 minutes => */15 * * * *",
+        convert("!Convert Cron every 15 minutes").await
+    )
+}
+#[tokio::test]
+async fn invalid_expression() {
+    assert_eq!(
+        "Invalid minute value 60. Must be between 0 and 59",
+        convert("!convert cron \"60 * * * *\"").await
+    )
+}
@@ -0,0 +1,46 @@
+use super::common::formatted_notice;
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::data_rate_conversion_handler::data_rate_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    data_rate_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn megabits() {
+    assert_eq!(
+        "100Mbps (megabits per second) => 12.5MBps (megabytes per second)",
+        convert("!convert 100mbps").await
+    )
+}
+#[tokio::test]
+async fn megabytes() {
+    assert_eq!(
+        "12.5MBps (megabytes per second) => 100Mbps (megabits per second)",
+        convert("!convert 12.5 MB/s").await
+    )
+}
+#[tokio::test]
+async fn gigabits_and_gigabytes() {
+    assert_eq!(
+        "8Gbps (gigabits per second) => 1GBps (gigabytes per second)",
+        convert("!convert 8Gbps").await
+    );
+    assert_eq!(
+        "2GBps (gigabytes per second) => 16Gbps (gigabits per second)",
+        convert("!convert 2GBps").await
+    )
+}
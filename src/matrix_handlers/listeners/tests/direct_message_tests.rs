@@ -0,0 +1,90 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{room_id, user_id};
+use tokio::sync::mpsc::{self, Receiver};
+
+fn corrections_config(tables: &str) -> MatrixListenerConfig {
+    listener_config(
+        "enable_corrections = true
+insensitive_corrections = ['Jellyfish']
+sensitive_corrections = ['JellyFin']
+correction_text = '{} said {}'",
+        tables,
+    )
+}
+
+async fn chat(body: &str, members: u64, config: &MatrixListenerConfig) -> Receiver<MatrixMessage> {
+    let (mut send, recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    storage
+        .joined_member_counts
+        .insert(room_id!("!room:example.com").to_owned(), members);
+    text_event(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut storage,
+        config,
+        &mut send,
+    )
+    .await;
+    recv
+}
+
+#[tokio::test]
+async fn command_in_direct_message() {
+    let mut recv = chat("!convert 22km", 2, &listener_config("", "")).await;
+    assert!(recv.try_recv().is_ok())
+}
+#[tokio::test]
+async fn corrections_suppressed_in_direct_message() {
+    let mut recv = chat("i love jellyfish", 2, &corrections_config("")).await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn corrections_in_group_room() {
+    let mut recv = chat("i love jellyfish", 3, &corrections_config("")).await;
+    assert!(recv.try_recv().is_ok())
+}
+#[tokio::test]
+async fn configured_direct_message_policy() {
+    let config = corrections_config(
+        "[direct_messages]
+corrections = true
+commands = false",
+    );
+    let mut recv = chat("i love jellyfish", 2, &config).await;
+    assert!(recv.try_recv().is_ok());
+    let mut recv = chat("!convert 22km", 2, &config).await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn about_reply_to_greeting() {
+    let config = listener_config("", "[direct_messages]\nabout = 'I convert units'");
+    let mut recv = chat("Hi!", 2, &config).await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => assert_eq!("I convert units", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+    let mut recv = chat("?", 2, &config).await;
+    assert!(recv.try_recv().is_ok())
+}
+#[tokio::test]
+async fn about_not_sent_in_group_room() {
+    let config = listener_config("", "[direct_messages]\nabout = 'I convert units'");
+    let mut recv = chat("hi", 3, &config).await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn about_not_sent_for_other_messages() {
+    let config = listener_config("", "[direct_messages]\nabout = 'I convert units'");
+    let mut recv = chat("hi, how do I convert units?", 2, &config).await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn about_not_sent_unless_configured() {
+    let mut recv = chat("hi", 2, &listener_config("", "")).await;
+    assert!(recv.try_recv().is_err())
+}
@@ -0,0 +1,53 @@
+use super::common::formatted_notice;
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::dpi_conversion_handler::dpi_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    dpi_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn dpi_to_dpcm() {
+    assert_eq!(
+        "300dpi => 118.11dpcm",
+        convert("!convert 300dpi to dpcm").await
+    )
+}
+#[tokio::test]
+async fn dpcm_to_dpi() {
+    assert_eq!("118ppcm => 299.72dpi", convert("!convert 118 PPCM").await)
+}
+#[tokio::test]
+async fn printed_size() {
+    assert_eq!(
+        "2400px at 300dpi => 8.00in (20.32cm)",
+        convert("!convert 2400px at 300dpi").await
+    )
+}
+#[tokio::test]
+async fn screen_dot_pitch() {
+    assert_eq!(
+        "1920x1080 at 27in => 81.59ppi, 0.311mm dot pitch",
+        convert("!convert 1920x1080 27in").await
+    )
+}
+#[tokio::test]
+async fn invalid_screen() {
+    assert_eq!(
+        "1920x0 at 27in is not a valid screen",
+        convert("!convert 1920x0 at 27in pitch").await
+    )
+}
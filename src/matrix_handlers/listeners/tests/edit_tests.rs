@@ -0,0 +1,100 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_edit_event;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::events::room::message::{Replacement, RoomMessageEventContent};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch};
+use tokio::sync::mpsc;
+
+async fn edit(body: &str, config: &MatrixListenerConfig) -> Option<MatrixMessage> {
+    edit_in(body, &mut ListenerStorage::default(), config).await
+}
+
+async fn edit_in(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<MatrixMessage> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let replacement = Replacement::new(
+        event_id!("$original:example.com").to_owned(),
+        Box::new(RoomMessageEventContent::text_plain(body)),
+    );
+    handle_edit_event(
+        &replacement,
+        event_id!("$edit:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &reqwest::Client::new(),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok()
+}
+
+#[tokio::test]
+async fn edit_adding_unit_converts() {
+    let config = listener_config("", "");
+    let message = edit("its 22km away", &config).await.unwrap();
+    assert_eq!(
+        Some(event_id!("$original:example.com").to_owned()),
+        message.trigger_event
+    );
+    match message.message {
+        MatrixMessageType::Edit(e, m) => {
+            assert_eq!("$original:example.com", e);
+            assert_eq!("22.00km => 13.67mi", m.body())
+        }
+        _ => panic!("Did not get an edit like expected"),
+    }
+}
+#[tokio::test]
+async fn edit_adding_unit_to_command_converts() {
+    let config = listener_config("convert_on_edit = true", "");
+    assert!(edit("!convert 22km", &config).await.is_some())
+}
+#[tokio::test]
+async fn edit_ignored_when_disabled() {
+    let config = listener_config("convert_on_edit = false", "");
+    assert!(edit("its 22km away", &config).await.is_none());
+    assert!(edit("!convert 22km", &config).await.is_none())
+}
+#[tokio::test]
+async fn passive_edits_respect_cooldown() {
+    let config = listener_config(
+        "enable_unit_conversions = false\npassive_conversions = ['!room:example.com']",
+        "",
+    );
+    let mut storage = ListenerStorage::default();
+    assert!(edit_in("its 22km away", &mut storage, &config)
+        .await
+        .is_some());
+    assert!(edit_in("its 23km away", &mut storage, &config)
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn edits_respect_direct_message_policy() {
+    let config = listener_config("", "[direct_messages]\nunit_conversions = false");
+    let mut storage = ListenerStorage::default();
+    storage
+        .joined_member_counts
+        .insert(room_id!("!room:example.com").to_owned(), 2);
+    assert!(edit_in("its 22km away", &mut storage, &config)
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn repeated_edit_conversion_suppressed() {
+    let config = listener_config("duplicate_conversion_seconds = 60", "");
+    let mut storage = ListenerStorage::default();
+    assert!(edit_in("its 22km away", &mut storage, &config)
+        .await
+        .is_some());
+    assert!(edit_in("its 22km away!", &mut storage, &config)
+        .await
+        .is_none())
+}
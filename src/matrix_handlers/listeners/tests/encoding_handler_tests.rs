@@ -0,0 +1,50 @@
+use super::common::formatted_notice;
+use crate::helpers::MAX_ENCODING_INPUT;
+use crate::matrix_handlers::listeners::encoding_handler::encoding_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    encoding_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn encode_base64() {
+    assert_eq!("aGVsbG8=", convert("!encode base64 hello").await)
+}
+#[tokio::test]
+async fn decode_base64() {
+    assert_eq!("hello", convert("!decode base64 aGVsbG8=").await)
+}
+#[tokio::test]
+async fn encode_keeps_inner_spaces() {
+    assert_eq!("hello%20world", convert("!encode url hello world").await)
+}
+#[tokio::test]
+async fn invalid_base64() {
+    assert_eq!(
+        "Unable to decode base64: Invalid base64",
+        convert("!decode base64 aGVs*G8=").await
+    )
+}
+#[tokio::test]
+async fn unknown_format() {
+    assert!(convert("!encode rot13 hello").await.starts_with("Usage:"))
+}
+#[tokio::test]
+async fn input_too_long() {
+    let body = format!("!encode hex {}", "a".repeat(MAX_ENCODING_INPUT + 1));
+    assert_eq!(
+        format!("Text must be at most {} characters", MAX_ENCODING_INPUT),
+        convert(&body).await
+    )
+}
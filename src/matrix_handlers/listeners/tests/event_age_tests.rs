@@ -0,0 +1,66 @@
+use crate::config::tests::common::listener_config;
+use crate::config::MatrixListenerConfig;
+use crate::matrix_handlers::listeners::is_stale_event;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{room_id, MilliSecondsSinceUnixEpoch, UInt};
+use std::time::{Duration, SystemTime};
+
+fn now() -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000)
+}
+
+fn seconds_ago(seconds: u64) -> MilliSecondsSinceUnixEpoch {
+    MilliSecondsSinceUnixEpoch(UInt::new((1_000_000 - seconds) * 1000).unwrap())
+}
+
+fn is_stale(body: &str, seconds: u64, config: &MatrixListenerConfig) -> bool {
+    is_stale_event(
+        &TextMessageEventContent::plain(body),
+        room_id!("!room:example.com"),
+        seconds_ago(seconds),
+        config,
+        now(),
+    )
+}
+
+#[test]
+fn old_event_skipped() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(is_stale("its 22km away", 301, &config))
+}
+#[test]
+fn recent_event_processed() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(!is_stale("its 22km away", 10, &config))
+}
+#[test]
+fn unlimited_by_default() {
+    let config = listener_config("", "");
+    assert!(!is_stale("its 22km away", 86400, &config))
+}
+#[test]
+fn moderation_command_processed() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(!is_stale("!ban @spam:example.com", 3600, &config))
+}
+#[test]
+fn unban_processed() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(!is_stale("!unban @spam:example.com", 3600, &config))
+}
+#[test]
+fn warn_processed() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(!is_stale("!warn @spam:example.com be nice", 3600, &config))
+}
+#[test]
+fn old_command_skipped() {
+    let config = listener_config("max_event_age_seconds = 300", "");
+    assert!(is_stale("!help", 3600, &config))
+}
+#[test]
+fn prefixed_moderation_command_processed() {
+    let config = listener_config("max_event_age_seconds = 300\ncommand_prefix = '?'", "");
+    assert!(!is_stale("?ban @spam:example.com", 3600, &config));
+    assert!(is_stale("?help", 3600, &config))
+}
@@ -0,0 +1,82 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::flood_handler::flood_handler;
+use crate::messages::MatrixMessageType;
+use ruma::{room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+const GENERAL: &str = "ban_rooms = ['!room:example.com']
+enable_flood_protection = true
+flood_message_limit = 3";
+
+/// Sends `count` messages from `sender`, returning which of them were caught flooding and the action sent
+async fn flood(
+    sender: &UserId,
+    count: usize,
+    general: &str,
+) -> (Vec<bool>, Option<MatrixMessageType>) {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let config = listener_config(general, "");
+    let mut storage = ListenerStorage::default();
+    let mut caught = Vec::new();
+    for _ in 0..count {
+        caught.push(
+            flood_handler(
+                sender,
+                room_id!("!room:example.com"),
+                &mut storage,
+                &config,
+                &mut send,
+            )
+            .await,
+        );
+    }
+    (caught, recv.try_recv().ok().map(|m| m.message))
+}
+
+#[tokio::test]
+async fn triggers_past_threshold() {
+    let (caught, message) = flood(user_id!("@spam:example.com"), 4, GENERAL).await;
+    assert_eq!(vec![false, false, false, true], caught);
+    match message {
+        Some(MatrixMessageType::Ban(m)) => {
+            assert_eq!(vec![user_id!("@spam:example.com")], m.users);
+            assert_eq!("@bot:example.com", m.admin.as_str());
+            assert!(m.rooms.contains(room_id!("!room:example.com")))
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+}
+#[tokio::test]
+async fn at_threshold_is_not_flooding() {
+    let (caught, message) = flood(user_id!("@spam:example.com"), 3, GENERAL).await;
+    assert_eq!(vec![false, false, false], caught);
+    assert!(message.is_none())
+}
+#[tokio::test]
+async fn mute_action() {
+    let general = format!("{}\nflood_action = 'mute'", GENERAL);
+    match flood(user_id!("@spam:example.com"), 4, &general).await.1 {
+        Some(MatrixMessageType::Mute(m)) => {
+            assert_eq!(vec![user_id!("@spam:example.com")], m.users)
+        }
+        _ => panic!("Did not get a mute like expected"),
+    }
+}
+#[tokio::test]
+async fn admins_are_exempt() {
+    let (caught, message) = flood(user_id!("@admin:example.com"), 10, GENERAL).await;
+    assert!(caught.iter().all(|c| !c));
+    assert!(message.is_none())
+}
+#[tokio::test]
+async fn disabled_by_default() {
+    let (caught, message) = flood(
+        user_id!("@spam:example.com"),
+        20,
+        "ban_rooms = ['!room:example.com']",
+    )
+    .await;
+    assert!(caught.iter().all(|c| !c));
+    assert!(message.is_none())
+}
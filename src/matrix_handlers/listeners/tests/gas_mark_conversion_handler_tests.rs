@@ -0,0 +1,63 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::gas_mark_conversion_handler::gas_mark_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    gas_mark_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn gas_mark_to_temperature() {
+    assert_eq!(
+        "gas mark 4 => 180°C (350°F)",
+        convert("!convert gas4").await
+    );
+    assert_eq!(
+        "gas mark 7 => 220°C (425°F)",
+        convert("!convert Gas Mark 7").await
+    )
+}
+#[tokio::test]
+async fn fractional_gas_mark() {
+    assert_eq!(
+        "gas mark 1/2 => 120°C (250°F)",
+        convert("!convert gas 1/2").await
+    );
+    assert_eq!(
+        "gas mark 1/4 => 110°C (225°F)",
+        convert("!convert gas¼").await
+    )
+}
+#[tokio::test]
+async fn temperature_to_gas_mark() {
+    assert_eq!("180°C => gas mark 4", convert("!convert 180c gas").await);
+    assert_eq!(
+        "400°F => gas mark 6",
+        convert("!convert 400°F to gas mark").await
+    )
+}
+#[tokio::test]
+async fn out_of_range_gas_mark() {
+    assert_eq!(
+        "Gas mark 12 is out of range. Gas marks go from 1/4 to 9",
+        convert("!convert gas12").await
+    )
+}
+#[tokio::test]
+async fn out_of_range_temperature() {
+    assert_eq!(
+        "300°C is out of range. Gas marks go from 110°C (225°F) to 240°C (475°F)",
+        convert("!convert 300c gas").await
+    )
+}
@@ -0,0 +1,36 @@
+use super::common::{first_reply, notice};
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use ruma::{room_id, user_id};
+use std::time::{Duration, SystemTime};
+
+async fn chat(body: &str, storage: &mut ListenerStorage) -> Option<String> {
+    notice(
+        first_reply(
+            body,
+            user_id!("@user:example.com"),
+            room_id!("!room:example.com"),
+            storage,
+            &listener_config(
+                "",
+                "[github_authentication]
+access_token = 'token'
+
+[searchable_repos]
+jf = 'jellyfin/jellyfin'",
+            ),
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn paused_replies_once() {
+    let mut storage = ListenerStorage::default();
+    storage.pause_github(SystemTime::now() + Duration::from_secs(60));
+    assert_eq!(
+        Some("GitHub rate limited, try later".to_string()),
+        chat("look at jf#1234", &mut storage).await
+    );
+    assert_eq!(None, chat("and jf#1235", &mut storage).await)
+}
@@ -0,0 +1,41 @@
+use super::common::{first_reply, notice};
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use ruma::{room_id, user_id};
+
+async fn chat(body: &str) -> Option<String> {
+    notice(
+        first_reply(
+            body,
+            user_id!("@user:example.com"),
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default(),
+            &listener_config(
+                "",
+                "[github_authentication]
+access_token = 'token'
+
+[searchable_repos]
+jf = 'jellyfin/jellyfin'
+jf-web = 'jellyfin/jellyfin-web'",
+            ),
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn near_miss_suggests_repo() {
+    assert_eq!(
+        Some("Did you mean jf-web?".to_string()),
+        chat("look at jfweb#1234").await
+    );
+    assert_eq!(
+        Some("Did you mean jf?".to_string()),
+        chat("look at jg#1234 and jg#1235").await
+    )
+}
+#[tokio::test]
+async fn unrelated_repo_ignored() {
+    assert_eq!(None, chat("look at kodi#1234").await)
+}
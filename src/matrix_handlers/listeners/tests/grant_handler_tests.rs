@@ -0,0 +1,160 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::grant_handler::{grant_handler, MAX_GRANT_DURATION};
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+
+fn super_admin_config() -> MatrixListenerConfig {
+    listener_config("super_admins = ['@admin:example.com']", "")
+}
+
+async fn grant(
+    body: &str,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    grant_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().ok().map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) => Some(m),
+        Some(_) => panic!("Did not get a notice like expected"),
+        None => None,
+    }
+}
+
+#[tokio::test]
+async fn grant_makes_admin() {
+    let config = super_admin_config();
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@oncall:example.com");
+    assert_eq!(
+        Some("Granted admin to @oncall:example.com for 1h".to_string()),
+        grant(
+            "!grant @oncall:example.com 1h",
+            user_id!("@admin:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    let now = SystemTime::now();
+    assert!(storage.is_admin(user, &config.admins, now));
+    assert!(!storage.is_admin(user, &config.admins, now + Duration::from_secs(3601)))
+}
+#[tokio::test]
+async fn grant_expires() {
+    let config = super_admin_config();
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@oncall:example.com");
+    let start = SystemTime::now() - Duration::from_secs(7200);
+    storage.grant_admin(user, Duration::from_secs(3600), start);
+    assert!(!storage.is_admin(user, &config.admins, SystemTime::now()))
+}
+#[tokio::test]
+async fn revoke_removes_admin() {
+    let config = super_admin_config();
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@oncall:example.com");
+    storage.grant_admin(user, Duration::from_secs(3600), SystemTime::now());
+    assert_eq!(
+        Some("Revoked admin from @oncall:example.com".to_string()),
+        grant(
+            "!revoke @oncall:example.com",
+            user_id!("@admin:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    assert!(!storage.is_admin(user, &config.admins, SystemTime::now()))
+}
+#[tokio::test]
+async fn revoke_without_grant() {
+    assert_eq!(
+        Some("@oncall:example.com has not been granted admin".to_string()),
+        grant(
+            "!revoke @oncall:example.com",
+            user_id!("@admin:example.com"),
+            &mut ListenerStorage::default(),
+            &super_admin_config()
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn admin_without_super_admin_ignored() {
+    let mut storage = ListenerStorage::default();
+    assert!(grant(
+        "!grant @oncall:example.com 1h",
+        user_id!("@admin:example.com"),
+        &mut storage,
+        &listener_config("", "")
+    )
+    .await
+    .is_none());
+    assert!(storage.temporary_admins.is_empty())
+}
+#[tokio::test]
+async fn granted_admin_cannot_grant() {
+    let config = super_admin_config();
+    let mut storage = ListenerStorage::default();
+    storage.grant_admin(
+        user_id!("@oncall:example.com"),
+        Duration::from_secs(3600),
+        SystemTime::now(),
+    );
+    assert!(grant(
+        "!grant @friend:example.com 1h",
+        user_id!("@oncall:example.com"),
+        &mut storage,
+        &config
+    )
+    .await
+    .is_none());
+    assert!(!storage.is_admin(
+        user_id!("@friend:example.com"),
+        &config.admins,
+        SystemTime::now()
+    ))
+}
+#[tokio::test]
+async fn duration_too_long() {
+    let days = MAX_GRANT_DURATION.as_secs() / 86400 + 1;
+    let mut storage = ListenerStorage::default();
+    let reply = grant(
+        &format!("!grant @oncall:example.com {}d", days),
+        user_id!("@admin:example.com"),
+        &mut storage,
+        &super_admin_config(),
+    )
+    .await
+    .unwrap();
+    assert!(reply.starts_with("Usage:"));
+    assert!(storage.temporary_admins.is_empty())
+}
+#[tokio::test]
+async fn configured_admin_not_granted() {
+    assert_eq!(
+        Some("@admin:example.com is already an admin".to_string()),
+        grant(
+            "!grant @admin:example.com 1h",
+            user_id!("@admin:example.com"),
+            &mut ListenerStorage::default(),
+            &super_admin_config()
+        )
+        .await
+    )
+}
@@ -0,0 +1,206 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::{needs_room_members, stale_group_rooms};
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{room_alias_id, room_id, user_id, OwnedUserId};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc;
+
+const GROUPS: &str = "[group_pings]
+backend = ['@user1:example.com', '@user2:example.com']";
+
+fn config() -> MatrixListenerConfig {
+    listener_config("", GROUPS)
+}
+
+fn storage_with_members() -> ListenerStorage {
+    let mut storage = ListenerStorage::default();
+    let members: Vec<OwnedUserId> = vec![
+        user_id!("@bot:example.com").to_owned(),
+        user_id!("@user1:example.com").to_owned(),
+        user_id!("@user3:example.com").to_owned(),
+    ];
+    storage.set_room_members(
+        room_id!("!room:example.com"),
+        members.into_iter().collect(),
+        Instant::now(),
+    );
+    storage
+}
+
+async fn ping(body: &str, storage: &mut ListenerStorage) -> Option<String> {
+    ping_with_config(body, storage, &config()).await
+}
+
+async fn ping_with_config(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        body,
+        user_id!("@user1:example.com"),
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().ok().map(|m| m.message) {
+        Some(MatrixMessageType::FormattedText(m)) => Some(m.plain_text),
+        Some(_) => panic!("Did not get formatted text like expected"),
+        None => None,
+    }
+}
+
+#[tokio::test]
+async fn here_pings_joined_members() {
+    assert_eq!(
+        Some("user3".to_string()),
+        ping("%here standup", &mut storage_with_members()).await
+    )
+}
+#[tokio::test]
+async fn here_without_members_pings_nobody() {
+    assert_eq!(
+        None,
+        ping("%here standup", &mut ListenerStorage::default()).await
+    )
+}
+#[tokio::test]
+async fn here_combines_with_groups() {
+    assert_eq!(
+        Some("user2 user3".to_string()),
+        ping("%here %backend standup", &mut storage_with_members()).await
+    )
+}
+#[test]
+fn members_needed_for_here() {
+    let text = TextMessageEventContent::plain("%HERE standup");
+    let now = Instant::now();
+    let room_id = room_id!("!room:example.com");
+    assert!(needs_room_members(
+        &text,
+        user_id!("@user1:example.com"),
+        room_id,
+        &ListenerStorage::default(),
+        &config(),
+        now
+    ));
+    assert!(!needs_room_members(
+        &text,
+        user_id!("@user1:example.com"),
+        room_id,
+        &storage_with_members(),
+        &config(),
+        now
+    ));
+    // Only users that can group ping cause members to be fetched
+    assert!(!needs_room_members(
+        &text,
+        user_id!("@user3:example.com"),
+        room_id,
+        &ListenerStorage::default(),
+        &config(),
+        now
+    ));
+    assert!(!needs_room_members(
+        &TextMessageEventContent::plain("%backend standup"),
+        user_id!("@user1:example.com"),
+        room_id,
+        &ListenerStorage::default(),
+        &config(),
+        now
+    ))
+}
+#[tokio::test]
+async fn cooldown_blocks_rapid_ping() {
+    let config = listener_config("group_ping_cooldown = 300", GROUPS);
+    let mut storage = ListenerStorage::default();
+    assert!(ping_with_config("%backend", &mut storage, &config)
+        .await
+        .is_some());
+    assert!(ping_with_config("%backend", &mut storage, &config)
+        .await
+        .is_none())
+}
+#[tokio::test]
+async fn cooldown_allows_ping_after_window() {
+    let config = listener_config("group_ping_cooldown = 300", GROUPS);
+    let mut storage = ListenerStorage::default();
+    storage.last_group_ping_time.insert(
+        user_id!("@user1:example.com").to_owned(),
+        SystemTime::now() - Duration::from_secs(301),
+    );
+    assert!(ping_with_config("%backend", &mut storage, &config)
+        .await
+        .is_some())
+}
+#[tokio::test]
+async fn no_cooldown_by_default() {
+    let mut storage = ListenerStorage::default();
+    assert!(ping("%backend", &mut storage).await.is_some());
+    assert!(ping("%backend", &mut storage).await.is_some())
+}
+#[tokio::test]
+async fn group_pings_room_members() {
+    let config = listener_config(
+        "",
+        "[group_pings]
+oncall = ['@user1:example.com', '#oncall:example.com']",
+    );
+    let room = room_alias_id!("#oncall:example.com");
+    let mut storage = ListenerStorage::default();
+    let sender = user_id!("@user1:example.com");
+    let text = TextMessageEventContent::plain("%oncall help");
+    assert_eq!(
+        vec![room.to_owned()],
+        stale_group_rooms(&text, sender, &storage, &config, Instant::now())
+    );
+    // Rooms whose members are unknown are skipped
+    assert_eq!(
+        Some("user1".to_string()),
+        ping_with_config("%oncall help", &mut storage, &config).await
+    );
+    let members: Vec<OwnedUserId> = vec![
+        user_id!("@bot:example.com").to_owned(),
+        user_id!("@user4:example.com").to_owned(),
+    ];
+    storage.set_alias_members(room, members.into_iter().collect(), Instant::now());
+    assert!(stale_group_rooms(&text, sender, &storage, &config, Instant::now()).is_empty());
+    assert_eq!(
+        Some("user4".to_string()),
+        ping_with_config("%oncall help", &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn all_pings_room_members() {
+    let config = listener_config(
+        "",
+        "[group_pings]
+backend = ['@user1:example.com', '@user2:example.com']
+oncall = ['@user1:example.com', '#oncall:example.com']",
+    );
+    let room = room_alias_id!("#oncall:example.com");
+    let mut storage = ListenerStorage::default();
+    let text = TextMessageEventContent::plain("%all help");
+    assert_eq!(
+        vec![room.to_owned()],
+        stale_group_rooms(
+            &text,
+            user_id!("@user1:example.com"),
+            &storage,
+            &config,
+            Instant::now()
+        )
+    );
+    let members: Vec<OwnedUserId> = vec![user_id!("@user4:example.com").to_owned()];
+    storage.set_alias_members(room, members.into_iter().collect(), Instant::now());
+    assert_eq!(
+        Some("user2 user4".to_string()),
+        ping_with_config("%all help", &mut storage, &config).await
+    )
+}
@@ -0,0 +1,55 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::help_handler::help_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+async fn help(body: &str, storage: &mut ListenerStorage, config: &MatrixListenerConfig) -> bool {
+    let (mut send, mut recv) = mpsc::channel(8);
+    help_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    recv.try_recv().is_ok()
+}
+
+#[tokio::test]
+async fn help_suppressed_within_cooldown() {
+    let config = listener_config("help_cooldown_seconds = 60", "");
+    let mut storage = ListenerStorage::default();
+    assert!(help("!help", &mut storage, &config).await);
+    assert!(!help("!help", &mut storage, &config).await);
+    assert!(help("!help ping", &mut storage, &config).await)
+}
+#[tokio::test]
+async fn help_allowed_after_cooldown() {
+    let config = listener_config("help_cooldown_seconds = 60", "");
+    let mut storage = ListenerStorage::default();
+    assert!(help("!help", &mut storage, &config).await);
+    let sent = storage
+        .last_help_time
+        .values_mut()
+        .next()
+        .expect("Help message was not recorded");
+    *sent = match sent.checked_sub(Duration::from_secs(60)) {
+        Some(v) => v,
+        // The clock started less than a minute ago, so no earlier instant exists
+        None => return,
+    };
+    assert!(help("!help", &mut storage, &config).await)
+}
+#[tokio::test]
+async fn help_unlimited_without_cooldown() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    assert!(help("!help", &mut storage, &config).await);
+    assert!(help("!help", &mut storage, &config).await)
+}
@@ -0,0 +1,182 @@
+use super::common::first_reply;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_edit_event;
+use crate::matrix_handlers::listeners::ignore_handler::ignore_handler;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::{Replacement, RoomMessageEventContent, TextMessageEventContent};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch, UserId};
+use tokio::sync::mpsc;
+
+async fn ignore(
+    body: &str,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ignore_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().ok().map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) => Some(m),
+        Some(_) => panic!("Did not get a notice like expected"),
+        None => None,
+    }
+}
+
+async fn chat(body: &str, storage: &mut ListenerStorage) -> bool {
+    first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        storage,
+        &listener_config("", ""),
+    )
+    .await
+    .is_some()
+}
+
+#[tokio::test]
+async fn ignore_and_unignore() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    assert_eq!(
+        Some("Ignoring @user:example.com".to_string()),
+        ignore("!ignore @user:example.com", admin, &mut storage, &config).await
+    );
+    assert!(storage
+        .ignored_users
+        .contains(user_id!("@user:example.com")));
+    assert_eq!(
+        Some("@user:example.com is already ignored".to_string()),
+        ignore("!ignore @user:example.com", admin, &mut storage, &config).await
+    );
+    assert_eq!(
+        Some("No longer ignoring @user:example.com".to_string()),
+        ignore("!unignore @user:example.com", admin, &mut storage, &config).await
+    );
+    assert!(storage.ignored_users.is_empty());
+    assert_eq!(
+        Some("@user:example.com is not ignored".to_string()),
+        ignore("!unignore @user:example.com", admin, &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn list() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    assert_eq!(
+        Some("Ignored users: none\nOpted out of corrections: none".to_string()),
+        ignore("!ignorelist", admin, &mut storage, &config).await
+    );
+    storage
+        .ignored_users
+        .insert(user_id!("@zed:example.com").to_owned());
+    storage
+        .ignored_users
+        .insert(user_id!("@amy:example.com").to_owned());
+    storage
+        .correction_opt_outs
+        .insert(user_id!("@bob:example.com").to_owned());
+    assert_eq!(
+        Some(
+            "Ignored users: @amy:example.com, @zed:example.com\nOpted out of corrections: @bob:example.com"
+                .to_string()
+        ),
+        ignore("!ignorelist", admin, &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn admins_not_ignored() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    assert_eq!(
+        Some("@admin:example.com is an admin and can't be ignored".to_string()),
+        ignore("!ignore @admin:example.com", admin, &mut storage, &config).await
+    );
+    assert!(storage.ignored_users.is_empty())
+}
+#[tokio::test]
+async fn unauthorized() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    assert_eq!(
+        None,
+        ignore(
+            "!ignore @other:example.com",
+            user_id!("@user:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    assert!(storage.ignored_users.is_empty())
+}
+#[tokio::test]
+async fn usage() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    assert_eq!(
+        Some("Usage: !ignore @user:server, !unignore @user:server, or !ignorelist".to_string()),
+        ignore("!ignore user", admin, &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn dispatcher_honors_changes_immediately() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let admin = user_id!("@admin:example.com");
+    assert!(chat("!convert 22km", &mut storage).await);
+    ignore("!ignore @user:example.com", admin, &mut storage, &config).await;
+    assert!(!chat("!convert 22km", &mut storage).await);
+    assert!(!chat("its 22km away", &mut storage).await);
+    ignore("!unignore @user:example.com", admin, &mut storage, &config).await;
+    assert!(chat("!convert 22km", &mut storage).await)
+}
+#[tokio::test]
+async fn ignored_edits() {
+    let mut storage = ListenerStorage::default();
+    storage
+        .ignored_users
+        .insert(user_id!("@user:example.com").to_owned());
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_edit_event(
+        &Replacement::new(
+            event_id!("$original:example.com").to_owned(),
+            Box::new(RoomMessageEventContent::text_plain("its 22km away")),
+        ),
+        event_id!("$edit:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut storage,
+        &listener_config("", ""),
+        &reqwest::Client::new(),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
+#[test]
+fn ignored_users_persist() {
+    let mut storage = ListenerStorage::default();
+    storage
+        .ignored_users
+        .insert(user_id!("@user:example.com").to_owned());
+    let storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    assert!(storage
+        .ignored_users
+        .contains(user_id!("@user:example.com")))
+}
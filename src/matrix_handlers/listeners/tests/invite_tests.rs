@@ -0,0 +1,64 @@
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::handle_invite_event;
+use crate::messages::{MatrixInviteType, MatrixMessageType};
+use ruma::{room_id, user_id, RoomId, UserId};
+use tokio::sync::mpsc;
+
+async fn invite(blocked_servers: &str, sender: &UserId, room_id: &RoomId) -> MatrixInviteType {
+    let config = listener_config(&format!("blocked_servers = {}", blocked_servers), "");
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_invite_event(sender, room_id, &config, &mut send).await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Invite(m) => m.kind,
+        _ => panic!("Did not get an invite response like expected"),
+    }
+}
+
+#[tokio::test]
+async fn admin_accepted() {
+    assert!(matches!(
+        invite(
+            "['example.org']",
+            user_id!("@admin:example.com"),
+            room_id!("!room:example.com")
+        )
+        .await,
+        MatrixInviteType::Accept
+    ))
+}
+#[tokio::test]
+async fn blocked_room_rejected() {
+    assert!(matches!(
+        invite(
+            "['example.org']",
+            user_id!("@admin:example.com"),
+            room_id!("!room:example.org")
+        )
+        .await,
+        MatrixInviteType::Reject
+    ))
+}
+#[tokio::test]
+async fn blocked_sender_rejected() {
+    assert!(matches!(
+        invite(
+            "['example.com']",
+            user_id!("@admin:example.com"),
+            room_id!("!room:example.org")
+        )
+        .await,
+        MatrixInviteType::Reject
+    ))
+}
+#[tokio::test]
+async fn non_admin_rejected() {
+    assert!(matches!(
+        invite(
+            "[]",
+            user_id!("@user:example.com"),
+            room_id!("!room:example.com")
+        )
+        .await,
+        MatrixInviteType::Reject
+    ))
+}
@@ -0,0 +1,82 @@
+use super::common::{formatted_notice, text_event};
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::ip_conversion_handler::ip_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ip_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn cidr_expansion() {
+    assert_eq!(
+        "192.168.1.0/24 => network 192.168.1.0, broadcast 192.168.1.255, netmask 255.255.255.0, 254 hosts (192.168.1.1 - 192.168.1.254)",
+        convert("!convert 192.168.1.0/24").await
+    );
+    assert_eq!(
+        "2001:db8::/32 => network 2001:db8::, last address 2001:db8:ffff:ffff:ffff:ffff:ffff:ffff, 2^96 addresses",
+        convert("!convert 2001:db8::/32").await
+    )
+}
+#[tokio::test]
+async fn netmask_to_cidr() {
+    assert_eq!(
+        "255.255.255.0 => /24",
+        convert("!convert 255.255.255.0 to cidr").await
+    );
+    assert_eq!(
+        "255.255.252.0 => /22",
+        convert("!convert 255.255.252.0 CIDR").await
+    )
+}
+#[tokio::test]
+async fn cidr_to_netmask() {
+    assert_eq!(
+        "/24 => 255.255.255.0",
+        convert("!convert /24 to netmask").await
+    )
+}
+#[tokio::test]
+async fn invalid_input() {
+    assert!(convert("!convert 192.168.1.300/24")
+        .await
+        .contains("192.168.1.300 is not a valid IP address"));
+    assert!(convert("!convert 10.0.0.0/40")
+        .await
+        .contains("/40 is too long for an IPv4 address"));
+    assert!(convert("!convert 255.0.255.0 to cidr")
+        .await
+        .contains("255.0.255.0 is not a valid netmask"));
+    assert!(convert("!convert /33 to netmask")
+        .await
+        .contains("/33 is too long for an IPv4 address"))
+}
+#[tokio::test]
+async fn dispatched_from_convert() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        "!convert 10.0.0.0/30",
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    assert_eq!(
+        "10.0.0.0/30 => network 10.0.0.0, broadcast 10.0.0.3, netmask 255.255.255.252, 2 hosts (10.0.0.1 - 10.0.0.2)",
+        formatted_notice(&mut recv)
+    )
+}
@@ -0,0 +1,99 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::{room_id, user_id, UserId};
+use tokio::sync::mpsc::{self, Sender};
+
+async fn chat(
+    body: &str,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    send: &mut Sender<MatrixMessage>,
+) {
+    text_event(
+        body,
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        &listener_config("enable_karma = true", ""),
+        send,
+    )
+    .await;
+}
+
+fn karma(storage: &ListenerStorage, user: &UserId) -> i64 {
+    storage.karma(room_id!("!room:example.com"), user)
+}
+
+#[tokio::test]
+async fn increment_and_decrement() {
+    let (mut send, _recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    chat("@alice:example.com++", user, &mut storage, &mut send).await;
+    chat("thanks @bob:example.com", user, &mut storage, &mut send).await;
+    chat("@carol:example.com-- rude", user, &mut storage, &mut send).await;
+    assert_eq!(1, karma(&storage, user_id!("@alice:example.com")));
+    assert_eq!(1, karma(&storage, user_id!("@bob:example.com")));
+    assert_eq!(-1, karma(&storage, user_id!("@carol:example.com")))
+}
+#[tokio::test]
+async fn query() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    chat("@alice:example.com++", user, &mut storage, &mut send).await;
+    chat("!karma @alice:example.com", user, &mut storage, &mut send).await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => assert_eq!("@alice:example.com has 1 karma", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn anti_farm_cooldown() {
+    let (mut send, _recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let alice = user_id!("@alice:example.com");
+    for _ in 0..3 {
+        chat(
+            "@alice:example.com++",
+            user_id!("@user:example.com"),
+            &mut storage,
+            &mut send,
+        )
+        .await;
+    }
+    assert_eq!(1, karma(&storage, alice));
+    chat(
+        "@alice:example.com++",
+        user_id!("@other:example.com"),
+        &mut storage,
+        &mut send,
+    )
+    .await;
+    assert_eq!(2, karma(&storage, alice))
+}
+#[tokio::test]
+async fn change_capped_per_message() {
+    let (mut send, _recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    chat(
+        "@a:example.com++ @a:example.com++ @b:example.com++ @c:example.com++ @d:example.com++",
+        user_id!("@user:example.com"),
+        &mut storage,
+        &mut send,
+    )
+    .await;
+    assert_eq!(1, karma(&storage, user_id!("@a:example.com")));
+    assert_eq!(1, karma(&storage, user_id!("@c:example.com")));
+    assert_eq!(0, karma(&storage, user_id!("@d:example.com")))
+}
+#[tokio::test]
+async fn own_karma_ignored() {
+    let (mut send, _recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    chat("@user:example.com++", user, &mut storage, &mut send).await;
+    assert_eq!(0, karma(&storage, user))
+}
@@ -0,0 +1,58 @@
+use crate::config::tests::common::listener_config;
+use crate::config::MatrixListenerConfig;
+use crate::matrix_handlers::listeners::link_handler::link_handler;
+use crate::messages::{MatrixFormattedMessage, MatrixMessageType};
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+fn config() -> MatrixListenerConfig {
+    listener_config(
+        "link_matchers = ['docs']",
+        "[linkable_urls]
+hwa = 'https://example.com/hwa'
+troubleshooting = 'https://example.com/troubleshooting'",
+    )
+}
+
+async fn search(body: &str) -> MatrixFormattedMessage {
+    let (mut send, mut recv) = mpsc::channel(8);
+    link_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &config(),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::FormattedNotice(m) => m,
+        _ => panic!("Did not get a formatted notice like expected"),
+    }
+}
+
+#[tokio::test]
+async fn exact_match() {
+    let message = search("!link hwa").await;
+    assert_eq!("hwa: https://example.com/hwa", message.plain_text);
+    assert_eq!(
+        Some(
+            "<ul><li>hwa: <a href=\"https://example.com/hwa\">https://example.com/hwa</a></li></ul>"
+                .to_string()
+        ),
+        message.formatted_text
+    )
+}
+#[tokio::test]
+async fn fuzzy_match() {
+    let message = search("!link trubleshoting").await;
+    assert_eq!(
+        "troubleshooting: https://example.com/troubleshooting",
+        message.plain_text
+    )
+}
+#[tokio::test]
+async fn no_match() {
+    let message = search("!link kodi").await;
+    assert_eq!("No links found matching kodi", message.plain_text)
+}
@@ -0,0 +1,155 @@
+use super::common::{first_reply, notice};
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MaintenanceBanner, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::maintenance_handler::with_banner;
+use crate::messages::{MatrixFormattedMessage, MatrixMessageType};
+use ruma::{room_id, user_id, UserId};
+
+async fn reply(
+    body: &str,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    notice(first_reply(body, sender, room_id!("!room:example.com"), storage, config).await)
+}
+
+#[tokio::test]
+async fn banner_on_replies() {
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("", "");
+    let admin = user_id!("@admin:example.com");
+    let user = user_id!("@user:example.com");
+    assert_eq!(
+        Some("Maintenance mode is on. Command replies will show: Restarting soon".to_string()),
+        reply(
+            "!maintenance on Restarting soon",
+            admin,
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    assert_eq!(
+        Some("Restarting soon\n5.00km => 3.11mi".to_string()),
+        reply("!convert 5km", user, &mut storage, &config).await
+    );
+    assert_eq!(
+        Some("Maintenance mode is off".to_string()),
+        reply("!maintenance off", admin, &mut storage, &config).await
+    );
+    assert_eq!(
+        Some("5.00km => 3.11mi".to_string()),
+        reply("!convert 5km", user, &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn banner_placement() {
+    let mut storage = ListenerStorage {
+        maintenance: Some("Restarting soon".to_string()),
+        ..ListenerStorage::default()
+    };
+    let user = user_id!("@user:example.com");
+    assert_eq!(
+        Some("5.00km => 3.11mi\nRestarting soon".to_string()),
+        reply(
+            "!convert 5km",
+            user,
+            &mut storage,
+            &listener_config("maintenance_banner = 'append'", "")
+        )
+        .await
+    );
+    assert_eq!(
+        Some("Restarting soon".to_string()),
+        reply(
+            "!convert 5km",
+            user,
+            &mut storage,
+            &listener_config("maintenance_banner = 'replace'", "")
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn banner_with_reply_budget() {
+    let mut storage = ListenerStorage {
+        maintenance: Some("Restarting soon".to_string()),
+        ..ListenerStorage::default()
+    };
+    let config = listener_config("max_replies_per_user = 1", "");
+    let user = user_id!("@user:example.com");
+    assert_eq!(
+        Some("Restarting soon\n5.00km => 3.11mi".to_string()),
+        reply("!convert 5km", user, &mut storage, &config).await
+    );
+    assert_eq!(
+        None,
+        reply("!convert 5km", user, &mut storage, &config).await
+    )
+}
+#[tokio::test]
+async fn only_admins_change_maintenance() {
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("", "");
+    assert_eq!(
+        None,
+        reply(
+            "!maintenance on Restarting soon",
+            user_id!("@user:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    assert_eq!(None, storage.maintenance)
+}
+#[tokio::test]
+async fn usage() {
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("", "");
+    let admin = user_id!("@admin:example.com");
+    let usage = Some("Usage: !maintenance on <message> or !maintenance off".to_string());
+    assert_eq!(
+        usage,
+        reply("!maintenance on", admin, &mut storage, &config).await
+    );
+    assert_eq!(
+        usage,
+        reply("!maintenance", admin, &mut storage, &config).await
+    );
+    assert_eq!(
+        Some("Maintenance mode is not on".to_string()),
+        reply("!maintenance off", admin, &mut storage, &config).await
+    )
+}
+#[test]
+fn persists() {
+    let storage = ListenerStorage {
+        maintenance: Some("Restarting soon".to_string()),
+        ..ListenerStorage::default()
+    };
+    let storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    assert_eq!(Some("Restarting soon".to_string()), storage.maintenance)
+}
+#[test]
+fn formatted_banner() {
+    let message = with_banner(
+        MatrixMessageType::FormattedNotice(MatrixFormattedMessage {
+            plain_text: "reply".to_string(),
+            formatted_text: Some("<b>reply</b>".to_string()),
+        }),
+        "Restarting soon",
+        MaintenanceBanner::Prepend,
+    );
+    match message {
+        MatrixMessageType::FormattedNotice(v) => {
+            assert_eq!("Restarting soon\nreply", v.plain_text);
+            assert_eq!(
+                Some("Restarting soon<br><b>reply</b>".to_string()),
+                v.formatted_text
+            )
+        }
+        _ => panic!("Did not get a formatted notice like expected"),
+    }
+}
@@ -0,0 +1,58 @@
+mod common;
+
+mod allowed_command_tests;
+mod announce_handler_tests;
+mod audit_handler_tests;
+mod ban_handler_tests;
+mod color_conversion_handler_tests;
+mod command_index_tests;
+mod command_prefix_tests;
+mod config_check_handler_tests;
+mod conversion_marker_tests;
+mod correction_batch_tests;
+mod correction_handler_tests;
+mod correctme_handler_tests;
+mod cron_conversion_handler_tests;
+mod data_rate_conversion_handler_tests;
+mod direct_message_tests;
+mod dpi_conversion_handler_tests;
+mod edit_tests;
+mod encoding_handler_tests;
+mod event_age_tests;
+mod flood_handler_tests;
+mod gas_mark_conversion_handler_tests;
+mod github_rate_limit_tests;
+mod github_search_tests;
+mod grant_handler_tests;
+mod group_ping_tests;
+mod help_handler_tests;
+mod ignore_handler_tests;
+mod invite_tests;
+mod ip_conversion_handler_tests;
+mod karma_tests;
+mod link_handler_tests;
+mod maintenance_handler_tests;
+mod own_event_tests;
+mod passive_conversion_tests;
+mod percent_conversion_handler_tests;
+mod ping_handler_tests;
+mod pingtest_handler_tests;
+mod ratio_conversion_handler_tests;
+mod raw_handler_tests;
+mod reaction_roles_handler_tests;
+mod reaction_tests;
+mod redaction_tests;
+mod reply_budget_tests;
+mod roman_conversion_handler_tests;
+mod slash_command_tests;
+mod stats_handler_tests;
+mod thread_tests;
+mod timezone_handler_tests;
+mod typing_tests;
+mod unauthorized_reply_tests;
+mod unban_handler_tests;
+mod unit_conversion_handler_tests;
+mod version_handler_tests;
+mod warn_handler_tests;
+mod wave_conversion_handler_tests;
+mod words_conversion_handler_tests;
@@ -0,0 +1,66 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::{handle_edit_event, handle_reaction_event};
+use ruma::events::reaction::Relation as Annotation;
+use ruma::events::room::message::{Replacement, RoomMessageEventContent};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn own_command_ignored() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        "!convert 22km",
+        user_id!("@bot:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn own_edit_ignored() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let replacement = Replacement::new(
+        event_id!("$original:example.com").to_owned(),
+        Box::new(RoomMessageEventContent::text_plain("22km => 13.67mi")),
+    );
+    handle_edit_event(
+        &replacement,
+        event_id!("$edit:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        user_id!("@bot:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("convert_on_edit = true", ""),
+        &reqwest::Client::new(),
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn own_reaction_ignored() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    // The bot being an admin must not let it confirm its own prompts
+    let mut config = listener_config("", "");
+    config
+        .admins
+        .insert(user_id!("@bot:example.com").to_owned());
+    handle_reaction_event(
+        &Annotation::new(
+            event_id!("$prompt:example.com").to_owned(),
+            "✅".to_string(),
+        ),
+        user_id!("@bot:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &config,
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
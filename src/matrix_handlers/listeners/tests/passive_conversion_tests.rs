@@ -0,0 +1,76 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::MatrixMessage;
+use ruma::{room_id, user_id, RoomId};
+use tokio::sync::mpsc::{self, Sender};
+
+fn config() -> MatrixListenerConfig {
+    listener_config("passive_conversions = ['!passive:example.com']", "")
+}
+
+async fn chat(
+    body: &str,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    send: &mut Sender<MatrixMessage>,
+) {
+    text_event(
+        body,
+        user_id!("@user:example.com"),
+        room_id,
+        storage,
+        &config(),
+        send,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn passive_conversion_triggers() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    chat(
+        "its 22km away",
+        room_id!("!passive:example.com"),
+        &mut storage,
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_ok());
+    assert!(storage
+        .last_passive_conversion_time
+        .contains_key(room_id!("!passive:example.com")))
+}
+#[tokio::test]
+async fn cooldown_suppresses() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    for _ in 0..2 {
+        chat(
+            "its 22km away",
+            room_id!("!passive:example.com"),
+            &mut storage,
+            &mut send,
+        )
+        .await;
+    }
+    assert!(recv.try_recv().is_ok());
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn other_rooms_without_cooldown() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    for _ in 0..2 {
+        chat(
+            "its 22km away",
+            room_id!("!other:example.com"),
+            &mut storage,
+            &mut send,
+        )
+        .await;
+    }
+    assert!(recv.try_recv().is_ok());
+    assert!(recv.try_recv().is_ok())
+}
@@ -0,0 +1,31 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::percent_conversion_handler::percent_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    percent_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn percent() {
+    assert_eq!("25% => 0.25, 1/4, 1:4", convert("!convert 25%").await)
+}
+#[tokio::test]
+async fn fraction_to_percent() {
+    assert_eq!("3/8 => 37.5%", convert("!convert 3/8 to %").await)
+}
+#[tokio::test]
+async fn divide_by_zero() {
+    assert_eq!("3/0 divides by zero", convert("!convert 3/0").await)
+}
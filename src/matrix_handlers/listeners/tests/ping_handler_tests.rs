@@ -0,0 +1,72 @@
+use crate::config::tests::common::listener_config;
+use crate::config::MatrixListenerConfig;
+use crate::matrix_handlers::listeners::ping_handler::{ping_handler, ping_message};
+use crate::messages::MatrixMessageType;
+use ruma::{event_id, room_id, MilliSecondsSinceUnixEpoch, UInt};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+
+fn at(secs: u64) -> SystemTime {
+    SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+}
+
+fn sent_at(millis: u64) -> MilliSecondsSinceUnixEpoch {
+    MilliSecondsSinceUnixEpoch(UInt::new(millis).unwrap())
+}
+
+async fn ping(config: &MatrixListenerConfig) -> Option<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ping_handler(
+        event_id!("$event:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        room_id!("!room:example.com"),
+        config,
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok().map(|m| m.message)
+}
+
+#[test]
+fn latency_and_uptime() {
+    let now = at(1_000_000);
+    assert_eq!(
+        format!(
+            "matrix-bot v{} is up. Latency: 250ms, uptime: 1d 1h 1m 1s",
+            env!("CARGO_PKG_VERSION")
+        ),
+        ping_message(sent_at(999_999_750), now - Duration::from_secs(90061), now)
+    )
+}
+#[test]
+fn short_uptime_leaves_out_zero_units() {
+    let now = at(1_000_000);
+    assert!(
+        ping_message(sent_at(1_000_000_000), now - Duration::from_secs(5), now)
+            .ends_with("Latency: 0ms, uptime: 5s")
+    );
+    assert!(
+        ping_message(sent_at(1_000_000_000), now - Duration::from_secs(3600), now)
+            .ends_with("uptime: 1h 0m 0s")
+    )
+}
+#[test]
+fn homeserver_clock_ahead() {
+    let now = at(1_000_000);
+    assert!(ping_message(sent_at(1_000_005_000), now, now).ends_with("Latency: 0ms, uptime: 0s"))
+}
+#[tokio::test]
+async fn ping_replies() {
+    match ping(&listener_config("", "")).await {
+        Some(MatrixMessageType::Notice(m)) => assert!(m.contains("is up. Latency: ")),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn ping_limited_to_help_rooms() {
+    assert!(
+        ping(&listener_config("help_rooms = ['!other:example.com']", ""))
+            .await
+            .is_none()
+    )
+}
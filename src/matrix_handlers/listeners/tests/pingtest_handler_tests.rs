@@ -0,0 +1,107 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::pingtest_handler::pingtest_handler;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+fn group_config() -> MatrixListenerConfig {
+    listener_config(
+        "",
+        "[group_pings]
+backend = ['@user2:example.com', '@user1:example.com']
+server = ['%backend']
+web = ['@user3:example.com']",
+    )
+}
+
+async fn pingtest(body: &str, sender: &UserId) -> Option<String> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    pingtest_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &group_config(),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().ok().map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) => Some(m),
+        Some(_) => panic!("Did not get a notice like expected"),
+        None => None,
+    }
+}
+
+#[tokio::test]
+async fn preview_group() {
+    assert_eq!(
+        Some("%backend would ping 2 users:\n@user1:example.com\n@user2:example.com".to_string()),
+        pingtest("!pingtest %backend", user_id!("@admin:example.com")).await
+    )
+}
+#[tokio::test]
+async fn preview_alias_group() {
+    assert_eq!(
+        Some("%server would ping 2 users:\n@user1:example.com\n@user2:example.com".to_string()),
+        pingtest("!pingtest Server", user_id!("@user3:example.com")).await
+    )
+}
+#[tokio::test]
+async fn preview_all() {
+    assert_eq!(
+        Some(
+            "%all would ping 3 users:\n@user1:example.com\n@user2:example.com\n@user3:example.com"
+                .to_string()
+        ),
+        pingtest("!pingtest all", user_id!("@admin:example.com")).await
+    )
+}
+#[tokio::test]
+async fn unknown_group() {
+    assert_eq!(
+        Some("Unknown group %frontend".to_string()),
+        pingtest("!pingtest %frontend", user_id!("@admin:example.com")).await
+    )
+}
+#[tokio::test]
+async fn unauthorized() {
+    assert_eq!(
+        None,
+        pingtest("!pingtest %backend", user_id!("@user:example.com")).await
+    )
+}
+#[tokio::test]
+async fn preview_here() {
+    assert_eq!(
+        Some("%here would ping the members currently in the room".to_string()),
+        pingtest("!pingtest %here", user_id!("@admin:example.com")).await
+    )
+}
+#[tokio::test]
+async fn preview_group_with_room() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    pingtest_handler(
+        &TextMessageEventContent::plain("!pingtest %oncall"),
+        event_id!("$event:example.com"),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            "",
+            "[group_pings]
+oncall = ['@user1:example.com', '#oncall:example.com']",
+        ),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => assert_eq!(
+            "%oncall would ping 1 user:\n@user1:example.com\nplus the members of #oncall:example.com",
+            m
+        ),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
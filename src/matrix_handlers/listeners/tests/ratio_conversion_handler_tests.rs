@@ -0,0 +1,40 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::ratio_conversion_handler::ratio_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    ratio_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn resolution_to_ratio() {
+    assert_eq!(
+        "1920x1080 => 16:9",
+        convert("!convert 1920x1080 ratio").await
+    )
+}
+#[tokio::test]
+async fn ratio_to_resolution() {
+    assert_eq!(
+        "16:9 at 1080p => 1920x1080",
+        convert("!convert 16:9 at 1080p").await
+    )
+}
+#[tokio::test]
+async fn invalid_resolution() {
+    assert_eq!(
+        "1920x0 is not a valid resolution",
+        convert("!convert 1920x0 ratio").await
+    )
+}
@@ -0,0 +1,66 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::handle_text_event;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::{InReplyTo, Relation, TextMessageEventContent};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch, UserId};
+use tokio::sync::mpsc;
+
+async fn raw(
+    body: &str,
+    relates_to: Option<&Relation>,
+    sender: &UserId,
+) -> Option<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_text_event(
+        &TextMessageEventContent::plain(body),
+        relates_to,
+        event_id!("$event:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        sender,
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &reqwest::Client::new(),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok().map(|m| m.message)
+}
+
+fn reply() -> Relation {
+    Relation::Reply {
+        in_reply_to: InReplyTo::new(event_id!("$original:example.com").to_owned()),
+    }
+}
+
+#[tokio::test]
+async fn reply_shows_replied_to_event() {
+    match raw("!raw", Some(&reply()), user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::RawEvent(e)) => assert_eq!("$original:example.com", e),
+        _ => panic!("Did not get a raw event request like expected"),
+    }
+}
+#[tokio::test]
+async fn reply_fallback_ignored() {
+    let body = "> <@user:example.com> hello\n> world\n\n!raw";
+    assert!(matches!(
+        raw(body, Some(&reply()), user_id!("@admin:example.com")).await,
+        Some(MatrixMessageType::RawEvent(_))
+    ))
+}
+#[tokio::test]
+async fn not_a_reply() {
+    match raw("!raw", None, user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::Notice(m)) => {
+            assert_eq!("Reply to a message with !raw to see its JSON", m)
+        }
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn admins_only() {
+    assert!(raw("!raw", Some(&reply()), user_id!("@user:example.com"))
+        .await
+        .is_none())
+}
@@ -0,0 +1,193 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_reaction_event;
+use crate::matrix_handlers::listeners::reaction_roles_handler::{role_message, roles_handler};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::events::reaction::Relation;
+use ruma::{event_id, room_id, user_id, RoomId, UserId};
+use tokio::sync::mpsc;
+
+const ROLES: &str = "[custom_emoji]
+approve = 'mxc://example.com/approve'
+[reaction_roles]
+room = '!roles:example.com'
+[reaction_roles.roles]
+'🎮' = '!gaming:example.com'
+':approve:' = '!announcements:example.com'";
+
+async fn react(
+    key: &str,
+    room_id: &RoomId,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<MatrixMessage> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_reaction_event(
+        &Relation::new(event_id!("$roles:example.com").to_owned(), key.to_string()),
+        sender,
+        room_id,
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok()
+}
+
+fn invited(message: Option<MatrixMessage>) -> Option<(String, String)> {
+    match message {
+        Some(MatrixMessage {
+            room_id: Some(room_id),
+            message: MatrixMessageType::InviteUser(user),
+            ..
+        }) => Some((room_id.to_string(), user.to_string())),
+        Some(_) => panic!("Did not get an invite like expected"),
+        None => None,
+    }
+}
+
+#[tokio::test]
+async fn emoji_invites_to_room() {
+    let config = listener_config("", ROLES);
+    let room = room_id!("!roles:example.com");
+    let user = user_id!("@user:example.com");
+    let mut storage = ListenerStorage::default();
+    assert_eq!(
+        Some((
+            "!gaming:example.com".to_string(),
+            "@user:example.com".to_string()
+        )),
+        invited(react("🎮", room, user, &mut storage, &config).await)
+    );
+    assert_eq!(
+        Some((
+            "!announcements:example.com".to_string(),
+            "@user:example.com".to_string()
+        )),
+        invited(
+            react(
+                "mxc://example.com/approve",
+                room,
+                user,
+                &mut storage,
+                &config
+            )
+            .await
+        )
+    )
+}
+#[tokio::test]
+async fn other_room_ignored() {
+    let config = listener_config("", ROLES);
+    let mut storage = ListenerStorage::default();
+    assert!(react(
+        "🎮",
+        room_id!("!room:example.com"),
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config
+    )
+    .await
+    .is_none())
+}
+#[tokio::test]
+async fn unmapped_emoji_ignored() {
+    let config = listener_config("", ROLES);
+    let mut storage = ListenerStorage::default();
+    assert!(react(
+        "👍",
+        room_id!("!roles:example.com"),
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config
+    )
+    .await
+    .is_none())
+}
+#[tokio::test]
+async fn repeat_reaction_rate_limited() {
+    let config = listener_config("", ROLES);
+    let room = room_id!("!roles:example.com");
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    assert!(react("🎮", room, user, &mut storage, &config)
+        .await
+        .is_some());
+    assert!(react("🎮", room, user, &mut storage, &config)
+        .await
+        .is_none());
+    // Other users aren't limited by it
+    assert!(react(
+        "🎮",
+        room,
+        user_id!("@other:example.com"),
+        &mut storage,
+        &config
+    )
+    .await
+    .is_some())
+}
+#[tokio::test]
+async fn ignored_user_not_invited() {
+    let config = listener_config("", ROLES);
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@user:example.com");
+    storage.ignored_users.insert(user.to_owned());
+    assert!(react(
+        "🎮",
+        room_id!("!roles:example.com"),
+        user,
+        &mut storage,
+        &config
+    )
+    .await
+    .is_none())
+}
+#[tokio::test]
+async fn disabled_without_config() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    assert!(react(
+        "🎮",
+        room_id!("!roles:example.com"),
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config
+    )
+    .await
+    .is_none())
+}
+#[test]
+fn lists_roles() {
+    let config = listener_config("", ROLES);
+    assert_eq!(
+        "React to be invited to a room:\n:approve: !announcements:example.com\n🎮 !gaming:example.com",
+        role_message(config.reaction_roles.as_ref().unwrap())
+    )
+}
+#[tokio::test]
+async fn admin_posts_role_message() {
+    let config = listener_config("", ROLES);
+    let (mut send, mut recv) = mpsc::channel(8);
+    roles_handler(
+        event_id!("$event:example.com"),
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &config,
+        &mut send,
+    )
+    .await;
+    match recv.try_recv() {
+        Ok(MatrixMessage {
+            room_id: Some(room_id),
+            message: MatrixMessageType::RoleMessage(_, emoji),
+            ..
+        }) => {
+            assert_eq!(room_id!("!roles:example.com"), room_id);
+            assert_eq!(2, emoji.len())
+        }
+        _ => panic!("Did not get a role message like expected"),
+    }
+}
@@ -0,0 +1,89 @@
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_reaction_event;
+use crate::messages::MatrixMessageType;
+use ruma::events::reaction::Relation;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+async fn react(key: &str, sender: &UserId) -> Option<MatrixMessageType> {
+    react_with_config(key, sender, &listener_config("", "")).await
+}
+
+async fn react_with_config(
+    key: &str,
+    sender: &UserId,
+    config: &MatrixListenerConfig,
+) -> Option<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_reaction_event(
+        &Relation::new(event_id!("$prompt:example.com").to_owned(), key.to_string()),
+        sender,
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        config,
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok().map(|m| m.message)
+}
+
+#[tokio::test]
+async fn admin_confirms() {
+    match react("✅", user_id!("@admin:example.com")).await {
+        Some(MatrixMessageType::ConfirmAction(e)) => assert_eq!("$prompt:example.com", e),
+        _ => panic!("Did not get a confirmation like expected"),
+    }
+}
+#[tokio::test]
+async fn variation_selector_confirms() {
+    assert!(react("✅\u{fe0f}", user_id!("@admin:example.com"))
+        .await
+        .is_some())
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert!(react("✅", user_id!("@user:example.com")).await.is_none())
+}
+#[tokio::test]
+async fn admin_without_ban_authorization_ignored() {
+    let config = listener_config("ban_authorized_users = ['@mod:example.com']", "");
+    assert!(
+        react_with_config("✅", user_id!("@admin:example.com"), &config)
+            .await
+            .is_none()
+    );
+    assert!(
+        react_with_config("✅", user_id!("@mod:example.com"), &config)
+            .await
+            .is_some()
+    )
+}
+#[tokio::test]
+async fn other_reaction_ignored() {
+    assert!(react("👍", user_id!("@admin:example.com")).await.is_none())
+}
+#[tokio::test]
+async fn custom_emoji_confirms() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let config = listener_config(
+        "confirmation_reaction = ':approve:'",
+        "[custom_emoji]\napprove = 'mxc://example.com/approve'",
+    );
+    for key in ["✅", "mxc://example.com/approve"].iter() {
+        handle_reaction_event(
+            &Relation::new(event_id!("$prompt:example.com").to_owned(), key.to_string()),
+            user_id!("@admin:example.com"),
+            room_id!("!room:example.com"),
+            &mut ListenerStorage::default(),
+            &config,
+            &mut send,
+        )
+        .await;
+    }
+    assert!(matches!(
+        recv.try_recv().map(|m| m.message),
+        Ok(MatrixMessageType::ConfirmAction(_))
+    ));
+    assert!(recv.try_recv().is_err())
+}
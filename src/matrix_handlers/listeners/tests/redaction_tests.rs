@@ -0,0 +1,56 @@
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::handle_redaction_event;
+use crate::messages::MatrixMessageType;
+use ruma::{event_id, room_id, user_id};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn enabled() {
+    let config = listener_config("enable_response_redaction = true", "");
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_redaction_event(
+        event_id!("$trigger:example.com"),
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &config,
+        &mut send,
+    )
+    .await;
+    let message = recv.try_recv().unwrap();
+    assert_eq!(
+        Some(room_id!("!room:example.com").to_owned()),
+        message.room_id
+    );
+    match message.message {
+        MatrixMessageType::RedactResponses(e) => assert_eq!("$trigger:example.com", e.as_str()),
+        _ => panic!("Did not get a redact responses message like expected"),
+    }
+}
+#[tokio::test]
+async fn disabled() {
+    let config = listener_config("", "");
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_redaction_event(
+        event_id!("$trigger:example.com"),
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &config,
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
+#[tokio::test]
+async fn from_self() {
+    let config = listener_config("enable_response_redaction = true", "");
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_redaction_event(
+        event_id!("$trigger:example.com"),
+        user_id!("@bot:example.com"),
+        room_id!("!room:example.com"),
+        &config,
+        &mut send,
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
@@ -0,0 +1,72 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::MatrixMessage;
+use ruma::{room_id, user_id, UserId};
+use tokio::sync::mpsc::{self, Receiver, Sender};
+
+async fn help(
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    text_event(
+        "!help",
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        config,
+        send,
+    )
+    .await;
+}
+
+fn received(recv: &mut Receiver<MatrixMessage>) -> usize {
+    let mut count = 0;
+    while recv.try_recv().is_ok() {
+        count += 1;
+    }
+    count
+}
+
+#[tokio::test]
+async fn budget_stops_replies() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("max_replies_per_user = 2", "");
+    for _ in 0..3 {
+        help(
+            user_id!("@user:example.com"),
+            &mut storage,
+            &config,
+            &mut send,
+        )
+        .await;
+    }
+    assert_eq!(2, received(&mut recv));
+    help(
+        user_id!("@other:example.com"),
+        &mut storage,
+        &config,
+        &mut send,
+    )
+    .await;
+    assert_eq!(1, received(&mut recv))
+}
+#[tokio::test]
+async fn admins_exempt() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    let mut storage = ListenerStorage::default();
+    let config = listener_config("max_replies_per_user = 1", "");
+    for _ in 0..3 {
+        help(
+            user_id!("@admin:example.com"),
+            &mut storage,
+            &config,
+            &mut send,
+        )
+        .await;
+    }
+    assert_eq!(3, received(&mut recv))
+}
@@ -0,0 +1,82 @@
+use super::common::{first_reply, formatted_notice};
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::roman_conversion_handler::roman_conversion_handler;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    roman_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn numeral_to_number() {
+    assert_eq!("XLII => 42", convert("!convert XLII").await)
+}
+#[tokio::test]
+async fn number_to_numeral() {
+    assert_eq!("42 => XLII", convert("!convert 42 roman").await)
+}
+#[tokio::test]
+async fn invalid_numeral() {
+    assert_eq!(
+        "IIII is not a valid Roman numeral",
+        convert("!convert IIII").await
+    )
+}
+#[tokio::test]
+async fn out_of_range() {
+    assert_eq!(
+        "4000 is out of range. Roman numerals can only represent 1 to 3999",
+        convert("!convert 4000 roman").await
+    )
+}
+#[tokio::test]
+async fn numeral_with_keyword() {
+    assert_eq!("MM => 2000", convert("!convert MM roman").await)
+}
+
+async fn dispatch(body: &str) -> MatrixMessageType {
+    first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+    )
+    .await
+    .unwrap()
+    .message
+}
+
+#[tokio::test]
+async fn unit_names_not_numerals() {
+    for &(body, hint) in &[
+        ("!convert C", "c converts to f"),
+        ("!convert L", "l converts to gal"),
+        ("!convert M", "m converts to ft"),
+    ] {
+        match dispatch(body).await {
+            MatrixMessageType::Notice(m) => assert_eq!(hint, m),
+            _ => panic!("Did not get a unit hint like expected"),
+        }
+    }
+}
+#[tokio::test]
+async fn numerals_routed() {
+    match dispatch("!convert XLII").await {
+        MatrixMessageType::FormattedNotice(m) => assert_eq!("XLII => 42", m.plain_text),
+        _ => panic!("Did not get a formatted notice like expected"),
+    }
+}
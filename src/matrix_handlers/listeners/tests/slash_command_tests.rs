@@ -0,0 +1,50 @@
+use super::common::first_reply;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::MatrixMessageType;
+use ruma::{room_id, user_id};
+
+async fn dispatch(body: &str, config: &MatrixListenerConfig) -> Option<MatrixMessageType> {
+    first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        config,
+    )
+    .await
+    .map(|m| m.message)
+}
+
+fn config() -> MatrixListenerConfig {
+    // Commandless conversions are disabled so only the convert command can reply
+    listener_config(
+        "enable_unit_conversions = false\nslash_commands = ['/convert']",
+        "",
+    )
+}
+
+#[tokio::test]
+async fn slash_command_converts() {
+    match dispatch("/convert 100km", &config()).await {
+        Some(MatrixMessageType::Notice(m)) => assert_eq!("100.00km => 62.14mi", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn slash_command_any_case() {
+    assert!(dispatch("/Convert 100km", &config()).await.is_some())
+}
+#[tokio::test]
+async fn bang_command_still_converts() {
+    assert!(dispatch("!convert 100km", &config()).await.is_some())
+}
+#[tokio::test]
+async fn unconfigured_slash_command_ignored() {
+    assert!(dispatch("/help", &config()).await.is_none())
+}
+#[tokio::test]
+async fn slash_commands_disabled_by_default() {
+    let config = listener_config("enable_unit_conversions = false", "");
+    assert!(dispatch("/convert 100km", &config).await.is_none())
+}
@@ -0,0 +1,198 @@
+use super::common::{corrections_config, first_reply, text_reply};
+use crate::config::tests::common::listener_config;
+use crate::config::{ActivityStats, ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::handle_stats_report;
+use crate::matrix_handlers::listeners::stats_handler::stats_report;
+use crate::messages::MatrixMessageType;
+use ruma::{room_id, user_id, UserId};
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc;
+
+async fn chat(
+    body: &str,
+    sender: &UserId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    text_reply(first_reply(body, sender, room_id!("!room:example.com"), storage, config).await)
+}
+
+#[tokio::test]
+async fn correction_counted() {
+    let config = corrections_config();
+    let mut storage = ListenerStorage::default();
+    assert_eq!(
+        Some("user said jellyfish".to_string()),
+        chat(
+            "i use jellyfish",
+            user_id!("@user:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    );
+    let stats = &storage.correction_stats[room_id!("!room:example.com")];
+    assert_eq!(1, stats.total);
+    assert_eq!(Some(&1), stats.spellings.get("jellyfish"))
+}
+#[tokio::test]
+async fn stats_rendered() {
+    let config = corrections_config();
+    let mut storage = ListenerStorage::default();
+    let room = room_id!("!room:example.com");
+    for _ in 0..3 {
+        storage.record_correction(room, "jellyfish");
+    }
+    storage.record_correction(room, "JellyFin");
+    assert_eq!(
+        Some(
+            "4 corrections made in this room\nMost corrected: jellyfish (3), JellyFin (1)"
+                .to_string()
+        ),
+        chat(
+            "!stats corrections",
+            user_id!("@admin:example.com"),
+            &mut storage,
+            &config
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn no_corrections() {
+    assert_eq!(
+        Some("No corrections made in this room".to_string()),
+        chat(
+            "!stats corrections",
+            user_id!("@admin:example.com"),
+            &mut ListenerStorage::default(),
+            &corrections_config()
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    assert!(chat(
+        "!stats corrections",
+        user_id!("@user:example.com"),
+        &mut ListenerStorage::default(),
+        &corrections_config()
+    )
+    .await
+    .is_none())
+}
+#[test]
+fn report_compiled() {
+    let activity = ActivityStats {
+        since: None,
+        messages: 120,
+        corrections: 3,
+        conversions: 10,
+        bans: 1,
+        errors: 0,
+    };
+    assert_eq!(
+        "Stats for the last 24 hours
+Messages processed: 120
+Corrections: 3
+Conversions: 10
+Users banned: 1
+Sync errors: 0",
+        stats_report(&activity, Duration::from_secs(24 * 3600))
+    );
+    assert!(
+        stats_report(&ActivityStats::default(), Duration::from_secs(3600))
+            .starts_with("Stats for the last 1 hour\n")
+    )
+}
+#[tokio::test]
+async fn activity_reported_and_reset() {
+    let config = listener_config("stats_room = '!stats:example.com'", "");
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    let start = SystemTime::now();
+    handle_stats_report(&mut storage, &config, &mut send, start).await;
+    assert!(recv.try_recv().is_err());
+
+    chat(
+        "!convert 22km",
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config,
+    )
+    .await;
+    chat(
+        "hello",
+        user_id!("@user:example.com"),
+        &mut storage,
+        &config,
+    )
+    .await;
+    assert_eq!(2, storage.activity.messages);
+    assert_eq!(1, storage.activity.conversions);
+
+    handle_stats_report(
+        &mut storage,
+        &config,
+        &mut send,
+        start + Duration::from_secs(60),
+    )
+    .await;
+    assert!(recv.try_recv().is_err());
+
+    let end = start + Duration::from_secs(24 * 3600);
+    handle_stats_report(&mut storage, &config, &mut send, end).await;
+    let message = recv.try_recv().unwrap();
+    assert_eq!(
+        Some(room_id!("!stats:example.com").to_owned()),
+        message.room_id
+    );
+    match message.message {
+        MatrixMessageType::Notice(m) => assert!(m.contains("Messages processed: 2\n")),
+        _ => panic!("Did not get a notice like expected"),
+    }
+    assert_eq!(
+        ActivityStats {
+            since: Some(end),
+            ..ActivityStats::default()
+        },
+        storage.activity
+    )
+}
+#[tokio::test]
+async fn responder_bans_reported() {
+    let config = listener_config("stats_room = '!stats:example.com'", "");
+    let mut storage = ListenerStorage::default();
+    let banned_users = Arc::clone(&storage.banned_users);
+    let (mut send, mut recv) = mpsc::channel(8);
+    let start = SystemTime::now();
+    handle_stats_report(&mut storage, &config, &mut send, start).await;
+
+    banned_users.fetch_add(2, Ordering::Relaxed);
+    let end = start + Duration::from_secs(24 * 3600);
+    handle_stats_report(&mut storage, &config, &mut send, end).await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => assert!(m.contains("Users banned: 2\n")),
+        _ => panic!("Did not get a notice like expected"),
+    }
+    assert_eq!(0, banned_users.load(Ordering::Relaxed))
+}
+#[tokio::test]
+async fn no_report_without_room() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    let (mut send, mut recv) = mpsc::channel(8);
+    let start = SystemTime::now();
+    handle_stats_report(&mut storage, &config, &mut send, start).await;
+    handle_stats_report(
+        &mut storage,
+        &config,
+        &mut send,
+        start + Duration::from_secs(48 * 3600),
+    )
+    .await;
+    assert!(recv.try_recv().is_err())
+}
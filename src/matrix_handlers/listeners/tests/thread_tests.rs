@@ -0,0 +1,98 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::handle_text_event;
+use crate::matrix_handlers::listeners::help_handler::help_handler;
+use crate::matrix_handlers::listeners::unit_conversion_handler::unit_conversion_handler;
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use ruma::events::room::message::{Relation, TextMessageEventContent, Thread};
+use ruma::{event_id, room_id, user_id, MilliSecondsSinceUnixEpoch};
+use tokio::sync::mpsc;
+
+fn in_thread() -> Relation {
+    Relation::Thread(Thread::plain(
+        event_id!("$root:example.com").to_owned(),
+        event_id!("$latest:example.com").to_owned(),
+    ))
+}
+
+async fn convert(body: &str, relates_to: Option<&Relation>) -> Option<MatrixMessage> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    unit_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        relates_to,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        None,
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().ok()
+}
+
+async fn help(relates_to: Option<&Relation>) -> MatrixMessage {
+    let (mut send, mut recv) = mpsc::channel(8);
+    help_handler(
+        &TextMessageEventContent::plain("!help"),
+        relates_to,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    recv.try_recv().unwrap()
+}
+
+#[tokio::test]
+async fn conversion_in_thread_replies_in_thread() {
+    let message = convert("!convert 22km", Some(&in_thread())).await.unwrap();
+    assert_eq!(
+        Some(event_id!("$root:example.com").to_owned()),
+        message.thread_root
+    );
+    match message.message {
+        MatrixMessageType::Notice(m) => assert_eq!("22.00km => 13.67mi", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn conversion_outside_thread_replies_in_room() {
+    let message = convert("!convert 22km", None).await.unwrap();
+    assert_eq!(None, message.thread_root)
+}
+#[tokio::test]
+async fn roman_conversion_in_thread_replies_in_thread() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    handle_text_event(
+        &TextMessageEventContent::plain("!convert XLII"),
+        Some(&in_thread()),
+        event_id!("$event:example.com"),
+        MilliSecondsSinceUnixEpoch::now(),
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &reqwest::Client::new(),
+        &mut send,
+    )
+    .await;
+    let message = recv.try_recv().unwrap();
+    assert_eq!(
+        Some(event_id!("$root:example.com").to_owned()),
+        message.thread_root
+    );
+    match message.message {
+        MatrixMessageType::FormattedNotice(m) => assert_eq!("XLII => 42", m.plain_text),
+        _ => panic!("Did not get a formatted notice like expected"),
+    }
+}
+#[tokio::test]
+async fn help_in_thread_replies_in_thread() {
+    assert_eq!(
+        Some(event_id!("$root:example.com").to_owned()),
+        help(Some(&in_thread())).await.thread_root
+    );
+    assert_eq!(None, help(None).await.thread_root)
+}
@@ -0,0 +1,146 @@
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::timezone_handler::{
+    time_conversion_handler, time_handler, tz_handler,
+};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use chrono::{DateTime, TimeZone, Utc};
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc::{self, Receiver};
+
+fn now() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 7, 1, 12, 0, 0).unwrap()
+}
+
+fn notice(mut recv: Receiver<MatrixMessage>) -> String {
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => m,
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+
+async fn tz(body: &str, sender: &UserId, storage: &mut ListenerStorage) -> String {
+    let (mut send, recv) = mpsc::channel(8);
+    tz_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        storage,
+        &mut send,
+    )
+    .await;
+    notice(recv)
+}
+
+async fn convert(body: &str, storage: &ListenerStorage) -> String {
+    let (mut send, recv) = mpsc::channel(8);
+    time_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        user_id!("@london:example.com"),
+        room_id!("!room:example.com"),
+        storage,
+        now(),
+        &mut send,
+    )
+    .await;
+    notice(recv)
+}
+
+#[tokio::test]
+async fn set_timezone() {
+    let mut storage = ListenerStorage::default();
+    let user = user_id!("@london:example.com");
+    assert_eq!(
+        "Your timezone is now Europe/London",
+        tz("!tz set europe/london", user, &mut storage).await
+    );
+    assert_eq!(
+        "Your timezone is Europe/London",
+        tz("!tz", user, &mut storage).await
+    );
+    assert_eq!(
+        "Unknown timezone Mars/Olympus. Use a name like Europe/London or America/New_York",
+        tz("!tz set Mars/Olympus", user, &mut storage).await
+    )
+}
+#[tokio::test]
+async fn timezone_persists() {
+    let mut storage = ListenerStorage::default();
+    tz(
+        "!tz set Europe/London",
+        user_id!("@london:example.com"),
+        &mut storage,
+    )
+    .await;
+    let storage: ListenerStorage = ron::from_str(&ron::to_string(&storage).unwrap()).unwrap();
+    assert_eq!(
+        "Europe/London",
+        storage
+            .user_timezone(user_id!("@london:example.com"))
+            .unwrap()
+            .name()
+    )
+}
+#[tokio::test]
+async fn convert_with_stored_zones() {
+    let mut storage = ListenerStorage::default();
+    tz(
+        "!tz set Europe/London",
+        user_id!("@london:example.com"),
+        &mut storage,
+    )
+    .await;
+    tz(
+        "!tz set Asia/Tokyo",
+        user_id!("@tokyo:example.com"),
+        &mut storage,
+    )
+    .await;
+    assert_eq!(
+        "15:00 Europe/London => 10:00 for America/New_York",
+        convert("!convert 3pm to America/New_York", &storage).await
+    );
+    assert_eq!(
+        "20:00 Europe/London => 04:00 the next day for @tokyo:example.com (Asia/Tokyo)",
+        convert("!convert 8pm to @tokyo:example.com", &storage).await
+    )
+}
+#[tokio::test]
+async fn convert_needs_a_zone() {
+    let storage = ListenerStorage::default();
+    assert!(convert("!convert 3pm to America/New_York", &storage)
+        .await
+        .starts_with("You haven't set a timezone"));
+    assert_eq!(
+        "15:00 Europe/Berlin => 09:00 for America/New_York",
+        convert("!convert 15:00 Europe/Berlin to America/New_York", &storage).await
+    )
+}
+#[tokio::test]
+async fn time_for_user() {
+    let mut storage = ListenerStorage::default();
+    tz(
+        "!tz set Asia/Tokyo",
+        user_id!("@tokyo:example.com"),
+        &mut storage,
+    )
+    .await;
+    let (mut send, recv) = mpsc::channel(8);
+    time_handler(
+        &TextMessageEventContent::plain("!time @tokyo:example.com"),
+        event_id!("$event:example.com"),
+        user_id!("@london:example.com"),
+        room_id!("!room:example.com"),
+        &storage,
+        now(),
+        &mut send,
+    )
+    .await;
+    assert_eq!(
+        "It's 21:00 on Monday for @tokyo:example.com (Asia/Tokyo)",
+        notice(recv)
+    )
+}
@@ -0,0 +1,54 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::messages::MatrixMessageType;
+use ruma::{room_id, user_id};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn github_search_types_while_searching() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        "look at jf#1234",
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            "",
+            "[github_authentication]
+access_token = 'token'
+
+[searchable_repos]
+jf = 'jellyfin/jellyfin'",
+        ),
+        &mut send,
+    )
+    .await;
+    // The search fails with an invalid token, which must still stop typing
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::Typing(true)
+    ));
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::Typing(false)
+    ))
+}
+#[tokio::test]
+async fn no_typing_without_search() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        "!convert 100mbps",
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+        &mut send,
+    )
+    .await;
+    assert!(matches!(
+        recv.try_recv().unwrap().message,
+        MatrixMessageType::FormattedNotice(_)
+    ));
+    assert!(recv.try_recv().is_err())
+}
@@ -0,0 +1,89 @@
+use super::common::{first_reply, notice, text_event};
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use ruma::{event_id, room_id, user_id};
+use tokio::sync::mpsc;
+
+async fn chat(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Option<String> {
+    notice(
+        first_reply(
+            body,
+            user_id!("@user:example.com"),
+            room_id!("!room:example.com"),
+            storage,
+            config,
+        )
+        .await,
+    )
+}
+
+#[tokio::test]
+async fn silent_by_default() {
+    assert_eq!(
+        None,
+        chat(
+            "!auditlog",
+            &mut ListenerStorage::default(),
+            &listener_config("", "")
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn reply_when_enabled() {
+    let config = listener_config(
+        "enable_unauthorized_reply = true\nban_rooms = ['!room:example.com']\nsuper_admins = ['@admin:example.com']",
+        "",
+    );
+    for command in [
+        "!auditlog",
+        "!ban @spam:example.com",
+        "!grant @user:example.com 1h",
+        "!stats corrections",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Some("You're not authorized to use that.".to_string()),
+            chat(command, &mut ListenerStorage::default(), &config).await
+        )
+    }
+}
+#[tokio::test]
+async fn reply_cooldown() {
+    let config = listener_config(
+        "enable_unauthorized_reply = true\nunauthorized_reply = 'Admins only'",
+        "",
+    );
+    let mut storage = ListenerStorage::default();
+    assert_eq!(
+        Some("Admins only".to_string()),
+        chat("!announce hello", &mut storage, &config).await
+    );
+    assert_eq!(None, chat("!reload pings", &mut storage, &config).await)
+}
+#[tokio::test]
+async fn ban_reply_links_command() {
+    let config = listener_config(
+        "enable_unauthorized_reply = true\nban_rooms = ['!room:example.com']",
+        "",
+    );
+    let (mut send, mut recv) = mpsc::channel(8);
+    text_event(
+        "!ban @spam:example.com",
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &config,
+        &mut send,
+    )
+    .await;
+    assert_eq!(
+        Some(event_id!("$event:example.com").to_owned()),
+        recv.try_recv().unwrap().trigger_event
+    )
+}
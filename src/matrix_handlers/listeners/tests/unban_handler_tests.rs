@@ -0,0 +1,91 @@
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::unban_handler::unban_handler;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, UserId};
+use tokio::sync::mpsc;
+
+async fn unban(body: &str, sender: &UserId, general: &str) -> Vec<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    unban_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config(
+            &format!(
+                "ban_rooms = ['!room:example.com', '!other:example.com']\n{}",
+                general
+            ),
+            "",
+        ),
+        &mut send,
+    )
+    .await;
+    let mut messages = Vec::new();
+    while let Ok(v) = recv.try_recv() {
+        messages.push(v.message);
+    }
+    messages
+}
+
+#[tokio::test]
+async fn admin_unbans_in_ban_rooms() {
+    let messages = unban(
+        "!unban @spam:example.com",
+        user_id!("@admin:example.com"),
+        "",
+    )
+    .await;
+    match messages.as_slice() {
+        [MatrixMessageType::Unban(m)] => {
+            assert_eq!("@admin:example.com", m.admin);
+            assert_eq!("@spam:example.com", m.user);
+            let mut rooms: Vec<&str> = m.rooms.iter().map(|r| r.as_str()).collect();
+            rooms.sort_unstable();
+            assert_eq!(vec!["!other:example.com", "!room:example.com"], rooms)
+        }
+        _ => panic!("Did not get an unban like expected"),
+    }
+}
+#[tokio::test]
+async fn non_admin_ignored() {
+    let messages = unban(
+        "!unban @spam:example.com",
+        user_id!("@user:example.com"),
+        "",
+    )
+    .await;
+    assert!(!messages
+        .iter()
+        .any(|m| matches!(m, MatrixMessageType::Unban(_))))
+}
+#[tokio::test]
+async fn invalid_user_shows_usage() {
+    match unban("!unban spam", user_id!("@admin:example.com"), "")
+        .await
+        .as_slice()
+    {
+        [MatrixMessageType::Notice(m)] => assert_eq!("Usage: !unban @user:server", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn unencrypted_room_rejected() {
+    match unban(
+        "!unban @spam:example.com",
+        user_id!("@admin:example.com"),
+        "moderation_requires_encryption = true",
+    )
+    .await
+    .as_slice()
+    {
+        [MatrixMessageType::Notice(m)] => assert_eq!(
+            "Moderation commands are only accepted in encrypted rooms",
+            m
+        ),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
@@ -0,0 +1,333 @@
+use super::common::first_reply;
+use crate::config::tests::common::listener_config;
+use crate::config::ListenerStorage;
+use crate::matrix_handlers::listeners::unit_conversion_handler::{
+    unit_conversion_handler, unit_list,
+};
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> Option<String> {
+    convert_with(
+        body,
+        "",
+        "[custom_conversions.'!room:example.com']
+pizzas = { factor = 8.0, unit = 'slices' }",
+    )
+    .await
+}
+
+/// Converts in a room where m is both meters and a custom unit for minutes
+async fn convert_ambiguous(body: &str, general: &str) -> Option<String> {
+    convert_with(
+        body,
+        general,
+        "[custom_conversions.'!room:example.com']
+m = { factor = 0.016666666666666666, unit = 'h' }",
+    )
+    .await
+}
+
+async fn convert_with(body: &str, general: &str, tables: &str) -> Option<String> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    unit_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        None,
+        &listener_config(general, tables),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().ok().map(|m| m.message) {
+        Some(MatrixMessageType::Notice(m)) => Some(m),
+        Some(_) => panic!("Did not get a notice like expected"),
+        None => None,
+    }
+}
+
+#[tokio::test]
+async fn unit_only_hint() {
+    assert_eq!(
+        Some("km converts to mi".to_string()),
+        convert("!convert km").await
+    )
+}
+#[tokio::test]
+async fn custom_unit_only_hint() {
+    assert_eq!(
+        Some("pizzas converts to slices".to_string()),
+        convert("!convert Pizzas").await
+    )
+}
+#[tokio::test]
+async fn unknown_unit_only() {
+    assert_eq!(
+        Some("No valid units were found to convert.".to_string()),
+        convert("!convert xyz").await
+    )
+}
+#[tokio::test]
+async fn unknown_unit_with_quantity() {
+    assert_eq!(
+        Some("No valid units were found to convert.".to_string()),
+        convert("!convert 5 xyz").await
+    )
+}
+#[tokio::test]
+async fn configured_no_conversion_reply() {
+    assert_eq!(
+        Some("Nothing to convert, try !convert 5km".to_string()),
+        convert_with(
+            "!convert xyz",
+            "no_conversion_reply = 'Nothing to convert, try !convert 5km'",
+            ""
+        )
+        .await
+    )
+}
+#[tokio::test]
+async fn quantity_still_converts() {
+    assert_eq!(
+        Some("100.00km => 62.14mi".to_string()),
+        convert("!convert 100km").await
+    )
+}
+#[tokio::test]
+async fn verbose_shows_formula() {
+    assert_eq!(
+        Some("20.00c => 68.00f (°F = °C × 9/5 + 32)".to_string()),
+        convert("!convert 20c -v").await
+    )
+}
+#[tokio::test]
+async fn normal_hides_formula() {
+    assert_eq!(
+        Some("20.00c => 68.00f".to_string()),
+        convert("!convert 20c").await
+    )
+}
+#[tokio::test]
+async fn verbose_config_shows_formula() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    unit_conversion_handler(
+        &TextMessageEventContent::plain("!convert 20c"),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        None,
+        &listener_config("verbose_conversions = true", ""),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::Notice(m) => assert_eq!("20.00c => 68.00f (°F = °C × 9/5 + 32)", m),
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+#[tokio::test]
+async fn ambiguous_unit_builtin_by_default() {
+    assert_eq!(
+        Some("90.00m => 295.28ft".to_string()),
+        convert_ambiguous("!convert 90m", "").await
+    )
+}
+#[tokio::test]
+async fn ambiguous_unit_custom_by_config() {
+    assert_eq!(
+        Some("90.00m => 1.50h".to_string()),
+        convert_ambiguous("!convert 90m", "ambiguous_units = 'custom'").await
+    )
+}
+#[tokio::test]
+async fn ambiguous_unit_asks() {
+    assert_eq!(
+        Some(
+            "m converts to ft as a length or to h as a custom unit
+End the command with \"as length\" or \"as custom\" to choose"
+                .to_string()
+        ),
+        convert_ambiguous("!convert 90m", "ambiguous_units = 'ask'").await
+    )
+}
+#[tokio::test]
+async fn ambiguous_unit_chosen() {
+    assert_eq!(
+        Some("90.00m => 1.50h".to_string()),
+        convert_ambiguous("!convert 90m as custom", "ambiguous_units = 'ask'").await
+    );
+    assert_eq!(
+        Some("90.00m => 295.28ft".to_string()),
+        convert_ambiguous("!convert 90m as length", "ambiguous_units = 'custom'").await
+    )
+}
+#[tokio::test]
+async fn compound_volumes() {
+    let convert = |body| convert_with(body, "enable_compound_volumes = true", "");
+    assert_eq!(
+        Some("1.00gal 2.00qt => 5.68l".to_string()),
+        convert("!convert 1gal 2qt").await
+    );
+    assert_eq!(
+        Some("1.00gal 1.00pt => 4.26l".to_string()),
+        convert("!convert 1 pt 1 gal").await
+    );
+    assert_eq!(
+        Some("1.00gal 2.00qt => 5.68l\n5.00km => 3.11mi".to_string()),
+        convert("!convert 1gal 2qt 5km").await
+    )
+}
+#[tokio::test]
+async fn compound_volumes_disabled() {
+    assert_eq!(
+        Some("1.00gal => 3.79l\n2.00qt => 1.89l".to_string()),
+        convert("!convert 1gal 2qt").await
+    )
+}
+#[tokio::test]
+async fn torque() {
+    assert_eq!(
+        Some("200.00Nm => 147.51lb-ft".to_string()),
+        convert("!convert 200Nm").await
+    );
+    assert_eq!(
+        Some("147.51lb-ft => 200.00Nm".to_string()),
+        convert("!convert 147.51 lb-ft").await
+    );
+    assert_eq!(
+        Some("100.00ft-lb => 135.58Nm".to_string()),
+        convert("!convert 100 FT-LB").await
+    );
+    // Lowercase nm is nanometers, which aren't a unit conversion
+    assert_eq!(
+        Some("No valid units were found to convert.".to_string()),
+        convert("!convert 200nm").await
+    )
+}
+#[tokio::test]
+async fn duplicate_conversion_answered_once() {
+    let config = listener_config("duplicate_conversion_seconds = 60", "");
+    let mut storage = ListenerStorage::default();
+    let mut answered = Vec::new();
+    for (body, room) in [
+        ("!convert 100km", room_id!("!room:example.com")),
+        ("!convert 100km", room_id!("!room:example.com")),
+        ("!convert 5km", room_id!("!room:example.com")),
+        ("!convert 100km", room_id!("!other:example.com")),
+    ] {
+        let (mut send, mut recv) = mpsc::channel(8);
+        unit_conversion_handler(
+            &TextMessageEventContent::plain(body),
+            None,
+            event_id!("$event:example.com"),
+            room,
+            Some(&mut storage),
+            &config,
+            &mut send,
+        )
+        .await;
+        answered.push(recv.try_recv().is_ok());
+    }
+    assert_eq!(vec![true, false, true, true], answered)
+}
+#[tokio::test]
+async fn duplicate_conversion_unlimited_without_window() {
+    let config = listener_config("", "");
+    let mut storage = ListenerStorage::default();
+    for _ in 0..2 {
+        let (mut send, mut recv) = mpsc::channel(8);
+        unit_conversion_handler(
+            &TextMessageEventContent::plain("!convert 100km"),
+            None,
+            event_id!("$event:example.com"),
+            room_id!("!room:example.com"),
+            Some(&mut storage),
+            &config,
+            &mut send,
+        )
+        .await;
+        assert!(recv.try_recv().is_ok());
+    }
+}
+#[tokio::test]
+async fn commandless_stays_silent() {
+    assert!(first_reply(
+        "i have 5 xyz",
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+    )
+    .await
+    .is_none())
+}
+#[tokio::test]
+async fn pressure() {
+    assert_eq!(
+        Some("2.20bar => 31.91psi".to_string()),
+        convert("!convert 2.2 bar").await
+    );
+    assert_eq!(
+        Some("32.00psi => 2.21bar".to_string()),
+        convert("!convert 32psi").await
+    );
+    assert_eq!(
+        Some("220.00kpa => 31.91psi".to_string()),
+        convert("!convert 220 kPa").await
+    )
+}
+
+async fn list_units(body: &str) -> Option<String> {
+    match first_reply(
+        body,
+        user_id!("@user:example.com"),
+        room_id!("!room:example.com"),
+        &mut ListenerStorage::default(),
+        &listener_config("", ""),
+    )
+    .await
+    .map(|m| m.message)
+    {
+        Some(MatrixMessageType::FormattedText(m)) => m.formatted_text,
+        _ => None,
+    }
+}
+
+#[tokio::test]
+async fn bare_command_lists_units() {
+    assert_eq!(unit_list().formatted_text, list_units("!convert").await)
+}
+#[tokio::test]
+async fn help_lists_units() {
+    assert_eq!(
+        unit_list().formatted_text,
+        list_units("!convert help").await
+    );
+    assert_eq!(
+        unit_list().formatted_text,
+        list_units("!convert HELP ").await
+    )
+}
+#[tokio::test]
+async fn conversion_does_not_list_units() {
+    assert_eq!(None, list_units("!convert 20c").await)
+}
+#[test]
+fn unit_list_groups_by_dimension() {
+    let list = unit_list();
+    assert!(list
+        .plain_text
+        .contains("\nLength: cm, m, km, in, ft, mi, mile, miles\n"));
+    assert!(list.plain_text.contains("\nTemperature: c, °c, f, °f\n"));
+    assert!(list.plain_text.contains("\nMass: kg, lbs\n"));
+    assert!(list
+        .plain_text
+        .contains("\nSpeed: km/h, kmh, kph, kmph, mph\n"));
+    let formatted = list.formatted_text.unwrap();
+    assert!(formatted.contains("<ul>"));
+    assert!(formatted.contains("<li><strong>Mass</strong>: kg, lbs</li>"));
+    assert!(formatted.ends_with("</ul>"))
+}
@@ -0,0 +1,34 @@
+use crate::matrix_handlers::listeners::version_handler::{version_handler, version_message};
+use crate::messages::MatrixMessageType;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+#[tokio::test]
+async fn version_replies_with_crate_version() {
+    let (mut send, mut recv) = mpsc::channel(8);
+    version_handler(
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    match recv.try_recv().unwrap().message {
+        MatrixMessageType::FormattedText(m) => {
+            let version = format!("matrix-bot v{}", env!("CARGO_PKG_VERSION"));
+            assert!(m.plain_text.starts_with(&version));
+            assert!(m
+                .formatted_text
+                .unwrap()
+                .contains(env!("CARGO_PKG_VERSION")))
+        }
+        _ => panic!("Did not get formatted text like expected"),
+    }
+}
+#[test]
+fn build_details_never_empty() {
+    let message = version_message();
+    let lines: Vec<&str> = message.plain_text.lines().collect();
+    assert_eq!(3, lines.len());
+    assert!(lines[1].len() > "Commit: ".len());
+    assert!(lines[2].len() > "Compiler: ".len())
+}
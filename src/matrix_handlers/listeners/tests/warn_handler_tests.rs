@@ -0,0 +1,257 @@
+use super::common::text_event;
+use crate::config::tests::common::listener_config;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::matrix_handlers::listeners::warn_handler::warn_handler;
+use crate::messages::MatrixMessageType;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id, user_id, RoomId, UserId};
+use tokio::sync::mpsc;
+
+fn config(general: &str) -> MatrixListenerConfig {
+    listener_config(
+        &format!(
+            "ban_rooms = ['!room:example.com', '!other:example.com']\nenable_warnings = true\n{}",
+            general
+        ),
+        "",
+    )
+}
+
+async fn warn_in(
+    body: &str,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Vec<MatrixMessageType> {
+    let (mut send, mut recv) = mpsc::channel(8);
+    warn_handler(
+        &TextMessageEventContent::plain(body),
+        event_id!("$event:example.com"),
+        sender,
+        room_id,
+        storage,
+        config,
+        &mut send,
+    )
+    .await;
+    let mut messages = Vec::new();
+    while let Ok(v) = recv.try_recv() {
+        messages.push(v.message);
+    }
+    messages
+}
+
+async fn warn(
+    body: &str,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+) -> Vec<MatrixMessageType> {
+    let (admin, room) = (
+        user_id!("@admin:example.com"),
+        room_id!("!room:example.com"),
+    );
+    warn_in(body, admin, room, storage, config).await
+}
+
+fn notice(messages: &[MatrixMessageType]) -> &str {
+    match messages.first() {
+        Some(MatrixMessageType::Notice(m)) => m,
+        _ => panic!("Did not get a notice like expected"),
+    }
+}
+
+#[tokio::test]
+async fn warnings_accumulate() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    let messages = warn("!warn @spam:example.com be nice", &mut storage, &config).await;
+    assert_eq!(1, messages.len());
+    assert_eq!("Warned @spam:example.com (1/3): be nice", notice(&messages));
+    let messages = warn("!warn @spam:example.com", &mut storage, &config).await;
+    assert_eq!("Warned @spam:example.com (2/3)", notice(&messages));
+    let messages = warn("!warnings @spam:example.com", &mut storage, &config).await;
+    assert_eq!("@spam:example.com has 2/3 warnings", notice(&messages))
+}
+#[tokio::test]
+async fn threshold_bans() {
+    let (config, mut storage) = (
+        config("warning_ban_threshold = 2"),
+        ListenerStorage::default(),
+    );
+    warn("!warn @spam:example.com first", &mut storage, &config).await;
+    let messages = warn("!warn @spam:example.com second", &mut storage, &config).await;
+    assert_eq!(
+        "Warned @spam:example.com (2/2): second\n@spam:example.com reached 2 warnings and is being banned",
+        notice(&messages)
+    );
+    match messages.get(1) {
+        Some(MatrixMessageType::Ban(m)) => {
+            assert_eq!(vec![user_id!("@spam:example.com").to_owned()], m.users);
+            assert_eq!(user_id!("@admin:example.com"), m.admin);
+            assert_eq!(Some("Reached 2 warnings: second"), m.reason.as_deref());
+            assert_eq!(2, m.rooms.len())
+        }
+        _ => panic!("Did not get a ban like expected"),
+    }
+    // Warnings start over after the ban
+    assert_eq!(
+        0,
+        storage.warning_count(user_id!("@spam:example.com"), None)
+    )
+}
+#[tokio::test]
+async fn threshold_ban_holds_for_room_confirmation() {
+    let (config, mut storage) = (
+        config("warning_ban_threshold = 2\nban_room_confirmation_threshold = 1"),
+        ListenerStorage::default(),
+    );
+    warn("!warn @spam:example.com first", &mut storage, &config).await;
+    let messages = warn("!warn @spam:example.com second", &mut storage, &config).await;
+    match messages.get(1) {
+        Some(MatrixMessageType::Notice(m)) => assert_eq!(
+            "This ban affects 2 rooms. Send !ban --confirm within 60 seconds to perform it",
+            m
+        ),
+        _ => panic!("Did not get a confirmation prompt like expected"),
+    }
+    assert!(storage
+        .pending_bans
+        .contains_key(user_id!("@admin:example.com")));
+    // The warnings stay until the ban is confirmed
+    assert_eq!(
+        2,
+        storage.warning_count(user_id!("@spam:example.com"), None)
+    )
+}
+#[tokio::test]
+async fn threshold_without_ban_rooms_keeps_warnings() {
+    let mut config = config("warning_ban_threshold = 1");
+    config.ban_rooms.clear();
+    let mut storage = ListenerStorage::default();
+    let messages = warn("!warn @spam:example.com", &mut storage, &config).await;
+    assert_eq!(1, messages.len());
+    assert_eq!(
+        "Warned @spam:example.com (1/1)\n@spam:example.com reached 1 warnings, but no ban rooms are configured",
+        notice(&messages)
+    );
+    assert_eq!(
+        1,
+        storage.warning_count(user_id!("@spam:example.com"), None)
+    )
+}
+#[tokio::test]
+async fn only_warning_commands_routed() {
+    let config = config("");
+    let mut storage = ListenerStorage::default();
+    for &(body, replies) in &[("!warnings @spam:example.com", true), ("!warnme", false)] {
+        let (mut send, mut recv) = mpsc::channel(8);
+        text_event(
+            body,
+            user_id!("@admin:example.com"),
+            room_id!("!room:example.com"),
+            &mut storage,
+            &config,
+            &mut send,
+        )
+        .await;
+        assert_eq!(replies, recv.try_recv().is_ok(), "{}", body)
+    }
+}
+#[tokio::test]
+async fn threshold_needs_ban_authorization() {
+    let (config, mut storage) = (
+        config("warning_ban_threshold = 2\nban_authorized_users = ['@mod:example.com']"),
+        ListenerStorage::default(),
+    );
+    warn("!warn @spam:example.com first", &mut storage, &config).await;
+    let messages = warn("!warn @spam:example.com second", &mut storage, &config).await;
+    assert_eq!(1, messages.len());
+    assert_eq!(
+        "Warned @spam:example.com (2/2): second\n@spam:example.com reached 2 warnings, but only users that can ban can ban them",
+        notice(&messages)
+    );
+    assert_eq!(
+        2,
+        storage.warning_count(user_id!("@spam:example.com"), None)
+    )
+}
+#[tokio::test]
+async fn clear_warnings() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    warn("!warn @spam:example.com", &mut storage, &config).await;
+    warn("!warn @spam:example.com", &mut storage, &config).await;
+    let messages = warn("!clearwarnings @spam:example.com", &mut storage, &config).await;
+    assert_eq!("Cleared 2 warnings of @spam:example.com", notice(&messages));
+    let messages = warn("!warn @spam:example.com", &mut storage, &config).await;
+    assert_eq!("Warned @spam:example.com (1/3)", notice(&messages))
+}
+#[tokio::test]
+async fn room_scope_counts_per_room() {
+    let (config, mut storage) = (config("warning_scope = 'room'"), ListenerStorage::default());
+    let (admin, other) = (
+        user_id!("@admin:example.com"),
+        room_id!("!other:example.com"),
+    );
+    warn("!warn @spam:example.com", &mut storage, &config).await;
+    let messages = warn_in(
+        "!warn @spam:example.com",
+        admin,
+        other,
+        &mut storage,
+        &config,
+    )
+    .await;
+    assert_eq!("Warned @spam:example.com (1/3)", notice(&messages));
+    let messages = warn("!warn @spam:example.com", &mut storage, &config).await;
+    assert_eq!("Warned @spam:example.com (2/3)", notice(&messages))
+}
+#[tokio::test]
+async fn global_scope_counts_every_room() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    let (admin, other) = (
+        user_id!("@admin:example.com"),
+        room_id!("!other:example.com"),
+    );
+    warn("!warn @spam:example.com", &mut storage, &config).await;
+    let messages = warn_in(
+        "!warn @spam:example.com",
+        admin,
+        other,
+        &mut storage,
+        &config,
+    )
+    .await;
+    assert_eq!("Warned @spam:example.com (2/3)", notice(&messages))
+}
+#[tokio::test]
+async fn admins_not_warned() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    let messages = warn("!warn @admin:example.com", &mut storage, &config).await;
+    assert_eq!(
+        "@admin:example.com is an admin and can't be warned",
+        notice(&messages)
+    );
+    assert!(storage.warnings.is_empty())
+}
+#[tokio::test]
+async fn non_admin_cannot_warn() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    let (user, room) = (user_id!("@user:example.com"), room_id!("!room:example.com"));
+    warn_in("!warn @spam:example.com", user, room, &mut storage, &config).await;
+    assert!(storage.warnings.is_empty())
+}
+#[tokio::test]
+async fn disabled_without_config() {
+    let config = listener_config("ban_rooms = ['!room:example.com']", "");
+    let mut storage = ListenerStorage::default();
+    assert!(warn("!warn @spam:example.com", &mut storage, &config)
+        .await
+        .is_empty());
+    assert!(storage.warnings.is_empty())
+}
+#[tokio::test]
+async fn invalid_user_shows_usage() {
+    let (config, mut storage) = (config(""), ListenerStorage::default());
+    let messages = warn("!warn spam", &mut storage, &config).await;
+    assert!(notice(&messages).starts_with("Usage: !warn"))
+}
@@ -0,0 +1,49 @@
+use super::common::formatted_notice;
+use crate::config::tests::common::listener_config;
+use crate::matrix_handlers::listeners::wave_conversion_handler::wave_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str, general: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    wave_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &listener_config(general, ""),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn light_wavelength() {
+    assert_eq!(
+        "500nm => 599.58THz (light)",
+        convert("!convert 500nm", "").await
+    )
+}
+#[tokio::test]
+async fn sound_frequency() {
+    assert_eq!(
+        "440hz => 0.78m (sound at 343m/s)",
+        convert("!convert 440Hz", "").await
+    )
+}
+#[tokio::test]
+async fn configured_speed_of_sound() {
+    assert_eq!(
+        "1khz => 1.48m (sound at 1481m/s)",
+        convert("!convert 1khz", "speed_of_sound = 1481.0").await
+    )
+}
+#[tokio::test]
+async fn zero_frequency() {
+    assert_eq!(
+        "0hz is not a valid wavelength or frequency",
+        convert("!convert 0hz", "").await
+    )
+}
@@ -0,0 +1,41 @@
+use super::common::formatted_notice;
+use crate::matrix_handlers::listeners::words_conversion_handler::words_conversion_handler;
+use ruma::events::room::message::TextMessageEventContent;
+use ruma::{event_id, room_id};
+use tokio::sync::mpsc;
+
+async fn convert(body: &str) -> String {
+    let (mut send, mut recv) = mpsc::channel(8);
+    words_conversion_handler(
+        &TextMessageEventContent::plain(body),
+        None,
+        event_id!("$event:example.com"),
+        room_id!("!room:example.com"),
+        &mut send,
+    )
+    .await;
+    formatted_notice(&mut recv)
+}
+
+#[tokio::test]
+async fn words_to_number() {
+    assert_eq!("forty two => 42", convert("!convert forty two").await)
+}
+#[tokio::test]
+async fn number_to_words() {
+    assert_eq!("42 => forty-two", convert("!convert 42 words").await)
+}
+#[tokio::test]
+async fn invalid_words() {
+    assert_eq!(
+        "two three is not a valid number",
+        convert("!convert two three").await
+    )
+}
+#[tokio::test]
+async fn number_too_large() {
+    assert_eq!(
+        "1000000000000 is too large. Only numbers up to 999999999999 can be written as words",
+        convert("!convert 1000000000000 to words").await
+    )
+}
@@ -0,0 +1,183 @@
+//! Handlers for the commands users set their timezone with and convert times between timezones with
+
+use super::thread_root;
+use crate::config::ListenerStorage;
+use crate::helpers::{convert_time, find_timezone, parse_time_of_day};
+use crate::messages::{MatrixMessage, MatrixMessageType};
+use crate::regex::TIME_CONVERSION;
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, OwnedEventId, RoomId, UserId,
+};
+use std::convert::TryFrom;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info};
+
+const USAGE: &str = "Usage: !tz set Europe/London, !tz unset, or !tz to show your timezone";
+const NO_TIMEZONE: &str =
+    "You haven't set a timezone. Set one with !tz set Europe/London or name the timezone like !convert 3pm Europe/London to America/New_York";
+
+/// Sets, removes, or shows the sender's timezone
+pub(super) async fn tz_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let args: Vec<&str> = text.body.split_whitespace().skip(1).collect();
+    let message = match args.as_slice() {
+        [] => match storage.user_timezone(sender) {
+            Some(tz) => format!("Your timezone is {}", tz.name()),
+            None => NO_TIMEZONE.to_string(),
+        },
+        [switch, zone] if switch.eq_ignore_ascii_case("set") => match find_timezone(zone) {
+            Some(tz) => {
+                info!("{} set their timezone to {}", sender, tz.name());
+                storage
+                    .user_timezones
+                    .insert(sender.to_owned(), tz.name().to_string());
+                format!("Your timezone is now {}", tz.name())
+            }
+            None => format!(
+                "Unknown timezone {}. Use a name like Europe/London or America/New_York",
+                zone
+            ),
+        },
+        [switch] if switch.eq_ignore_ascii_case("unset") => {
+            match storage.user_timezones.remove(sender) {
+                Some(_) => "Your timezone was removed".to_string(),
+                None => "You haven't set a timezone".to_string(),
+            }
+        }
+        _ => USAGE.to_string(),
+    };
+    send_notice(message, event_id, None, room_id, send).await
+}
+
+/// Shows the current time for the sender or a mentioned user in their timezone
+pub(super) async fn time_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    now: DateTime<Utc>,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let message = match text.body.split_whitespace().nth(1) {
+        None => match storage.user_timezone(sender) {
+            Some(tz) => format!("It's {} for you ({})", local_time(now, tz), tz.name()),
+            None => NO_TIMEZONE.to_string(),
+        },
+        Some(user) => match <&UserId>::try_from(user) {
+            Ok(user) => match storage.user_timezone(user) {
+                Some(tz) => format!("It's {} for {} ({})", local_time(now, tz), user, tz.name()),
+                None => format!("{} hasn't set a timezone", user),
+            },
+            Err(_) => format!("{} is not a valid user like @user:example.com", user),
+        },
+    };
+    send_notice(message, event_id, None, room_id, send).await
+}
+
+/// Converts a time of day from the sender's or a named timezone to a user's or a named timezone
+///
+/// The time is taken to be on today's date in the timezone it's converted from.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn time_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &ListenerStorage,
+    now: DateTime<Utc>,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match TIME_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No time conversion found. No reply will be constructed.");
+            return;
+        }
+    };
+    let message = match convert(&cap, sender, storage, now) {
+        Ok(v) | Err(v) => v,
+    };
+    send_notice(message, event_id, thread_root(relates_to), room_id, send).await
+}
+
+fn convert(
+    cap: &regex::Captures,
+    sender: &UserId,
+    storage: &ListenerStorage,
+    now: DateTime<Utc>,
+) -> Result<String, String> {
+    let time = parse_time_of_day(&cap[1])
+        .ok_or_else(|| format!("{} is not a valid time of day", &cap[1]))?;
+    let from = match cap.get(2) {
+        Some(zone) => find_timezone(zone.as_str())
+            .ok_or_else(|| format!("Unknown timezone {}", zone.as_str()))?,
+        None => storage
+            .user_timezone(sender)
+            .ok_or_else(|| NO_TIMEZONE.to_string())?,
+    };
+    let target = &cap[3];
+    let (to, label) = if target.starts_with('@') {
+        let user = <&UserId>::try_from(target)
+            .map_err(|_| format!("{} is not a valid user like @user:example.com", target))?;
+        let tz = storage
+            .user_timezone(user)
+            .ok_or_else(|| format!("{} hasn't set a timezone", user))?;
+        (tz, format!("{} ({})", user, tz.name()))
+    } else {
+        let tz = find_timezone(target).ok_or_else(|| format!("Unknown timezone {}", target))?;
+        (tz, tz.name().to_string())
+    };
+    let date = now.with_timezone(&from).date_naive();
+    let converted = convert_time(time, date, from, to)
+        .ok_or_else(|| format!("{} doesn't exist in {} today", &cap[1], from.name()))?;
+    let day = match (converted.date_naive() - date).num_days() {
+        1 => " the next day",
+        -1 => " the previous day",
+        _ => "",
+    };
+    Ok(format!(
+        "{} {} => {}{} for {}",
+        time.format("%H:%M"),
+        from.name(),
+        converted.format("%H:%M"),
+        day,
+        label
+    ))
+}
+
+/// Time of day and weekday like "15:04 on Monday".
+fn local_time(now: DateTime<Utc>, tz: Tz) -> String {
+    now.with_timezone(&tz).format("%H:%M on %A").to_string()
+}
+
+async fn send_notice(
+    message: String,
+    event_id: &EventId,
+    thread_root: Option<OwnedEventId>,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
@@ -0,0 +1,70 @@
+//! Handler for the command admins unban users in every ban room with
+
+use super::ban_handler::{ban_authorized, moderation_allowed};
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig};
+use crate::messages::{MatrixMessage, MatrixMessageType, MatrixUnbanMessage};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info, trace};
+
+/// Unbans a user in every ban room. Only users that can ban can use it.
+pub(super) async fn unban_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if config.ban_rooms.is_empty() {
+        trace!("No rooms specified, ban feature is disabled. Skipping...");
+        return;
+    }
+    let authorized = ban_authorized(sender, storage, config);
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for unbanning. Skipping...");
+        return;
+    }
+    let message = if !moderation_allowed(room_id, storage, config) {
+        debug!("Moderation commands require an encrypted room. Rejecting...");
+        MatrixMessageType::Notice(
+            "Moderation commands are only accepted in encrypted rooms".to_string(),
+        )
+    } else {
+        match text.body.split_whitespace().nth(1).map(UserId::parse) {
+            Some(Ok(user)) => {
+                info!("{} is unbanning {}", sender, user);
+                MatrixMessageType::Unban(MatrixUnbanMessage {
+                    admin: sender.to_owned(),
+                    user,
+                    rooms: config.ban_rooms.clone(),
+                })
+            }
+            _ => MatrixMessageType::Notice("Usage: !unban @user:server".to_string()),
+        }
+    };
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message,
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
@@ -1,46 +1,229 @@
 //! Handler for the unit conversion command
 
-use crate::helpers::convert_unit;
+use super::{is_duplicate_conversion, thread_root};
+use crate::config::{AmbiguousUnits, ListenerStorage, MatrixListenerConfig};
 use crate::helpers::MatrixNoticeResponse;
-use crate::messages::{MatrixMessage, MatrixMessageType};
+use crate::helpers::{
+    ambiguous_units, conversion_target, convert_compound_volume, convert_unit,
+    split_compound_volumes, unit_key, AmbiguousUnit, BUILTIN_UNITS,
+};
+use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
 use crate::regex::UNIT_CONVERSION;
 use ruma::{
     events::room::message::{Relation, TextMessageEventContent},
-    RoomId,
+    EventId, OwnedEventId, RoomId,
 };
 use tokio::sync::mpsc::Sender;
 use tracing::{debug, error};
 
 /// Command based unit conversion handler that will parse, generate a response body, and send it
+///
+/// Ending the command with "-v" shows the formula used for each conversion, and "as <dimension>"
+/// chooses how units that are both a built-in and a custom unit are converted.
+/// "!convert" on its own or "!convert help" lists the supported units.
+/// Conversions already answered in the room are skipped if storage is supplied.
+/// Commands with nothing that can be converted get the configured no conversion reply
 pub(super) async fn unit_conversion_handler(
     text: &TextMessageEventContent,
     relates_to: Option<&Relation>,
+    event_id: &EventId,
     room_id: &RoomId,
+    storage: Option<&mut ListenerStorage>,
+    config: &MatrixListenerConfig,
     send: &mut Sender<MatrixMessage>,
 ) {
-    if relates_to.is_none() && text.formatted.is_none() {
-        let mut conversions = Vec::new();
-        for cap in UNIT_CONVERSION.captures_iter(&text.body.to_lowercase()) {
-            conversions.push((cap[1].to_string(), cap[2].to_string()));
-        }
-        let result = match convert_unit(conversions) {
-            Some(v) => v,
-            None => {
-                debug!("No convertable units found. No reply will be constructed.");
-                return;
+    // Replies and edits aren't converted, but messages in threads are
+    let thread_root = thread_root(relates_to);
+    if (relates_to.is_none() || thread_root.is_some()) && text.formatted.is_none() {
+        if is_unit_list_request(&text.body) {
+            debug!("Listing supported units");
+            let message = MatrixMessageType::FormattedText(unit_list());
+            if send
+                .send(MatrixMessage {
+                    room_id: Some(room_id.to_owned()),
+                    message,
+                    trigger_event: Some(event_id.to_owned()),
+                    thread_root,
+                })
+                .await
+                .is_err()
+            {
+                error!("Channel closed. Unable to send message.");
             }
+            return;
+        }
+        let mut conversions = Vec::new();
+        for cap in UNIT_CONVERSION.captures_iter(&text.body) {
+            conversions.push((cap[1].to_string(), unit_key(&cap[2])));
+        }
+        if conversions.is_empty() {
+            debug!("No quantity found, attempting to reply with unit hint");
+            let reply = unit_hint(&text.body, room_id, config)
+                .unwrap_or_else(|| config.no_conversion_reply.clone());
+            send_notice(reply, event_id, thread_root, room_id, send).await;
+            return;
+        }
+        if is_duplicate_conversion(&conversions, room_id, storage, config) {
+            debug!("Conversions were recently answered in this room. Skipping...");
+            return;
+        }
+        let custom_conversions = config.custom_conversions.get(room_id);
+        let prefer_custom = match chosen_dimension(&text.body) {
+            Some(v) => v == "custom",
+            None => match config.ambiguous_units {
+                AmbiguousUnits::Builtin => false,
+                AmbiguousUnits::Custom => true,
+                AmbiguousUnits::Ask => {
+                    let ambiguous = ambiguous_units(&conversions, custom_conversions);
+                    if !ambiguous.is_empty() {
+                        debug!("Ambiguous units found, asking which were meant");
+                        send_notice(
+                            clarification(&ambiguous),
+                            event_id,
+                            thread_root,
+                            room_id,
+                            send,
+                        )
+                        .await;
+                        return;
+                    }
+                    false
+                }
+            },
         };
+        let (compounds, conversions) = if config.enable_compound_volumes {
+            split_compound_volumes(conversions)
+        } else {
+            (Vec::new(), conversions)
+        };
+        let mut result: Vec<_> = compounds
+            .into_iter()
+            .filter_map(|c| {
+                convert_compound_volume(
+                    c,
+                    config.default_unit_conversion_precision,
+                    &config.unit_conversion_precision,
+                    config.rounding_mode,
+                )
+            })
+            .collect();
+        if let Some(v) = convert_unit(
+            conversions,
+            custom_conversions,
+            prefer_custom,
+            config.default_unit_conversion_precision,
+            &config.unit_conversion_precision,
+            config.rounding_mode,
+        ) {
+            result.extend(v);
+        }
+        if result.is_empty() {
+            debug!("No convertable units found. Replying that nothing was converted.");
+            send_notice(
+                config.no_conversion_reply.clone(),
+                event_id,
+                thread_root,
+                room_id,
+                send,
+            )
+            .await;
+            return;
+        }
         let mut response = MatrixNoticeResponse::default();
         response.set_unit_conversions(result);
-        if send
-            .send(MatrixMessage {
-                room_id: Some(room_id.to_owned()),
-                message: MatrixMessageType::Notice(response.to_string()),
-            })
-            .await
-            .is_err()
-        {
-            error!("Channel closed. Unable to send message.");
+        response
+            .set_show_formulas(config.verbose_conversions || text.body.trim_end().ends_with(" -v"));
+        send_notice(response.to_string(), event_id, thread_root, room_id, send).await;
+    }
+}
+
+/// Checks if a conversion command is "!convert" on its own or "!convert help"
+fn is_unit_list_request(body: &str) -> bool {
+    let mut words = body.split_whitespace().skip(1);
+    match (words.next(), words.next()) {
+        (None, _) => true,
+        (Some(v), None) => v.eq_ignore_ascii_case("help"),
+        _ => false,
+    }
+}
+
+/// Lists every built-in unit grouped by dimension, as a bulleted list when formatted
+pub(super) fn unit_list() -> MatrixFormattedMessage {
+    let mut plain_text = String::from("Supported units:");
+    let mut formatted_text = String::from("<p>Supported units:</p>\n<ul>\n");
+    for (dimension, units) in BUILTIN_UNITS {
+        let mut name = dimension.to_string();
+        name[..1].make_ascii_uppercase();
+        let units = units.join(", ");
+        plain_text.push_str(&format!("\n{}: {}", name, units));
+        formatted_text.push_str(&format!("<li><strong>{}</strong>: {}</li>\n", name, units));
+    }
+    formatted_text.push_str("</ul>");
+    MatrixFormattedMessage {
+        plain_text,
+        formatted_text: Some(formatted_text),
+    }
+}
+
+/// Returns the dimension chosen by ending a conversion command with "as <dimension>"
+fn chosen_dimension(body: &str) -> Option<String> {
+    let words: Vec<&str> = body.split_whitespace().filter(|w| *w != "-v").collect();
+    match words.as_slice() {
+        [.., "as", dimension] => Some(dimension.to_lowercase()),
+        _ => None,
+    }
+}
+
+/// Asks which meaning of each ambiguous unit was meant
+fn clarification(ambiguous: &[AmbiguousUnit]) -> String {
+    let mut message: String = ambiguous.iter().map(|a| format!("{}\n", a)).collect();
+    let mut dimensions: Vec<String> = Vec::new();
+    for a in ambiguous {
+        let dimension = format!("\"as {}\"", a.dimension);
+        if !dimensions.contains(&dimension) {
+            dimensions.push(dimension);
         }
     }
+    message.push_str(&format!(
+        "End the command with {} or \"as custom\" to choose",
+        dimensions.join(", ")
+    ));
+    message
+}
+
+/// Builds a hint like "km converts to mi" for a conversion command that only has a unit
+fn unit_hint(body: &str, room_id: &RoomId, config: &MatrixListenerConfig) -> Option<String> {
+    let mut words = body.split_whitespace().skip(1);
+    let unit = match (words.next(), words.next()) {
+        (Some(v), None) => unit_key(v),
+        _ => return None,
+    };
+    match conversion_target(&unit, config.custom_conversions.get(room_id)) {
+        Some(v) => Some(format!("{} converts to {}", unit, v)),
+        None => {
+            debug!("Unknown unit {:?}, no hint will be constructed.", unit);
+            None
+        }
+    }
+}
+
+async fn send_notice(
+    message: String,
+    event_id: &EventId,
+    thread_root: Option<OwnedEventId>,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::Notice(message),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
 }
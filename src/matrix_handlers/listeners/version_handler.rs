@@ -0,0 +1,41 @@
+//! Handler for the command that shows which build of the bot is running
+
+use crate::config::{GIT_COMMIT, NAME, RUSTC_VERSION, VERSION};
+use crate::messages::{MatrixFormattedMessage, MatrixMessage, MatrixMessageType};
+use ruma::{EventId, RoomId};
+use tokio::sync::mpsc::Sender;
+use tracing::error;
+
+/// Replies with the bot's version, the git commit it was built from, and the compiler that built it
+pub(super) async fn version_handler(
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    if send
+        .send(MatrixMessage {
+            room_id: Some(room_id.to_owned()),
+            message: MatrixMessageType::FormattedText(version_message()),
+            trigger_event: Some(event_id.to_owned()),
+            thread_root: None,
+        })
+        .await
+        .is_err()
+    {
+        error!("Channel closed. Unable to send message.");
+    }
+}
+
+/// Builds the version reply, with the build details as a list when formatted
+pub(super) fn version_message() -> MatrixFormattedMessage {
+    MatrixFormattedMessage {
+        plain_text: format!(
+            "{} v{}\nCommit: {}\nCompiler: {}",
+            NAME, VERSION, GIT_COMMIT, RUSTC_VERSION
+        ),
+        formatted_text: Some(format!(
+            "<p><strong>{}</strong> v{}</p>\n<ul>\n<li>Commit: <code>{}</code></li>\n<li>Compiler: {}</li>\n</ul>",
+            NAME, VERSION, GIT_COMMIT, RUSTC_VERSION
+        )),
+    }
+}
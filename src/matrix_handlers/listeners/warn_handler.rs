@@ -0,0 +1,204 @@
+//! Handler for the commands admins warn users with, banning users that get too many warnings
+
+use super::ban_handler::{ban_authorized, hold_or_ban, moderation_allowed};
+use super::check_authorized;
+use crate::config::{ListenerStorage, MatrixListenerConfig, Warning, Warnings};
+use crate::messages::{MatrixBanMessage, MatrixMessage, MatrixMessageType};
+use ruma::{events::room::message::TextMessageEventContent, EventId, RoomId, UserId};
+use std::time::SystemTime;
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error, info, trace};
+
+const USAGE: &str =
+    "Usage: !warn @user:server reason, !warnings @user:server, or !clearwarnings @user:server";
+
+/// Warns a user, shows their number of warnings, or clears their warnings. Only admins can use it.
+///
+/// Users are banned from every ban room once they reach the configured number of warnings, if the
+/// admin warning them can ban. Admins can't be warned.
+pub(super) async fn warn_handler(
+    text: &TextMessageEventContent,
+    event_id: &EventId,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let warnings = match &config.warnings {
+        Some(v) => *v,
+        None => {
+            trace!("Warnings are disabled. Skipping...");
+            return;
+        }
+    };
+    let authorized = storage.is_admin(sender, &config.admins, SystemTime::now());
+    if !check_authorized(
+        authorized,
+        Some(event_id),
+        sender,
+        room_id,
+        storage,
+        config,
+        send,
+    )
+    .await
+    {
+        debug!("Unauthorized user for warnings. Skipping...");
+        return;
+    }
+    let (notice, ban) = if moderation_allowed(room_id, storage, config) {
+        let can_ban = ban_authorized(sender, storage, config);
+        change_warnings(
+            &text.body,
+            sender,
+            room_id,
+            storage,
+            config,
+            warnings,
+            can_ban,
+            SystemTime::now(),
+        )
+    } else {
+        debug!("Moderation commands require an encrypted room. Rejecting...");
+        (
+            "Moderation commands are only accepted in encrypted rooms".to_string(),
+            None,
+        )
+    };
+    let mut messages = vec![MatrixMessageType::Notice(notice)];
+    if let Some(v) = ban {
+        let user = v.users[0].clone();
+        let message = hold_or_ban(v, sender, storage, config);
+        // Banned users start over if they're ever unbanned. Held bans keep the warnings until
+        // they're confirmed, so the user can still be banned if they expire
+        if matches!(message, MatrixMessageType::Ban(_)) {
+            storage.clear_warnings(&user, warnings.counted_in(room_id));
+        }
+        messages.push(message);
+    }
+    for message in messages {
+        if send
+            .send(MatrixMessage {
+                room_id: Some(room_id.to_owned()),
+                message,
+                trigger_event: Some(event_id.to_owned()),
+                thread_root: None,
+            })
+            .await
+            .is_err()
+        {
+            error!("Channel closed. Unable to send message.");
+        }
+    }
+}
+
+/// Performs a warning command, returning the reply and the ban to perform if the user reached the threshold.
+///
+/// Users that reach the threshold keep their warnings instead of being banned if `can_ban` is false
+/// or there are no ban rooms. Warnings of banned users are cleared by the caller once the ban is performed.
+#[allow(clippy::too_many_arguments)]
+fn change_warnings(
+    body: &str,
+    sender: &UserId,
+    room_id: &RoomId,
+    storage: &mut ListenerStorage,
+    config: &MatrixListenerConfig,
+    warnings: Warnings,
+    can_ban: bool,
+    now: SystemTime,
+) -> (String, Option<MatrixBanMessage>) {
+    let mut args = body.split_whitespace();
+    let command = args.next().unwrap_or_default().to_lowercase();
+    let user = match args.next().map(UserId::parse) {
+        Some(Ok(v)) => v,
+        _ => return (USAGE.to_string(), None),
+    };
+    let counted_in = warnings.counted_in(room_id);
+    match command.as_str() {
+        "!warn" => {
+            if storage.is_admin(&user, &config.admins, now) {
+                return (format!("{} is an admin and can't be warned", user), None);
+            }
+            let reason = args.collect::<Vec<_>>().join(" ");
+            let reason = if reason.is_empty() {
+                None
+            } else {
+                Some(reason)
+            };
+            let warning = Warning {
+                time: now,
+                room: room_id.to_owned(),
+                admin: sender.to_owned(),
+                reason: reason.clone(),
+            };
+            let count = storage.add_warning(&user, warning, counted_in);
+            info!(
+                "{} warned {} ({}/{})",
+                sender, user, count, warnings.ban_threshold
+            );
+            let notice = match &reason {
+                Some(v) => format!(
+                    "Warned {} ({}/{}): {}",
+                    user, count, warnings.ban_threshold, v
+                ),
+                None => format!("Warned {} ({}/{})", user, count, warnings.ban_threshold),
+            };
+            if count < warnings.ban_threshold {
+                return (notice, None);
+            }
+            if !can_ban {
+                debug!("{} can't ban. Not banning {}...", sender, user);
+                return (
+                    format!(
+                        "{}\n{} reached {} warnings, but only users that can ban can ban them",
+                        notice, user, count
+                    ),
+                    None,
+                );
+            }
+            if config.ban_rooms.is_empty() {
+                debug!("No ban rooms configured. Not banning {}...", user);
+                return (
+                    format!(
+                        "{}\n{} reached {} warnings, but no ban rooms are configured",
+                        notice, user, count
+                    ),
+                    None,
+                );
+            }
+            info!("{} reached the warning threshold, banning", user);
+            let ban = MatrixBanMessage {
+                admin: sender.to_owned(),
+                users: vec![user.clone()],
+                reason: Some(match reason {
+                    Some(v) => format!("Reached {} warnings: {}", count, v),
+                    None => format!("Reached {} warnings", count),
+                }),
+                rooms: config.ban_rooms.clone(),
+            };
+            (
+                format!(
+                    "{}\n{} reached {} warnings and is being banned",
+                    notice, user, count
+                ),
+                Some(ban),
+            )
+        }
+        "!warnings" => (
+            format!(
+                "{} has {}/{} warnings",
+                user,
+                storage.warning_count(&user, counted_in),
+                warnings.ban_threshold
+            ),
+            None,
+        ),
+        "!clearwarnings" => {
+            let removed = storage.clear_warnings(&user, counted_in);
+            info!("{} cleared {} warnings of {}", sender, removed, user);
+            (format!("Cleared {} warnings of {}", removed, user), None)
+        }
+        _ => (USAGE.to_string(), None),
+    }
+}
@@ -0,0 +1,50 @@
+//! Handler for the wavelength and frequency conversion command
+
+use super::send_conversion_reply;
+use crate::config::MatrixListenerConfig;
+use crate::helpers::{convert_wave, round, Wave, DEFAULT_PRECISION};
+use crate::messages::MatrixMessage;
+use crate::regex::WAVE_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::debug;
+
+/// Command based wave conversion handler that replies with the frequency of a wavelength or the wavelength of a frequency
+pub(super) async fn wave_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    config: &MatrixListenerConfig,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match WAVE_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No wavelength or frequency found. No reply will be constructed.");
+            return;
+        }
+    };
+    let quantity = cap[1].parse::<f64>().unwrap_or(0.0);
+    let unit = &cap[2].to_lowercase();
+    let result = match convert_wave(quantity, unit, config.speed_of_sound) {
+        Some(v) => {
+            let converted = round(v.quantity, DEFAULT_PRECISION, config.rounding_mode);
+            Ok(match v.wave {
+                Wave::Light => format!("{}{} => {}{} (light)", &cap[1], unit, converted, v.unit),
+                Wave::Sound => format!(
+                    "{}{} => {}{} (sound at {}m/s)",
+                    &cap[1], unit, converted, v.unit, config.speed_of_sound
+                ),
+            })
+        }
+        None => Err(format!(
+            "{}{} is not a valid wavelength or frequency",
+            &cap[1], unit
+        )),
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
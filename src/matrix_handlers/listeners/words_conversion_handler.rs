@@ -0,0 +1,48 @@
+//! Handler for the number word conversion command
+
+use super::send_conversion_reply;
+use crate::helpers::{from_words, to_words, MAX_WORDS};
+use crate::messages::MatrixMessage;
+use crate::regex::WORDS_CONVERSION;
+use ruma::{
+    events::room::message::{Relation, TextMessageEventContent},
+    EventId, RoomId,
+};
+use tokio::sync::mpsc::Sender;
+use tracing::{debug, error};
+
+/// Command based number word conversion handler that replies with the number or its words
+pub(super) async fn words_conversion_handler(
+    text: &TextMessageEventContent,
+    relates_to: Option<&Relation>,
+    event_id: &EventId,
+    room_id: &RoomId,
+    send: &mut Sender<MatrixMessage>,
+) {
+    let cap = match WORDS_CONVERSION.captures(&text.body) {
+        Some(v) => v,
+        None => {
+            debug!("No number or number words found. No reply will be constructed.");
+            return;
+        }
+    };
+    let result = match (cap.get(1), cap.get(2)) {
+        (Some(number), _) => match number.as_str().parse().ok().and_then(to_words) {
+            Some(v) => Ok(format!("{} => {}", number.as_str(), v)),
+            None => Err(format!(
+                "{} is too large. Only numbers up to {} can be written as words",
+                number.as_str(),
+                MAX_WORDS
+            )),
+        },
+        (None, Some(words)) => match from_words(words.as_str()) {
+            Some(v) => Ok(format!("{} => {}", words.as_str(), v)),
+            None => Err(format!("{} is not a valid number", words.as_str())),
+        },
+        (None, None) => {
+            error!("Somehow lost number words between regex match and conversion!");
+            return;
+        }
+    };
+    send_conversion_reply(result, event_id, room_id, relates_to, send).await;
+}
@@ -0,0 +1,36 @@
+//! Recording and formatting of moderation actions in the audit log
+
+use crate::config::{AuditAction, AuditEntry};
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::fmt::Write;
+
+/// Formats the `count` most recent actions in the audit log, oldest first
+pub fn audit_tail(log: &VecDeque<AuditEntry>, count: usize) -> String {
+    if log.is_empty() {
+        return "No moderation actions recorded".to_string();
+    }
+    let mut tail = String::new();
+    for entry in log.iter().skip(log.len().saturating_sub(count)) {
+        let action = match entry.action {
+            AuditAction::Ban => "banned",
+            AuditAction::Mute => "muted",
+            AuditAction::Unban => "unbanned",
+        };
+        let users: Vec<&str> = entry.users.iter().map(|u| u.as_str()).collect();
+        let _ = write!(
+            tail,
+            "{} {} {} {}",
+            DateTime::<Utc>::from(entry.time).format("%Y-%m-%d %H:%M UTC"),
+            entry.admin,
+            action,
+            users.join(", ")
+        );
+        if let Some(reason) = &entry.reason {
+            let _ = write!(tail, " ({})", reason.trim());
+        }
+        tail.push('\n');
+    }
+    tail.pop();
+    tail
+}
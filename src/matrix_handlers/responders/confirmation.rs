@@ -0,0 +1,60 @@
+//! Short lived store for actions that wait on an admin to confirm them
+
+use ruma::{EventId, OwnedEventId};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a pending action waits for confirmation before it is aborted.
+pub const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, PartialEq)]
+/// Outcome of trying to confirm a pending action
+pub enum Confirmation<T> {
+    /// The action was confirmed in time and should proceed
+    Confirmed(T),
+    /// The action was confirmed too late and has been aborted
+    Expired,
+    /// No action is waiting on the event
+    Unknown,
+}
+
+#[derive(Debug)]
+/// Actions keyed by the event id of the prompt asking to confirm them
+pub struct PendingActions<T> {
+    actions: HashMap<OwnedEventId, (T, Instant)>,
+    timeout: Duration,
+}
+
+impl<T> Default for PendingActions<T> {
+    fn default() -> Self {
+        Self::new(CONFIRMATION_TIMEOUT)
+    }
+}
+
+impl<T> PendingActions<T> {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            actions: HashMap::new(),
+            timeout,
+        }
+    }
+
+    /// Stores `action` until the prompt with id `prompt` is confirmed, dropping any actions that already timed out.
+    pub fn insert(&mut self, prompt: OwnedEventId, action: T, now: Instant) {
+        let timeout = self.timeout;
+        self.actions
+            .retain(|_, (_, created)| now.duration_since(*created) <= timeout);
+        self.actions.insert(prompt, (action, now));
+    }
+
+    /// Removes the action waiting on `prompt`, returning it only if it has not timed out.
+    pub fn confirm(&mut self, prompt: &EventId, now: Instant) -> Confirmation<T> {
+        match self.actions.remove(prompt) {
+            Some((action, created)) if now.duration_since(created) <= self.timeout => {
+                Confirmation::Confirmed(action)
+            }
+            Some(_) => Confirmation::Expired,
+            None => Confirmation::Unknown,
+        }
+    }
+}
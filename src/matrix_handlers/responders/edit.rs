@@ -0,0 +1,78 @@
+//! Functions used to edit responses the bot sent
+
+use super::send_throttled;
+use crate::config::ResponderStorage;
+use crate::matrix::MatrixClient;
+use ruma::{
+    api::client::message::send_message_event,
+    events::room::message::{MessageType, Relation, Replacement, RoomMessageEventContent},
+    EventId, OwnedEventId, OwnedRoomId,
+};
+use tracing::{debug, error};
+
+/// Builds the content replacing `event_id` with `new_content`
+///
+/// Text and notice bodies are prefixed with "*" for clients that don't support edits, like other clients do.
+pub fn edit_content(
+    event_id: &EventId,
+    new_content: RoomMessageEventContent,
+) -> RoomMessageEventContent {
+    let mut content = new_content.clone();
+    match &mut content.msgtype {
+        MessageType::Text(t) => t.body = format!("* {}", t.body),
+        MessageType::Notice(n) => n.body = format!("* {}", n.body),
+        _ => {}
+    }
+    content.relates_to = Some(Relation::Replacement(Replacement::new(
+        event_id.to_owned(),
+        Box::new(new_content),
+    )));
+    content
+}
+
+/// Edits the bot's response to `trigger` to have the supplied content
+///
+/// Sends the content as a new message if the bot hasn't responded to `trigger`, returning its event ID.
+pub async fn send_edit(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    trigger: &EventId,
+    content: RoomMessageEventContent,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Edit message was not provided with room_id");
+            return None;
+        }
+    };
+    let original = storage.response_to(trigger).cloned();
+    let content = match &original {
+        Some(v) => {
+            debug!("Editing response {} to {}", v, trigger);
+            edit_content(v, content)
+        }
+        None => {
+            debug!(
+                "No response to {} to edit. Sending a new response...",
+                trigger
+            );
+            content
+        }
+    };
+    let next_txn_id = storage.next_txn_id();
+    let req = || {
+        send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
+            .expect("m.room.message serialization must work")
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        // Later edits have to replace the original response, not this edit
+        Ok(_) if original.is_some() => None,
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("Unable to send edit due to error {:?}", e);
+            None
+        }
+    }
+}
@@ -1,35 +1,102 @@
-use crate::config::ResponderStorage;
+#[cfg(test)]
+mod tests;
+
+mod audit;
+mod confirmation;
+mod edit;
+mod mute;
+mod pin;
+mod raw_event;
+mod reaction;
+mod thread;
+mod throttle;
+
+pub use audit::audit_tail;
+pub use confirmation::{Confirmation, PendingActions};
+#[cfg(test)]
+use edit::edit_content;
+pub use edit::send_edit;
+#[cfg(test)]
+use mute::muted_in;
+pub use mute::send_mute_message;
+pub use pin::{announce, unannounce};
+#[cfg(test)]
+use pin::{pinned_with, pinned_without};
+pub use raw_event::send_raw_event;
+#[cfg(test)]
+use raw_event::{raw_event_dump, redact_sensitive, MAX_RAW_EVENT_LENGTH};
+#[cfg(test)]
+use reaction::reaction_content;
+pub use reaction::send_reaction;
+#[cfg(test)]
+use thread::thread_content;
+pub use thread::{message_content, send_in_thread};
+use throttle::send_throttled;
+pub use throttle::Throttle;
+#[cfg(test)]
+use throttle::{rate_limited, DEFAULT_RETRY_AFTER};
+
+use crate::config::{AuditAction, AuditEntry, ResponderStorage};
 use crate::matrix::MatrixClient;
+use crate::messages::{MatrixBanMessage, MatrixUnbanMessage};
 use ruma::{
     api::client::{
-        membership::{ban_user, join_room_by_id, leave_room},
+        error::ErrorKind,
+        membership::{
+            ban_user,
+            invite_user::{self, v3::InvitationRecipient},
+            join_room_by_id, leave_room, unban_user,
+        },
         message::send_message_event,
+        redact::redact_event,
+        typing::create_typing_event::{self, v3::Typing},
     },
+    api::error::{FromHttpResponseError, ServerError},
     events::room::message::RoomMessageEventContent,
-    OwnedRoomId, UserId,
+    EventId, OwnedEventId, OwnedRoomId, RoomId, UserId,
 };
 use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, SystemTime};
 use tracing::{debug, error, info};
 
+/// Time the bot shows as typing unless it stops typing sooner
+const TYPING_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Checks if a failed request was refused because the bot lacks permission
+fn is_forbidden(error: &ruma::client::Error<reqwest::Error, ruma::api::client::Error>) -> bool {
+    matches!(
+        error,
+        ruma::client::Error::FromHttpResponse(FromHttpResponseError::Server(ServerError::Known(e)))
+            if e.kind == ErrorKind::Forbidden
+    )
+}
+
 pub async fn send_notice(
     client: &MatrixClient,
     room_id: Option<OwnedRoomId>,
     storage: &mut ResponderStorage,
     message: String,
-) {
+) -> Option<OwnedEventId> {
     let content = RoomMessageEventContent::notice_plain(message);
     let next_txn_id = storage.next_txn_id();
     let room_id = match room_id {
         Some(v) => v,
         None => {
             error!("Notice message was not provided with room_id");
-            return;
+            return None;
         }
     };
-    let req = send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
-        .expect("m.room.message serialization must work");
-    if let Err(e) = client.send_request(req).await {
-        error!("{:?}", e);
+    let req = || {
+        send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
+            .expect("m.room.message serialization must work")
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("{:?}", e);
+            None
+        }
     }
 }
 pub async fn send_plain_text(
@@ -37,20 +104,26 @@ pub async fn send_plain_text(
     storage: &mut ResponderStorage,
     message: String,
     client: &MatrixClient,
-) {
+) -> Option<OwnedEventId> {
     let content = RoomMessageEventContent::text_plain(message);
     let next_txn_id = storage.next_txn_id();
     let room_id = match room_id {
         Some(v) => v,
         None => {
             error!("Plain text message was not provided with room_id");
-            return;
+            return None;
         }
     };
-    let req = send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
-        .expect("m.room.message serialization must work");
-    if let Err(e) = client.send_request(req).await {
-        error!("Unable to send response due to error {:?}", e);
+    let req = || {
+        send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
+            .expect("m.room.message serialization must work")
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("Unable to send response due to error {:?}", e);
+            None
+        }
     }
 }
 
@@ -60,7 +133,7 @@ pub async fn send_formatted_text(
     message: String,
     formatted_message: Option<String>,
     client: &MatrixClient,
-) {
+) -> Option<OwnedEventId> {
     let content =
         RoomMessageEventContent::text_html(message, formatted_message.unwrap_or_default());
     let next_txn_id = storage.next_txn_id();
@@ -68,13 +141,19 @@ pub async fn send_formatted_text(
         Some(v) => v,
         None => {
             error!("Formatted text message was not provided with room_id");
-            return;
+            return None;
         }
     };
-    let req = send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
-        .expect("m.room.message serialization must work");
-    if let Err(e) = client.send_request(req).await {
-        error!("Unable to send response due to error {:?}", e);
+    let req = || {
+        send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
+            .expect("m.room.message serialization must work")
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("Unable to send response due to error {:?}", e);
+            None
+        }
     }
 }
 
@@ -84,7 +163,7 @@ pub async fn send_formatted_notice(
     message: String,
     formatted_message: Option<String>,
     client: &MatrixClient,
-) {
+) -> Option<OwnedEventId> {
     let content =
         RoomMessageEventContent::notice_html(message, formatted_message.unwrap_or_default());
     let next_txn_id = storage.next_txn_id();
@@ -92,29 +171,187 @@ pub async fn send_formatted_notice(
         Some(v) => v,
         None => {
             error!("Formatted notice message was not provided with room_id");
-            return;
+            return None;
         }
     };
-    let req = send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
-        .expect("m.room.message serialization must work");
-    if let Err(e) = client.send_request(req).await {
-        error!("{:?}", e);
+    let req = || {
+        send_message_event::v3::Request::new(&room_id, &next_txn_id, &content)
+            .expect("m.room.message serialization must work")
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("{:?}", e);
+            None
+        }
     }
 }
 
+/// Builds the notice telling the admin which rooms the bot lacks permission to ban in
+///
+/// Returns None if every ban was permitted.
+fn missing_ban_permission(rooms: &[&RoomId]) -> Option<String> {
+    if rooms.is_empty() {
+        return None;
+    }
+    let mut rooms: Vec<&str> = rooms.iter().map(|r| r.as_str()).collect();
+    rooms.sort_unstable();
+    Some(format!(
+        "I don't have permission to ban in {}",
+        rooms.join(", ")
+    ))
+}
+
+/// Bans the users in every ban room and records the ban in the audit log
+///
+/// Tells the admin in `room_id` which rooms the bot lacks permission to ban in.
 pub async fn send_ban_message(
-    user: &UserId,
-    reason: Option<String>,
-    rooms: HashSet<OwnedRoomId>,
+    room_id: Option<OwnedRoomId>,
+    message: MatrixBanMessage,
+    storage: &mut ResponderStorage,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let mut forbidden_rooms = Vec::new();
+    let mut banned_users = HashSet::new();
+    for ban_room in &message.rooms {
+        for user in &message.users {
+            debug!("Banning user {} in room {}...", user, ban_room);
+            let req = || {
+                let mut req = ban_user::v3::Request::new(ban_room, user);
+                req.reason = message.reason.as_deref();
+                req
+            };
+            match send_throttled(client, &mut storage.throttle, req).await {
+                Ok(_) => {
+                    banned_users.insert(user);
+                }
+                Err(e) if is_forbidden(&e) => {
+                    error!("Missing permission to ban in room {}", ban_room);
+                    // Every other ban in the room would be refused too
+                    forbidden_rooms.push(ban_room.as_ref());
+                    break;
+                }
+                Err(e) => error!("{:?}", e),
+            }
+        }
+    }
+    let notice = missing_ban_permission(&forbidden_rooms);
+    // Users no room accepted the ban for weren't banned, so they aren't counted
+    storage
+        .banned_users
+        .fetch_add(banned_users.len() as u64, Ordering::Relaxed);
+    storage.record_audit_entry(AuditEntry {
+        time: SystemTime::now(),
+        admin: message.admin,
+        action: AuditAction::Ban,
+        users: message.users,
+        reason: message.reason,
+    });
+    if let Err(e) = storage.save_storage() {
+        error!(
+            "Unable to save matrix_responder.ron after recording a ban. {}",
+            e
+        )
+    }
+    match notice {
+        Some(v) => send_notice(client, room_id, storage, v).await,
+        None => None,
+    }
+}
+
+/// Unbans the user in every ban room and records the unban in the audit log
+///
+/// Tells the admin in `room_id` which rooms the bot lacks permission to unban in.
+pub async fn send_unban_message(
+    room_id: Option<OwnedRoomId>,
+    message: MatrixUnbanMessage,
+    storage: &mut ResponderStorage,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let mut forbidden_rooms = Vec::new();
+    for ban_room in &message.rooms {
+        debug!("Unbanning user {} in room {}...", message.user, ban_room);
+        let req = || unban_user::v3::Request::new(ban_room, &message.user);
+        match send_throttled(client, &mut storage.throttle, req).await {
+            Ok(_) => {}
+            Err(e) if is_forbidden(&e) => {
+                error!("Missing permission to unban in room {}", ban_room);
+                forbidden_rooms.push(ban_room.as_ref());
+            }
+            Err(e) => error!("{:?}", e),
+        }
+    }
+    // Unbanning takes the same power level as banning
+    let notice = missing_ban_permission(&forbidden_rooms);
+    storage.record_audit_entry(AuditEntry {
+        time: SystemTime::now(),
+        admin: message.admin,
+        action: AuditAction::Unban,
+        users: vec![message.user],
+        reason: None,
+    });
+    if let Err(e) = storage.save_storage() {
+        error!(
+            "Unable to save matrix_responder.ron after recording an unban. {}",
+            e
+        )
+    }
+    match notice {
+        Some(v) => send_notice(client, room_id, storage, v).await,
+        None => None,
+    }
+}
+
+/// Redacts every response the bot remembers sending because of `trigger`
+pub async fn redact_responses(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    trigger: &EventId,
     client: &MatrixClient,
 ) {
-    for room_id in rooms {
-        debug!("Banning user {} in room {}...", user, room_id);
-        let mut req = ban_user::v3::Request::new(&room_id, user);
-        req.reason = reason.as_deref();
-        if let Err(e) = client.send_request(req).await {
-            error!("{:?}", e);
-        };
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Redact responses message was not provided with room_id");
+            return;
+        }
+    };
+    for response in storage.take_responses(trigger) {
+        debug!(
+            "Redacting response {} to redacted event {}",
+            response, trigger
+        );
+        let next_txn_id = storage.next_txn_id();
+        let req = || redact_event::v3::Request::new(&room_id, &response, &next_txn_id);
+        if let Err(e) = send_throttled(client, &mut storage.throttle, req).await {
+            error!("Unable to redact response due to error {:?}", e);
+        }
+    }
+}
+
+/// Shows the bot as typing in a room, or stops showing it
+pub async fn send_typing(
+    room_id: Option<OwnedRoomId>,
+    user_id: &UserId,
+    typing: bool,
+    client: &MatrixClient,
+) {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Typing message was not provided with room_id");
+            return;
+        }
+    };
+    // Typing expires on its own in case the bot never gets to stop it
+    let state = if typing {
+        Typing::Yes(TYPING_TIMEOUT)
+    } else {
+        Typing::No
+    };
+    let req = create_typing_event::v3::Request::new(user_id, &room_id, state);
+    if let Err(e) = client.send_request(req).await {
+        debug!("Unable to update typing status due to error {:?}", e);
     }
 }
 
@@ -136,6 +373,34 @@ pub async fn accept_invite(sender: &UserId, room_id: Option<OwnedRoomId>, client
     }
 }
 
+/// Invites a user to a room, logging if the bot lacks permission to invite there
+pub async fn send_user_invite(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    user: &UserId,
+    client: &MatrixClient,
+) {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Invite user message was not provided with room_id");
+            return;
+        }
+    };
+    let req =
+        || invite_user::v3::Request::new(&room_id, InvitationRecipient::UserId { user_id: user });
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(_) => info!("Invited user {} to room {}", user, room_id),
+        Err(e) if is_forbidden(&e) => {
+            error!("Missing permission to invite in room {}", room_id)
+        }
+        Err(e) => debug!(
+            "Unable to invite {} to {} due to error {:?}",
+            user, room_id, e
+        ),
+    }
+}
+
 /// Will reject an invite and print the user that tried to logs
 pub async fn reject_invite(sender: &UserId, room_id: Option<OwnedRoomId>, client: &MatrixClient) {
     let room_id = match room_id {
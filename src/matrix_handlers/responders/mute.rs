@@ -0,0 +1,109 @@
+//! Functions used to mute users by lowering their power level
+
+use super::{is_forbidden, send_throttled};
+use crate::config::{AuditAction, AuditEntry, ResponderStorage};
+use crate::matrix::MatrixClient;
+use crate::messages::MatrixBanMessage;
+use ruma::{
+    api::client::state::{get_state_events_for_key, send_state_event},
+    events::{
+        room::power_levels::RoomPowerLevelsEventContent, EmptyStateKey, RoomEventType,
+        StateEventType,
+    },
+    int, RoomId, UserId,
+};
+use std::time::SystemTime;
+use tracing::{debug, error};
+
+/// Builds power levels content with `user` below the level needed to send messages
+///
+/// Users already below that level keep their level.
+pub fn muted_in(
+    mut power_levels: RoomPowerLevelsEventContent,
+    user: &UserId,
+) -> RoomPowerLevelsEventContent {
+    let message_level = power_levels
+        .events
+        .get(&RoomEventType::RoomMessage)
+        .copied()
+        .unwrap_or(power_levels.events_default);
+    let level = power_levels
+        .users
+        .get(user)
+        .copied()
+        .unwrap_or(power_levels.users_default);
+    power_levels
+        .users
+        .insert(user.to_owned(), level.min(message_level - int!(1)));
+    power_levels
+}
+
+/// Mutes the users in every ban room and records the mute in the audit log
+pub async fn send_mute_message(
+    message: MatrixBanMessage,
+    storage: &mut ResponderStorage,
+    client: &MatrixClient,
+) {
+    for room_id in &message.rooms {
+        let mut power_levels = match power_levels(room_id, client).await {
+            Some(v) => v,
+            None => continue,
+        };
+        for user in &message.users {
+            debug!("Muting user {} in room {}...", user, room_id);
+            power_levels = muted_in(power_levels, user);
+        }
+        let req = || {
+            send_state_event::v3::Request::new(room_id, &EmptyStateKey, &power_levels)
+                .expect("m.room.power_levels serialization must work")
+        };
+        match send_throttled(client, &mut storage.throttle, req).await {
+            Ok(_) => {}
+            Err(e) if is_forbidden(&e) => {
+                error!(
+                    "Missing permission to change power levels in room {}",
+                    room_id
+                )
+            }
+            Err(e) => error!("Unable to mute users due to error {:?}", e),
+        }
+    }
+    storage.record_audit_entry(AuditEntry {
+        time: SystemTime::now(),
+        admin: message.admin,
+        action: AuditAction::Mute,
+        users: message.users,
+        reason: message.reason,
+    });
+    if let Err(e) = storage.save_storage() {
+        error!(
+            "Unable to save matrix_responder.ron after recording a mute. {}",
+            e
+        )
+    }
+}
+
+/// Fetches the current power levels of a room
+async fn power_levels(
+    room_id: &RoomId,
+    client: &MatrixClient,
+) -> Option<RoomPowerLevelsEventContent> {
+    let req =
+        get_state_events_for_key::v3::Request::new(room_id, StateEventType::RoomPowerLevels, "");
+    match client.send_request(req).await {
+        Ok(v) => match v.content.deserialize_as::<RoomPowerLevelsEventContent>() {
+            Ok(c) => Some(c),
+            Err(e) => {
+                error!("Unable to parse power levels due to error {:?}", e);
+                None
+            }
+        },
+        Err(e) => {
+            error!(
+                "Unable to fetch power levels of room {} due to error {:?}",
+                room_id, e
+            );
+            None
+        }
+    }
+}
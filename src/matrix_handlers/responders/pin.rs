@@ -0,0 +1,159 @@
+//! Functions used to post announcements and keep them pinned in a room
+
+use super::{is_forbidden, send_notice};
+use crate::config::ResponderStorage;
+use crate::matrix::MatrixClient;
+use ruma::{
+    api::client::{
+        error::ErrorKind,
+        state::{get_state_events_for_key, send_state_event},
+    },
+    api::error::{FromHttpResponseError, ServerError},
+    events::{room::pinned_events::RoomPinnedEventsEventContent, EmptyStateKey, StateEventType},
+    EventId, OwnedEventId, OwnedRoomId, RoomId,
+};
+use tracing::{debug, error, info};
+
+/// Builds pinned events content with `event_id` added after the `existing` pins
+///
+/// Will not pin the same event twice
+pub fn pinned_with(existing: &[OwnedEventId], event_id: &EventId) -> RoomPinnedEventsEventContent {
+    let mut pinned = existing.to_vec();
+    if !pinned.iter().any(|e| e == event_id) {
+        pinned.push(event_id.to_owned());
+    }
+    RoomPinnedEventsEventContent::new(pinned)
+}
+
+/// Builds pinned events content with `event_id` removed from the `existing` pins
+pub fn pinned_without(
+    existing: &[OwnedEventId],
+    event_id: &EventId,
+) -> RoomPinnedEventsEventContent {
+    RoomPinnedEventsEventContent::new(
+        existing
+            .iter()
+            .filter(|e| *e != event_id)
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Posts an announcement and pins it, replying with an explanation if the bot cannot pin
+pub async fn announce(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    message: String,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Announce message was not provided with room_id");
+            return None;
+        }
+    };
+    let event_id = send_notice(client, Some(room_id.clone()), storage, message).await?;
+    let existing = match pinned_events(&room_id, client).await {
+        Some(v) => v,
+        None => return Some(event_id),
+    };
+    if update_pins(&room_id, storage, pinned_with(&existing, &event_id), client).await {
+        storage
+            .announcements
+            .entry(room_id)
+            .or_default()
+            .push(event_id.clone());
+    }
+    Some(event_id)
+}
+
+/// Unpins the most recent announcement the bot made in the room
+pub async fn unannounce(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    client: &MatrixClient,
+) {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Unannounce message was not provided with room_id");
+            return;
+        }
+    };
+    let event_id = match storage.announcements.get_mut(&room_id).and_then(Vec::pop) {
+        Some(v) => v,
+        None => {
+            debug!(
+                "No announcements pinned in room {}. Doing nothing.",
+                room_id
+            );
+            return;
+        }
+    };
+    let existing = match pinned_events(&room_id, client).await {
+        Some(v) => v,
+        None => return,
+    };
+    update_pins(
+        &room_id,
+        storage,
+        pinned_without(&existing, &event_id),
+        client,
+    )
+    .await;
+}
+
+/// Fetches the currently pinned events of a room
+async fn pinned_events(room_id: &RoomId, client: &MatrixClient) -> Option<Vec<OwnedEventId>> {
+    let req =
+        get_state_events_for_key::v3::Request::new(room_id, StateEventType::RoomPinnedEvents, "");
+    match client.send_request(req).await {
+        Ok(v) => match v.content.deserialize_as::<RoomPinnedEventsEventContent>() {
+            Ok(c) => Some(c.pinned),
+            Err(e) => {
+                error!("Unable to parse pinned events due to error {:?}", e);
+                None
+            }
+        },
+        Err(ruma::client::Error::FromHttpResponse(FromHttpResponseError::Server(
+            ServerError::Known(e),
+        ))) if e.kind == ErrorKind::NotFound => Some(Vec::new()),
+        Err(e) => {
+            error!("Unable to fetch pinned events due to error {:?}", e);
+            None
+        }
+    }
+}
+
+/// Replaces the pinned events of a room, returning `true` on success
+async fn update_pins(
+    room_id: &RoomId,
+    storage: &mut ResponderStorage,
+    content: RoomPinnedEventsEventContent,
+    client: &MatrixClient,
+) -> bool {
+    let req = send_state_event::v3::Request::new(room_id, &EmptyStateKey, &content)
+        .expect("m.room.pinned_events serialization must work");
+    match client.send_request(req).await {
+        Ok(_) => {
+            info!("Updated pinned events in room {}", room_id);
+            true
+        }
+        Err(e) if is_forbidden(&e) => {
+            error!("Missing permission to pin events in room {}", room_id);
+            send_notice(
+                client,
+                Some(room_id.to_owned()),
+                storage,
+                "I don't have permission to change pinned messages in this room".to_string(),
+            )
+            .await;
+            false
+        }
+        Err(e) => {
+            error!("Unable to update pinned events due to error {:?}", e);
+            false
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Functions used to show the raw JSON of an event for debugging
+
+use super::{send_formatted_notice, send_throttled};
+use crate::config::ResponderStorage;
+use crate::matrix::MatrixClient;
+use ruma::{api::client::room::get_room_event, EventId, OwnedEventId, OwnedRoomId};
+use serde_json::Value;
+use tracing::{debug, error};
+
+/// Longest raw event shown before it is truncated, in characters
+pub const MAX_RAW_EVENT_LENGTH: usize = 4000;
+
+/// Fields whose values are replaced wherever they appear in an event.
+///
+/// Covers secrets, encryption material, and the signing noise every federated event carries.
+const REDACTED_FIELDS: [&str; 10] = [
+    "access_token",
+    "password",
+    "token",
+    "transaction_id",
+    "device_id",
+    "session_id",
+    "sender_key",
+    "ciphertext",
+    "signatures",
+    "hashes",
+];
+
+/// Replaces the values of sensitive fields at any depth of `value`
+pub fn redact_sensitive(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if REDACTED_FIELDS.contains(&key.as_str()) {
+                    *value = Value::String("<redacted>".to_string());
+                } else {
+                    redact_sensitive(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_sensitive),
+        _ => (),
+    }
+}
+
+/// Pretty prints raw event JSON with sensitive fields redacted, truncated to `MAX_RAW_EVENT_LENGTH` characters
+pub fn raw_event_dump(json: &str) -> String {
+    let mut value: Value = match serde_json::from_str(json) {
+        Ok(v) => v,
+        Err(e) => return format!("Unable to parse event JSON: {}", e),
+    };
+    redact_sensitive(&mut value);
+    let mut dump = serde_json::to_string_pretty(&value).unwrap_or_default();
+    if let Some((i, _)) = dump.char_indices().nth(MAX_RAW_EVENT_LENGTH) {
+        dump.truncate(i);
+        dump.push_str("\n… (truncated)");
+    }
+    dump
+}
+
+/// Fetches an event and replies with its redacted raw JSON
+pub async fn send_raw_event(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    event_id: &EventId,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Raw event request was not provided with room_id");
+            return None;
+        }
+    };
+    let req = || get_room_event::v3::Request::new(&room_id, event_id);
+    let dump = match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => raw_event_dump(v.event.json().get()),
+        Err(e) => {
+            error!("Unable to fetch event {}. {:?}", event_id, e);
+            return None;
+        }
+    };
+    debug!("Raw JSON of event {}: {}", event_id, dump);
+    let formatted = format!(
+        "<pre><code class=\"language-json\">{}</code></pre>",
+        dump.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+    );
+    send_formatted_notice(Some(room_id), storage, dump, Some(formatted), client).await
+}
@@ -0,0 +1,61 @@
+//! Functions used to react to events with unicode or custom emoji
+
+use super::send_throttled;
+use crate::config::{Emoji, ResponderStorage};
+use crate::matrix::MatrixClient;
+use ruma::{
+    api::client::message::send_message_event, events::MessageLikeEventType, serde::Raw, EventId,
+    OwnedEventId, RoomId,
+};
+use serde_json::{json, Value};
+use tracing::error;
+
+/// Builds the content of a reaction to `event_id`
+///
+/// Custom emoji are keyed by their mxc:// URI and carry their shortcode so clients can name the image.
+pub fn reaction_content(event_id: &EventId, emoji: &Emoji) -> Value {
+    let mut content = json!({
+        "m.relates_to": {
+            "rel_type": "m.annotation",
+            "event_id": event_id,
+            "key": emoji.key(),
+        }
+    });
+    if let Emoji::Custom { .. } = emoji {
+        content["com.beeper.reaction.shortcode"] = Value::String(emoji.to_string());
+    }
+    content
+}
+
+/// Reacts to an event with the supplied emoji, returning the event id of the reaction
+pub async fn send_reaction(
+    room_id: &RoomId,
+    storage: &mut ResponderStorage,
+    event_id: &EventId,
+    emoji: &Emoji,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let content = match serde_json::value::to_raw_value(&reaction_content(event_id, emoji)) {
+        Ok(v) => Raw::from_json(v),
+        Err(e) => {
+            error!("Unable to serialize reaction due to error {:?}", e);
+            return None;
+        }
+    };
+    let next_txn_id = storage.next_txn_id();
+    let req = || {
+        send_message_event::v3::Request::new_raw(
+            room_id,
+            &next_txn_id,
+            MessageLikeEventType::Reaction,
+            content.clone(),
+        )
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("Unable to send reaction due to error {:?}", e);
+            None
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::config::{AuditAction, AuditEntry};
+use crate::matrix_handlers::responders::audit_tail;
+use ruma::{user_id, UserId};
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+fn entry(minutes: u64, user: &str, reason: Option<&str>) -> AuditEntry {
+    AuditEntry {
+        time: SystemTime::UNIX_EPOCH + Duration::from_secs(1_643_637_900 + minutes * 60),
+        admin: user_id!("@admin:example.com").to_owned(),
+        action: AuditAction::Ban,
+        users: vec![UserId::parse(user).unwrap()],
+        reason: reason.map(str::to_string),
+    }
+}
+
+fn log() -> VecDeque<AuditEntry> {
+    vec![
+        entry(0, "@spam:example.com", Some("spamming ")),
+        entry(1, "@eggs:example.com", None),
+        entry(2, "@ham:example.com", Some("scam links ")),
+    ]
+    .into()
+}
+
+#[test]
+fn tail_formatting() {
+    assert_eq!(
+        "2022-01-31 14:06 UTC @admin:example.com banned @eggs:example.com
+2022-01-31 14:07 UTC @admin:example.com banned @ham:example.com (scam links)",
+        audit_tail(&log(), 2)
+    )
+}
+#[test]
+fn tail_longer_than_log() {
+    assert_eq!(3, audit_tail(&log(), 20).lines().count())
+}
+#[test]
+fn multiple_users() {
+    let mut entry = entry(0, "@spam:example.com", None);
+    entry.users.push(user_id!("@eggs:example.com").to_owned());
+    assert_eq!(
+        "2022-01-31 14:05 UTC @admin:example.com banned @spam:example.com, @eggs:example.com",
+        audit_tail(&vec![entry].into(), 5)
+    )
+}
+#[test]
+fn empty_log() {
+    assert_eq!(
+        "No moderation actions recorded",
+        audit_tail(&VecDeque::new(), 5)
+    )
+}
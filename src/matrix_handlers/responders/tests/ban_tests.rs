@@ -0,0 +1,50 @@
+use crate::matrix_handlers::responders::{is_forbidden, missing_ban_permission};
+use axum::http::StatusCode;
+use ruma::api::client::error::ErrorKind;
+use ruma::api::error::{FromHttpResponseError, ServerError};
+use ruma::room_id;
+
+fn error(
+    kind: ErrorKind,
+    status_code: StatusCode,
+) -> ruma::client::Error<reqwest::Error, ruma::api::client::Error> {
+    ruma::client::Error::FromHttpResponse(FromHttpResponseError::Server(ServerError::Known(
+        ruma::api::client::Error {
+            kind,
+            message: "You don't have permission to ban".to_string(),
+            status_code,
+        },
+    )))
+}
+
+#[test]
+fn insufficient_power_is_forbidden() {
+    assert!(is_forbidden(&error(
+        ErrorKind::Forbidden,
+        StatusCode::FORBIDDEN
+    )))
+}
+#[test]
+fn other_errors_not_forbidden() {
+    assert!(!is_forbidden(&error(
+        ErrorKind::LimitExceeded {
+            retry_after_ms: None
+        },
+        StatusCode::TOO_MANY_REQUESTS
+    )));
+    assert!(!is_forbidden(&error(
+        ErrorKind::Unknown,
+        StatusCode::INTERNAL_SERVER_ERROR
+    )))
+}
+#[test]
+fn missing_permission_lists_rooms() {
+    assert_eq!(
+        Some("I don't have permission to ban in !a:example.com, !b:example.com".to_string()),
+        missing_ban_permission(&[room_id!("!b:example.com"), room_id!("!a:example.com")])
+    )
+}
+#[test]
+fn no_message_when_permitted() {
+    assert_eq!(None, missing_ban_permission(&[]))
+}
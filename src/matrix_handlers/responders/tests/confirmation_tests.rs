@@ -0,0 +1,64 @@
+use crate::matrix_handlers::responders::{Confirmation, PendingActions};
+use ruma::event_id;
+use std::time::{Duration, Instant};
+
+#[test]
+fn confirm_and_proceed() {
+    let now = Instant::now();
+    let mut pending = PendingActions::new(Duration::from_secs(60));
+    pending.insert(event_id!("$prompt:example.com").to_owned(), "ban", now);
+    assert_eq!(
+        Confirmation::Confirmed("ban"),
+        pending.confirm(
+            event_id!("$prompt:example.com"),
+            now + Duration::from_secs(30)
+        )
+    )
+}
+#[test]
+fn timeout_abort() {
+    let now = Instant::now();
+    let mut pending = PendingActions::new(Duration::from_secs(60));
+    pending.insert(event_id!("$prompt:example.com").to_owned(), "ban", now);
+    assert_eq!(
+        Confirmation::Expired,
+        pending.confirm(
+            event_id!("$prompt:example.com"),
+            now + Duration::from_secs(61)
+        )
+    )
+}
+#[test]
+fn confirm_only_once() {
+    let now = Instant::now();
+    let mut pending = PendingActions::new(Duration::from_secs(60));
+    pending.insert(event_id!("$prompt:example.com").to_owned(), "ban", now);
+    pending.confirm(event_id!("$prompt:example.com"), now);
+    assert_eq!(
+        Confirmation::Unknown,
+        pending.confirm(event_id!("$prompt:example.com"), now)
+    )
+}
+#[test]
+fn unknown_prompt() {
+    let mut pending: PendingActions<&str> = PendingActions::default();
+    assert_eq!(
+        Confirmation::Unknown,
+        pending.confirm(event_id!("$other:example.com"), Instant::now())
+    )
+}
+#[test]
+fn insert_prunes_timed_out() {
+    let now = Instant::now();
+    let mut pending = PendingActions::new(Duration::from_secs(60));
+    pending.insert(event_id!("$old:example.com").to_owned(), "ban", now);
+    pending.insert(
+        event_id!("$new:example.com").to_owned(),
+        "ban",
+        now + Duration::from_secs(120),
+    );
+    assert_eq!(
+        Confirmation::Unknown,
+        pending.confirm(event_id!("$old:example.com"), now)
+    )
+}
@@ -0,0 +1,39 @@
+use crate::matrix_handlers::responders::edit_content;
+use ruma::event_id;
+use ruma::events::room::message::RoomMessageEventContent;
+use serde_json::json;
+
+#[test]
+fn edit_replaces_original() {
+    assert_eq!(
+        json!({
+            "msgtype": "m.notice",
+            "body": "* 22.00km => 13.67mi",
+            "m.new_content": {
+                "msgtype": "m.notice",
+                "body": "22.00km => 13.67mi",
+            },
+            "m.relates_to": {
+                "rel_type": "m.replace",
+                "event_id": "$response:example.com",
+            },
+        }),
+        serde_json::to_value(edit_content(
+            event_id!("$response:example.com"),
+            RoomMessageEventContent::notice_plain("22.00km => 13.67mi")
+        ))
+        .unwrap()
+    )
+}
+#[test]
+fn edit_keeps_message_type() {
+    let content = serde_json::to_value(edit_content(
+        event_id!("$response:example.com"),
+        RoomMessageEventContent::text_plain("teh => the"),
+    ))
+    .unwrap();
+    assert_eq!("m.text", content["msgtype"]);
+    assert_eq!("* teh => the", content["body"]);
+    assert_eq!("m.text", content["m.new_content"]["msgtype"]);
+    assert_eq!("teh => the", content["m.new_content"]["body"])
+}
@@ -0,0 +1,10 @@
+mod audit_tests;
+mod ban_tests;
+mod confirmation_tests;
+mod edit_tests;
+mod mute_tests;
+mod pin_tests;
+mod raw_event_tests;
+mod reaction_tests;
+mod thread_tests;
+mod throttle_tests;
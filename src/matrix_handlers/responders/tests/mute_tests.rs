@@ -0,0 +1,49 @@
+use crate::matrix_handlers::responders::muted_in;
+use ruma::events::{room::power_levels::RoomPowerLevelsEventContent, RoomEventType};
+use ruma::{int, user_id};
+
+#[test]
+fn mute_lowers_below_events_default() {
+    let content = muted_in(
+        RoomPowerLevelsEventContent::new(),
+        user_id!("@spammer:example.com"),
+    );
+    assert_eq!(
+        Some(&int!(-1)),
+        content.users.get(user_id!("@spammer:example.com"))
+    )
+}
+#[test]
+fn mute_uses_message_level() {
+    let mut power_levels = RoomPowerLevelsEventContent::new();
+    power_levels
+        .events
+        .insert(RoomEventType::RoomMessage, int!(10));
+    power_levels
+        .users
+        .insert(user_id!("@spammer:example.com").to_owned(), int!(50));
+    let content = muted_in(power_levels, user_id!("@spammer:example.com"));
+    assert_eq!(
+        Some(&int!(9)),
+        content.users.get(user_id!("@spammer:example.com"))
+    )
+}
+#[test]
+fn mute_keeps_lower_level_and_other_users() {
+    let mut power_levels = RoomPowerLevelsEventContent::new();
+    power_levels
+        .users
+        .insert(user_id!("@spammer:example.com").to_owned(), int!(-5));
+    power_levels
+        .users
+        .insert(user_id!("@admin:example.com").to_owned(), int!(100));
+    let content = muted_in(power_levels, user_id!("@spammer:example.com"));
+    assert_eq!(
+        Some(&int!(-5)),
+        content.users.get(user_id!("@spammer:example.com"))
+    );
+    assert_eq!(
+        Some(&int!(100)),
+        content.users.get(user_id!("@admin:example.com"))
+    )
+}
@@ -0,0 +1,45 @@
+use crate::matrix_handlers::responders::{pinned_with, pinned_without};
+use ruma::{event_id, OwnedEventId};
+
+fn existing() -> Vec<OwnedEventId> {
+    vec![
+        event_id!("$first:example.com").to_owned(),
+        event_id!("$second:example.com").to_owned(),
+    ]
+}
+
+#[test]
+fn pin_appends() {
+    let content = pinned_with(&existing(), event_id!("$new:example.com"));
+    assert_eq!(
+        vec![
+            "$first:example.com",
+            "$second:example.com",
+            "$new:example.com"
+        ],
+        content.pinned
+    )
+}
+#[test]
+fn pin_without_existing() {
+    let content = pinned_with(&[], event_id!("$new:example.com"));
+    assert_eq!(vec!["$new:example.com"], content.pinned)
+}
+#[test]
+fn pin_already_pinned() {
+    let content = pinned_with(&existing(), event_id!("$second:example.com"));
+    assert_eq!(existing(), content.pinned)
+}
+#[test]
+fn unpin() {
+    let content = pinned_without(&existing(), event_id!("$first:example.com"));
+    assert_eq!(vec!["$second:example.com"], content.pinned)
+}
+#[test]
+fn pinned_events_serialization() {
+    let content = pinned_with(&[], event_id!("$new:example.com"));
+    assert_eq!(
+        r#"{"pinned":["$new:example.com"]}"#,
+        serde_json::to_string(&content).unwrap()
+    )
+}
@@ -0,0 +1,72 @@
+use crate::matrix_handlers::responders::{raw_event_dump, redact_sensitive, MAX_RAW_EVENT_LENGTH};
+use serde_json::json;
+
+fn sample_event(body: &str) -> serde_json::Value {
+    json!({
+        "type": "m.room.message",
+        "event_id": "$event:example.com",
+        "room_id": "!room:example.com",
+        "sender": "@user:example.com",
+        "origin_server_ts": 1_600_000_000_000u64,
+        "content": {
+            "msgtype": "m.text",
+            "body": body,
+        },
+        "hashes": { "sha256": "abc" },
+        "signatures": { "example.com": { "ed25519:key": "def" } },
+        "unsigned": { "age": 10, "transaction_id": "m1600000000000.0" },
+    })
+}
+
+#[test]
+fn redacts_sensitive_fields() {
+    let mut event = sample_event("hello");
+    redact_sensitive(&mut event);
+    assert_eq!("<redacted>", event["hashes"]);
+    assert_eq!("<redacted>", event["signatures"]);
+    assert_eq!("<redacted>", event["unsigned"]["transaction_id"]);
+    assert_eq!(10, event["unsigned"]["age"]);
+    assert_eq!("hello", event["content"]["body"]);
+    assert_eq!("@user:example.com", event["sender"])
+}
+#[test]
+fn redacts_nested_encryption_fields() {
+    let mut event = json!({
+        "type": "m.room.encrypted",
+        "content": {
+            "algorithm": "m.megolm.v1.aes-sha2",
+            "ciphertext": "secret",
+            "device_id": "DEVICE",
+            "sender_key": "key",
+            "session_id": "session",
+        },
+        "forwarded": [{ "password": "hunter2" }],
+    });
+    redact_sensitive(&mut event);
+    for field in ["ciphertext", "device_id", "sender_key", "session_id"].iter() {
+        assert_eq!("<redacted>", event["content"][field]);
+    }
+    assert_eq!("m.megolm.v1.aes-sha2", event["content"]["algorithm"]);
+    assert_eq!("<redacted>", event["forwarded"][0]["password"])
+}
+#[test]
+fn dump_is_redacted() {
+    let dump = raw_event_dump(&sample_event("hello").to_string());
+    assert!(dump.contains("\"body\": \"hello\""));
+    assert!(dump.contains("\"hashes\": \"<redacted>\""));
+    assert!(!dump.contains("ed25519"));
+    assert!(!dump.ends_with("(truncated)"))
+}
+#[test]
+fn long_dump_truncated() {
+    let dump = raw_event_dump(&sample_event(&"ä".repeat(MAX_RAW_EVENT_LENGTH)).to_string());
+    assert!(dump.ends_with("\n… (truncated)"));
+    assert_eq!(
+        MAX_RAW_EVENT_LENGTH,
+        dump.trim_end_matches("\n… (truncated)").chars().count()
+    )
+}
+#[test]
+fn invalid_json() {
+    assert!(raw_event_dump("{").starts_with("Unable to parse event JSON"))
+}
@@ -0,0 +1,41 @@
+use crate::config::Emoji;
+use crate::matrix_handlers::responders::reaction_content;
+use ruma::{event_id, mxc_uri};
+use serde_json::json;
+
+#[test]
+fn unicode_reaction() {
+    assert_eq!(
+        json!({
+            "m.relates_to": {
+                "rel_type": "m.annotation",
+                "event_id": "$prompt:example.com",
+                "key": "✅",
+            }
+        }),
+        reaction_content(
+            event_id!("$prompt:example.com"),
+            &Emoji::Unicode("✅".to_string())
+        )
+    )
+}
+#[test]
+fn custom_emoji_reaction() {
+    assert_eq!(
+        json!({
+            "m.relates_to": {
+                "rel_type": "m.annotation",
+                "event_id": "$prompt:example.com",
+                "key": "mxc://example.com/approve",
+            },
+            "com.beeper.reaction.shortcode": ":approve:",
+        }),
+        reaction_content(
+            event_id!("$prompt:example.com"),
+            &Emoji::Custom {
+                shortcode: "approve".to_string(),
+                url: mxc_uri!("mxc://example.com/approve").to_owned(),
+            }
+        )
+    )
+}
@@ -0,0 +1,28 @@
+use crate::matrix_handlers::responders::thread_content;
+use ruma::event_id;
+use ruma::events::room::message::RoomMessageEventContent;
+use serde_json::json;
+
+#[test]
+fn reply_carries_thread_relation() {
+    assert_eq!(
+        json!({
+            "msgtype": "m.notice",
+            "body": "22.00km => 13.67mi",
+            "m.relates_to": {
+                "rel_type": "m.thread",
+                "event_id": "$root:example.com",
+                "is_falling_back": true,
+                "m.in_reply_to": {
+                    "event_id": "$command:example.com",
+                },
+            },
+        }),
+        thread_content(
+            &RoomMessageEventContent::notice_plain("22.00km => 13.67mi"),
+            event_id!("$root:example.com"),
+            event_id!("$command:example.com")
+        )
+        .unwrap()
+    )
+}
@@ -0,0 +1,72 @@
+use crate::matrix_handlers::responders::{rate_limited, Throttle, DEFAULT_RETRY_AFTER};
+use axum::http::StatusCode;
+use ruma::api::client::error::ErrorKind;
+use ruma::api::error::{FromHttpResponseError, ServerError};
+use std::time::{Duration, Instant};
+
+fn error(kind: ErrorKind) -> ruma::client::Error<reqwest::Error, ruma::api::client::Error> {
+    ruma::client::Error::FromHttpResponse(FromHttpResponseError::Server(ServerError::Known(
+        ruma::api::client::Error {
+            kind,
+            message: "Too Many Requests".to_string(),
+            status_code: StatusCode::TOO_MANY_REQUESTS,
+        },
+    )))
+}
+
+#[test]
+fn unlimited_never_waits() {
+    let mut throttle = Throttle::new(None);
+    let now = Instant::now();
+    for _ in 0..10 {
+        assert_eq!(Duration::ZERO, throttle.reserve(now))
+    }
+}
+#[test]
+fn sends_are_spaced() {
+    let mut throttle = Throttle::new(Some(4.0));
+    let now = Instant::now();
+    assert_eq!(Duration::ZERO, throttle.reserve(now));
+    assert_eq!(Duration::from_millis(250), throttle.reserve(now));
+    assert_eq!(Duration::from_millis(500), throttle.reserve(now));
+    // Slots that have already passed are not waited on
+    assert_eq!(
+        Duration::ZERO,
+        throttle.reserve(now + Duration::from_secs(1))
+    )
+}
+#[test]
+fn retry_after_pauses_sends() {
+    let mut throttle = Throttle::new(Some(4.0));
+    let now = Instant::now();
+    throttle.reserve(now);
+    let retry_after = rate_limited(&error(ErrorKind::LimitExceeded {
+        retry_after_ms: Some(Duration::from_secs(2)),
+    }))
+    .unwrap();
+    throttle.pause(retry_after, now);
+    assert_eq!(Duration::from_secs(2), throttle.reserve(now));
+    assert_eq!(Duration::from_millis(2250), throttle.reserve(now))
+}
+#[test]
+fn shorter_pause_keeps_schedule() {
+    let mut throttle = Throttle::new(Some(1.0));
+    let now = Instant::now();
+    throttle.reserve(now);
+    throttle.reserve(now);
+    throttle.pause(Duration::from_millis(100), now);
+    assert_eq!(Duration::from_secs(2), throttle.reserve(now))
+}
+#[test]
+fn retry_after_defaults() {
+    assert_eq!(
+        Some(DEFAULT_RETRY_AFTER),
+        rate_limited(&error(ErrorKind::LimitExceeded {
+            retry_after_ms: None
+        }))
+    )
+}
+#[test]
+fn other_errors_not_rate_limited() {
+    assert_eq!(None, rate_limited(&error(ErrorKind::Forbidden)))
+}
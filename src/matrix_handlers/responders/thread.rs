@@ -0,0 +1,95 @@
+//! Functions used to send responses inside the thread of the message that triggered them
+
+use super::send_throttled;
+use crate::config::ResponderStorage;
+use crate::matrix::MatrixClient;
+use crate::messages::MatrixMessageType;
+use ruma::{
+    api::client::message::send_message_event,
+    events::{room::message::RoomMessageEventContent, MessageLikeEventType},
+    serde::Raw,
+    EventId, OwnedEventId, OwnedRoomId,
+};
+use serde_json::{json, Value};
+use tracing::error;
+
+/// Builds the content of a text or notice response, or None if the message isn't one
+pub fn message_content(message: &MatrixMessageType) -> Option<RoomMessageEventContent> {
+    match message {
+        MatrixMessageType::Text(m) => Some(RoomMessageEventContent::text_plain(m)),
+        MatrixMessageType::Notice(m) => Some(RoomMessageEventContent::notice_plain(m)),
+        MatrixMessageType::FormattedText(m) => Some(RoomMessageEventContent::text_html(
+            &m.plain_text,
+            m.formatted_text.as_deref().unwrap_or_default(),
+        )),
+        MatrixMessageType::FormattedNotice(m) => Some(RoomMessageEventContent::notice_html(
+            &m.plain_text,
+            m.formatted_text.as_deref().unwrap_or_default(),
+        )),
+        _ => None,
+    }
+}
+
+/// Adds an `m.thread` relation to `root` to the content
+///
+/// Clients without thread support show the message as a reply to `latest`, the newest event in the thread
+/// the bot knows of.
+pub fn thread_content(
+    content: &RoomMessageEventContent,
+    root: &EventId,
+    latest: &EventId,
+) -> serde_json::Result<Value> {
+    let mut content = serde_json::to_value(content)?;
+    content["m.relates_to"] = json!({
+        "rel_type": "m.thread",
+        "event_id": root,
+        "is_falling_back": true,
+        "m.in_reply_to": {
+            "event_id": latest,
+        },
+    });
+    Ok(content)
+}
+
+/// Sends the content inside the thread starting at `root`
+pub async fn send_in_thread(
+    room_id: Option<OwnedRoomId>,
+    storage: &mut ResponderStorage,
+    content: RoomMessageEventContent,
+    root: &EventId,
+    latest: &EventId,
+    client: &MatrixClient,
+) -> Option<OwnedEventId> {
+    let room_id = match room_id {
+        Some(v) => v,
+        None => {
+            error!("Threaded message was not provided with room_id");
+            return None;
+        }
+    };
+    let content = match thread_content(&content, root, latest)
+        .and_then(|v| serde_json::value::to_raw_value(&v))
+    {
+        Ok(v) => Raw::from_json(v),
+        Err(e) => {
+            error!("Unable to serialize threaded message due to error {:?}", e);
+            return None;
+        }
+    };
+    let next_txn_id = storage.next_txn_id();
+    let req = || {
+        send_message_event::v3::Request::new_raw(
+            &room_id,
+            &next_txn_id,
+            MessageLikeEventType::RoomMessage,
+            content.clone(),
+        )
+    };
+    match send_throttled(client, &mut storage.throttle, req).await {
+        Ok(v) => Some(v.event_id),
+        Err(e) => {
+            error!("Unable to send response in thread due to error {:?}", e);
+            None
+        }
+    }
+}
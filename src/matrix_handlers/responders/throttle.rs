@@ -0,0 +1,102 @@
+//! Spaces out requests to the homeserver so the bot stays under its rate limits
+
+use super::MatrixClient;
+use ruma::api::client::error::ErrorKind;
+use ruma::api::error::{FromHttpResponseError, ServerError};
+use ruma::api::OutgoingRequest;
+use ruma::client::http_client::Reqwest;
+use ruma::client::ResponseResult;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// How long to pause when the homeserver rate limits a request without saying for how long.
+pub const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// How many times a rate limited request is retried before giving up.
+pub const MAX_RATE_LIMIT_RETRIES: usize = 3;
+
+#[derive(Debug, Default)]
+/// Send schedule shared by every request the responder makes
+pub struct Throttle {
+    /// Minimum time between two requests. Zero if unlimited.
+    interval: Duration,
+    /// Earliest time the next request can be sent.
+    next_send: Option<Instant>,
+}
+
+impl Throttle {
+    /// Creates a throttle allowing `messages_per_second` requests per second, or unlimited requests if `None`.
+    pub fn new(messages_per_second: Option<f64>) -> Self {
+        Self {
+            interval: messages_per_second
+                .map(|v| Duration::from_secs_f64(1.0 / v))
+                .unwrap_or_default(),
+            next_send: None,
+        }
+    }
+
+    /// Reserves the next free send slot, returning how long to wait from `now` until it.
+    pub fn reserve(&mut self, now: Instant) -> Duration {
+        let slot = match self.next_send {
+            Some(t) if t > now => t,
+            _ => now,
+        };
+        self.next_send = Some(slot + self.interval);
+        slot - now
+    }
+
+    /// Holds back every request until `retry_after` has passed since `now`.
+    pub fn pause(&mut self, retry_after: Duration, now: Instant) {
+        let resume = now + retry_after;
+        if !matches!(self.next_send, Some(t) if t >= resume) {
+            self.next_send = Some(resume);
+        }
+    }
+}
+
+/// Returns how long the homeserver asked to wait if a request failed because it was rate limited.
+pub fn rate_limited(
+    error: &ruma::client::Error<reqwest::Error, ruma::api::client::Error>,
+) -> Option<Duration> {
+    match error {
+        ruma::client::Error::FromHttpResponse(FromHttpResponseError::Server(
+            ServerError::Known(ruma::api::client::Error {
+                kind: ErrorKind::LimitExceeded { retry_after_ms },
+                ..
+            }),
+        )) => Some(retry_after_ms.unwrap_or(DEFAULT_RETRY_AFTER)),
+        _ => None,
+    }
+}
+
+/// Sends the request built by `request` once the throttle allows it.
+///
+/// Rate limited requests are rebuilt and retried after the delay the homeserver asked for,
+/// up to [MAX_RATE_LIMIT_RETRIES](constant.MAX_RATE_LIMIT_RETRIES.html) times.
+pub async fn send_throttled<R, F>(
+    client: &MatrixClient,
+    throttle: &mut Throttle,
+    request: F,
+) -> ResponseResult<Reqwest, R>
+where
+    R: OutgoingRequest<EndpointError = ruma::api::client::Error>,
+    F: Fn() -> R,
+{
+    let mut retries = 0;
+    loop {
+        tokio::time::sleep(throttle.reserve(Instant::now())).await;
+        match client.send_request(request()).await {
+            Err(e) if retries < MAX_RATE_LIMIT_RETRIES => match rate_limited(&e) {
+                Some(retry_after) => {
+                    warn!(
+                        "Rate limited by the homeserver, pausing requests for {:?}",
+                        retry_after
+                    );
+                    throttle.pause(retry_after, Instant::now());
+                    retries += 1;
+                }
+                None => return Err(e),
+            },
+            result => return result,
+        }
+    }
+}
@@ -1,10 +1,17 @@
-use ruma::{OwnedRoomId, OwnedUserId};
+use crate::config::Emoji;
+use ruma::{
+    events::room::message::RoomMessageEventContent, OwnedEventId, OwnedRoomId, OwnedUserId,
+};
 use std::collections::HashSet;
 
 #[derive(Debug)]
 pub struct MatrixMessage {
     pub room_id: Option<OwnedRoomId>,
     pub message: MatrixMessageType,
+    /// Event that caused this message to be sent, if any
+    pub trigger_event: Option<OwnedEventId>,
+    /// Root of the thread the message is sent in, if any
+    pub thread_root: Option<OwnedEventId>,
     // pub resp: Responder<MatrixMessageResult>,
 }
 
@@ -15,7 +22,39 @@ pub enum MatrixMessageType {
     Notice(String),
     FormattedText(MatrixFormattedMessage),
     FormattedNotice(MatrixFormattedMessage),
+    /// Edits the bot's response to the contained triggering event to have the contained content,
+    /// or sends the content if there was no response
+    Edit(OwnedEventId, RoomMessageEventContent),
     Ban(MatrixBanMessage),
+    /// Unbans the contained user in the contained rooms
+    Unban(MatrixUnbanMessage),
+    /// Asks for confirmation before performing the contained ban
+    ConfirmBan(MatrixBanMessage),
+    /// Mutes the contained users in the contained rooms instead of banning them
+    Mute(MatrixBanMessage),
+    /// Confirms the pending action prompted by the contained event
+    ConfirmAction(OwnedEventId),
+    /// Redacts every response the bot sent for the contained triggering event
+    RedactResponses(OwnedEventId),
+    /// Posts the contained text as a notice and pins it
+    Announce(String),
+    /// Unpins the most recent announcement
+    Unannounce,
+    /// Replies with the contained number of most recent moderation actions
+    AuditLog(usize),
+    /// Starts showing the bot as typing if true, stops if false
+    Typing(bool),
+    /// Shows the redacted raw JSON of the contained event
+    RawEvent(OwnedEventId),
+    /// Reacts to the event with the key
+    React {
+        event_id: OwnedEventId,
+        key: Emoji,
+    },
+    /// Invites the contained user to the room
+    InviteUser(OwnedUserId),
+    /// Posts the contained text as a notice and reacts to it with each of the contained emoji
+    RoleMessage(String, Vec<Emoji>),
 }
 
 #[derive(Debug)]
@@ -38,11 +77,21 @@ pub struct MatrixInviteMessage {
 
 #[derive(Debug)]
 pub struct MatrixBanMessage {
-    pub user: OwnedUserId,
+    /// Admin that requested the ban
+    pub admin: OwnedUserId,
+    pub users: Vec<OwnedUserId>,
     pub reason: Option<String>,
     pub rooms: HashSet<OwnedRoomId>,
 }
 
+#[derive(Debug)]
+pub struct MatrixUnbanMessage {
+    /// Admin that requested the unban
+    pub admin: OwnedUserId,
+    pub user: OwnedUserId,
+    pub rooms: HashSet<OwnedRoomId>,
+}
+
 // #[derive(Debug)]
 // pub enum MatrixMessageResult {
 //     Sent,
@@ -8,7 +8,17 @@
 #[cfg(test)]
 mod tests;
 
+mod single_flight;
+
+pub use single_flight::SingleFlight;
+
 use graphql_client::*;
+use reqwest::header::HeaderMap;
+use reqwest::{StatusCode, Url};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Pause used when GitHub reports the rate limit was hit without saying when it resets
+pub const DEFAULT_RATE_LIMIT_PAUSE: Duration = Duration::from_secs(60);
 
 /// Type that represents URI results from query
 /// Cannot be `Url` as the returned URI is not a complete URL
@@ -25,3 +35,41 @@ type URI = String;
 ///
 /// Reference that file for further details on structure composition
 pub struct IssueOrPull;
+
+/// Owner, name, and number of an issue or pull to look up
+pub type IssueOrPullSearch = (String, String, i64);
+
+#[derive(Clone, Debug, Default)]
+/// Result of looking up an issue or pull, shared by every search for it made while it was in flight
+pub struct IssueOrPullLookup {
+    /// Url of the issue or pull if it was found
+    pub url: Option<Url>,
+    /// When searches can resume if the response showed the rate limit was hit
+    pub rate_limit_reset: Option<SystemTime>,
+    /// Whether GitHub refused the request because of the rate limit
+    pub rate_limited: bool,
+}
+
+/// Returns when GitHub searches can resume if a response shows the rate limit was hit
+///
+/// Secondary rate limits are 403 or 429 responses with a `retry-after` in seconds.
+/// Otherwise the limit is hit once `x-ratelimit-remaining` is 0, until the `x-ratelimit-reset` unix time
+pub fn rate_limit_reset(
+    status: StatusCode,
+    headers: &HeaderMap,
+    now: SystemTime,
+) -> Option<SystemTime> {
+    let header = |name: &str| -> Option<u64> { headers.get(name)?.to_str().ok()?.parse().ok() };
+    if status == StatusCode::FORBIDDEN || status == StatusCode::TOO_MANY_REQUESTS {
+        if let Some(v) = header("retry-after") {
+            return Some(now + Duration::from_secs(v));
+        }
+    }
+    match header("x-ratelimit-remaining") {
+        Some(0) => Some(match header("x-ratelimit-reset") {
+            Some(v) => UNIX_EPOCH + Duration::from_secs(v),
+            None => now + DEFAULT_RATE_LIMIT_PAUSE,
+        }),
+        _ => None,
+    }
+}
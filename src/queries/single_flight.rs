@@ -0,0 +1,60 @@
+//! Shares the result of a request between every caller that makes it while it's in flight
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use tokio::sync::OnceCell;
+use tracing::error;
+
+/// Requests in flight, keyed by what they request.
+///
+/// Identical requests made while one is in flight wait for it and get a copy of its result
+/// instead of making their own. Results aren't kept once every caller has them.
+pub struct SingleFlight<K, V> {
+    in_flight: Mutex<HashMap<K, Arc<OnceCell<V>>>>,
+}
+
+impl<K, V> Default for SingleFlight<K, V> {
+    fn default() -> Self {
+        Self {
+            in_flight: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<K, V> fmt::Debug for SingleFlight<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SingleFlight").finish_non_exhaustive()
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> SingleFlight<K, V> {
+    /// Returns the result of `fetch` for `key`, or of the identical request already in flight.
+    pub async fn run<F, Fut>(&self, key: K, fetch: F) -> V
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = V>,
+    {
+        let cell = match self.in_flight.lock() {
+            Ok(mut v) => Some(Arc::clone(v.entry(key.clone()).or_default())),
+            Err(_) => None,
+        };
+        let cell = match cell {
+            Some(v) => v,
+            None => {
+                error!("Single flight lock is poisoned. Fetching without sharing...");
+                return fetch().await;
+            }
+        };
+        let value = cell.get_or_init(fetch).await.clone();
+        // Only the caller that finds its own request still in flight clears it, so a newer one is kept
+        if let Ok(mut v) = self.in_flight.lock() {
+            if matches!(v.get(&key), Some(c) if Arc::ptr_eq(c, &cell)) {
+                v.remove(&key);
+            }
+        }
+        value
+    }
+}
@@ -1,4 +1,6 @@
 mod common;
+mod rate_limit_tests;
+mod single_flight_tests;
 
 use super::issue_or_pull::IssueOrPullRepositoryIssueOrPullRequest::{Issue, PullRequest};
 use super::*;
@@ -0,0 +1,86 @@
+use crate::queries::{rate_limit_reset, DEFAULT_RATE_LIMIT_PAUSE};
+use reqwest::header::{HeaderMap, HeaderValue};
+use reqwest::StatusCode;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn headers(headers: &[(&'static str, &'static str)]) -> HeaderMap {
+    let mut map = HeaderMap::new();
+    for (name, value) in headers {
+        map.insert(*name, HeaderValue::from_static(value));
+    }
+    map
+}
+
+#[test]
+fn limit_used_up() {
+    let now = SystemTime::now();
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        rate_limit_reset(
+            StatusCode::FORBIDDEN,
+            &headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1700000000")
+            ]),
+            now
+        )
+    );
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000)),
+        rate_limit_reset(
+            StatusCode::OK,
+            &headers(&[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1700000000")
+            ]),
+            now
+        )
+    )
+}
+#[test]
+fn secondary_limit() {
+    let now = SystemTime::now();
+    assert_eq!(
+        Some(now + Duration::from_secs(30)),
+        rate_limit_reset(
+            StatusCode::TOO_MANY_REQUESTS,
+            &headers(&[("retry-after", "30"), ("x-ratelimit-remaining", "100")]),
+            now
+        )
+    )
+}
+#[test]
+fn missing_reset() {
+    let now = SystemTime::now();
+    assert_eq!(
+        Some(now + DEFAULT_RATE_LIMIT_PAUSE),
+        rate_limit_reset(
+            StatusCode::FORBIDDEN,
+            &headers(&[("x-ratelimit-remaining", "0")]),
+            now
+        )
+    )
+}
+#[test]
+fn not_limited() {
+    let now = SystemTime::now();
+    assert_eq!(
+        None,
+        rate_limit_reset(
+            StatusCode::OK,
+            &headers(&[
+                ("x-ratelimit-remaining", "4999"),
+                ("x-ratelimit-reset", "1700000000")
+            ]),
+            now
+        )
+    );
+    assert_eq!(
+        None,
+        rate_limit_reset(StatusCode::FORBIDDEN, &HeaderMap::new(), now)
+    );
+    assert_eq!(
+        None,
+        rate_limit_reset(StatusCode::OK, &headers(&[("retry-after", "30")]), now)
+    )
+}
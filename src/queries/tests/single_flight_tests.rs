@@ -0,0 +1,68 @@
+use crate::queries::SingleFlight;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+async fn fetch(fetches: &AtomicUsize, value: &'static str) -> &'static str {
+    fetches.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    value
+}
+
+#[tokio::test]
+async fn concurrent_identical_requests_fetch_once() {
+    let in_flight = SingleFlight::default();
+    let fetches = AtomicUsize::new(0);
+    let (a, b, c) = tokio::join!(
+        in_flight.run("jf#1", || fetch(&fetches, "first")),
+        in_flight.run("jf#1", || fetch(&fetches, "second")),
+        in_flight.run("jf#1", || fetch(&fetches, "third")),
+    );
+    assert_eq!(1, fetches.load(Ordering::SeqCst));
+    assert_eq!(("first", "first", "first"), (a, b, c))
+}
+#[tokio::test]
+async fn different_requests_fetch_separately() {
+    let in_flight = SingleFlight::default();
+    let fetches = AtomicUsize::new(0);
+    let (a, b) = tokio::join!(
+        in_flight.run("jf#1", || fetch(&fetches, "first")),
+        in_flight.run("jf#2", || fetch(&fetches, "second")),
+    );
+    assert_eq!(2, fetches.load(Ordering::SeqCst));
+    assert_eq!(("first", "second"), (a, b))
+}
+#[tokio::test]
+async fn finished_requests_not_kept() {
+    let in_flight = SingleFlight::default();
+    let fetches = AtomicUsize::new(0);
+    assert_eq!(
+        "first",
+        in_flight.run("jf#1", || fetch(&fetches, "first")).await
+    );
+    assert_eq!(
+        "second",
+        in_flight.run("jf#1", || fetch(&fetches, "second")).await
+    );
+    assert_eq!(2, fetches.load(Ordering::SeqCst))
+}
+#[tokio::test]
+async fn shared_between_tasks() {
+    use std::sync::Arc;
+    let in_flight = Arc::new(SingleFlight::default());
+    let fetches = Arc::new(AtomicUsize::new(0));
+    let mut tasks = tokio::task::JoinSet::new();
+    for _ in 0..5 {
+        let (in_flight, fetches) = (Arc::clone(&in_flight), Arc::clone(&fetches));
+        tasks.spawn(async move {
+            in_flight
+                .run(("jellyfin".to_string(), 1234), || async {
+                    fetch(&fetches, "found").await
+                })
+                .await
+        });
+    }
+    while let Some(v) = tasks.join_next().await {
+        assert_eq!("found", v.unwrap())
+    }
+    assert_eq!(1, fetches.load(Ordering::SeqCst))
+}
@@ -7,6 +7,7 @@
 #[cfg(test)]
 mod tests;
 
+use crate::helpers::NAMED_COLORS;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
@@ -14,12 +15,234 @@ pub static UNIT_CONVERSION: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
     r"(?x)
     (?:^|\s+)
-    ([+-]?[0-9]+(?:.[0-9]+)?)                   # The number to convert, will only allow 1 period for floating points (captured)
+    ([+-]?[0-9]+(?:.[0-9]+)?(?:[eE][+-]?[0-9]+)?)  # The number to convert, will only allow 1 period for floating points and an optional exponent (captured)
     (?:[[\t\v\f\r ][:blank:]])*?                # Any amount of whitespace but not \n
-    ([^\s]?[[:alpha:]]+(?:[/\.][[:alpha:]]+)?)  # The unit to convert from including potential ° and / (captured)
+    ([^\s]?[[:alpha:]]+(?:[/\.][[:alpha:]]+|-(?i:ft|lbs?))?)  # The unit to convert from including potential °, /, and torque hyphens like lb-ft (captured)
     ").unwrap()
 });
 
+pub static DATE_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^!convert\s+
+    ([0-9]{4}-[0-9]{1,2}-[0-9]{1,2})    # The date to convert (captured)
+    (?:\s+to\s+[[:alpha:]]+)?          # Optional target representation, all are shown regardless
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static TIME_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?xi)
+    ^!convert\s+
+    ([0-9]{1,2}(?::[0-9]{2})?\s*[ap]m|[0-9]{1,2}:[0-9]{2})     # The time of day to convert (captured)
+    (?:\s+(?:in\s+)?([a-z_]+(?:/[a-z0-9_+\-]+)*))?           # The timezone of the time if not the sender's (captured)
+    \s+to\s+
+    (@[^\s:]+:\S+|[a-z_]+(?:/[a-z0-9_+\-]+)*)                 # The user or timezone to convert to (captured)
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static COLOR_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    let names: Vec<&str> = NAMED_COLORS.iter().map(|(name, _)| *name).collect();
+    Regex::new(&format!(
+        r"(?x)
+    ^(?i:!convert)\s+
+    (                               # The color to convert (captured)
+        \#[[:alnum:]]+             # Hex colors, validated when converting
+        |(?i:rgb|hsl)\s*\([^)]*\)  # rgb() and hsl() colors
+        |(?i:{})                    # Named colors
+    )
+    \s*$
+    ",
+        names.join("|")
+    ))
+    .unwrap()
+});
+
+pub static CRON_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)
+    ^(?i:!convert)\s+(?i:cron)\s+
+    "?([^"]+?)"?        # The cron expression or description to convert, optionally quoted (captured)
+    \s*$
+    "#,
+    )
+    .unwrap()
+});
+
+pub static WORDS_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    (?:
+        ([0-9]+)\s+(?i:(?:to\s+)?words)     # The number to write as words (captured)
+        |
+        (                                    # The number words to convert to a number (captured)
+            (?i:zero|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve|thirteen
+            |fourteen|fifteen|sixteen|seventeen|eighteen|nineteen|twenty|thirty|forty|fifty
+            |sixty|seventy|eighty|ninety|hundred|thousand|million|billion|and)
+            (?:[\s-]+
+            (?i:zero|one|two|three|four|five|six|seven|eight|nine|ten|eleven|twelve|thirteen
+            |fourteen|fifteen|sixteen|seventeen|eighteen|nineteen|twenty|thirty|forty|fifty
+            |sixty|seventy|eighty|ninety|hundred|thousand|million|billion|and))*
+        )
+    )
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static WAVE_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    ([0-9]+(?:\.[0-9]+)?)           # The wavelength or frequency to convert (captured)
+    \s*(nm|(?i:um|µm|thz|khz|hz))   # The unit of the wavelength or frequency, nm is case sensitive to leave Nm to torque (captured)
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static DATA_RATE_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    ([0-9]+(?:\.[0-9]+)?)              # The data rate to convert (captured)
+    \s*([kKmMgGtT][bB](?:ps|/s))      # The unit, where b is bits and B is bytes (captured)
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static DPI_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?x)
+    ^!convert\s+
+    (?:
+        ([0-9]+(?:\.[0-9]+)?)\s*(dpi|ppi|dpcm|ppcm)     # The density to convert and its unit (captured)
+        (?:\s+to\s+(?:dpi|ppi|dpcm|ppcm))?
+        |
+        ([0-9]+)\s*px\s+at\s+                           # The pixels to measure (captured)
+        ([0-9]+(?:\.[0-9]+)?)\s*(?:dpi|ppi)             # The density they're printed at (captured)
+        |
+        ([0-9]+)\s*[x×]\s*([0-9]+)\s+(?:at\s+)?         # The screen resolution (captured)
+        ([0-9]+(?:\.[0-9]+)?)\s*(?:in|")                # The screen diagonal in inches (captured)
+        (?:\s+(?:dot\s+)?pitch)?
+    )
+    \s*$
+    "#,
+    )
+    .unwrap()
+});
+
+pub static RATIO_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^!convert\s+
+    (?:
+        ([0-9]+)\s*[x×]\s*([0-9]+)           # The resolution to simplify (captured)
+        \s+(?:to\s+)?ratio
+        |
+        ([0-9]+)\s*:\s*([0-9]+)              # The aspect ratio to expand (captured)
+        \s+at\s+
+        ([0-9]+p?|[48]k)                     # The height of the resolution (captured)
+    )
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static PERCENT_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^!convert\s+
+    (                                       # The proportion to convert (captured)
+        [0-9]+(?:\.[0-9]+)?\s*%             # Percentages
+        |[0-9]+\s*[/:]\s*[0-9]+              # Fractions and ratios
+    )
+    (?:\s+(?:to\s+)?(%|percent|percentage|fraction|ratio|decimal))?  # Optional target notation (captured)
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static ROMAN_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    (?:
+        ([IVXLCDM]+)                        # The uppercase numeral to convert to a number (captured)
+        (\s+(?i:(?:from\s+)?roman))?        # Keyword marking the numeral as one even if it's a unit (captured)
+        |
+        ([0-9]+)\s+(?i:(?:to\s+)?roman)     # The number to convert to a numeral (captured)
+    )
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static IP_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    (?:
+        ([0-9]+(?:\.[0-9]+)+/[0-9]+|[[:xdigit:]]*:[[:xdigit:]:.]*/[0-9]+)   # The IPv4 or IPv6 range in CIDR notation (captured)
+        |
+        ([0-9]+(?:\.[0-9]+){3})\s+(?i:(?:to\s+)?(?:cidr|prefix))           # The netmask to convert to a prefix length (captured)
+        |
+        /([0-9]+)\s+(?i:(?:to\s+)?(?:netmask|mask))                         # The prefix length to convert to a netmask (captured)
+    )
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static GAS_MARK_CONVERSION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    ^(?i:!convert)\s+
+    (?:
+        (?i:gas(?:\s*mark)?)\s*([0-9]+(?:\.[0-9]+)?|[0-9]+\s*/\s*[0-9]+|¼|½)    # The gas mark to convert (captured)
+        |
+        ([+-]?[0-9]+(?:\.[0-9]+)?)\s*°?([cfCF])                               # The temperature and its unit (captured)
+        \s+(?i:(?:to\s+)?gas(?:\s*marks?)?)                                   # Converting to a gas mark
+    )
+    \s*$
+    ",
+    )
+    .unwrap()
+});
+
+pub static FEELS_LIKE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    (?:^|\s)
+    ([+-]?[0-9]+(?:\.[0-9]+)?)          # The temperature (captured)
+    \s*(°?[cf])                         # The unit of the temperature (captured)
+    \s*,?\s+(?:with\s+|and\s+)?
+    ([0-9]+(?:\.[0-9]+)?)\s*            # The humidity or wind speed (captured)
+    (?:
+        %\s*(?:humidity|rh)\b           # Humidity is a percentage
+        |
+        (km/h|kmh|kph|mph)\s+wind\b     # The unit of the wind speed (captured)
+    )
+    ",
+    )
+    .unwrap()
+});
+
 pub static GITHUB_SEARCH: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
     r"(?x)
@@ -66,6 +289,34 @@ pub static TEXT_EXPANSION: Lazy<Regex> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static KARMA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?x)
+    (?:^|\s)
+    (@[^\s:]+:[[:alnum:].\-]+?(?::[0-9]+)?)   # The user to change karma of (captured)
+    (\+\+|--)                                 # Increment or decrement (captured)
+    ",
+    )
+    .unwrap()
+});
+
+pub static GREETING: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(?i)\s*(?:hi|hello|hey|help|\?+)\s*[!.?]*\s*$").unwrap());
+
+pub static THANKS: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"(?xi)
+    \b(?:thanks|thank\s+you|thx|ty)\b
+    [,:]?\s+
+    (@[^\s:]+:[[:alnum:]\-]+(?:\.[[:alnum:]\-]+)*(?::[0-9]+)?)  # The user being thanked (captured)
+    ",
+    )
+    .unwrap()
+});
+
+pub static TEMPLATE_PLACEHOLDER: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\{([[:alpha:]]+)\}").unwrap());
+
 pub static CODE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)(<code>.*</code>)*").unwrap());
 
 pub static PRE_TAG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)(<pre>.*</pre>)*").unwrap());
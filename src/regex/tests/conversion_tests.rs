@@ -166,3 +166,392 @@ mod capture {
         assert_eq!(actual_username, captured_username)
     }
 }
+
+mod unit_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn hyphenated_torque_unit() {
+        let cap = UNIT_CONVERSION.captures("!convert 200 lb-ft").unwrap();
+        assert_eq!("200", &cap[1]);
+        assert_eq!("lb-ft", &cap[2]);
+        let cap = UNIT_CONVERSION.captures("!convert 200ft-lb").unwrap();
+        assert_eq!("ft-lb", &cap[2])
+    }
+    #[test]
+    fn other_hyphens_not_captured() {
+        let cap = UNIT_CONVERSION.captures("a 5km-long road").unwrap();
+        assert_eq!("km", &cap[2])
+    }
+    #[test]
+    fn scientific_notation() {
+        let cap = UNIT_CONVERSION.captures("!convert 1.5e6 m").unwrap();
+        assert_eq!("1.5e6", &cap[1]);
+        assert_eq!("m", &cap[2]);
+        let cap = UNIT_CONVERSION.captures("it's -1E-2c out").unwrap();
+        assert_eq!("-1E-2", &cap[1]);
+        assert_eq!("c", &cap[2])
+    }
+    #[test]
+    fn decimal_comma() {
+        let cap = UNIT_CONVERSION.captures("!convert 1,5km").unwrap();
+        assert_eq!("1,5", &cap[1]);
+        assert_eq!("km", &cap[2])
+    }
+    #[test]
+    fn unit_starting_with_e() {
+        let cap = UNIT_CONVERSION.captures("!convert 5em").unwrap();
+        assert_eq!("5", &cap[1]);
+        assert_eq!("em", &cap[2])
+    }
+}
+
+mod date_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn date() {
+        let cap = DATE_CONVERSION.captures("!convert 2024-03-15").unwrap();
+        assert_eq!("2024-03-15", &cap[1])
+    }
+    #[test]
+    fn date_with_target() {
+        let cap = DATE_CONVERSION
+            .captures("!convert 2024-03-15 to julian")
+            .unwrap();
+        assert_eq!("2024-03-15", &cap[1])
+    }
+    #[test]
+    fn unit_conversion_not_date() {
+        assert!(!DATE_CONVERSION.is_match("!convert 2024km"))
+    }
+}
+
+mod roman_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn numeral() {
+        let cap = ROMAN_CONVERSION.captures("!convert XLII").unwrap();
+        assert_eq!("XLII", &cap[1])
+    }
+    #[test]
+    fn number() {
+        let cap = ROMAN_CONVERSION.captures("!convert 42 roman").unwrap();
+        assert_eq!("42", &cap[3])
+    }
+    #[test]
+    fn number_with_target() {
+        let cap = ROMAN_CONVERSION.captures("!Convert 42 to Roman").unwrap();
+        assert_eq!("42", &cap[3])
+    }
+    #[test]
+    fn numeral_with_keyword() {
+        let cap = ROMAN_CONVERSION.captures("!convert MM roman").unwrap();
+        assert_eq!("MM", &cap[1]);
+        assert!(cap.get(2).is_some());
+        assert!(ROMAN_CONVERSION
+            .captures("!convert MM")
+            .unwrap()
+            .get(2)
+            .is_none())
+    }
+    #[test]
+    fn lowercase_unit_not_numeral() {
+        assert!(!ROMAN_CONVERSION.is_match("!convert mi"));
+        assert!(!ROMAN_CONVERSION.is_match("!convert 22cm"))
+    }
+}
+
+mod gas_mark_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn gas_mark() {
+        let cap = GAS_MARK_CONVERSION.captures("!convert gas4").unwrap();
+        assert_eq!("4", &cap[1]);
+        let cap = GAS_MARK_CONVERSION
+            .captures("!convert gas mark 1/2")
+            .unwrap();
+        assert_eq!("1/2", &cap[1])
+    }
+    #[test]
+    fn temperature() {
+        let cap = GAS_MARK_CONVERSION
+            .captures("!convert 350°F to gas mark")
+            .unwrap();
+        assert_eq!("350", &cap[2]);
+        assert_eq!("F", &cap[3])
+    }
+    #[test]
+    fn plain_temperature_not_gas_mark() {
+        assert!(!GAS_MARK_CONVERSION.is_match("!convert 180c"));
+        assert!(!GAS_MARK_CONVERSION.is_match("!convert 4gal"))
+    }
+}
+
+mod ip_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn cidr_range() {
+        let cap = IP_CONVERSION.captures("!convert 192.168.1.0/24").unwrap();
+        assert_eq!("192.168.1.0/24", &cap[1]);
+        let cap = IP_CONVERSION.captures("!convert 2001:db8::/32").unwrap();
+        assert_eq!("2001:db8::/32", &cap[1])
+    }
+    #[test]
+    fn netmask() {
+        let cap = IP_CONVERSION
+            .captures("!convert 255.255.255.0 to cidr")
+            .unwrap();
+        assert_eq!("255.255.255.0", &cap[2])
+    }
+    #[test]
+    fn prefix_length() {
+        let cap = IP_CONVERSION.captures("!convert /24 to netmask").unwrap();
+        assert_eq!("24", &cap[3])
+    }
+    #[test]
+    fn fractions_and_ratios_not_ip() {
+        assert!(!IP_CONVERSION.is_match("!convert 3/8"));
+        assert!(!IP_CONVERSION.is_match("!convert 16:9 at 1080p"));
+        assert!(!IP_CONVERSION.is_match("!convert 1.5km"))
+    }
+}
+
+mod ratio_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn resolution() {
+        let cap = RATIO_CONVERSION
+            .captures("!convert 1920x1080 ratio")
+            .unwrap();
+        assert_eq!("1920", &cap[1]);
+        assert_eq!("1080", &cap[2])
+    }
+    #[test]
+    fn ratio_at_height() {
+        let cap = RATIO_CONVERSION.captures("!convert 16:9 at 1080p").unwrap();
+        assert_eq!("16", &cap[3]);
+        assert_eq!("9", &cap[4]);
+        assert_eq!("1080p", &cap[5])
+    }
+    #[test]
+    fn ratio_at_named_height() {
+        let cap = RATIO_CONVERSION.captures("!convert 21:9 at 4k").unwrap();
+        assert_eq!("4k", &cap[5])
+    }
+    #[test]
+    fn resolution_without_ratio() {
+        assert!(!RATIO_CONVERSION.is_match("!convert 1920x1080"))
+    }
+}
+
+mod wave_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn wavelength() {
+        let cap = WAVE_CONVERSION.captures("!convert 500nm").unwrap();
+        assert_eq!("500", &cap[1]);
+        assert_eq!("nm", &cap[2])
+    }
+    #[test]
+    fn frequency_with_space() {
+        let cap = WAVE_CONVERSION.captures("!convert 2.5 khz").unwrap();
+        assert_eq!("2.5", &cap[1]);
+        assert_eq!("khz", &cap[2])
+    }
+    #[test]
+    fn unit_conversion_not_wave() {
+        assert!(!WAVE_CONVERSION.is_match("!convert 200Nm"));
+        assert!(!WAVE_CONVERSION.is_match("!convert 22mi"));
+        assert!(!WAVE_CONVERSION.is_match("!convert 500nm 20c"))
+    }
+}
+
+mod words_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn number() {
+        let cap = WORDS_CONVERSION.captures("!convert 42 words").unwrap();
+        assert_eq!("42", &cap[1])
+    }
+    #[test]
+    fn words() {
+        let cap = WORDS_CONVERSION
+            .captures("!convert Forty-two thousand and one")
+            .unwrap();
+        assert_eq!("Forty-two thousand and one", &cap[2])
+    }
+    #[test]
+    fn unit_not_words() {
+        assert!(!WORDS_CONVERSION.is_match("!convert km"));
+        assert!(!WORDS_CONVERSION.is_match("!convert 22 km"))
+    }
+}
+
+mod cron_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn quoted() {
+        let cap = CRON_CONVERSION
+            .captures("!convert cron \"0 9 * * 1\"")
+            .unwrap();
+        assert_eq!("0 9 * * 1", &cap[1])
+    }
+    #[test]
+    fn unquoted() {
+        let cap = CRON_CONVERSION.captures("!convert cron 0 9 * * 1").unwrap();
+        assert_eq!("0 9 * * 1", &cap[1])
+    }
+    #[test]
+    fn unit_not_cron() {
+        assert!(!CRON_CONVERSION.is_match("!convert 22km"));
+        assert!(!CRON_CONVERSION.is_match("!convert cron"))
+    }
+}
+
+mod color_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn hex() {
+        let cap = COLOR_CONVERSION.captures("!convert #ff8800").unwrap();
+        assert_eq!("#ff8800", &cap[1])
+    }
+    #[test]
+    fn rgb() {
+        let cap = COLOR_CONVERSION
+            .captures("!convert RGB(255, 136, 0)")
+            .unwrap();
+        assert_eq!("RGB(255, 136, 0)", &cap[1])
+    }
+    #[test]
+    fn named() {
+        let cap = COLOR_CONVERSION.captures("!convert red").unwrap();
+        assert_eq!("red", &cap[1])
+    }
+    #[test]
+    fn unit_not_color() {
+        assert!(!COLOR_CONVERSION.is_match("!convert 22km"));
+        assert!(!COLOR_CONVERSION.is_match("!convert redish"))
+    }
+}
+
+mod percent_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn percent() {
+        let cap = PERCENT_CONVERSION.captures("!convert 12.5%").unwrap();
+        assert_eq!("12.5%", &cap[1]);
+        assert!(cap.get(2).is_none())
+    }
+    #[test]
+    fn fraction_with_target() {
+        let cap = PERCENT_CONVERSION.captures("!convert 3/8 to %").unwrap();
+        assert_eq!("3/8", &cap[1]);
+        assert_eq!("%", &cap[2])
+    }
+    #[test]
+    fn ratio() {
+        let cap = PERCENT_CONVERSION
+            .captures("!convert 3 : 8 fraction")
+            .unwrap();
+        assert_eq!("3 : 8", &cap[1]);
+        assert_eq!("fraction", &cap[2])
+    }
+    #[test]
+    fn not_aspect_ratio() {
+        assert!(!PERCENT_CONVERSION.is_match("!convert 16:9 at 1080p"));
+        assert!(!PERCENT_CONVERSION.is_match("!convert 20km/h"))
+    }
+}
+
+mod data_rate_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn bits() {
+        let cap = DATA_RATE_CONVERSION.captures("!convert 100mbps").unwrap();
+        assert_eq!("100", &cap[1]);
+        assert_eq!("mbps", &cap[2])
+    }
+    #[test]
+    fn bytes_with_slash() {
+        let cap = DATA_RATE_CONVERSION.captures("!convert 12.5 MB/s").unwrap();
+        assert_eq!("12.5", &cap[1]);
+        assert_eq!("MB/s", &cap[2])
+    }
+    #[test]
+    fn unit_not_data_rate() {
+        assert!(!DATA_RATE_CONVERSION.is_match("!convert 100mph"));
+        assert!(!DATA_RATE_CONVERSION.is_match("!convert 100mb"))
+    }
+}
+
+mod dpi_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn density() {
+        let cap = DPI_CONVERSION.captures("!convert 300dpi to dpcm").unwrap();
+        assert_eq!("300", &cap[1]);
+        assert_eq!("dpi", &cap[2])
+    }
+    #[test]
+    fn printed_size() {
+        let cap = DPI_CONVERSION
+            .captures("!convert 2400px at 300dpi")
+            .unwrap();
+        assert_eq!("2400", &cap[3]);
+        assert_eq!("300", &cap[4])
+    }
+    #[test]
+    fn screen() {
+        let cap = DPI_CONVERSION
+            .captures("!convert 1920x1080 at 27\" dot pitch")
+            .unwrap();
+        assert_eq!("1920", &cap[5]);
+        assert_eq!("1080", &cap[6]);
+        assert_eq!("27", &cap[7])
+    }
+    #[test]
+    fn not_units() {
+        assert!(!DPI_CONVERSION.is_match("!convert 27in"));
+        assert!(!DPI_CONVERSION.is_match("!convert 1920x1080 ratio"))
+    }
+}
+
+mod time_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn to_user() {
+        let cap = TIME_CONVERSION
+            .captures("!convert 3:30 PM to @user:example.com")
+            .unwrap();
+        assert_eq!("3:30 PM", &cap[1]);
+        assert!(cap.get(2).is_none());
+        assert_eq!("@user:example.com", &cap[3])
+    }
+    #[test]
+    fn between_zones() {
+        let cap = TIME_CONVERSION
+            .captures("!convert 15:00 in Europe/London to America/New_York")
+            .unwrap();
+        assert_eq!("15:00", &cap[1]);
+        assert_eq!("Europe/London", &cap[2]);
+        assert_eq!("America/New_York", &cap[3])
+    }
+    #[test]
+    fn not_units() {
+        assert!(!TIME_CONVERSION.is_match("!convert 3km to mi"));
+        assert!(!TIME_CONVERSION.is_match("!convert 15 to utc"))
+    }
+}
@@ -0,0 +1,28 @@
+mod feels_like_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn humidity() {
+        let cap = FEELS_LIKE.captures("it is 25c 60% humidity here").unwrap();
+        assert_eq!("25", &cap[1]);
+        assert_eq!("c", &cap[2]);
+        assert_eq!("60", &cap[3]);
+        assert!(cap.get(4).is_none())
+    }
+    #[test]
+    fn wind() {
+        let cap = FEELS_LIKE.captures("-5.5°f, 20mph wind").unwrap();
+        assert_eq!("-5.5", &cap[1]);
+        assert_eq!("°f", &cap[2]);
+        assert_eq!("20", &cap[3]);
+        assert_eq!("mph", &cap[4])
+    }
+    #[test]
+    fn temperature_only() {
+        assert!(!FEELS_LIKE.is_match("it is 25c outside"))
+    }
+    #[test]
+    fn not_a_temperature() {
+        assert!(!FEELS_LIKE.is_match("25cm 60% humidity"))
+    }
+}
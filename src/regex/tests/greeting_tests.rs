@@ -0,0 +1,16 @@
+mod greeting_match {
+    use crate::regex::*;
+
+    #[test]
+    fn greetings() {
+        assert!(GREETING.is_match("hi"));
+        assert!(GREETING.is_match("Hello!"));
+        assert!(GREETING.is_match(" ?? "))
+    }
+    #[test]
+    fn not_greetings() {
+        assert!(!GREETING.is_match("hi there, how do I convert units?"));
+        assert!(!GREETING.is_match("!help"));
+        assert!(!GREETING.is_match("this"))
+    }
+}
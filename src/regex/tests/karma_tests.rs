@@ -0,0 +1,36 @@
+mod karma_capture {
+    use crate::regex::*;
+
+    #[test]
+    fn increment() {
+        let cap = KARMA.captures("nice one @user:example.com++").unwrap();
+        assert_eq!("@user:example.com", &cap[1]);
+        assert_eq!("++", &cap[2])
+    }
+    #[test]
+    fn decrement_hyphenated_server() {
+        let cap = KARMA.captures("@user:my-server.com-- ouch").unwrap();
+        assert_eq!("@user:my-server.com", &cap[1]);
+        assert_eq!("--", &cap[2])
+    }
+    #[test]
+    fn no_operator() {
+        assert!(!KARMA.is_match("hey @user:example.com"))
+    }
+    #[test]
+    fn thanks() {
+        let cap = THANKS.captures("Thanks @user:example.com.").unwrap();
+        assert_eq!("@user:example.com", &cap[1])
+    }
+    #[test]
+    fn thank_you() {
+        let cap = THANKS
+            .captures("thank you, @user:example.com for the help")
+            .unwrap();
+        assert_eq!("@user:example.com", &cap[1])
+    }
+    #[test]
+    fn thanks_in_word() {
+        assert!(!THANKS.is_match("nothanks @user:example.com"))
+    }
+}
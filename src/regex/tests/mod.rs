@@ -1 +1,4 @@
 mod conversion_tests;
+mod feels_like_tests;
+mod greeting_tests;
+mod karma_tests;
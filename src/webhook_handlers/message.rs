@@ -20,6 +20,8 @@ pub async fn message(
         let matrix_message = MatrixMessage {
             room_id: Some(message.room_id.clone()),
             message: MatrixMessageType::Notice(message.message.clone()),
+            trigger_event: None,
+            thread_root: None,
         };
         if state.send.clone().send(matrix_message).await.is_err() {
             return StatusCode::INTERNAL_SERVER_ERROR;
@@ -34,6 +36,8 @@ pub async fn message(
                     plain_text: response.to_string(),
                     formatted_text: response.format_text(),
                 }),
+                trigger_event: None,
+                thread_root: None,
             };
             if state.send.clone().send(matrix_message).await.is_err() {
                 return StatusCode::INTERNAL_SERVER_ERROR;